@@ -0,0 +1,272 @@
+//! AMP and print-view alternates for walled and JS-heavy pages.
+//!
+//! Many pages that come back walled or as a thin JS shell advertise a
+//! server-rendered variant of the same article:
+//!
+//! - **AMP**: `<link rel="amphtml">`, plus the same document on the Google
+//!   AMP cache (`https://<host>.cdn.ampproject.org/c/s/<host>/<path>`,
+//!   [`amp_cache_url`]), which serves it from outside the origin's wall.
+//! - **Print / text-only views**: `<link rel="alternate" media="print">`
+//!   and same-site "Print" / "Text-only" links or `?print=1`, `/print/`
+//!   URLs.
+//!
+//! The web pipeline tries these ([`alternate_pages`]), in that order,
+//! after the plain fetch and before the Playwright render — a few plain
+//! HTTP fetches instead of a 45 s browser render. The first alternate that
+//! yields more article content than the plain fetch wins. On by default;
+//! [`crate::ScrapeOptions::alternate_fallback`] and
+//! [`UNINEWS_ALTERNATE_FALLBACK_ENV`] turn it off.
+
+use std::env;
+use std::fmt;
+
+use reqwest::Url;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+
+/// Environment variable that toggles the AMP / print alternate fallback.
+///
+/// The fallback is on by default; set the variable to `0`, `false`, `no`,
+/// or `off` (any case) to disable it.
+pub const UNINEWS_ALTERNATE_FALLBACK_ENV: &str = "UNINEWS_ALTERNATE_FALLBACK";
+
+/// Whether the AMP / print alternate fallback is enabled.
+///
+/// Enabled by default; disabled when [`UNINEWS_ALTERNATE_FALLBACK_ENV`] is
+/// set to `0`, `false`, `no`, or `off` (case-insensitive, surrounding
+/// whitespace ignored).
+pub fn alternate_fallback_enabled() -> bool {
+    match env::var(UNINEWS_ALTERNATE_FALLBACK_ENV) {
+        Ok(value) => !matches!(
+            value.trim().to_ascii_lowercase().as_str(),
+            "0" | "false" | "no" | "off"
+        ),
+        Err(_) => true,
+    }
+}
+
+/// The Google AMP cache host suffix.
+const AMP_CACHE_SUFFIX: &str = "cdn.ampproject.org";
+
+/// Longest DNS label the AMP cache subdomain may be; longer hosts are
+/// cached under a hashed name that cannot be derived here.
+const MAX_DNS_LABEL: usize = 63;
+
+/// Link texts that name a print or text-only view of the page.
+const PRINT_LINK_TEXTS: &[&str] = &[
+    "print",
+    "print article",
+    "print this article",
+    "print this story",
+    "print this page",
+    "print version",
+    "printable version",
+    "printer friendly",
+    "printer-friendly",
+    "printer friendly version",
+    "printer-friendly version",
+    "text only",
+    "text-only",
+    "text version",
+    "text-only version",
+];
+
+/// Path segments that mark a print or text-only URL.
+const PRINT_PATH_SEGMENTS: &[&str] = &["print", "print.html", "printable", "text-only", "textonly"];
+
+/// Query flags that mark a print or text-only URL (`?print=1`).
+const PRINT_QUERY_FLAGS: &[&str] = &["print", "printable", "printer", "textonly", "text_only"];
+
+/// Query parameters whose `print` value marks a print URL
+/// (`?view=print`).
+const PRINT_QUERY_VIEWS: &[&str] = &["view", "output", "outputtype", "format", "template", "mode"];
+
+/// The kind of server-rendered variant an [`AlternatePage`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlternateKind {
+    /// The page's `<link rel="amphtml">` document.
+    Amp,
+    /// The AMP document on the Google AMP cache ([`amp_cache_url`]).
+    AmpCache,
+    /// A print or text-only view of the page.
+    Print,
+}
+
+impl AlternateKind {
+    /// Stable snake_case name, identical to the serde representation.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Amp => "amp",
+            Self::AmpCache => "amp_cache",
+            Self::Print => "print",
+        }
+    }
+}
+
+impl fmt::Display for AlternateKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A server-rendered variant of a page, found by [`alternate_pages`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlternatePage {
+    /// What kind of variant the URL is.
+    pub kind: AlternateKind,
+    /// The variant's absolute URL.
+    pub url: String,
+}
+
+/// The server-rendered variants `html` (the page at `page_url`) links, in
+/// the order the web pipeline tries them: the AMP document, the AMP
+/// document on the Google AMP cache, and a print or text-only view. At
+/// most one of each kind; empty when the page links none.
+///
+/// ```
+/// use uninews::{alternate_pages, AlternateKind};
+/// let html = r#"<html><head>
+///   <link rel="amphtml" href="https://news.example/amp/transit">
+/// </head><body><a href="/transit?print=1">Print</a></body></html>"#;
+/// let alternates = alternate_pages("https://news.example/transit", html);
+/// let kinds: Vec<AlternateKind> = alternates.iter().map(|page| page.kind).collect();
+/// assert_eq!(
+///     kinds,
+///     [AlternateKind::Amp, AlternateKind::AmpCache, AlternateKind::Print]
+/// );
+/// assert_eq!(
+///     alternates[1].url,
+///     "https://news-example.cdn.ampproject.org/c/s/news.example/amp/transit"
+/// );
+/// ```
+pub fn alternate_pages(page_url: &str, html: &str) -> Vec<AlternatePage> {
+    let Ok(page) = Url::parse(page_url) else {
+        return Vec::new();
+    };
+    let document = Html::parse_document(html);
+    let mut alternates = Vec::new();
+
+    let amp_selector =
+        Selector::parse(r#"link[rel~="amphtml"][href]"#).expect("valid AMP selector");
+    let amp = document
+        .select(&amp_selector)
+        .filter_map(|link| resolve(&page, link.value().attr("href")?))
+        .find(|url| !same_document(url, &page));
+    if let Some(amp) = amp {
+        let cache = amp_cache_url(amp.as_str());
+        alternates.push(AlternatePage {
+            kind: AlternateKind::Amp,
+            url: amp.to_string(),
+        });
+        if let Some(cache) = cache {
+            alternates.push(AlternatePage {
+                kind: AlternateKind::AmpCache,
+                url: cache,
+            });
+        }
+    }
+
+    let print_selector = Selector::parse(r#"link[rel~="alternate"][media][href], a[href]"#)
+        .expect("valid print selector");
+    let print = document.select(&print_selector).find_map(|link| {
+        let url = resolve(&page, link.value().attr("href")?)?;
+        let named_print = if link.value().name() == "link" {
+            link.value()
+                .attr("media")
+                .is_some_and(|media| media.trim().eq_ignore_ascii_case("print"))
+        } else {
+            let text = link.text().collect::<Vec<_>>().join(" ");
+            let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+            PRINT_LINK_TEXTS.contains(&text.to_lowercase().as_str()) || is_print_url(&url)
+        };
+        (named_print && url.host_str() == page.host_str() && !same_document(&url, &page))
+            .then_some(url)
+    });
+    if let Some(print) = print {
+        alternates.push(AlternatePage {
+            kind: AlternateKind::Print,
+            url: print.to_string(),
+        });
+    }
+
+    alternates
+}
+
+/// The Google AMP cache URL of the AMP document at `amp_url`
+/// (`https://news.example/amp/a` →
+/// `https://news-example.cdn.ampproject.org/c/s/news.example/amp/a`).
+/// `None` for hosts the cache does not serve under a derivable name: IP
+/// addresses, explicit ports, internationalized and over-long hosts.
+pub fn amp_cache_url(amp_url: &str) -> Option<String> {
+    let url = Url::parse(amp_url).ok()?;
+    let secure = match url.scheme() {
+        "https" => true,
+        "http" => false,
+        _ => return None,
+    };
+    let host = url.domain()?.to_ascii_lowercase();
+    if url.port().is_some()
+        || !host.contains('.')
+        || host.ends_with(AMP_CACHE_SUFFIX)
+        || host.split('.').any(|label| label.starts_with("xn--"))
+    {
+        return None;
+    }
+    let mut subdomain = host.replace('-', "--").replace('.', "-");
+    // A label with `--` at positions 3-4 would read as an IDN prefix.
+    if subdomain.get(2..4) == Some("--") {
+        subdomain = format!("0-{subdomain}-0");
+    }
+    if subdomain.len() > MAX_DNS_LABEL {
+        return None;
+    }
+    let mut cache_url = format!(
+        "https://{subdomain}.{AMP_CACHE_SUFFIX}/c/{}{host}{}",
+        if secure { "s/" } else { "" },
+        url.path()
+    );
+    if let Some(query) = url.query() {
+        cache_url.push('?');
+        cache_url.push_str(query);
+    }
+    Some(cache_url)
+}
+
+/// `href` resolved against `page`, without its fragment; `None` for
+/// non-HTTP links.
+fn resolve(page: &Url, href: &str) -> Option<Url> {
+    let mut url = page.join(href.trim()).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    url.set_fragment(None);
+    Some(url)
+}
+
+/// Whether `url` is `page` itself (fragments aside).
+fn same_document(url: &Url, page: &Url) -> bool {
+    let mut page = page.clone();
+    page.set_fragment(None);
+    *url == page
+}
+
+/// Whether `url` looks like a print or text-only view
+/// ([`PRINT_PATH_SEGMENTS`], [`PRINT_QUERY_FLAGS`], [`PRINT_QUERY_VIEWS`]).
+fn is_print_url(url: &Url) -> bool {
+    let print_path = url.path_segments().is_some_and(|mut segments| {
+        segments.any(|segment| PRINT_PATH_SEGMENTS.contains(&segment.to_ascii_lowercase().as_str()))
+    });
+    print_path
+        || url.query_pairs().any(|(key, value)| {
+            let key = key.to_ascii_lowercase();
+            let value = value.trim().to_ascii_lowercase();
+            (PRINT_QUERY_FLAGS.contains(&key.as_str())
+                && !matches!(value.as_str(), "0" | "false" | "no" | "off"))
+                || (PRINT_QUERY_VIEWS.contains(&key.as_str())
+                    && matches!(
+                        value.as_str(),
+                        "print" | "printable" | "text-only" | "textonly"
+                    ))
+        })
+}
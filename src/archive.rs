@@ -19,10 +19,9 @@
 use std::env;
 
 use reqwest::header::HeaderMap;
+use reqwest::Client;
 use serde::Deserialize;
 
-use crate::http::api_client;
-
 /// Environment variable that toggles the archive.org fallback.
 ///
 /// The fallback is on by default; set the variable to `0`, `false`, `no`,
@@ -183,13 +182,16 @@ pub fn parse_availability_response(body: &str) -> Option<ArchiveSnapshot> {
 ///
 /// Returns `Ok(None)` when archive.org has no usable snapshot. The
 /// availability endpoint returns the snapshot closest to the current time,
-/// i.e. the most recent capture.
-pub(crate) async fn latest_snapshot(url: &str) -> Result<Option<ArchiveSnapshot>, String> {
+/// i.e. the most recent capture. `client` is the scraper's JSON API client.
+pub(crate) async fn latest_snapshot(
+    client: &Client,
+    url: &str,
+) -> Result<Option<ArchiveSnapshot>, String> {
     let endpoint =
         reqwest::Url::parse_with_params("https://archive.org/wayback/available", &[("url", url)])
             .map_err(|error| format!("Failed to build archive.org availability URL: {}", error))?;
 
-    let response = api_client()
+    let response = client
         .get(endpoint)
         .send()
         .await
@@ -0,0 +1,333 @@
+//! Batch scraping with bounded concurrency and per-host politeness.
+//!
+//! Looping over [`crate::universal_scrape`] is slow, and naively running
+//! it in parallel can hammer one site or launch dozens of headless browsers
+//! at once. [`Scraper::scrape_many`] (and the [`scrape_many`] shorthand)
+//! scrapes a list of URLs concurrently under three limits from
+//! [`ScrapeOptions`]:
+//!
+//! - **Global concurrency** ([`ScrapeOptions::concurrency`]): at most this
+//!   many scrapes run at once.
+//! - **Per-host concurrency and delay**
+//!   ([`ScrapeOptions::per_host_concurrency`],
+//!   [`ScrapeOptions::per_host_delay`]): at most this many scrapes hit one
+//!   host at once, and consecutive scrape starts on a host are spaced by at
+//!   least the delay. URLs waiting on a busy host, or on its delay, do not
+//!   hold a global slot.
+//! - **Browser renders** ([`ScrapeOptions::max_concurrent_renders`]):
+//!   Playwright / Chrome renders are capped separately, because one render
+//!   costs far more than a plain fetch.
+//!
+//! Results arrive as a boxed [`Stream`](futures::Stream) of `(url, Post)` in
+//! completion order. Every scrape emits the usual [`crate::ScrapeEvent`]s,
+//! bracketed by [`crate::ScrapeEvent::BatchStarted`] and
+//! [`crate::ScrapeEvent::BatchCompleted`]. Dropping the stream cancels the
+//! scrapes still in flight.
+//!
+//! ```rust,no_run
+//! use futures::StreamExt;
+//! use std::time::Duration;
+//! use uninews::{ScrapeOptions, Scraper};
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let scraper = Scraper::new(
+//!     ScrapeOptions::from_env()
+//!         .concurrency(16)
+//!         .per_host_concurrency(2)
+//!         .per_host_delay(Duration::from_secs(1))
+//!         .max_concurrent_renders(2),
+//! );
+//! let urls = ["https://example.com/a", "https://example.org/b"];
+//! let mut results = scraper.scrape_many(urls, "english");
+//! while let Some((url, post)) = results.next().await {
+//!     println!("{url}: {}", if post.error.is_empty() { "ok" } else { &post.error });
+//! }
+//! # }
+//! ```
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::stream::{self, BoxStream, StreamExt};
+use tokio::sync::Semaphore;
+use tokio::task::{Id, JoinSet};
+use tokio::time::Instant;
+
+use crate::canonical::cache_key_url;
+use crate::events::{emit_event, ScrapeEvent};
+use crate::{Post, ScrapeError, ScrapeOptions, Scraper};
+
+/// Per-host gate: a concurrency semaphore plus the earliest instant the
+/// next scrape on the host may start.
+struct HostGate {
+    permits: Semaphore,
+    next_start: Mutex<Instant>,
+}
+
+/// Limits shared by every task of one batch.
+struct BatchLimits {
+    global: Semaphore,
+    per_host_concurrency: usize,
+    per_host_delay: Duration,
+    hosts: Mutex<HashMap<String, Arc<HostGate>>>,
+}
+
+impl BatchLimits {
+    fn new(options: &ScrapeOptions) -> Self {
+        Self {
+            global: Semaphore::new(options.concurrency_limit()),
+            per_host_concurrency: options.per_host_concurrency_limit(),
+            per_host_delay: options.per_host_delay_duration(),
+            hosts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn gate(&self, host: &str) -> Arc<HostGate> {
+        let mut hosts = self.hosts.lock().unwrap_or_else(|err| err.into_inner());
+        hosts
+            .entry(host.to_string())
+            .or_insert_with(|| {
+                Arc::new(HostGate {
+                    permits: Semaphore::new(self.per_host_concurrency),
+                    next_start: Mutex::new(Instant::now()),
+                })
+            })
+            .clone()
+    }
+}
+
+/// Batch key for `url`: its lowercase host, or the whole URL when it has
+/// none (so unparseable URLs never share a gate).
+fn host_key(url: &str) -> String {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_ascii_lowercase))
+        .unwrap_or_else(|| url.to_string())
+}
+
+/// Scrape one batch URL under the batch limits.
+async fn scrape_limited(
+    scraper: Scraper,
+    limits: Arc<BatchLimits>,
+    url: String,
+    language: Arc<str>,
+) -> (String, Post) {
+    let gate = limits.gate(&host_key(&url));
+    // Host first, then the per-host delay, then global: URLs queued behind
+    // a busy or rate-limited host must not starve other hosts of global
+    // slots.
+    let _host_permit = gate
+        .permits
+        .acquire()
+        .await
+        .expect("host semaphore is never closed");
+
+    // The start is only reserved once the global slot is held: a host peer
+    // may have started while this URL waited for it, in which case the slot
+    // goes back and the delay is waited out again.
+    let _global_permit = loop {
+        let earliest = *gate
+            .next_start
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        tokio::time::sleep_until(earliest).await;
+
+        let permit = limits
+            .global
+            .acquire()
+            .await
+            .expect("batch semaphore is never closed");
+        let mut next_start = gate
+            .next_start
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        let now = Instant::now();
+        if *next_start <= now {
+            *next_start = now + limits.per_host_delay;
+            break permit;
+        }
+    };
+
+    let post = scraper.scrape(&url, &language).await;
+    (url, post)
+}
+
+/// Stream state: URLs not yet spawned, then the running tasks.
+enum BatchState {
+    Pending {
+        /// Boxed: a `Scraper` is much larger than the running state.
+        scraper: Box<Scraper>,
+        urls: Vec<String>,
+        language: Arc<str>,
+    },
+    Running {
+        tasks: JoinSet<(String, Post)>,
+        urls_by_task: HashMap<Id, String>,
+        total: usize,
+        failed: usize,
+    },
+}
+
+impl Scraper {
+    /// Scrape every URL in `urls` concurrently and stream `(url, Post)`
+    /// pairs as they complete, under this scraper's batch limits:
+    /// [`ScrapeOptions::concurrency`] scrapes at once,
+    /// [`ScrapeOptions::per_host_concurrency`] per host with starts spaced
+    /// by [`ScrapeOptions::per_host_delay`], and browser renders capped by
+    /// [`ScrapeOptions::max_concurrent_renders`].
+    ///
+    /// Nothing starts until the stream is first polled; dropping the
+    /// stream aborts the scrapes still in flight. Must be polled inside a
+    /// Tokio runtime.
+    pub fn scrape_many<I, S>(&self, urls: I, language: &str) -> BoxStream<'static, (String, Post)>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let state = BatchState::Pending {
+            scraper: Box::new(self.clone()),
+            urls: urls.into_iter().map(Into::into).collect(),
+            language: Arc::from(language),
+        };
+        stream::unfold(state, next_batch_result).boxed()
+    }
+}
+
+impl Scraper {
+    /// Scrape the URL of each `(url, item)` pair as a
+    /// [`Scraper::scrape_many`] batch and stream `(item, post)` pairs in
+    /// completion order, after `merge` has folded the item's metadata into
+    /// the post. Pairs repeating a URL (its cache-key form) are
+    /// dropped before the batch starts, and posts repeating an earlier
+    /// post's [`Post::canonical_url`] (say, after a redirect) as they
+    /// complete; the first one wins.
+    pub(crate) fn scrape_many_items<T, I>(
+        &self,
+        items: I,
+        language: &str,
+        merge: fn(&T, &mut Post),
+    ) -> BoxStream<'static, (T, Post)>
+    where
+        T: Send + 'static,
+        I: IntoIterator<Item = (String, T)>,
+    {
+        let mut items_by_url: HashMap<String, T> = HashMap::new();
+        let mut urls = Vec::new();
+        let mut seen = HashSet::new();
+        for (url, item) in items {
+            if !seen.insert(cache_key_url(&url)) {
+                continue;
+            }
+            if let Entry::Vacant(slot) = items_by_url.entry(url) {
+                urls.push(slot.key().clone());
+                slot.insert(item);
+            }
+        }
+        let mut canonical_urls = HashSet::new();
+        self.scrape_many(urls, language)
+            .filter_map(move |(url, mut post)| {
+                let repeated = post
+                    .canonical_url
+                    .as_deref()
+                    .is_some_and(|canonical| !canonical_urls.insert(cache_key_url(canonical)));
+                let paired = items_by_url.remove(&url).filter(|_| !repeated).map(|item| {
+                    merge(&item, &mut post);
+                    (item, post)
+                });
+                futures::future::ready(paired)
+            })
+            .boxed()
+    }
+}
+
+/// Drive the batch: spawn every task on the first poll, then yield results
+/// until the set is drained.
+async fn next_batch_result(mut state: BatchState) -> Option<((String, Post), BatchState)> {
+    loop {
+        match state {
+            BatchState::Pending {
+                scraper,
+                urls,
+                language,
+            } => {
+                emit_event(ScrapeEvent::BatchStarted { urls: urls.len() });
+                let limits = Arc::new(BatchLimits::new(scraper.options()));
+                let mut tasks = JoinSet::new();
+                let mut urls_by_task = HashMap::with_capacity(urls.len());
+                let total = urls.len();
+                for url in urls {
+                    let handle = tasks.spawn(scrape_limited(
+                        (*scraper).clone(),
+                        limits.clone(),
+                        url.clone(),
+                        language.clone(),
+                    ));
+                    urls_by_task.insert(handle.id(), url);
+                }
+                state = BatchState::Running {
+                    tasks,
+                    urls_by_task,
+                    total,
+                    failed: 0,
+                };
+            }
+            BatchState::Running {
+                mut tasks,
+                mut urls_by_task,
+                total,
+                mut failed,
+            } => {
+                let Some(joined) = tasks.join_next_with_id().await else {
+                    emit_event(ScrapeEvent::BatchCompleted {
+                        urls: total,
+                        failed,
+                    });
+                    return None;
+                };
+                let (url, post) = match joined {
+                    Ok((id, (url, post))) => {
+                        urls_by_task.remove(&id);
+                        (url, post)
+                    }
+                    Err(error) => {
+                        let url = urls_by_task.remove(&error.id()).unwrap_or_default();
+                        let post = Post::from_error(ScrapeError::Other {
+                            message: format!("Scrape task for {} failed: {}", url, error),
+                            fallbacks: Vec::new(),
+                        });
+                        (url, post)
+                    }
+                };
+                if !post.error.is_empty() {
+                    failed += 1;
+                }
+                return Some((
+                    (url, post),
+                    BatchState::Running {
+                        tasks,
+                        urls_by_task,
+                        total,
+                        failed,
+                    },
+                ));
+            }
+        }
+    }
+}
+
+/// Scrape `urls` with a [`Scraper`] built from `options`; see
+/// [`Scraper::scrape_many`].
+pub fn scrape_many<I, S>(
+    urls: I,
+    language: &str,
+    options: ScrapeOptions,
+) -> BoxStream<'static, (String, Post)>
+where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+{
+    Scraper::new(options).scrape_many(urls, language)
+}
@@ -0,0 +1,714 @@
+//! Bluesky posts and threads through the public AppView API.
+//!
+//! `bsky.app` post pages are a JavaScript application, but every public
+//! post is readable without authentication from the Bluesky AppView. The
+//! built-in [`BlueskyHandler`] scrapes `bsky.app/profile/:actor/post/:rkey`
+//! URLs in two XRPC calls:
+//!
+//! 1. **`com.atproto.identity.resolveHandle`** — the handle in the URL
+//!    (`alice.bsky.social`) becomes the account's DID (skipped when the URL
+//!    already names a DID).
+//! 2. **`app.bsky.feed.getPostThread`** — the post at
+//!    `at://<did>/app.bsky.feed.post/<rkey>` with its parents and replies.
+//!
+//! The author's thread around the post (the parents and replies the author
+//! wrote themselves) is assembled oldest first ([`BlueskyThread`]), with
+//! link facets, image and video embeds, external link cards, and quoted
+//! posts rendered as HTML for the Markdown conversion. Link-only posts are
+//! followed to the article they link to, the way link-only tweets are
+//! ([`crate::x::x_post_is_link_only`]).
+
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::FallbackStage;
+use crate::events::{emit_event, ScrapeEvent};
+use crate::handler::{HandlerFuture, ScrapeContext, SourceHandler};
+use crate::html::{push_escaped_attribute, push_escaped_text};
+use crate::util::{array, parse_w3c_datetime, text, TITLE_TEXT_CHARS};
+use crate::web::{convert_scraped_post, fetch_api_page, scrape_linked_article};
+use crate::{Post, PostMetadata, Provenance, ScrapeError, Scraper};
+
+/// The public Bluesky AppView, which serves every public post without
+/// authentication.
+pub const BLUESKY_PUBLIC_APPVIEW: &str = "https://public.api.bsky.app";
+
+/// How many levels of parents and replies `getPostThread` returns.
+const THREAD_DEPTH: usize = 100;
+
+/// The built-in [`SourceHandler`] for `bsky.app` post URLs.
+///
+/// Reads posts from [`BLUESKY_PUBLIC_APPVIEW`] by default; register one
+/// built with [`BlueskyHandler::with_appview`] to use another AppView.
+#[derive(Debug, Clone)]
+pub struct BlueskyHandler {
+    appview: String,
+}
+
+impl Default for BlueskyHandler {
+    fn default() -> Self {
+        Self::with_appview(BLUESKY_PUBLIC_APPVIEW)
+    }
+}
+
+impl BlueskyHandler {
+    /// A handler reading posts from the AppView at `appview`
+    /// (`https://public.api.bsky.app`).
+    pub fn with_appview(appview: impl Into<String>) -> Self {
+        Self {
+            appview: appview.into().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// The AppView this handler reads posts from.
+    pub fn appview(&self) -> &str {
+        &self.appview
+    }
+
+    /// Fetch the Bluesky post at `url` and the author's thread around it.
+    ///
+    /// Fails with [`ScrapeError::Network`] / [`ScrapeError::HttpStatus`]
+    /// when the AppView cannot be reached or rejects the request (unknown
+    /// handle, deleted post), or [`ScrapeError::Other`] when `url` is not
+    /// a post URL or the post is blocked.
+    pub async fn fetch_thread(
+        &self,
+        scraper: &Scraper,
+        url: &str,
+    ) -> Result<BlueskyThread, ScrapeError> {
+        let (actor, rkey) = bluesky_post_ref(url)
+            .ok_or_else(|| ScrapeError::other(format!("Not a Bluesky post URL: {}", url)))?;
+        let did = if actor.starts_with("did:") {
+            actor
+        } else {
+            let resolve_url = format!(
+                "{}/xrpc/com.atproto.identity.resolveHandle?handle={}",
+                self.appview, actor
+            );
+            let body = self.xrpc(scraper, &resolve_url).await?;
+            serde_json::from_str::<Value>(&body)
+                .ok()
+                .and_then(|value| value["did"].as_str().map(str::to_string))
+                .ok_or_else(|| {
+                    ScrapeError::other(format!("Bluesky could not resolve the handle {}", actor))
+                })?
+        };
+
+        let thread_url = format!(
+            "{}/xrpc/app.bsky.feed.getPostThread?uri=at://{}/app.bsky.feed.post/{}&depth={}&parentHeight={}",
+            self.appview, did, rkey, THREAD_DEPTH, THREAD_DEPTH
+        );
+        let body = self.xrpc(scraper, &thread_url).await?;
+        parse_bluesky_thread(&body).map_err(ScrapeError::other)
+    }
+
+    /// GET an XRPC endpoint and return its JSON body.
+    async fn xrpc(&self, scraper: &Scraper, url: &str) -> Result<String, ScrapeError> {
+        let page = fetch_api_page(scraper, url, "application/json")
+            .await
+            .map_err(ScrapeError::network)?;
+        match page.json_body() {
+            Ok(_) => Ok(page.body),
+            Err(error) if page.status.is_success() => Err(ScrapeError::other(error)),
+            Err(_) => Err(ScrapeError::http_status(
+                page.status.as_u16(),
+                format!(
+                    "Bluesky AppView returned HTTP {}: {}",
+                    page.status,
+                    xrpc_error(&page.body)
+                ),
+            )),
+        }
+    }
+}
+
+impl SourceHandler for BlueskyHandler {
+    fn name(&self) -> &str {
+        "bluesky"
+    }
+
+    fn matches(&self, url: &str) -> bool {
+        bluesky_post_ref(url).is_some()
+    }
+
+    fn scrape<'a>(&'a self, url: &'a str, ctx: &'a ScrapeContext<'a>) -> HandlerFuture<'a> {
+        Box::pin(scrape_bluesky_url(self, ctx.scraper(), url, ctx.language()))
+    }
+
+    fn cache_fingerprint(&self) -> String {
+        self.appview.clone()
+    }
+}
+
+/// The account that wrote a post.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlueskyAuthor {
+    /// The account's DID (`did:plc:…`).
+    pub did: String,
+    /// The handle (`alice.bsky.social`).
+    pub handle: String,
+    /// Display name; empty when the account has none.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub display_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar: Option<String>,
+}
+
+/// A link facet: the byte range of the post text that links to `uri`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlueskyLink {
+    /// UTF-8 byte offset where the link text starts.
+    pub byte_start: usize,
+    /// UTF-8 byte offset where the link text ends (exclusive).
+    pub byte_end: usize,
+    /// The full link target (the text is often shortened).
+    pub uri: String,
+}
+
+/// An image (or video thumbnail) embedded in a post.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlueskyImage {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alt: Option<String>,
+}
+
+/// An external link card embedded in a post.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlueskyExternal {
+    pub uri: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thumb: Option<String>,
+}
+
+/// A post quoted by another post.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlueskyQuote {
+    /// The quoted post's `bsky.app` URL.
+    pub url: String,
+    /// The quoted author's handle.
+    pub handle: String,
+    pub text: String,
+}
+
+/// One Bluesky post, from an `app.bsky.feed.defs#postView`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlueskyPost {
+    /// The post's AT URI (`at://did:plc:…/app.bsky.feed.post/…`).
+    pub uri: String,
+    /// The post's `bsky.app` URL.
+    pub url: String,
+    pub author: BlueskyAuthor,
+    /// The post text, as plain text.
+    pub text: String,
+    /// `createdAt`, as RFC 3339.
+    pub created_at: String,
+    /// The AT URI of the post this one replies to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<String>,
+    /// Link facets, in text order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<BlueskyLink>,
+    /// Hashtags, without the `#`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Language codes from the record's `langs`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub langs: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<BlueskyImage>,
+    /// The playlist URL of an embedded video.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub video: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external: Option<BlueskyExternal>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote: Option<BlueskyQuote>,
+}
+
+/// A post and the author's thread around it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlueskyThread {
+    /// The author's thread, oldest first. Holds at least the requested
+    /// post.
+    pub posts: Vec<BlueskyPost>,
+    /// The index of the requested post in `posts`.
+    pub focus: usize,
+}
+
+impl BlueskyPost {
+    /// Whether the post text is nothing but links (a post with no text
+    /// and only an external link card counts too).
+    pub fn is_link_only(&self) -> bool {
+        if self.text.trim().is_empty() {
+            return self.external.is_some() && self.images.is_empty() && self.video.is_none();
+        }
+        !self.links.is_empty() && self.text_without_links().trim().is_empty()
+    }
+
+    /// The external article the post links to: its first link facet that
+    /// is not another Bluesky post, else its link card.
+    pub fn linked_article_url(&self) -> Option<String> {
+        self.links
+            .iter()
+            .map(|link| link.uri.as_str())
+            .find(|uri| bluesky_post_ref(uri).is_none())
+            .or_else(|| self.external.as_ref().map(|external| external.uri.as_str()))
+            .map(str::to_string)
+    }
+
+    /// The text with every link facet's range removed.
+    fn text_without_links(&self) -> String {
+        let mut text = String::new();
+        let mut position = 0;
+        for link in self.valid_links() {
+            text.push_str(&self.text[position..link.byte_start]);
+            position = link.byte_end;
+        }
+        text.push_str(&self.text[position..]);
+        text
+    }
+
+    /// The link facets whose ranges fall on character boundaries, in
+    /// order and without overlaps.
+    fn valid_links(&self) -> Vec<&BlueskyLink> {
+        let mut links: Vec<&BlueskyLink> = self
+            .links
+            .iter()
+            .filter(|link| {
+                link.byte_start < link.byte_end
+                    && link.byte_end <= self.text.len()
+                    && self.text.is_char_boundary(link.byte_start)
+                    && self.text.is_char_boundary(link.byte_end)
+            })
+            .collect();
+        links.sort_by_key(|link| link.byte_start);
+        let mut end = 0;
+        links.retain(|link| {
+            let keep = link.byte_start >= end;
+            if keep {
+                end = link.byte_end;
+            }
+            keep
+        });
+        links
+    }
+
+    /// The post as HTML: the text in paragraphs with its links, then the
+    /// images, video, link card, and quoted post.
+    fn to_html(&self) -> String {
+        let mut inline = String::new();
+        let mut position = 0;
+        for link in self.valid_links() {
+            push_escaped_text(&mut inline, &self.text[position..link.byte_start]);
+            inline.push_str("<a href=\"");
+            push_escaped_attribute(&mut inline, &link.uri);
+            inline.push_str("\">");
+            push_escaped_text(&mut inline, &self.text[link.byte_start..link.byte_end]);
+            inline.push_str("</a>");
+            position = link.byte_end;
+        }
+        push_escaped_text(&mut inline, &self.text[position..]);
+
+        let mut html = String::new();
+        for paragraph in inline.split("\n\n").map(str::trim) {
+            if !paragraph.is_empty() {
+                html.push_str("<p>");
+                html.push_str(&paragraph.replace('\n', "<br>"));
+                html.push_str("</p>");
+            }
+        }
+        for image in &self.images {
+            html.push_str("<p><img src=\"");
+            push_escaped_attribute(&mut html, &image.url);
+            html.push_str("\" alt=\"");
+            push_escaped_attribute(&mut html, image.alt.as_deref().unwrap_or_default());
+            html.push_str("\"></p>");
+        }
+        if let Some(video) = &self.video {
+            html.push_str("<p><a href=\"");
+            push_escaped_attribute(&mut html, video);
+            html.push_str("\">[video]</a></p>");
+        }
+        if let Some(external) = &self.external {
+            html.push_str("<p><a href=\"");
+            push_escaped_attribute(&mut html, &external.uri);
+            html.push_str("\">");
+            let title = if external.title.is_empty() {
+                &external.uri
+            } else {
+                &external.title
+            };
+            push_escaped_text(&mut html, title);
+            html.push_str("</a>");
+            if let Some(description) = &external.description {
+                html.push_str(" — ");
+                push_escaped_text(&mut html, description);
+            }
+            html.push_str("</p>");
+        }
+        if let Some(quote) = &self.quote {
+            html.push_str("<blockquote><p>");
+            push_escaped_text(&mut html, &quote.text);
+            html.push_str("</p><p>— <a href=\"");
+            push_escaped_attribute(&mut html, &quote.url);
+            html.push_str("\">@");
+            push_escaped_text(&mut html, &quote.handle);
+            html.push_str("</a></p></blockquote>");
+        }
+        html
+    }
+}
+
+impl BlueskyThread {
+    /// The requested post.
+    pub fn post(&self) -> &BlueskyPost {
+        &self.posts[self.focus]
+    }
+
+    /// The thread as a [`Post`] scraped from `url`: every post's HTML,
+    /// separated by `<hr>` and stamped with its time when there are
+    /// several.
+    fn to_post(&self, url: &str) -> Post {
+        let first = &self.posts[0];
+        let author = &first.author;
+        let byline = if author.display_name.is_empty() {
+            format!("@{}", author.handle)
+        } else {
+            format!("@{} ({})", author.handle, author.display_name)
+        };
+
+        let mut content = String::new();
+        for (index, post) in self.posts.iter().enumerate() {
+            if index > 0 {
+                content.push_str("<hr>");
+            }
+            if self.posts.len() > 1 {
+                content.push_str("<p><em>");
+                push_escaped_text(&mut content, &post.created_at);
+                content.push_str("</em></p>");
+            }
+            content.push_str(&post.to_html());
+        }
+
+        let text = first.text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let summary = if text.is_empty() {
+            "Bluesky post".to_string()
+        } else {
+            text.chars().take(TITLE_TEXT_CHARS).collect()
+        };
+        let image = self
+            .posts
+            .iter()
+            .flat_map(|post| &post.images)
+            .map(|image| image.url.clone())
+            .next()
+            .or_else(|| author.avatar.clone());
+        let mut keywords: Vec<String> = Vec::new();
+        for tag in self.posts.iter().flat_map(|post| &post.tags) {
+            if !keywords.contains(tag) {
+                keywords.push(tag.clone());
+            }
+        }
+        let metadata = PostMetadata {
+            schema_type: Some("SocialMediaPosting".to_string()),
+            authors: vec![if author.display_name.is_empty() {
+                author.handle.clone()
+            } else {
+                author.display_name.clone()
+            }],
+            date_published: Some(first.created_at.clone()),
+            publisher: Some("Bluesky".to_string()),
+            keywords,
+            image_url: image.clone(),
+            ..PostMetadata::default()
+        };
+        Post {
+            title: format!("{}: {}", byline, summary),
+            content,
+            featured_image_url: image.unwrap_or_default(),
+            publication_date: Some(first.created_at.clone()),
+            author: Some(byline),
+            provenance: Some(Provenance {
+                canonical_url: Some(self.post().url.clone()),
+                ..Provenance::new(FallbackStage::Bluesky, url)
+            }),
+            metadata: Some(metadata),
+            ..Post::default()
+        }
+    }
+}
+
+/// The actor (handle or DID) and record key of a `bsky.app` post URL
+/// (`https://bsky.app/profile/:actor/post/:rkey`); `None` for other URLs.
+///
+/// ```
+/// use uninews::bluesky_post_ref;
+/// assert_eq!(
+///     bluesky_post_ref("https://bsky.app/profile/alice.bsky.social/post/3l6oveex3ii2l"),
+///     Some(("alice.bsky.social".to_string(), "3l6oveex3ii2l".to_string()))
+/// );
+/// assert_eq!(bluesky_post_ref("https://bsky.app/profile/alice.bsky.social"), None);
+/// ```
+pub fn bluesky_post_ref(url: &str) -> Option<(String, String)> {
+    let url = Url::parse(url).ok()?;
+    let host = url.host_str()?;
+    if host != "bsky.app" && !host.ends_with(".bsky.app") {
+        return None;
+    }
+    let segments: Vec<&str> = url
+        .path_segments()?
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let ["profile", actor, "post", rkey] = segments.as_slice() else {
+        return None;
+    };
+    let valid_rkey = rkey.len() <= 512
+        && rkey
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || b"._:~-".contains(&byte));
+    (valid_rkey && !actor.is_empty()).then(|| (actor.to_string(), rkey.to_string()))
+}
+
+/// Parse an `app.bsky.feed.getPostThread` response into the author's
+/// thread around the requested post: its parents and replies written by
+/// the same account, oldest first. Fails with a message starting
+/// `Not a Bluesky thread:`, or naming why the post is unavailable.
+pub fn parse_bluesky_thread(json: &str) -> Result<BlueskyThread, String> {
+    let value: Value = serde_json::from_str(json)
+        .map_err(|error| format!("Not a Bluesky thread: invalid JSON ({})", error))?;
+    let root = &value["thread"];
+    match root["$type"].as_str() {
+        Some("app.bsky.feed.defs#threadViewPost") => {}
+        Some("app.bsky.feed.defs#notFoundPost") => {
+            return Err("The Bluesky post was not found (deleted?)".to_string())
+        }
+        Some("app.bsky.feed.defs#blockedPost") => {
+            return Err("The Bluesky post is blocked".to_string())
+        }
+        _ => return Err("Not a Bluesky thread: no thread post".to_string()),
+    }
+    let focus = post_view(&root["post"])?;
+    let did = focus.author.did.clone();
+
+    let mut posts = Vec::new();
+    let mut parent = &root["parent"];
+    while is_thread_post(parent) {
+        match post_view(&parent["post"]) {
+            Ok(post) if post.author.did == did => posts.push(post),
+            _ => break,
+        }
+        parent = &parent["parent"];
+    }
+    collect_self_replies(root, &did, &mut posts);
+    let focus_uri = focus.uri.clone();
+    posts.push(focus);
+
+    posts.sort_by_key(|post| parse_w3c_datetime(&post.created_at));
+    let focus = posts
+        .iter()
+        .position(|post| post.uri == focus_uri)
+        .unwrap_or_default();
+    Ok(BlueskyThread { posts, focus })
+}
+
+/// Add `node`'s replies written by `did`, and theirs, to `posts`.
+fn collect_self_replies(node: &Value, did: &str, posts: &mut Vec<BlueskyPost>) {
+    for reply in node["replies"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+    {
+        if !is_thread_post(reply) {
+            continue;
+        }
+        if let Ok(post) = post_view(&reply["post"]) {
+            if post.author.did == did {
+                posts.push(post);
+                collect_self_replies(reply, did, posts);
+            }
+        }
+    }
+}
+
+fn is_thread_post(node: &Value) -> bool {
+    node["$type"] == "app.bsky.feed.defs#threadViewPost"
+}
+
+/// A post from an `app.bsky.feed.defs#postView`.
+fn post_view(view: &Value) -> Result<BlueskyPost, String> {
+    let uri = text(&view["uri"]).ok_or("Not a Bluesky thread: post without uri")?;
+    let author = &view["author"];
+    let did = text(&author["did"]).ok_or("Not a Bluesky thread: post without author")?;
+    let handle = text(&author["handle"]).unwrap_or_else(|| did.clone());
+    let record = &view["record"];
+
+    let mut links = Vec::new();
+    let mut tags = Vec::new();
+    for facet in array(&record["facets"]) {
+        let index = &facet["index"];
+        for feature in array(&facet["features"]) {
+            match feature["$type"].as_str() {
+                Some("app.bsky.richtext.facet#link") => {
+                    let (Some(byte_start), Some(byte_end), Some(uri)) = (
+                        index["byteStart"].as_u64(),
+                        index["byteEnd"].as_u64(),
+                        text(&feature["uri"]),
+                    ) else {
+                        continue;
+                    };
+                    links.push(BlueskyLink {
+                        byte_start: byte_start as usize,
+                        byte_end: byte_end as usize,
+                        uri,
+                    });
+                }
+                Some("app.bsky.richtext.facet#tag") => {
+                    if let Some(tag) = text(&feature["tag"]) {
+                        tags.push(tag);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    links.sort_by_key(|link| link.byte_start);
+
+    let mut post = BlueskyPost {
+        url: post_url(&uri, &handle),
+        uri,
+        author: BlueskyAuthor {
+            did,
+            handle,
+            display_name: text(&author["displayName"]).unwrap_or_default(),
+            avatar: text(&author["avatar"]),
+        },
+        text: record["text"].as_str().unwrap_or_default().to_string(),
+        created_at: text(&record["createdAt"])
+            .or_else(|| text(&view["indexedAt"]))
+            .unwrap_or_default(),
+        reply_to: text(&record["reply"]["parent"]["uri"]),
+        links,
+        tags,
+        langs: array(&record["langs"]).iter().filter_map(text).collect(),
+        ..BlueskyPost::default()
+    };
+    read_embed(&view["embed"], &mut post);
+    Ok(post)
+}
+
+/// Fill `post`'s images, video, link card, and quote from an embed view.
+fn read_embed(embed: &Value, post: &mut BlueskyPost) {
+    match embed["$type"].as_str() {
+        Some("app.bsky.embed.images#view") => {
+            post.images = array(&embed["images"])
+                .iter()
+                .filter_map(|image| {
+                    Some(BlueskyImage {
+                        url: text(&image["fullsize"]).or_else(|| text(&image["thumb"]))?,
+                        alt: text(&image["alt"]),
+                    })
+                })
+                .collect();
+        }
+        Some("app.bsky.embed.video#view") => {
+            post.video = text(&embed["playlist"]);
+            if let Some(thumbnail) = text(&embed["thumbnail"]) {
+                post.images.push(BlueskyImage {
+                    url: thumbnail,
+                    alt: text(&embed["alt"]),
+                });
+            }
+        }
+        Some("app.bsky.embed.external#view") => {
+            let external = &embed["external"];
+            post.external = text(&external["uri"]).map(|uri| BlueskyExternal {
+                uri,
+                title: text(&external["title"]).unwrap_or_default(),
+                description: text(&external["description"]),
+                thumb: text(&external["thumb"]),
+            });
+        }
+        Some("app.bsky.embed.record#view") => post.quote = quote(&embed["record"]),
+        Some("app.bsky.embed.recordWithMedia#view") => {
+            read_embed(&embed["media"], post);
+            post.quote = quote(&embed["record"]["record"]);
+        }
+        _ => {}
+    }
+}
+
+/// A quoted post from an `app.bsky.embed.record#viewRecord`.
+fn quote(record: &Value) -> Option<BlueskyQuote> {
+    if record["$type"] != "app.bsky.embed.record#viewRecord" {
+        return None;
+    }
+    let uri = text(&record["uri"])?;
+    let handle = text(&record["author"]["handle"]).unwrap_or_default();
+    Some(BlueskyQuote {
+        url: post_url(&uri, &handle),
+        text: record["value"]["text"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        handle,
+    })
+}
+
+/// The `bsky.app` URL of the post at AT URI `uri` by `handle`.
+fn post_url(uri: &str, handle: &str) -> String {
+    let rkey = uri.rsplit('/').next().unwrap_or_default();
+    format!("https://bsky.app/profile/{}/post/{}", handle, rkey)
+}
+
+/// The `error: message` of an XRPC error body, or the body itself.
+fn xrpc_error(body: &str) -> String {
+    let Ok(value) = serde_json::from_str::<Value>(body) else {
+        return crate::util::summarize_body(body, 200);
+    };
+    match (text(&value["error"]), text(&value["message"])) {
+        (Some(error), Some(message)) => format!("{}: {}", error, message),
+        (Some(error), None) => error,
+        (None, Some(message)) => message,
+        (None, None) => crate::util::summarize_body(body, 200),
+    }
+}
+
+/// Scrape a Bluesky post: the author's thread converted to Markdown, or
+/// the linked article of a link-only post.
+async fn scrape_bluesky_url(
+    handler: &BlueskyHandler,
+    scraper: &Scraper,
+    url: &str,
+    language: &str,
+) -> Post {
+    let fallbacks = [FallbackStage::Bluesky];
+    let thread = match handler.fetch_thread(scraper, url).await {
+        Ok(thread) => thread,
+        Err(error) => return Post::from_error(error).with_fallbacks(&fallbacks),
+    };
+
+    let post = thread.post();
+    if post.is_link_only() {
+        if let Some(article_url) = post.linked_article_url() {
+            let title_override = post
+                .external
+                .as_ref()
+                .filter(|external| external.uri == article_url && !external.title.is_empty())
+                .map(|external| external.title.as_str());
+            return scrape_linked_article(scraper, &article_url, language, title_override).await;
+        }
+    }
+
+    let post = thread.to_post(url);
+    emit_event(ScrapeEvent::ContentExtracted {
+        url: url.to_string(),
+        content_bytes: post.content.len(),
+    });
+    convert_scraped_post(scraper, post, language, &fallbacks).await
+}
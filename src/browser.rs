@@ -189,7 +189,7 @@ pub fn parse_playwright_timeout_ms(raw: Option<&str>) -> u64 {
 }
 
 /// Playwright navigation / content-wait timeout (from env or default).
-fn playwright_timeout() -> Duration {
+pub(crate) fn playwright_timeout() -> Duration {
     let raw = env::var(UNINEWS_PLAYWRIGHT_TIMEOUT_MS_ENV).ok();
     if let Some(raw) = raw.as_deref() {
         if !matches!(raw.trim().parse::<u64>(), Ok(ms) if ms > 0) {
@@ -599,7 +599,18 @@ pub fn looks_like_browser_not_installed_message(message: &str) -> bool {
 /// budget ([`playwright_overall_budget_ms`]) expires, or the DOM is empty.
 /// Callers treat any `Err` as "continue to archive.org".
 pub async fn fetch_rendered_dom_with_playwright(url: &str) -> Result<String, String> {
-    match fetch_rendered_dom_with_playwright_once(url).await {
+    fetch_rendered_dom_with_playwright_timeout(url, playwright_timeout()).await
+}
+
+/// [`fetch_rendered_dom_with_playwright`] with an explicit per-step
+/// `timeout` instead of [`UNINEWS_PLAYWRIGHT_TIMEOUT_MS_ENV`]; the overall
+/// budget is `timeout` plus [`PLAYWRIGHT_OVERALL_GRACE_MS`]. Used by
+/// [`crate::Scraper`], whose options carry their own timeout.
+pub(crate) async fn fetch_rendered_dom_with_playwright_timeout(
+    url: &str,
+    timeout: Duration,
+) -> Result<String, String> {
+    match fetch_rendered_dom_with_playwright_once(url, timeout).await {
         Ok(html) => Ok(html),
         Err(err) if looks_like_browser_not_installed_message(&err) => {
             if !playwright_autoinstall_enabled() {
@@ -611,7 +622,7 @@ pub async fn fetch_rendered_dom_with_playwright(url: &str) -> Result<String, Str
             // Awaits any in-flight install started by a concurrent scrape
             // and reuses the cached outcome, then retries exactly once.
             ensure_playwright_browser_installed().await?;
-            fetch_rendered_dom_with_playwright_once(url).await
+            fetch_rendered_dom_with_playwright_once(url, timeout).await
         }
         Err(err) => Err(err),
     }
@@ -655,23 +666,26 @@ async fn ensure_playwright_browser_installed() -> Result<(), String> {
 /// ([`playwright_overall_budget_ms`]) so every internal await — driver
 /// spawn, context/page creation, content reads, cleanup — is bounded and a
 /// wedged driver cannot hang a scrape forever.
-async fn fetch_rendered_dom_with_playwright_once(url: &str) -> Result<String, String> {
-    let budget = Duration::from_millis(playwright_overall_budget_ms());
-    match tokio::time::timeout(budget, render_dom_attempt(url)).await {
+async fn fetch_rendered_dom_with_playwright_once(
+    url: &str,
+    timeout: Duration,
+) -> Result<String, String> {
+    let budget = timeout + Duration::from_millis(PLAYWRIGHT_OVERALL_GRACE_MS);
+    match tokio::time::timeout(budget, render_dom_attempt(url, timeout)).await {
         Ok(result) => result,
         Err(_) => Err(format!(
             "Playwright render of {url} timed out: exceeded the overall budget of {} ms \
              ({} ms configured timeout + {} ms grace)",
             budget.as_millis(),
-            playwright_timeout().as_millis(),
+            timeout.as_millis(),
             PLAYWRIGHT_OVERALL_GRACE_MS
         )),
     }
 }
 
 /// Launch the Playwright Node driver plus a headless Chromium instance.
-async fn launch_playwright_browser() -> Result<SharedBrowser, String> {
-    let timeout_ms = playwright_timeout().as_millis() as f64;
+async fn launch_playwright_browser(timeout: Duration) -> Result<SharedBrowser, String> {
+    let timeout_ms = timeout.as_millis() as f64;
 
     let playwright = Playwright::launch()
         .await
@@ -718,8 +732,7 @@ async fn launch_playwright_browser() -> Result<SharedBrowser, String> {
 /// end — never the shared browser, which lives until process exit. When the
 /// cached browser's connection died, one fresh uncached browser is launched
 /// for this attempt (and dropped with it).
-async fn render_dom_attempt(url: &str) -> Result<String, String> {
-    let timeout = playwright_timeout();
+async fn render_dom_attempt(url: &str, timeout: Duration) -> Result<String, String> {
     let timeout_ms = timeout.as_millis() as f64;
 
     // `fresh` keeps a re-launched browser (and its driver) alive for the
@@ -730,11 +743,13 @@ async fn render_dom_attempt(url: &str) -> Result<String, String> {
     let browser = {
         // A failed launch is not cached: the next scrape retries and
         // surfaces the same error again.
-        let shared = cell.get_or_try_init(launch_playwright_browser).await?;
+        let shared = cell
+            .get_or_try_init(|| launch_playwright_browser(timeout))
+            .await?;
         if shared.browser.is_connected() {
             shared.browser.clone()
         } else {
-            fresh = launch_playwright_browser().await?;
+            fresh = launch_playwright_browser(timeout).await?;
             fresh.browser.clone()
         }
     };
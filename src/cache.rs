@@ -0,0 +1,350 @@
+//! Optional on-disk cache for raw responses and converted posts.
+//!
+//! Re-scraping the same article during development or a re-run repeats the
+//! HTTP fetch, possibly a 45 s Playwright render, and an LLM call. A
+//! [`ScrapeCache`] configured on the [`crate::ScrapeOptions`] (or seeded
+//! from [`UNINEWS_CACHE_DIR_ENV`]) short-circuits both expensive halves:
+//!
+//! - **Responses** — the final URL, status, headers, and body of every
+//!   successful (2xx, non-walled) plain HTTP fetch, keyed by URL. A hit
+//!   skips the network; extraction and the fallback chain still run.
+//! - **Posts** — the final converted [`Post`], keyed by URL, output
+//!   language, conversion mode, LLM provider and model,
+//!   [`MARKDOWN_PROMPT_VERSION`], the extraction rules in effect, the
+//!   article page cap, the CMS API and alternate-page toggles, and the
+//!   source handlers' settings ([`crate::SourceHandler::cache_fingerprint`]).
+//!   A hit skips the whole pipeline. Only successful posts are stored.
+//!
+//! Both layers key URLs by their canonical form ([`crate::normalize_url`]),
+//! so the same article requested with `utm_*` / `fbclid` parameters, a
+//! fragment, or as its AMP variant shares one entry, except that an
+//! archive.org snapshot keeps its own key: its older text must not answer
+//! for the live article. AMP responses are not stored, since their markup
+//! must not answer a fetch of the article either. [`crate::Scraper::scrape`]
+//! also stores each post under where the request URL's redirects end and
+//! under the post's [`Post::canonical_url`].
+//!
+//! Entries older than the cache TTL are ignored (and overwritten by the
+//! next store). Each entry is one JSON file under `responses/` or `posts/`
+//! in the cache directory, named by a hash of its key; the full key is
+//! stored inside and checked on read, so a hash collision is just a miss.
+//! Cache I/O never fails a scrape: unreadable entries are misses and
+//! failed writes are reported on stderr.
+//!
+//! Every lookup emits [`ScrapeEvent::CacheHit`] or
+//! [`ScrapeEvent::CacheMiss`].
+
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::canonical::{cache_key_url, is_amp_variant};
+use crate::events::{emit_event, ScrapeEvent};
+use crate::llm::{normalized_output_language, resolve_llm_model, MARKDOWN_PROMPT_VERSION};
+use crate::{Post, ScrapeOptions};
+
+/// Environment variable naming the cache directory. Unset or empty
+/// disables the cache.
+pub const UNINEWS_CACHE_DIR_ENV: &str = "UNINEWS_CACHE_DIR";
+
+/// Environment variable overriding the cache TTL, in seconds.
+pub const UNINEWS_CACHE_TTL_SECS_ENV: &str = "UNINEWS_CACHE_TTL_SECS";
+
+/// Default age after which cache entries are ignored (24 hours).
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Which cache layer a [`ScrapeEvent::CacheHit`] / [`ScrapeEvent::CacheMiss`]
+/// refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheKind {
+    /// A raw HTTP response (final URL, status, headers, body).
+    Response,
+    /// A fully converted [`Post`].
+    Post,
+}
+
+impl CacheKind {
+    /// Stable snake_case name, identical to the serde representation.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CacheKind::Response => "response",
+            CacheKind::Post => "post",
+        }
+    }
+
+    /// Subdirectory holding this layer's entries.
+    fn dir_name(self) -> &'static str {
+        match self {
+            CacheKind::Response => "responses",
+            CacheKind::Post => "posts",
+        }
+    }
+}
+
+impl fmt::Display for CacheKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// On-disk cache location and freshness window.
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+/// use uninews::{ScrapeCache, ScrapeOptions, Scraper};
+///
+/// let scraper = Scraper::new(
+///     ScrapeOptions::from_env()
+///         .cache(Some(ScrapeCache::new("/tmp/uninews-cache").ttl(Duration::from_secs(3600)))),
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScrapeCache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+impl ScrapeCache {
+    /// A cache rooted at `dir` (created on first store) with
+    /// [`DEFAULT_CACHE_TTL`].
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl: DEFAULT_CACHE_TTL,
+        }
+    }
+
+    /// The cache configured by [`UNINEWS_CACHE_DIR_ENV`] and
+    /// [`UNINEWS_CACHE_TTL_SECS_ENV`], or `None` when no directory is set.
+    /// An invalid or zero TTL is reported on stderr and replaced by the
+    /// default.
+    pub fn from_env() -> Option<Self> {
+        let dir = env::var(UNINEWS_CACHE_DIR_ENV).ok()?;
+        let dir = dir.trim();
+        if dir.is_empty() {
+            return None;
+        }
+        let mut cache = Self::new(dir);
+        if let Ok(raw) = env::var(UNINEWS_CACHE_TTL_SECS_ENV) {
+            match raw.trim().parse::<u64>() {
+                Ok(secs) if secs > 0 => cache.ttl = Duration::from_secs(secs),
+                _ => eprintln!(
+                    "uninews: invalid {}={:?}; using default {} s",
+                    UNINEWS_CACHE_TTL_SECS_ENV,
+                    raw,
+                    DEFAULT_CACHE_TTL.as_secs()
+                ),
+            }
+        }
+        Some(cache)
+    }
+
+    /// Ignore entries older than `ttl`. A zero duration is rejected and
+    /// leaves the current value in place.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        if !ttl.is_zero() {
+            self.ttl = ttl;
+        }
+        self
+    }
+
+    /// The cache directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The freshness window.
+    pub fn ttl_duration(&self) -> Duration {
+        self.ttl
+    }
+
+    /// The cached response for a fetch of `url`, if fresh.
+    pub(crate) async fn load_response(&self, url: &str) -> Option<CachedResponse> {
+        self.load(CacheKind::Response, url, &cache_key_url(url))
+            .await
+    }
+
+    /// Store the response of a successful fetch of `url`. AMP renderings
+    /// are not stored: they share the article's key, and their markup must
+    /// not answer a later fetch of the article itself.
+    pub(crate) async fn store_response(&self, url: &str, response: &CachedResponse) {
+        if !is_amp_variant(url) {
+            self.store(CacheKind::Response, url, &cache_key_url(url), response)
+                .await;
+        }
+    }
+
+    /// The cached converted post for `url` under `options`, if fresh.
+    pub(crate) async fn load_post(
+        &self,
+        url: &str,
+        language: &str,
+        options: &ScrapeOptions,
+    ) -> Option<Post> {
+        self.load(CacheKind::Post, url, &post_key(url, language, options))
+            .await
+    }
+
+    /// Store the converted post for `url` under `options`.
+    pub(crate) async fn store_post(
+        &self,
+        url: &str,
+        language: &str,
+        options: &ScrapeOptions,
+        post: &Post,
+    ) {
+        self.store(
+            CacheKind::Post,
+            url,
+            &post_key(url, language, options),
+            post,
+        )
+        .await;
+    }
+
+    fn entry_path(&self, kind: CacheKind, key: &str) -> PathBuf {
+        self.dir
+            .join(kind.dir_name())
+            .join(format!("{:016x}.json", fnv1a_64(key)))
+    }
+
+    async fn load<T: DeserializeOwned>(&self, kind: CacheKind, url: &str, key: &str) -> Option<T> {
+        let value = match tokio::fs::read(self.entry_path(kind, key)).await {
+            Ok(bytes) => serde_json::from_slice::<CacheEntry<T>>(&bytes)
+                .ok()
+                .filter(|entry| entry.key == key && self.is_fresh(entry.stored_at_ms))
+                .map(|entry| entry.value),
+            Err(_) => None,
+        };
+        let event = if value.is_some() {
+            ScrapeEvent::CacheHit {
+                url: url.to_string(),
+                kind,
+            }
+        } else {
+            ScrapeEvent::CacheMiss {
+                url: url.to_string(),
+                kind,
+            }
+        };
+        emit_event(event);
+        value
+    }
+
+    async fn store<T: Serialize>(&self, kind: CacheKind, url: &str, key: &str, value: &T) {
+        let entry = CacheEntry {
+            key: key.to_string(),
+            stored_at_ms: unix_millis(),
+            value,
+        };
+        let path = self.entry_path(kind, key);
+        if let Err(err) = write_atomically(&path, &entry).await {
+            eprintln!(
+                "uninews: failed to write {} cache entry for {} to {}: {}",
+                kind,
+                url,
+                path.display(),
+                err
+            );
+        }
+    }
+
+    fn is_fresh(&self, stored_at_ms: u64) -> bool {
+        let age_ms = unix_millis().saturating_sub(stored_at_ms);
+        u128::from(age_ms) < self.ttl.as_millis()
+    }
+}
+
+/// One cache file: the full key (checked on read), the store time, and the
+/// cached value.
+#[derive(Serialize, Deserialize)]
+struct CacheEntry<T> {
+    key: String,
+    stored_at_ms: u64,
+    value: T,
+}
+
+/// A raw HTTP response as stored in the response cache.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CachedResponse {
+    /// The URL after redirects.
+    pub(crate) final_url: String,
+    pub(crate) status: u16,
+    /// Header name / value pairs; values that are not valid UTF-8 are
+    /// dropped.
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) body: String,
+}
+
+/// Key of a converted post: everything that changes the conversion output,
+/// including the pipeline toggles and source-handler settings that change
+/// what is extracted.
+fn post_key(url: &str, language: &str, options: &ScrapeOptions) -> String {
+    let mode = options.conversion_mode();
+    let (provider, model) = if mode.uses_llm() {
+        let provider = options.llm_client_name();
+        (
+            provider.to_string(),
+            resolve_llm_model(provider, options.llm_model_override()),
+        )
+    } else {
+        (String::new(), String::new())
+    };
+    format!(
+        "{}\n{}\n{}\n{}\n{}\nprompt-v{}\nrules-{:016x}\npages-{} cms-{} alternate-{}\nhandlers-{:016x}",
+        cache_key_url(url),
+        normalized_output_language(language).to_lowercase(),
+        mode,
+        provider,
+        model,
+        MARKDOWN_PROMPT_VERSION,
+        fnv1a_64(&options.extraction_rules().to_json()),
+        options.article_page_limit(),
+        options.cms_api_enabled(),
+        options.alternate_fallback_enabled(),
+        fnv1a_64(&options.source_handlers().cache_fingerprint())
+    )
+}
+
+/// 64-bit FNV-1a: stable across Rust versions and platforms, unlike
+/// `DefaultHasher`, so entry file names survive toolchain upgrades.
+fn fnv1a_64(value: &str) -> u64 {
+    value.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Write `entry` as JSON to a temporary sibling of `path`, then rename it
+/// into place, so concurrent readers never see a half-written file.
+async fn write_atomically<T: Serialize>(path: &Path, entry: &T) -> std::io::Result<()> {
+    static NEXT_TMP: AtomicU64 = AtomicU64::new(0);
+
+    let bytes = serde_json::to_vec(entry)?;
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let tmp = path.with_extension(format!(
+        "tmp{}-{}",
+        std::process::id(),
+        NEXT_TMP.fetch_add(1, Ordering::Relaxed)
+    ));
+    tokio::fs::write(&tmp, bytes).await?;
+    if let Err(err) = tokio::fs::rename(&tmp, path).await {
+        let _ = tokio::fs::remove_file(&tmp).await;
+        return Err(err);
+    }
+    Ok(())
+}
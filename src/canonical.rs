@@ -0,0 +1,386 @@
+//! Canonical article URLs: tracking-parameter stripping, AMP and archive
+//! variant folding, shortener resolution, and the page's declared
+//! canonical.
+//!
+//! The same story arrives as `…?utm_source=twitter`, `…?fbclid=…`, its AMP
+//! document, the AMP document on the Google AMP cache, or a `bit.ly`
+//! shortlink. [`normalize_url`] folds those spellings into one:
+//!
+//! - tracking parameters (`utm_*`, `fbclid`, `gclid`, `msclkid`, `mc_cid`,
+//!   … — [`is_tracking_parameter`]) and the fragment are dropped;
+//! - Google AMP cache and Google AMP viewer URLs, and archive.org
+//!   snapshot URLs, become the URL they wrap;
+//! - AMP variants of the path and query (`/amp/`, `.amp.html`, `?amp=1`,
+//!   `?outputType=amp`) become the regular article URL.
+//!
+//! Successful posts carry the normalized form of the page's
+//! `<link rel="canonical">` (else `og:url`, else the final URL) in
+//! [`crate::Post::canonical_url`] ([`canonical_url`]). The same form keys
+//! the [`crate::ScrapeCache`] and batch dedup ([`cache_key_url`]), with one
+//! exception: archive.org snapshots keep their wrapper there, so a snapshot
+//! never stands in for the live article. [`Scraper::scrape`] looks a post
+//! up under the request URL, then under where its redirects end
+//! ([`Scraper::resolve_url`], any host), and stores it under both and its
+//! canonical URL; feed and sitemap batches drop items whose URLs or
+//! canonical URLs repeat.
+
+use reqwest::{Client, Url};
+use scraper::{Html, Selector};
+
+use crate::events::{emit_event, ScrapeEvent};
+use crate::Scraper;
+
+/// Query parameters that only track the click, exact names
+/// (case-insensitive).
+const TRACKING_PARAMETERS: &[&str] = &[
+    "fbclid",
+    "gclid",
+    "gclsrc",
+    "dclid",
+    "gbraid",
+    "wbraid",
+    "msclkid",
+    "yclid",
+    "twclid",
+    "ttclid",
+    "igshid",
+    "li_fat_id",
+    "mc_cid",
+    "mc_eid",
+    "mkt_tok",
+    "_ga",
+    "_gl",
+    "_hsenc",
+    "_hsmi",
+    "ref_src",
+    "ref_url",
+    "s_cid",
+    "cmpid",
+    "ocid",
+    "smid",
+    "sr_share",
+    "__twitter_impression",
+];
+
+/// Prefixes of tracking query parameters (`utm_source`, `pk_campaign`,
+/// `mtm_medium`, `hsa_acc`, `oly_anon_id`).
+const TRACKING_PREFIXES: &[&str] = &["utm_", "pk_", "mtm_", "hsa_", "oly_"];
+
+/// URL shortener hosts ([`is_short_link`]).
+const SHORTENER_HOSTS: &[&str] = &[
+    "bit.ly",
+    "bitly.com",
+    "j.mp",
+    "buff.ly",
+    "ow.ly",
+    "t.co",
+    "tinyurl.com",
+    "trib.al",
+    "lnkd.in",
+    "dlvr.it",
+    "ift.tt",
+    "is.gd",
+    "v.gd",
+    "goo.gl",
+    "rebrand.ly",
+    "cutt.ly",
+    "shorturl.at",
+    "t.ly",
+    "tiny.cc",
+    "rb.gy",
+    "s.id",
+    "fb.me",
+    "wp.me",
+    "flip.it",
+    "nyti.ms",
+    "wapo.st",
+    "bloom.bg",
+    "reut.rs",
+    "on.ft.com",
+    "econ.st",
+    "cnn.it",
+    "bbc.in",
+    "n.pr",
+    "politi.co",
+];
+
+/// Whether the query parameter `name` only tracks the click (`utm_*`,
+/// `fbclid`, `gclid`, …) and can be dropped without changing the page.
+///
+/// ```
+/// use uninews::is_tracking_parameter;
+/// assert!(is_tracking_parameter("utm_source"));
+/// assert!(is_tracking_parameter("FBCLID"));
+/// assert!(!is_tracking_parameter("page"));
+/// ```
+pub fn is_tracking_parameter(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    TRACKING_PARAMETERS.contains(&name.as_str())
+        || TRACKING_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+}
+
+/// Whether `url` is a link on a known URL shortener (`bit.ly`, `t.co`,
+/// `buff.ly`, `trib.al`, `nyti.ms`, …): a bare host plus a single path
+/// segment.
+pub fn is_short_link(url: &str) -> bool {
+    let Ok(url) = Url::parse(url) else {
+        return false;
+    };
+    let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+    SHORTENER_HOSTS.contains(&host)
+        && url
+            .path_segments()
+            .is_some_and(|segments| segments.filter(|segment| !segment.is_empty()).count() == 1)
+}
+
+/// The canonical spelling of `url`: tracking parameters and the fragment
+/// dropped, AMP-cache / AMP-viewer / archive.org wrappers unwrapped, and
+/// AMP variants of the path and query folded into the regular article
+/// URL. Non-HTTP and unparsable input comes back trimmed but otherwise
+/// unchanged.
+///
+/// ```
+/// use uninews::normalize_url;
+/// assert_eq!(
+///     normalize_url("https://News.example/2026/10/transit/amp/?utm_source=twitter&id=7#top"),
+///     "https://news.example/2026/10/transit/?id=7"
+/// );
+/// assert_eq!(
+///     normalize_url("https://news-example.cdn.ampproject.org/c/s/news.example/transit.amp.html"),
+///     "https://news.example/transit.html"
+/// );
+/// ```
+pub fn normalize_url(url: &str) -> String {
+    normalize(url, true)
+}
+
+/// `url` as a cache and batch-dedup key: [`normalize_url`], except that an
+/// archive.org snapshot keeps its wrapper (only its tracking parameters
+/// and fragment are dropped), so it never shares a key with the live
+/// article.
+pub(crate) fn cache_key_url(url: &str) -> String {
+    normalize(url, !is_archive_snapshot(url))
+}
+
+/// Whether `url` is a `web.archive.org/web/<timestamp>/<url>` snapshot.
+pub(crate) fn is_archive_snapshot(url: &str) -> bool {
+    Url::parse(url.trim()).is_ok_and(|parsed| {
+        parsed.host_str() == Some("web.archive.org") && unwrap_url(&parsed).is_some()
+    })
+}
+
+/// Whether `url` is an AMP rendering of an article (an AMP-cache or
+/// AMP-viewer URL, or an AMP path or query variant): it shares the
+/// article's [`cache_key_url`], but not its markup.
+pub(crate) fn is_amp_variant(url: &str) -> bool {
+    !is_archive_snapshot(url) && normalize(url, true) != normalize(url, false)
+}
+
+/// [`normalize_url`] when `fold_variants` is set; otherwise only the
+/// tracking parameters and the fragment are dropped.
+fn normalize(url: &str, fold_variants: bool) -> String {
+    let trimmed = url.trim();
+    let Ok(mut parsed) = Url::parse(trimmed) else {
+        return trimmed.to_string();
+    };
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return trimmed.to_string();
+    }
+    if fold_variants {
+        if let Some(inner) = unwrap_url(&parsed) {
+            if inner != trimmed {
+                return normalize(&inner, true);
+            }
+        }
+    }
+
+    parsed.set_fragment(None);
+    let pairs: Vec<(String, String)> = parsed
+        .query_pairs()
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    let kept: Vec<&(String, String)> = pairs
+        .iter()
+        .filter(|(key, value)| {
+            !(is_tracking_parameter(key) || (fold_variants && is_amp_parameter(key, value)))
+        })
+        .collect();
+    if kept.is_empty() {
+        parsed.set_query(None);
+    } else if kept.len() < pairs.len() {
+        parsed.set_query(None);
+        parsed
+            .query_pairs_mut()
+            .extend_pairs(kept.iter().map(|(key, value)| (key, value)));
+    }
+
+    if let Some(path) = non_amp_path(parsed.path()).filter(|_| fold_variants) {
+        parsed.set_path(&path);
+    }
+    parsed.to_string()
+}
+
+/// The canonical URL of the page at `page_url`: the normalized
+/// ([`normalize_url`]) `<link rel="canonical">`, else `og:url`, else
+/// `page_url`. A declared canonical pointing at the site's home page from
+/// a deeper page (a common theme misconfiguration) is ignored.
+///
+/// ```
+/// use uninews::canonical_url;
+/// let html = r#"<html><head>
+///   <meta property="og:url" content="https://news.example/transit?utm_medium=social">
+/// </head></html>"#;
+/// assert_eq!(
+///     canonical_url("https://news.example/transit?fbclid=abc", html),
+///     "https://news.example/transit"
+/// );
+/// ```
+pub fn canonical_url(page_url: &str, html: &str) -> String {
+    canonical_url_in(page_url, &Html::parse_document(html))
+}
+
+/// [`canonical_url`] over an already parsed document.
+pub(crate) fn canonical_url_in(page_url: &str, document: &Html) -> String {
+    let selector = Selector::parse(
+        r#"link[rel~="canonical"][href], meta[property="og:url"][content], meta[name="og:url"][content]"#,
+    )
+    .expect("valid canonical selector");
+    let page = Url::parse(page_url).ok();
+    let declared = |attribute: &str| {
+        document.select(&selector).find_map(|element| {
+            let href = element.value().attr(attribute)?;
+            let url = page.as_ref()?.join(href.trim()).ok()?;
+            (matches!(url.scheme(), "http" | "https")
+                && !is_misplaced_home_page(&url, page.as_ref()?))
+            .then_some(url)
+        })
+    };
+    // `<link rel="canonical">` wins over `og:url` wherever each appears.
+    declared("href")
+        .or_else(|| declared("content"))
+        .map(|url| normalize_url(url.as_str()))
+        .unwrap_or_else(|| normalize_url(page_url))
+}
+
+impl Scraper {
+    /// The URL [`Scraper::scrape`] keys the cache by for `url` when no
+    /// post is cached under `url` itself: `url`'s redirects (on any host)
+    /// are followed with this scraper's web client, and where they end is
+    /// normalized ([`normalize_url`]), archive.org snapshots keeping their
+    /// wrapper. A link whose redirects cannot be followed is only
+    /// normalized.
+    pub async fn resolve_url(&self, url: &str) -> String {
+        let key = cache_key_url(url);
+        match follow_redirects(self.web_client(), url).await {
+            Some(resolved_url) if cache_key_url(&resolved_url) != key => {
+                emit_event(ScrapeEvent::ShortLinkResolved {
+                    url: url.to_string(),
+                    resolved_url: resolved_url.clone(),
+                });
+                cache_key_url(&resolved_url)
+            }
+            _ => key,
+        }
+    }
+}
+
+/// Follow `url`'s redirects and return where they end (`url` itself when
+/// it does not redirect); `None` when the request fails. Works for any
+/// host; the response body is not read.
+pub(crate) async fn follow_redirects(client: &Client, url: &str) -> Option<String> {
+    let response = client.get(url).send().await.ok()?;
+    let final_url = response.url().as_str().trim().to_string();
+    (!final_url.is_empty()).then_some(final_url)
+}
+
+/// The URL a Google AMP cache, Google AMP viewer, or archive.org snapshot
+/// URL wraps.
+fn unwrap_url(url: &Url) -> Option<String> {
+    let host = url.host_str()?;
+    let path = url.path();
+    let wrapped = if host.ends_with(".cdn.ampproject.org") {
+        path.strip_prefix("/c/")
+            .or_else(|| path.strip_prefix("/v/"))?
+    } else if matches!(host, "www.google.com" | "google.com") {
+        path.strip_prefix("/amp/")?
+    } else if host == "web.archive.org" {
+        // `/web/<timestamp>[id_]/<original URL>`
+        let rest = path.strip_prefix("/web/")?;
+        let (timestamp, original) = rest.split_once('/')?;
+        let (scheme, rest) = original.split_once(':')?;
+        if !timestamp.starts_with(|c: char| c.is_ascii_digit())
+            || !matches!(scheme, "http" | "https")
+        {
+            return None;
+        }
+        let mut original = format!("{scheme}://{}", rest.trim_start_matches('/'));
+        if let Some(query) = url.query() {
+            original.push('?');
+            original.push_str(query);
+        }
+        return Some(original);
+    } else {
+        return None;
+    };
+    let (scheme, rest) = match wrapped.strip_prefix("s/") {
+        Some(rest) => ("https", rest),
+        None => ("http", wrapped),
+    };
+    let mut inner = format!("{scheme}://{rest}");
+    if let Some(query) = url.query() {
+        inner.push('?');
+        inner.push_str(query);
+    }
+    Some(inner)
+}
+
+/// Whether `key=value` only selects the AMP rendering (`amp`, `amp=1`,
+/// `outputType=amp`).
+fn is_amp_parameter(key: &str, value: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    let value = value.trim().to_ascii_lowercase();
+    (key == "amp" && matches!(value.as_str(), "" | "1" | "true"))
+        || (matches!(key.as_str(), "outputtype" | "output" | "format") && value == "amp")
+}
+
+/// `path` without its AMP marker (`/amp/story` → `/story`, `/story/amp/` →
+/// `/story/`, `/story.amp.html` → `/story.html`); `None` when it has
+/// none.
+fn non_amp_path(path: &str) -> Option<String> {
+    let mut segments: Vec<String> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(str::to_string)
+        .collect();
+    let last = segments.last()?.to_ascii_lowercase();
+    if last == "amp" {
+        segments.pop();
+    } else if segments.len() > 1 && segments[0].eq_ignore_ascii_case("amp") {
+        segments.remove(0);
+    } else if let Some(stem) = last.strip_suffix(".amp.html") {
+        let stem_len = stem.len();
+        let last = segments.last_mut()?;
+        *last = format!("{}.html", &last[..stem_len]);
+    } else if let Some(stem) = last.strip_suffix(".amp") {
+        let stem_len = stem.len();
+        segments.last_mut()?.truncate(stem_len);
+    } else {
+        return None;
+    }
+    let mut normalized = format!("/{}", segments.join("/"));
+    if path.ends_with('/') && normalized != "/" {
+        normalized.push('/');
+    }
+    Some(normalized)
+}
+
+/// Whether `canonical` is the site's home page while `page` is not.
+fn is_misplaced_home_page(canonical: &Url, page: &Url) -> bool {
+    canonical.path() == "/"
+        && canonical.query().is_none()
+        && page.path() != "/"
+        && canonical.host_str() == page.host_str()
+}
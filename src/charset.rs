@@ -0,0 +1,221 @@
+//! Character-set detection and decoding for fetched pages.
+//!
+//! Plenty of news sites still serve Shift_JIS, EUC-KR, GBK, windows-1251,
+//! or ISO-8859-1 pages. Decoding those as UTF-8 turns every non-ASCII
+//! character into mojibake before extraction ever runs, so text responses
+//! are decoded with the first encoding found, in order:
+//!
+//! 1. **Byte order mark** — a UTF-8 or UTF-16 BOM at the start of the body.
+//! 2. **`Content-Type` header** — its `charset=` parameter.
+//! 3. **In-document declaration** — `<meta charset>` or
+//!    `<meta http-equiv="Content-Type" content="…; charset=…">` in the
+//!    first [`PRESCAN_BYTES`] of the body, or the `encoding` of an XML
+//!    declaration (feeds, sitemaps).
+//! 4. **Statistical detection** — [`chardetng`], hinted with the URL's
+//!    top-level domain. It also recognizes valid UTF-8.
+//!
+//! Decoding uses [`encoding_rs`], which maps labels the way browsers do
+//! (`iso-8859-1` and `latin1` decode as windows-1252, `gb2312` as GBK).
+
+use chardetng::EncodingDetector;
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+/// How much of the body is scanned for a `<meta>` declaration (4 KiB;
+/// browsers scan 1 KiB, but CMS templates often put the declaration after
+/// long `<script>` blocks).
+pub const PRESCAN_BYTES: usize = 4 * 1024;
+
+/// Where a page's character encoding came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CharsetSource {
+    /// A UTF-8 or UTF-16 byte order mark.
+    ByteOrderMark,
+    /// The `charset` parameter of the `Content-Type` header.
+    ContentType,
+    /// A `<meta charset>` / `http-equiv` declaration.
+    Meta,
+    /// The `encoding` of an `<?xml …?>` declaration.
+    XmlDeclaration,
+    /// Statistical detection over the body.
+    Detected,
+}
+
+/// The character encoding a body is decoded with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DetectedCharset {
+    /// The encoding's canonical name (`UTF-8`, `Shift_JIS`,
+    /// `windows-1251`, …).
+    pub encoding: &'static str,
+    /// Where the encoding came from.
+    pub source: CharsetSource,
+}
+
+impl DetectedCharset {
+    fn new(encoding: &'static Encoding, source: CharsetSource) -> Self {
+        Self {
+            encoding: encoding.name(),
+            source,
+        }
+    }
+
+    pub(crate) fn to_encoding(self) -> &'static Encoding {
+        Encoding::for_label(self.encoding.as_bytes()).unwrap_or(UTF_8)
+    }
+}
+
+/// Detect the character encoding of a response `body` served with
+/// `content_type` from `url` (whose top-level domain hints the
+/// statistical detector).
+///
+/// ```
+/// use uninews::{detect_charset, CharsetSource};
+///
+/// let page = b"<html><head><meta charset=\"euc-kr\"></head></html>";
+/// let charset = detect_charset(page, Some("text/html"), None);
+/// assert_eq!(charset.encoding, "EUC-KR");
+/// assert_eq!(charset.source, CharsetSource::Meta);
+/// ```
+pub fn detect_charset(
+    body: &[u8],
+    content_type: Option<&str>,
+    url: Option<&str>,
+) -> DetectedCharset {
+    if let Some((encoding, _)) = Encoding::for_bom(body) {
+        return DetectedCharset::new(encoding, CharsetSource::ByteOrderMark);
+    }
+    if let Some(encoding) = content_type.and_then(header_charset) {
+        return DetectedCharset::new(encoding, CharsetSource::ContentType);
+    }
+    let prescan = &body[..body.len().min(PRESCAN_BYTES)];
+    if let Some(encoding) = xml_declaration_charset(prescan) {
+        return DetectedCharset::new(encoding, CharsetSource::XmlDeclaration);
+    }
+    if let Some(encoding) = meta_charset(prescan) {
+        return DetectedCharset::new(encoding, CharsetSource::Meta);
+    }
+
+    let mut detector = EncodingDetector::new();
+    detector.feed(body, true);
+    let tld = url.and_then(top_level_domain);
+    let encoding = detector.guess(tld.as_deref().map(str::as_bytes), true);
+    DetectedCharset::new(encoding, CharsetSource::Detected)
+}
+
+/// Decode a response `body` to text with [`detect_charset`]'s encoding.
+/// Malformed sequences become U+FFFD; a byte order mark is dropped.
+pub fn decode_body(
+    body: &[u8],
+    content_type: Option<&str>,
+    url: Option<&str>,
+) -> (String, DetectedCharset) {
+    let charset = detect_charset(body, content_type, url);
+    (decode_with(body, charset), charset)
+}
+
+/// Decode `body` with an already detected `charset`.
+pub(crate) fn decode_with(body: &[u8], charset: DetectedCharset) -> String {
+    let (text, _) = charset.to_encoding().decode_with_bom_removal(body);
+    text.into_owned()
+}
+
+/// Whether a response with `content_type` is text worth decoding (HTML,
+/// XML, JSON, plain text, or undeclared).
+pub(crate) fn is_text_content(content_type: Option<&str>) -> bool {
+    let Some(content_type) = content_type else {
+        return true;
+    };
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    mime.is_empty()
+        || mime.starts_with("text/")
+        || ["html", "xml", "json", "javascript"]
+            .iter()
+            .any(|kind| mime.contains(kind))
+}
+
+/// The `charset` parameter of a `Content-Type` value.
+fn header_charset(content_type: &str) -> Option<&'static Encoding> {
+    content_type.split(';').skip(1).find_map(|parameter| {
+        let (name, value) = parameter.split_once('=')?;
+        if !name.trim().eq_ignore_ascii_case("charset") {
+            return None;
+        }
+        Encoding::for_label(value.trim().trim_matches(['"', '\'']).as_bytes())
+    })
+}
+
+/// The encoding named by the first `<meta>` tag declaring one. UTF-16
+/// declarations are read as UTF-8 (a UTF-16 page would have been caught
+/// by its BOM, so the declaration is wrong), and `x-user-defined` as
+/// windows-1252, as browsers do.
+fn meta_charset(prescan: &[u8]) -> Option<&'static Encoding> {
+    let lower = prescan.to_ascii_lowercase();
+    let mut rest = lower.as_slice();
+    while let Some(start) = find(rest, b"<meta") {
+        rest = &rest[start + b"<meta".len()..];
+        let tag = &rest[..find(rest, b">").unwrap_or(rest.len())];
+        let Some(label) = find(tag, b"charset").and_then(|at| attribute_value(&tag[at + 7..]))
+        else {
+            continue;
+        };
+        let Some(encoding) = Encoding::for_label(label) else {
+            continue;
+        };
+        return Some(if encoding == UTF_16BE || encoding == UTF_16LE {
+            UTF_8
+        } else if encoding.name() == "x-user-defined" {
+            WINDOWS_1252
+        } else {
+            encoding
+        });
+    }
+    None
+}
+
+/// The `encoding` of an XML declaration at the start of the body.
+fn xml_declaration_charset(prescan: &[u8]) -> Option<&'static Encoding> {
+    let declaration = prescan.strip_prefix(b"<?xml")?;
+    let declaration = &declaration[..find(declaration, b"?>")?];
+    let at = find(declaration, b"encoding")?;
+    Encoding::for_label(attribute_value(&declaration[at + b"encoding".len()..])?)
+}
+
+/// The value after `=` in `bytes` (optionally quoted), up to the closing
+/// quote, whitespace, `;`, `/`, or `>`.
+fn attribute_value(bytes: &[u8]) -> Option<&[u8]> {
+    let bytes = bytes
+        .trim_ascii_start()
+        .strip_prefix(b"=")?
+        .trim_ascii_start();
+    let bytes = bytes
+        .strip_prefix(b"\"")
+        .or_else(|| bytes.strip_prefix(b"'"))
+        .unwrap_or(bytes);
+    let end = bytes
+        .iter()
+        .position(|byte| {
+            matches!(byte, b'"' | b'\'' | b';' | b'/' | b'>') || byte.is_ascii_whitespace()
+        })
+        .unwrap_or(bytes.len());
+    (end > 0).then(|| &bytes[..end])
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// The lowercase top-level domain of `url` (`jp`, `ru`, …).
+fn top_level_domain(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    let tld = url.host_str()?.rsplit('.').next()?.to_ascii_lowercase();
+    (tld.len() >= 2 && tld.bytes().all(|byte| byte.is_ascii_alphabetic())).then_some(tld)
+}
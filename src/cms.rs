@@ -0,0 +1,550 @@
+//! WordPress and Ghost REST API shortcut for CMS-backed pages.
+//!
+//! Blog themes wrap the post body in navigation, share bars, related-post
+//! widgets, and comment forms, but the CMS behind them serves the clean
+//! body from its REST API. When a fetched page identifies its CMS, the web
+//! pipeline reads the post from that API instead of extracting it from the
+//! DOM:
+//!
+//! - **WordPress**: `<link rel="https://api.w.org/">` (the API root), the
+//!   post's own `<link rel="alternate" type="application/json">`
+//!   endpoint, or a `WordPress` generator `<meta>`. The post is resolved
+//!   via `/wp/v2/posts?slug=…` (then `/wp/v2/pages?slug=…`) with its
+//!   author, featured media, and terms embedded.
+//! - **Ghost**: the Content API URL and key the Portal and search scripts
+//!   carry (`data-api` / `data-sodo-search` and `data-key`). The post is
+//!   resolved via `/posts/slug/…/` (then `/pages/slug/…/`).
+//!
+//! The API's rendered content, title, authors, dates, categories, tags, and
+//! featured image replace the DOM extraction; any failure (no slug, no
+//! such post, a password-protected post, an API disabled by a security
+//! plugin) leaves the page to the DOM extraction as before. The shortcut
+//! is on by default; [`crate::ScrapeOptions::cms_api`] and
+//! [`UNINEWS_CMS_API_ENV`] turn it off.
+
+use std::env;
+use std::fmt;
+
+use reqwest::Url;
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::FallbackStage;
+use crate::web::fetch_api_page;
+use crate::{Post, PostMetadata, Provenance, Scraper};
+
+/// Environment variable that toggles the CMS API shortcut.
+///
+/// The shortcut is on by default; set the variable to `0`, `false`, `no`,
+/// or `off` (any case) to disable it.
+pub const UNINEWS_CMS_API_ENV: &str = "UNINEWS_CMS_API";
+
+/// Whether the CMS API shortcut is enabled.
+///
+/// Enabled by default; disabled when [`UNINEWS_CMS_API_ENV`] is set to
+/// `0`, `false`, `no`, or `off` (case-insensitive, surrounding whitespace
+/// ignored).
+pub fn cms_api_enabled() -> bool {
+    match env::var(UNINEWS_CMS_API_ENV) {
+        Ok(value) => !matches!(
+            value.trim().to_ascii_lowercase().as_str(),
+            "0" | "false" | "no" | "off"
+        ),
+        Err(_) => true,
+    }
+}
+
+/// A content management system with a REST API uninews can read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CmsKind {
+    #[serde(rename = "wordpress")]
+    WordPress,
+    Ghost,
+}
+
+impl CmsKind {
+    /// Stable snake_case name, identical to the serde representation.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::WordPress => "wordpress",
+            Self::Ghost => "ghost",
+        }
+    }
+}
+
+impl fmt::Display for CmsKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The REST API a page's CMS exposes, as detected by [`detect_cms_api`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CmsApi {
+    #[serde(rename = "wordpress")]
+    WordPress {
+        /// The API root (`https://example.com/wp-json/`, or
+        /// `https://example.com/?rest_route=/` without pretty permalinks).
+        api_root: String,
+        /// The post's own endpoint, when the page links it
+        /// (`…/wp-json/wp/v2/posts/123`).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        post_endpoint: Option<String>,
+    },
+    Ghost {
+        /// The Content API root (`https://example.com/ghost/api/content/`).
+        api_root: String,
+        /// The public Content API key.
+        key: String,
+    },
+}
+
+impl CmsApi {
+    /// Which CMS the API belongs to.
+    pub fn kind(&self) -> CmsKind {
+        match self {
+            Self::WordPress { .. } => CmsKind::WordPress,
+            Self::Ghost { .. } => CmsKind::Ghost,
+        }
+    }
+
+    /// The API URLs that may hold the post at `page_url`, in the order
+    /// they are tried: the linked post endpoint, else the post and then the
+    /// page with the URL's slug (or `?p=` / `?page_id=` id). Empty when the
+    /// URL names no post (a home page).
+    pub fn post_urls(&self, page_url: &str) -> Vec<String> {
+        let Ok(page) = Url::parse(page_url) else {
+            return Vec::new();
+        };
+        match self {
+            Self::WordPress {
+                post_endpoint: Some(endpoint),
+                ..
+            } => vec![with_query(endpoint, "_embed")],
+            Self::WordPress { api_root, .. } => {
+                let query_id = |name: &str| {
+                    page.query_pairs()
+                        .find(|(key, value)| key == name && value.parse::<u64>().is_ok())
+                        .map(|(_, value)| value.into_owned())
+                };
+                if let Some(id) = query_id("p") {
+                    return vec![wordpress_url(
+                        api_root,
+                        &format!("wp/v2/posts/{}", id),
+                        "_embed",
+                    )];
+                }
+                if let Some(id) = query_id("page_id") {
+                    return vec![wordpress_url(
+                        api_root,
+                        &format!("wp/v2/pages/{}", id),
+                        "_embed",
+                    )];
+                }
+                let Some(slug) = page_slug(&page) else {
+                    return Vec::new();
+                };
+                ["posts", "pages"]
+                    .iter()
+                    .map(|kind| {
+                        wordpress_url(
+                            api_root,
+                            &format!("wp/v2/{}", kind),
+                            &format!("slug={}&_embed", slug),
+                        )
+                    })
+                    .collect()
+            }
+            Self::Ghost { api_root, key } => {
+                let Some(slug) = page_slug(&page) else {
+                    return Vec::new();
+                };
+                ["posts", "pages"]
+                    .iter()
+                    .map(|kind| {
+                        format!(
+                            "{}{}/slug/{}/?key={}&include=authors,tags",
+                            api_root, kind, slug, key
+                        )
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// A post read from a CMS REST API.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CmsPost {
+    pub cms: CmsKind,
+    /// The title, as plain text (HTML entities decoded).
+    pub title: String,
+    /// The rendered post body, as HTML.
+    pub html: String,
+    /// The excerpt, as plain text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub excerpt: Option<String>,
+    /// The post's permalink (Ghost's `canonical_url` when set).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+    /// Publication time (RFC 3339).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_published: Option<String>,
+    /// Last modification time (RFC 3339).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_modified: Option<String>,
+    /// WordPress categories, or Ghost's primary tag.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+    /// Tags (Ghost's internal `#tags` excluded).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub featured_image: Option<String>,
+}
+
+impl CmsPost {
+    /// The post as [`PostMetadata`]: a `BlogPosting` whose section is the
+    /// first category and whose keywords are the tags.
+    pub fn metadata(&self) -> PostMetadata {
+        PostMetadata {
+            schema_type: Some("BlogPosting".to_string()),
+            headline: (!self.title.is_empty()).then(|| self.title.clone()),
+            description: self.excerpt.clone(),
+            authors: self.authors.clone(),
+            date_published: self.date_published.clone(),
+            date_modified: self.date_modified.clone(),
+            section: self.categories.first().cloned(),
+            keywords: self.tags.clone(),
+            image_url: self.featured_image.clone(),
+            ..PostMetadata::default()
+        }
+    }
+
+    /// The post as a [`Post`] for the page at `final_url`.
+    pub(crate) fn to_post(&self, final_url: &str, title_override: Option<&str>) -> Post {
+        let metadata = self.metadata();
+        Post {
+            title: title_override
+                .filter(|title| !title.trim().is_empty())
+                .map(|title| title.trim().to_string())
+                .unwrap_or_else(|| self.title.clone()),
+            content: self.html.clone(),
+            featured_image_url: self.featured_image.clone().unwrap_or_default(),
+            publication_date: self.date_published.clone(),
+            author: metadata.author_line(),
+            provenance: Some(Provenance {
+                canonical_url: self.url.clone(),
+                ..Provenance::new(FallbackStage::CmsApi, final_url)
+            }),
+            metadata: Some(metadata),
+            ..Post::default()
+        }
+    }
+}
+
+/// Detect the CMS behind the HTML page at `page_url` from its API
+/// discovery links, generator `<meta>`, and Ghost script attributes.
+/// `None` for other pages, and for Ghost sites that do not publish a
+/// Content API key.
+///
+/// ```
+/// use uninews::{detect_cms_api, CmsApi};
+/// let html = r#"<html><head>
+///   <link rel="https://api.w.org/" href="https://blog.example/wp-json/">
+/// </head><body></body></html>"#;
+/// assert_eq!(
+///     detect_cms_api("https://blog.example/2026/10/transit-plan/", html),
+///     Some(CmsApi::WordPress {
+///         api_root: "https://blog.example/wp-json/".to_string(),
+///         post_endpoint: None,
+///     })
+/// );
+/// ```
+pub fn detect_cms_api(page_url: &str, html: &str) -> Option<CmsApi> {
+    let page = Url::parse(page_url).ok()?;
+    let document = Html::parse_document(html);
+    let first_attr = |css: &str, attr: &str| {
+        document
+            .select(&selector(css))
+            .find_map(|element| element.value().attr(attr))
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+    let resolve = |href: &str| page.join(href).ok().map(String::from);
+    let generator = first_attr(r#"meta[name="generator"]"#, "content")
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    let api_root = first_attr(r#"link[rel="https://api.w.org/"]"#, "href").and_then(resolve);
+    let post_endpoint = document
+        .select(&selector(r#"link[rel="alternate"][href]"#))
+        .filter(|link| {
+            link.value()
+                .attr("type")
+                .is_some_and(|kind| kind.trim().eq_ignore_ascii_case("application/json"))
+        })
+        .filter_map(|link| link.value().attr("href"))
+        .find(|href| href.contains("/wp/v2/"))
+        .and_then(resolve);
+    if api_root.is_some() || post_endpoint.is_some() || generator.starts_with("wordpress") {
+        let api_root = api_root.unwrap_or_else(|| format!("{}/wp-json/", origin(&page)));
+        return Some(CmsApi::WordPress {
+            api_root,
+            post_endpoint,
+        });
+    }
+
+    let key = first_attr("script[data-key]", "data-key")?;
+    let api_root = first_attr("script[data-key][data-api]", "data-api")
+        .and_then(resolve)
+        .or_else(|| {
+            let site = first_attr("script[data-key][data-sodo-search]", "data-sodo-search")
+                .and_then(resolve)?;
+            Some(format!("{}/ghost/api/content/", site.trim_end_matches('/')))
+        })?;
+    Some(CmsApi::Ghost {
+        api_root: format!("{}/", api_root.trim_end_matches('/')),
+        key: key.to_string(),
+    })
+}
+
+/// Parse a WordPress REST API post (or page) response: one post object,
+/// or the array a `?slug=` query returns. Fails for empty results, API
+/// errors, and password-protected posts.
+pub fn parse_wordpress_post(json: &str) -> Result<CmsPost, String> {
+    let value: Value = serde_json::from_str(json)
+        .map_err(|error| format!("Invalid WordPress API response: {}", error))?;
+    let post = match &value {
+        Value::Array(posts) => posts
+            .first()
+            .ok_or_else(|| "WordPress has no post with that slug".to_string())?,
+        post => post,
+    };
+    if let Some(code) = post.get("code").and_then(Value::as_str) {
+        let message = post.get("message").and_then(Value::as_str).unwrap_or(code);
+        return Err(format!("WordPress API error: {}", message));
+    }
+    let rendered = |field: &str| post.get(field)?.get("rendered")?.as_str();
+    if post
+        .pointer("/content/protected")
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+    {
+        return Err("The WordPress post is password-protected".to_string());
+    }
+    let html = rendered("content")
+        .filter(|html| !html.trim().is_empty())
+        .ok_or_else(|| "The WordPress post has no content".to_string())?;
+
+    let embedded = post.get("_embedded");
+    let authors = embedded
+        .and_then(|embedded| embedded.get("author")?.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|author| author.get("name")?.as_str())
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect();
+    let featured_image = embedded
+        .and_then(|embedded| embedded.get("wp:featuredmedia")?.get(0)?.get("source_url"))
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let mut categories = Vec::new();
+    let mut tags = Vec::new();
+    let terms = embedded
+        .and_then(|embedded| embedded.get("wp:term")?.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_array)
+        .flatten();
+    for term in terms {
+        let Some(name) = term.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+        let name = html_text(name);
+        match term.get("taxonomy").and_then(Value::as_str) {
+            Some("category") => categories.push(name),
+            Some("post_tag") => tags.push(name),
+            _ => {}
+        }
+    }
+    let gmt = |field: &str, local: &str| {
+        let (date, utc) = match post.get(field).and_then(Value::as_str) {
+            Some(date) => (date, true),
+            None => (post.get(local)?.as_str()?, false),
+        };
+        Some(if utc && !has_offset(date) {
+            format!("{}Z", date)
+        } else {
+            date.to_string()
+        })
+    };
+
+    Ok(CmsPost {
+        cms: CmsKind::WordPress,
+        title: rendered("title").map(html_text).unwrap_or_default(),
+        html: html.to_string(),
+        excerpt: rendered("excerpt")
+            .map(html_text)
+            .filter(|excerpt| !excerpt.is_empty()),
+        url: post.get("link").and_then(Value::as_str).map(str::to_string),
+        authors,
+        date_published: gmt("date_gmt", "date"),
+        date_modified: gmt("modified_gmt", "modified"),
+        categories,
+        tags,
+        featured_image,
+    })
+}
+
+/// Parse a Ghost Content API post (or page) response (`{"posts": […]}`
+/// or `{"pages": […]}`). Fails for empty results, API errors, and posts
+/// without HTML (members-only content).
+pub fn parse_ghost_post(json: &str) -> Result<CmsPost, String> {
+    let value: Value = serde_json::from_str(json)
+        .map_err(|error| format!("Invalid Ghost API response: {}", error))?;
+    if let Some(error) = value.pointer("/errors/0") {
+        let message = error
+            .get("message")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown error");
+        return Err(format!("Ghost API error: {}", message));
+    }
+    let post = ["posts", "pages"]
+        .iter()
+        .find_map(|kind| value.get(*kind)?.get(0))
+        .ok_or_else(|| "Ghost has no post with that slug".to_string())?;
+    let text = |field: &str| {
+        post.get(field)
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(str::to_string)
+    };
+    let html = text("html").ok_or_else(|| "The Ghost post has no public content".to_string())?;
+
+    let names = |field: &str| -> Vec<String> {
+        post.get(field)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter(|item| item.get("visibility").and_then(Value::as_str) != Some("internal"))
+            .filter_map(|item| item.get("name")?.as_str())
+            .map(str::trim)
+            .filter(|name| !name.is_empty() && !name.starts_with('#'))
+            .map(str::to_string)
+            .collect()
+    };
+    let mut authors = names("authors");
+    if authors.is_empty() {
+        authors.extend(
+            post.pointer("/primary_author/name")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        );
+    }
+
+    Ok(CmsPost {
+        cms: CmsKind::Ghost,
+        title: text("title").unwrap_or_default(),
+        html,
+        excerpt: text("custom_excerpt").or_else(|| text("excerpt")),
+        url: text("canonical_url").or_else(|| text("url")),
+        authors,
+        date_published: text("published_at"),
+        date_modified: text("updated_at"),
+        categories: post
+            .pointer("/primary_tag/name")
+            .and_then(Value::as_str)
+            .filter(|name| !name.starts_with('#'))
+            .map(str::to_string)
+            .into_iter()
+            .collect(),
+        tags: names("tags"),
+        featured_image: text("feature_image"),
+    })
+}
+
+/// Read the post at `page_url` from `api`, trying each of
+/// [`CmsApi::post_urls`] in turn. Returns the API URL that answered with
+/// the post, or the last failure.
+pub(crate) async fn fetch_cms_post(
+    scraper: &Scraper,
+    api: &CmsApi,
+    page_url: &str,
+) -> Result<(String, CmsPost), String> {
+    let parse = match api.kind() {
+        CmsKind::WordPress => parse_wordpress_post,
+        CmsKind::Ghost => parse_ghost_post,
+    };
+    let mut last_error = format!("No {} API URL for {}", api.kind(), page_url);
+    for api_url in api.post_urls(page_url) {
+        let result = fetch_api_page(scraper, &api_url, "application/json")
+            .await
+            .and_then(|page| parse(page.json_body()?));
+        match result {
+            Ok(post) => return Ok((api_url, post)),
+            Err(error) => last_error = error,
+        }
+    }
+    Err(last_error)
+}
+
+/// `route` and `query` under a WordPress API root, which is either a path
+/// (`…/wp-json/`) or a `?rest_route=/` query.
+fn wordpress_url(api_root: &str, route: &str, query: &str) -> String {
+    with_query(&format!("{}{}", api_root, route), query)
+}
+
+/// `url` with `query` appended to its query string.
+fn with_query(url: &str, query: &str) -> String {
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{}{}{}", url, separator, query)
+}
+
+/// The post slug of a page URL: its last path segment, skipping a
+/// trailing `amp` segment. `None` for the site root.
+fn page_slug(page: &Url) -> Option<String> {
+    let mut segments: Vec<&str> = page
+        .path_segments()?
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    if segments.last() == Some(&"amp") {
+        segments.pop();
+    }
+    let slug = segments.last()?;
+    let slug = slug.strip_suffix(".html").unwrap_or(slug);
+    (!slug.is_empty()).then(|| slug.to_string())
+}
+
+/// `scheme://host[:port]` of `url`.
+fn origin(url: &Url) -> String {
+    url.origin().ascii_serialization()
+}
+
+/// Whether an ISO 8601 timestamp ends with a UTC offset or `Z`.
+fn has_offset(date: &str) -> bool {
+    let Some((_, time)) = date.split_once('T') else {
+        return false;
+    };
+    time.ends_with(['Z', 'z']) || time.contains(['+', '-'])
+}
+
+/// The text of an HTML fragment, entities decoded and whitespace
+/// collapsed.
+fn html_text(html: &str) -> String {
+    let fragment = Html::parse_fragment(html);
+    let text: String = fragment.root_element().text().collect();
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn selector(css: &str) -> Selector {
+    Selector::parse(css).expect("hard-coded CSS selector must be valid")
+}
@@ -0,0 +1,405 @@
+//! Article-link discovery on section pages and homepages.
+//!
+//! A section page (`/technology`, a homepage) is a list of links, not an
+//! article. [`Scraper::discover_article_links`] fetches one and ranks its
+//! anchors as probable article URLs ([`rank_article_links`]), so the
+//! caller can hand the best ones to [`Scraper::scrape_many`].
+//!
+//! Each same-site anchor is scored on the shape of its URL and its
+//! placement in the page:
+//!
+//! - **Path depth**: sections sit one level deep, articles deeper.
+//! - **Date segments**: `/2026/10/17/…` or `…-2026-10-17-…`.
+//! - **Slug shape**: a multi-word hyphenated slug, a long numeric id, or
+//!   an `.html` page.
+//! - **Anchor text**: headline-length text, inside an `<h1>`–`<h4>` or an
+//!   `<article>`; links in `<nav>`, `<header>`, `<footer>`, and `<aside>`
+//!   are penalized.
+//!
+//! Off-site links, the homepage, assets, and utility paths (`/tag/…`,
+//! `/author/…`, `/login`, `/newsletter`, …) are dropped outright. Links
+//! are deduplicated by their normalized URL ([`crate::normalize_url`]:
+//! fragments, tracking parameters, and AMP variants ignored) and those
+//! scoring below [`MIN_ARTICLE_LINK_SCORE`] are discarded.
+//!
+//! JavaScript application shells (axios.com/technology and the like),
+//! whose links only exist in the rendered DOM, are rendered with
+//! Playwright when it is enabled in the [`crate::ScrapeOptions`] — the
+//! same triggers as the scrape pipeline's thin-content render.
+//!
+//! ```rust,no_run
+//! use futures::StreamExt;
+//! use uninews::{ScrapeOptions, Scraper};
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let scraper = Scraper::new(ScrapeOptions::from_env());
+//! let links = scraper
+//!     .discover_article_links("https://www.axios.com/technology")
+//!     .await
+//!     .unwrap_or_default();
+//! let urls = links.into_iter().take(10).map(|link| link.url);
+//! let mut posts = scraper.scrape_many(urls, "english");
+//! while let Some((url, post)) = posts.next().await {
+//!     println!("{url} -> {}", post.title);
+//! }
+//! # }
+//! ```
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use reqwest::Url;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
+
+use crate::archive::looks_like_bot_protection;
+use crate::browser::fetch_rendered_dom_with_playwright_timeout;
+use crate::canonical::normalize_url;
+use crate::events::{emit_event, ScrapeEvent};
+use crate::web::{fetch_page, JS_SHELL_MAX_BYTES};
+use crate::{ScrapeError, ScrapeOptions, Scraper};
+
+/// Minimum score for a link to be reported as a probable article.
+///
+/// A dated URL or a multi-word slug under a section clears it on its own;
+/// a bare section path (`/technology`) or a short utility page
+/// (`/about-us`) does not.
+pub const MIN_ARTICLE_LINK_SCORE: i32 = 4;
+
+/// Path segments that mark a listing or utility page, never an article.
+const NON_ARTICLE_SEGMENTS: [&str; 30] = [
+    "about",
+    "account",
+    "advertise",
+    "author",
+    "authors",
+    "careers",
+    "category",
+    "categories",
+    "cdn-cgi",
+    "contact",
+    "feed",
+    "feeds",
+    "jobs",
+    "login",
+    "logout",
+    "newsletter",
+    "newsletters",
+    "page",
+    "privacy",
+    "register",
+    "rss",
+    "search",
+    "signin",
+    "signup",
+    "subscribe",
+    "tag",
+    "tags",
+    "terms",
+    "wp-admin",
+    "wp-login.php",
+];
+
+/// File extensions an article page may carry; any other extension is an
+/// asset (image, stylesheet, archive, …).
+const ARTICLE_EXTENSIONS: [&str; 6] = ["html", "htm", "shtml", "php", "asp", "aspx"];
+
+/// A probable article link found on a section page.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArticleLink {
+    /// Absolute URL, without its fragment.
+    pub url: String,
+    /// The longest anchor text the page uses for this URL,
+    /// whitespace-collapsed (empty for image-only links).
+    pub text: String,
+    /// Heuristic score; higher is more article-like. Always at least
+    /// [`MIN_ARTICLE_LINK_SCORE`].
+    pub score: i32,
+}
+
+/// Rank the anchors of the section page `html` (served from `page_url`)
+/// as probable article links, best first; ties keep page order.
+///
+/// See the [module docs](self) for the scoring rules.
+///
+/// ```rust
+/// let html = r#"<nav><a href="/technology">Tech</a></nav>
+///     <h2><a href="/2026/10/17/council-approves-transit-plan">Council approves transit plan</a></h2>"#;
+/// let links = uninews::rank_article_links(html, "https://example.com/");
+/// assert_eq!(links.len(), 1);
+/// assert_eq!(
+///     links[0].url,
+///     "https://example.com/2026/10/17/council-approves-transit-plan"
+/// );
+/// ```
+pub fn rank_article_links(html: &str, page_url: &str) -> Vec<ArticleLink> {
+    let Ok(page) = Url::parse(page_url) else {
+        return Vec::new();
+    };
+    let Some(page_site) = page.host_str().map(site_of) else {
+        return Vec::new();
+    };
+    let mut page_without_fragment = page.clone();
+    page_without_fragment.set_fragment(None);
+
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("a[href]").expect("valid anchor selector");
+    let mut links: Vec<ArticleLink> = Vec::new();
+    let mut index_by_url: HashMap<String, usize> = HashMap::new();
+    for anchor in document.select(&selector) {
+        let Some(mut url) = anchor
+            .value()
+            .attr("href")
+            .and_then(|href| page.join(href.trim()).ok())
+        else {
+            continue;
+        };
+        url.set_fragment(None);
+        if !matches!(url.scheme(), "http" | "https")
+            || url == page_without_fragment
+            || !url
+                .host_str()
+                .is_some_and(|host| same_site(host, &page_site))
+        {
+            continue;
+        }
+        let text = anchor.text().collect::<Vec<_>>().join(" ");
+        let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let Some(score) = score_link(&url, &text, anchor) else {
+            continue;
+        };
+
+        let key = normalize_url(url.as_str());
+        match index_by_url.get(&key) {
+            Some(&index) => {
+                let link = &mut links[index];
+                link.score = link.score.max(score);
+                if text.len() > link.text.len() {
+                    link.text = text;
+                }
+            }
+            None => {
+                index_by_url.insert(key, links.len());
+                links.push(ArticleLink {
+                    url: url.into(),
+                    text,
+                    score,
+                });
+            }
+        }
+    }
+
+    links.retain(|link| link.score >= MIN_ARTICLE_LINK_SCORE);
+    links.sort_by_key(|link| Reverse(link.score));
+    links
+}
+
+impl Scraper {
+    /// Fetch the section page or homepage at `url` with this scraper's web
+    /// client and rank its anchors as probable article links
+    /// ([`rank_article_links`]).
+    ///
+    /// A page that fails to load, looks like a bot-protection wall or a
+    /// JavaScript shell, or yields no links is rendered with Playwright
+    /// when enabled, and the rendered DOM is ranked instead. Fails with
+    /// [`ScrapeError::Network`] / [`ScrapeError::HttpStatus`] when neither
+    /// the fetch nor the render produced a page; a page without article
+    /// links is an empty list, not an error.
+    pub async fn discover_article_links(&self, url: &str) -> Result<Vec<ArticleLink>, ScrapeError> {
+        let fetched = fetch_page(self, url).await;
+        let (page_url, plain_links, needs_render) = match &fetched {
+            Ok(page) if page.status.is_success() => {
+                let links = rank_article_links(&page.body, &page.final_url);
+                let needs_render = links.is_empty()
+                    || page.body.len() < JS_SHELL_MAX_BYTES
+                    || looks_like_bot_protection(page.status.as_u16(), &page.headers, &page.body);
+                (page.final_url.clone(), links, needs_render)
+            }
+            Ok(page) => (page.final_url.clone(), Vec::new(), true),
+            Err(_) => (url.to_string(), Vec::new(), true),
+        };
+
+        if needs_render && self.options().playwright_enabled() {
+            if let Some(links) = render_article_links(self, url, &page_url).await {
+                return Ok(discovered(page_url, links, true));
+            }
+        }
+
+        let page = fetched.map_err(ScrapeError::network)?;
+        if !page.status.is_success() {
+            return Err(ScrapeError::http_status(
+                page.status.as_u16(),
+                format!("Section request for {} returned HTTP {}", url, page.status),
+            ));
+        }
+        Ok(discovered(page_url, plain_links, false))
+    }
+}
+
+/// Fetch the section page at `url` and rank its article links with a
+/// [`Scraper`] built from `options`; see
+/// [`Scraper::discover_article_links`].
+pub async fn discover_article_links(
+    url: &str,
+    options: ScrapeOptions,
+) -> Result<Vec<ArticleLink>, ScrapeError> {
+    Scraper::new(options).discover_article_links(url).await
+}
+
+/// Render `url` with Playwright and rank the rendered DOM's links against
+/// `page_url`; `None` when the render fails, is still walled, or yields
+/// no article links.
+async fn render_article_links(
+    scraper: &Scraper,
+    url: &str,
+    page_url: &str,
+) -> Option<Vec<ArticleLink>> {
+    let render_permit = scraper.render_permit().await;
+    let html = fetch_rendered_dom_with_playwright_timeout(
+        url,
+        scraper.options().playwright_timeout_duration(),
+    )
+    .await;
+    drop(render_permit);
+    let html = html.ok()?;
+    if looks_like_bot_protection(200, &Default::default(), &html) {
+        return None;
+    }
+    let links = rank_article_links(&html, page_url);
+    (!links.is_empty()).then_some(links)
+}
+
+/// Emit [`ScrapeEvent::ArticleLinksDiscovered`] for `links` and return
+/// them.
+fn discovered(url: String, links: Vec<ArticleLink>, rendered: bool) -> Vec<ArticleLink> {
+    emit_event(ScrapeEvent::ArticleLinksDiscovered {
+        url,
+        links: links.len(),
+        rendered,
+    });
+    links
+}
+
+/// Score `url` (linked as `text` by `anchor`); `None` for links that can
+/// never be articles.
+fn score_link(url: &Url, text: &str, anchor: ElementRef) -> Option<i32> {
+    let segments: Vec<&str> = url
+        .path_segments()
+        .map(|segments| segments.filter(|segment| !segment.is_empty()).collect())
+        .unwrap_or_default();
+    let last = segments.last()?;
+    let (stem, extension) = match last.rsplit_once('.') {
+        Some((stem, extension)) => (stem, Some(extension.to_ascii_lowercase())),
+        None => (*last, None),
+    };
+    if extension
+        .as_deref()
+        .is_some_and(|extension| !ARTICLE_EXTENSIONS.contains(&extension))
+    {
+        return None;
+    }
+    if segments
+        .iter()
+        .any(|segment| NON_ARTICLE_SEGMENTS.contains(&segment.to_ascii_lowercase().as_str()))
+    {
+        return None;
+    }
+
+    let mut score = (segments.len() as i32 - 1).min(2);
+    if has_date(&segments) {
+        score += 3;
+    }
+    score += match slug_words(stem) {
+        0 | 1 => 0,
+        2 => 1,
+        3 => 2,
+        _ => 3,
+    };
+    if segments.iter().any(|segment| has_numeric_id(segment)) {
+        score += 2;
+    }
+    if extension.is_some() {
+        score += 1;
+    }
+    if url.query().is_some() {
+        score -= 1;
+    }
+
+    if text.split_whitespace().count() >= 4 {
+        score += 2;
+    }
+    let mut in_headline = false;
+    let mut in_article = false;
+    let mut in_chrome = false;
+    for ancestor in anchor.ancestors().filter_map(ElementRef::wrap) {
+        match ancestor.value().name() {
+            "h1" | "h2" | "h3" | "h4" => in_headline = true,
+            "article" => in_article = true,
+            "nav" | "header" | "footer" | "aside" => in_chrome = true,
+            _ => {}
+        }
+    }
+    score += i32::from(in_headline) + i32::from(in_article) - 3 * i32::from(in_chrome);
+    Some(score)
+}
+
+/// Whether the path carries a publication date: a `/YYYY/MM/` segment
+/// pair, or a `YYYY-MM-DD` / `YYYYMMDD` run inside a segment.
+fn has_date(segments: &[&str]) -> bool {
+    let is_year = |segment: &str| {
+        segment.len() == 4
+            && segment
+                .parse::<u32>()
+                .is_ok_and(|year| (1990..=2100).contains(&year))
+    };
+    let is_month = |segment: &str| {
+        segment.len() <= 2
+            && segment
+                .parse::<u32>()
+                .is_ok_and(|month| (1..=12).contains(&month))
+    };
+    segments
+        .windows(2)
+        .any(|pair| is_year(pair[0]) && is_month(pair[1]))
+        || segments.iter().any(|segment| {
+            let digits: String = segment.chars().filter(|c| *c != '-').collect();
+            let dated = digit_runs(&digits)
+                .any(|run| run.len() == 8 && is_year(&run[..4]) && is_month(&run[4..6]));
+            dated
+        })
+}
+
+/// Whether `segment` contains a run of at least five digits that is not a
+/// date (`/story/4821937`, `…-transit-plan-67123456`).
+fn has_numeric_id(segment: &str) -> bool {
+    digit_runs(segment).any(|run| run.len() >= 5 && !(run.len() == 8 && has_date(&[run])))
+}
+
+/// The maximal runs of ASCII digits in `text`.
+fn digit_runs(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !c.is_ascii_digit())
+        .filter(|run| !run.is_empty())
+}
+
+/// Number of `-` / `_` separated words with at least one letter in the
+/// slug `stem`.
+fn slug_words(stem: &str) -> usize {
+    stem.split(['-', '_'])
+        .filter(|word| word.chars().any(|c| c.is_alphabetic()))
+        .count()
+}
+
+/// A host without its `www.` prefix.
+fn site_of(host: &str) -> String {
+    host.strip_prefix("www.")
+        .unwrap_or(host)
+        .to_ascii_lowercase()
+}
+
+/// Whether `host` is on `site`: the same host (ignoring `www.`), or one
+/// is a subdomain of the other (`edition.cnn.com` ↔ `cnn.com`).
+fn same_site(host: &str, site: &str) -> bool {
+    let host = site_of(host);
+    host == site || host.ends_with(&format!(".{}", site)) || site.ends_with(&format!(".{}", host))
+}
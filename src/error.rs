@@ -0,0 +1,356 @@
+//! Typed scrape failures.
+//!
+//! [`Post::error`](crate::Post::error) stays a human-readable string (and
+//! keeps its exact wording, so existing JSON consumers and string checks
+//! keep working). Alongside it, every failed scrape now carries a
+//! [`ScrapeError`] in [`Post::error_kind`](crate::Post::error_kind) that
+//! classifies the failure and records which [`FallbackStage`]s were tried,
+//! so callers can decide whether to retry without string-matching:
+//!
+//! ```rust,no_run
+//! use uninews::{universal_scrape, ScrapeError};
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! match universal_scrape("https://example.com/a", "english", None)
+//!     .await
+//!     .into_result()
+//! {
+//!     Ok(post) => println!("{}", post.content),
+//!     Err(error) if error.is_retryable() => eprintln!("retry later: {error}"),
+//!     Err(ScrapeError::BlockedContent { .. }) => eprintln!("paywalled"),
+//!     Err(error) => eprintln!("giving up: {error}"),
+//! }
+//! # }
+//! ```
+//!
+//! In JSON the kind is serialized under `error_kind` with a snake_case
+//! `kind` tag (`{"kind":"bot_wall","message":"…","fallbacks":[…]}`) and is
+//! omitted entirely for successful posts.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// One stage of the scrape pipeline's fallback chain.
+///
+/// Recorded in [`ScrapeError::fallbacks`] in the order the stages ran, and
+/// in [`crate::Provenance::source`] for the stage that produced a post's
+/// content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum FallbackStage {
+    /// The plain HTTP fetch with a browser User-Agent.
+    PlainFetch,
+    /// The headless-Chrome `--dump-dom` render used for X Articles.
+    ChromeRender,
+    /// The Playwright Chromium render.
+    Playwright,
+    /// The host-installed content fallback hook
+    /// ([`crate::set_content_fallback`]).
+    ContentFallback,
+    /// The archive.org Wayback Machine lookup.
+    Archive,
+    /// The X API v2 / X web GraphQL (X.com URLs only).
+    XApi,
+    /// The native YouTube watch-page and caption extraction
+    /// ([`crate::YouTubeHandler`]).
+    YouTube,
+    /// The Mastodon API / ActivityStreams status fetch
+    /// ([`crate::MastodonHandler`]).
+    Mastodon,
+    /// The Bluesky AppView post-thread fetch ([`crate::BlueskyHandler`]).
+    Bluesky,
+    /// The Reddit `.json` submission fetch ([`crate::RedditHandler`]).
+    Reddit,
+    /// The Hacker News Firebase item fetch ([`crate::HackerNewsHandler`]).
+    HackerNews,
+    /// The Telegram `t.me/s/` channel preview fetch
+    /// ([`crate::TelegramHandler`]).
+    Telegram,
+    /// The WordPress / Ghost REST API post fetch for a CMS-backed page
+    /// ([`crate::detect_cms_api`]).
+    CmsApi,
+    /// A server-rendered AMP or print variant of a walled or thin page
+    /// ([`crate::alternate_pages`]).
+    Alternate,
+}
+
+impl FallbackStage {
+    /// Stable snake_case name, identical to the serde representation.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PlainFetch => "plain_fetch",
+            Self::ChromeRender => "chrome_render",
+            Self::Playwright => "playwright",
+            Self::ContentFallback => "content_fallback",
+            Self::Archive => "archive",
+            Self::XApi => "x_api",
+            Self::YouTube => "youtube",
+            Self::Mastodon => "mastodon",
+            Self::Bluesky => "bluesky",
+            Self::Reddit => "reddit",
+            Self::HackerNews => "hacker_news",
+            Self::Telegram => "telegram",
+            Self::CmsApi => "cms_api",
+            Self::Alternate => "alternate",
+        }
+    }
+}
+
+impl fmt::Display for FallbackStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for FallbackStage {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "plain_fetch" => Ok(Self::PlainFetch),
+            "chrome_render" => Ok(Self::ChromeRender),
+            "playwright" => Ok(Self::Playwright),
+            "content_fallback" => Ok(Self::ContentFallback),
+            "archive" => Ok(Self::Archive),
+            "x_api" => Ok(Self::XApi),
+            "youtube" => Ok(Self::YouTube),
+            "mastodon" => Ok(Self::Mastodon),
+            "bluesky" => Ok(Self::Bluesky),
+            "reddit" => Ok(Self::Reddit),
+            "hacker_news" => Ok(Self::HackerNews),
+            "telegram" => Ok(Self::Telegram),
+            "cms_api" => Ok(Self::CmsApi),
+            "alternate" => Ok(Self::Alternate),
+            other => Err(format!("Unknown fallback stage '{}'", other)),
+        }
+    }
+}
+
+/// Classified scrape failure.
+///
+/// `message` is the originating failure's text;
+/// [`Post::error`](crate::Post::error) may add fallback annotations
+/// around it.
+/// `fallbacks` lists the pipeline stages that were attempted before giving
+/// up (empty when the failure happened before any fetch, e.g. an LLM
+/// configuration error on a pre-extracted post).
+///
+/// New variants may be added in minor releases; match with a wildcard arm.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ScrapeError {
+    /// No usable response: DNS, connect, TLS, timeout, body read failure,
+    /// or an oversize body.
+    Network {
+        message: String,
+        #[serde(default)]
+        fallbacks: Vec<FallbackStage>,
+    },
+    /// The server answered with a non-success status and no usable content.
+    HttpStatus {
+        status: u16,
+        message: String,
+        #[serde(default)]
+        fallbacks: Vec<FallbackStage>,
+    },
+    /// A bot-protection wall (Cloudflare challenge and similar).
+    BotWall {
+        message: String,
+        #[serde(default)]
+        fallbacks: Vec<FallbackStage>,
+    },
+    /// A paywall, login wall, or soft-block page (`BlockedContent:`).
+    BlockedContent {
+        message: String,
+        #[serde(default)]
+        fallbacks: Vec<FallbackStage>,
+    },
+    /// The page loaded but no meaningful article content was extracted.
+    InsufficientContent {
+        message: String,
+        #[serde(default)]
+        fallbacks: Vec<FallbackStage>,
+    },
+    /// The LLM provider is unsupported or its API key is missing.
+    LlmConfig {
+        message: String,
+        #[serde(default)]
+        fallbacks: Vec<FallbackStage>,
+    },
+    /// The extracted article does not fit the LLM context window.
+    LlmContextOverflow {
+        estimated_tokens: usize,
+        context_window: usize,
+        message: String,
+        #[serde(default)]
+        fallbacks: Vec<FallbackStage>,
+    },
+    /// The LLM provider call failed.
+    LlmProvider {
+        message: String,
+        #[serde(default)]
+        fallbacks: Vec<FallbackStage>,
+    },
+    /// The X API (or X web GraphQL) failed; `status` is set when the API
+    /// answered with a non-success HTTP status.
+    XApi {
+        #[serde(default)]
+        status: Option<u16>,
+        message: String,
+        #[serde(default)]
+        fallbacks: Vec<FallbackStage>,
+    },
+    /// Unclassified failure, e.g. a legacy [`crate::Post`] deserialized
+    /// without an `error_kind`.
+    Other {
+        message: String,
+        #[serde(default)]
+        fallbacks: Vec<FallbackStage>,
+    },
+}
+
+impl ScrapeError {
+    pub(crate) fn network(message: impl Into<String>) -> Self {
+        Self::Network {
+            message: message.into(),
+            fallbacks: Vec::new(),
+        }
+    }
+
+    pub(crate) fn http_status(status: u16, message: impl Into<String>) -> Self {
+        Self::HttpStatus {
+            status,
+            message: message.into(),
+            fallbacks: Vec::new(),
+        }
+    }
+
+    pub(crate) fn bot_wall(message: impl Into<String>) -> Self {
+        Self::BotWall {
+            message: message.into(),
+            fallbacks: Vec::new(),
+        }
+    }
+
+    pub(crate) fn blocked_content(message: impl Into<String>) -> Self {
+        Self::BlockedContent {
+            message: message.into(),
+            fallbacks: Vec::new(),
+        }
+    }
+
+    pub(crate) fn insufficient_content(message: impl Into<String>) -> Self {
+        Self::InsufficientContent {
+            message: message.into(),
+            fallbacks: Vec::new(),
+        }
+    }
+
+    pub(crate) fn llm_config(message: impl Into<String>) -> Self {
+        Self::LlmConfig {
+            message: message.into(),
+            fallbacks: Vec::new(),
+        }
+    }
+
+    pub(crate) fn llm_provider(message: impl Into<String>) -> Self {
+        Self::LlmProvider {
+            message: message.into(),
+            fallbacks: Vec::new(),
+        }
+    }
+
+    pub(crate) fn x_api(status: Option<u16>, message: impl Into<String>) -> Self {
+        Self::XApi {
+            status,
+            message: message.into(),
+            fallbacks: Vec::new(),
+        }
+    }
+
+    pub(crate) fn other(message: impl Into<String>) -> Self {
+        Self::Other {
+            message: message.into(),
+            fallbacks: Vec::new(),
+        }
+    }
+
+    /// The originating failure's message.
+    pub fn message(&self) -> &str {
+        match self {
+            Self::Network { message, .. }
+            | Self::HttpStatus { message, .. }
+            | Self::BotWall { message, .. }
+            | Self::BlockedContent { message, .. }
+            | Self::InsufficientContent { message, .. }
+            | Self::LlmConfig { message, .. }
+            | Self::LlmContextOverflow { message, .. }
+            | Self::LlmProvider { message, .. }
+            | Self::XApi { message, .. }
+            | Self::Other { message, .. } => message,
+        }
+    }
+
+    /// The fallback stages attempted before the scrape gave up, in order.
+    pub fn fallbacks(&self) -> &[FallbackStage] {
+        match self {
+            Self::Network { fallbacks, .. }
+            | Self::HttpStatus { fallbacks, .. }
+            | Self::BotWall { fallbacks, .. }
+            | Self::BlockedContent { fallbacks, .. }
+            | Self::InsufficientContent { fallbacks, .. }
+            | Self::LlmConfig { fallbacks, .. }
+            | Self::LlmContextOverflow { fallbacks, .. }
+            | Self::LlmProvider { fallbacks, .. }
+            | Self::XApi { fallbacks, .. }
+            | Self::Other { fallbacks, .. } => fallbacks,
+        }
+    }
+
+    /// Replace the recorded fallback chain.
+    pub(crate) fn with_fallbacks(mut self, stages: &[FallbackStage]) -> Self {
+        match &mut self {
+            Self::Network { fallbacks, .. }
+            | Self::HttpStatus { fallbacks, .. }
+            | Self::BotWall { fallbacks, .. }
+            | Self::BlockedContent { fallbacks, .. }
+            | Self::InsufficientContent { fallbacks, .. }
+            | Self::LlmConfig { fallbacks, .. }
+            | Self::LlmContextOverflow { fallbacks, .. }
+            | Self::LlmProvider { fallbacks, .. }
+            | Self::XApi { fallbacks, .. }
+            | Self::Other { fallbacks, .. } => *fallbacks = stages.to_vec(),
+        }
+        self
+    }
+
+    /// Whether retrying the same URL later could plausibly succeed:
+    /// network failures, 5xx / 408 / 429 statuses, bot walls, LLM provider
+    /// errors, and X API rate limits or server errors. Configuration
+    /// errors, paywalls, and extraction failures are not retryable.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Network { .. } | Self::BotWall { .. } | Self::LlmProvider { .. } => true,
+            Self::HttpStatus { status, .. } => is_retryable_status(*status),
+            Self::XApi { status, .. } => status.is_some_and(is_retryable_status),
+            _ => false,
+        }
+    }
+}
+
+fn is_retryable_status(status: u16) -> bool {
+    status >= 500 || status == 408 || status == 429
+}
+
+impl fmt::Display for ScrapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for ScrapeError {}
@@ -44,6 +44,11 @@ use std::sync::{Arc, RwLock};
 
 use serde::Serialize;
 
+use crate::alternate::AlternateKind;
+use crate::cache::CacheKind;
+use crate::cms::CmsKind;
+use crate::feed::FeedFormat;
+
 /// A snapshot of pipeline progress, emitted by [`emit_event`].
 ///
 /// New variants are **additive** in minor releases; listeners must always
@@ -60,6 +65,7 @@ use serde::Serialize;
 ///     url: "https://example.com/a".to_string(),
 ///     status: 200,
 ///     body_bytes: 42_000,
+///     encoding: Some("Shift_JIS".to_string()),
 /// };
 /// let json = serde_json::to_value(&event).unwrap();
 /// assert_eq!(json["event"], "fetch_succeeded");
@@ -68,11 +74,96 @@ use serde::Serialize;
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "event", rename_all = "snake_case")]
 pub enum ScrapeEvent {
+    /// [`crate::Scraper::scrape_many`] is about to scrape `urls` URLs.
+    /// Each URL then emits its own `ScrapeStarted` … `ScrapeCompleted` /
+    /// `ScrapeFailed` sequence; events of concurrent scrapes interleave.
+    BatchStarted {
+        /// Number of URLs in the batch.
+        urls: usize,
+    },
+    /// Every URL of a [`crate::Scraper::scrape_many`] batch has finished.
+    BatchCompleted {
+        /// Number of URLs in the batch.
+        urls: usize,
+        /// How many of them produced a failed [`crate::Post`].
+        failed: usize,
+    },
+    /// [`crate::Scraper::fetch_feed`] found a feed declared by the HTML
+    /// page at `url` and is about to fetch it.
+    FeedDiscovered {
+        /// The page that declared the feed.
+        url: String,
+        /// The declared feed URL.
+        feed_url: String,
+    },
+    /// [`crate::Scraper::fetch_feed`] parsed a feed document.
+    FeedParsed {
+        /// The feed URL.
+        url: String,
+        /// The feed syntax.
+        format: FeedFormat,
+        /// Number of items with a usable link.
+        items: usize,
+    },
+    /// [`crate::Scraper::fetch_sitemap`] parsed one sitemap document: a
+    /// `<urlset>` (`entries` pages) or a `<sitemapindex>` (`sitemaps`
+    /// children).
+    SitemapParsed {
+        /// The sitemap URL.
+        url: String,
+        /// Number of page entries in the document.
+        entries: usize,
+        /// Number of child sitemaps in the document.
+        sitemaps: usize,
+    },
+    /// [`crate::Scraper::discover_article_links`] ranked the links of a
+    /// section page.
+    ArticleLinksDiscovered {
+        /// The section page URL (after redirects).
+        url: String,
+        /// Number of probable article links found.
+        links: usize,
+        /// The links came from a Playwright render of the page rather than
+        /// its plain HTML.
+        rendered: bool,
+    },
     /// [`crate::universal_scrape`] has begun processing `url`.
     ScrapeStarted {
         /// The URL being scraped.
         url: String,
     },
+    /// `url` redirects ([`crate::Scraper::resolve_url`]) and was followed
+    /// to its target, whose canonical form keys the cache lookup.
+    ShortLinkResolved {
+        /// The redirecting link.
+        url: String,
+        /// Where its redirects end.
+        resolved_url: String,
+    },
+    /// A [`crate::SourceHandler`] claimed `url`; it scrapes the URL instead
+    /// of the generic web pipeline.
+    SourceHandlerSelected {
+        /// The URL being scraped.
+        url: String,
+        /// The handler's [`crate::SourceHandler::name`].
+        handler: String,
+    },
+    /// A [`crate::ScrapeCache`] lookup found a fresh entry; the fetch (for
+    /// [`crate::CacheKind::Response`]) or the whole pipeline (for
+    /// [`crate::CacheKind::Post`]) is skipped.
+    CacheHit {
+        /// The URL being looked up.
+        url: String,
+        /// Which cache layer answered.
+        kind: CacheKind,
+    },
+    /// A [`crate::ScrapeCache`] lookup found no fresh entry.
+    CacheMiss {
+        /// The URL being looked up.
+        url: String,
+        /// Which cache layer was consulted.
+        kind: CacheKind,
+    },
     /// An HTTP request is about to be sent.
     FetchStarted {
         /// The request URL (page URL or API endpoint).
@@ -86,6 +177,10 @@ pub enum ScrapeEvent {
         status: u16,
         /// Size of the response body in bytes.
         body_bytes: usize,
+        /// The character encoding the body is decoded with (`UTF-8`,
+        /// `Shift_JIS`, `windows-1251`, …; see [`crate::detect_charset`]).
+        /// `None` for binary bodies (PDFs) and X API responses.
+        encoding: Option<String>,
     },
     /// An HTTP request failed (DNS, TLS, connect/read timeout, body error).
     FetchFailed {
@@ -94,6 +189,61 @@ pub enum ScrapeEvent {
         /// Human-readable failure description.
         error: String,
     },
+    /// The [`crate::YouTubeHandler`] fetched a video's captions.
+    YouTubeTranscriptFetched {
+        /// The video URL being scraped.
+        url: String,
+        /// The caption track's language code.
+        language: String,
+        /// Whether the track is speech-recognition captions.
+        auto_generated: bool,
+        /// Number of caption cues.
+        cues: usize,
+    },
+    /// The [`crate::YouTubeHandler`] could not fetch a video's captions;
+    /// the video is scraped from its description alone.
+    YouTubeTranscriptFailed {
+        /// The video URL being scraped.
+        url: String,
+        /// Why the captions could not be used.
+        error: String,
+    },
+    /// The fetched body was a PDF document; its text was extracted.
+    PdfExtracted {
+        /// The PDF's URL, after redirects.
+        url: String,
+        /// Number of pages in the document.
+        pages: usize,
+    },
+    /// The article is split across pages; the following pages were fetched
+    /// and their bodies appended to the first page's.
+    ArticlePagesFollowed {
+        /// The first page's URL, after redirects.
+        url: String,
+        /// Number of pages read, the first one included.
+        pages: usize,
+    },
+    /// The page is CMS-backed ([`crate::detect_cms_api`]) and its post was
+    /// read from the CMS REST API instead of the DOM.
+    CmsApiResolved {
+        /// The page URL, after redirects.
+        url: String,
+        /// The CMS behind the page.
+        cms: CmsKind,
+        /// The API URL that returned the post.
+        api_url: String,
+    },
+    /// The page is CMS-backed but its post could not be read from the CMS
+    /// REST API (no such post, API disabled, protected post, …); the DOM
+    /// extraction runs instead.
+    CmsApiFailed {
+        /// The page URL, after redirects.
+        url: String,
+        /// The CMS behind the page.
+        cms: CmsKind,
+        /// Human-readable failure description.
+        error: String,
+    },
     /// Article content was successfully extracted from the HTML.
     ContentExtracted {
         /// The page URL the content was extracted from.
@@ -114,6 +264,42 @@ pub enum ScrapeEvent {
         /// The protected page URL.
         url: String,
     },
+    /// A walled or thin page links a server-rendered AMP or print variant
+    /// ([`crate::alternate_pages`]), which is about to be fetched before
+    /// the Playwright render.
+    AlternateFetchStarted {
+        /// The page URL.
+        url: String,
+        /// The variant's URL.
+        alternate_url: String,
+        /// Which variant it is.
+        kind: AlternateKind,
+    },
+    /// The variant yielded more article content than the page itself and
+    /// replaces it.
+    AlternateFetchSucceeded {
+        /// The page URL.
+        url: String,
+        /// The variant's URL.
+        alternate_url: String,
+        /// Which variant it is.
+        kind: AlternateKind,
+        /// Byte length of the variant's extracted content.
+        content_bytes: usize,
+    },
+    /// The variant could not be fetched, was walled too, or yielded no
+    /// more content than the page; the next variant (or Playwright) is
+    /// tried.
+    AlternateFetchFailed {
+        /// The page URL.
+        url: String,
+        /// The variant's URL.
+        alternate_url: String,
+        /// Which variant it is.
+        kind: AlternateKind,
+        /// Human-readable failure description.
+        error: String,
+    },
     /// The Playwright Chromium fallback is about to render a bot-protected
     /// (or otherwise JS-gated) page. Emitted only when `UNINEWS_PLAYWRIGHT`
     /// is enabled (the default).
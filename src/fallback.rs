@@ -14,7 +14,9 @@
 //!    article-equivalent payload of a video (its transcript) is not part
 //!    of the watch-page HTML. When the hook returns
 //!    [`ContentFallback::Extracted`], the content flows through the normal
-//!    LLM Markdown step like any extracted article body.
+//!    LLM Markdown step like any extracted article body. When the hook has
+//!    nothing for a video, the built-in [`crate::YouTubeHandler`] reads its
+//!    description and captions natively.
 //! 2. **Bot-protection walls and thin-content pages** — consulted *after*
 //!    the built-in Playwright render when that render is disabled or did
 //!    not yield usable content, and *before* the archive.org fallback.
@@ -0,0 +1,730 @@
+//! RSS / Atom / JSON Feed ingestion.
+//!
+//! Pipelines usually start from a publisher's feed rather than from single
+//! article URLs. [`Scraper::fetch_feed`] downloads a feed with the
+//! scraper's web client and parses it into a [`Feed`]:
+//!
+//! - **RSS 2.0** (and RSS 0.9x / 1.0 RDF): `<item>` `link`, `title`,
+//!   `guid`, `pubDate` / `dc:date`, `author` / `dc:creator`, and
+//!   `<enclosure>`.
+//! - **Atom**: `<entry>` alternate `link`, `title`, `id`, `published` /
+//!   `updated`, `author/name` (falling back to the feed's authors), and
+//!   `rel="enclosure"` links.
+//! - **JSON Feed** (1.0 / 1.1): `url` (or `external_url`), `title`, `id`,
+//!   `date_published`, `authors` / `author`, and the first attachment.
+//!
+//! Item links are resolved against the feed URL; items without an
+//! http(s) link are skipped. RFC 2822 dates (`pubDate`) are normalized to
+//! RFC 3339 UTC like the rest of [`Post`]; other dates are kept as written.
+//!
+//! Given a homepage instead of a feed, [`Scraper::fetch_feed`] follows the
+//! page's `<link rel="alternate" type="application/rss+xml">` (or Atom /
+//! JSON Feed) declarations ([`discover_feed_urls`]). Feed documents bypass
+//! the [`crate::ScrapeCache`]: they change far more often than articles.
+//!
+//! [`Scraper::scrape_feed`] then scrapes every item through the normal
+//! pipeline as a [`Scraper::scrape_many`] batch and merges the feed
+//! metadata into each [`Post`] ([`FeedItem::merge_into`]):
+//!
+//! ```rust,no_run
+//! use futures::StreamExt;
+//! use uninews::{ScrapeOptions, Scraper};
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let scraper = Scraper::new(ScrapeOptions::from_env());
+//! match scraper.scrape_feed("https://example.com/", "english").await {
+//!     Ok(mut posts) => {
+//!         while let Some((item, post)) = posts.next().await {
+//!             println!("{} -> {}", item.link, post.title);
+//!         }
+//!     }
+//!     Err(error) => eprintln!("no feed: {error}"),
+//! }
+//! # }
+//! ```
+
+use std::fmt;
+
+use futures::stream::BoxStream;
+use roxmltree::{Node, ParsingOptions};
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::events::{emit_event, ScrapeEvent};
+use crate::html::resolve_href;
+use crate::util::{days_from_civil, format_rfc3339_utc};
+use crate::web::{fetch_page, FetchedPage};
+use crate::{Post, ScrapeError, ScrapeOptions, Scraper};
+
+/// `<link type>` values [`discover_feed_urls`] treats as feeds.
+const FEED_LINK_TYPES: [&str; 4] = [
+    "application/rss+xml",
+    "application/atom+xml",
+    "application/rdf+xml",
+    "application/feed+json",
+];
+
+/// Most discovered feeds [`Scraper::fetch_feed`] tries for one homepage.
+const MAX_DISCOVERED_FEEDS: usize = 3;
+
+const ATOM_NS: &str = "http://www.w3.org/2005/Atom";
+const RSS1_NS: &str = "http://purl.org/rss/1.0/";
+const RDF_NS: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+const DC_NS: &str = "http://purl.org/dc/elements/1.1/";
+
+/// Namespaces of the feed formats' own elements. Extension elements that
+/// share a local name (`media:title`, `itunes:author`) are ignored.
+const CORE_NAMESPACES: [Option<&str>; 3] = [None, Some(RSS1_NS), Some(ATOM_NS)];
+const DC_NAMESPACES: [Option<&str>; 1] = [Some(DC_NS)];
+
+/// The syntax a [`Feed`] was parsed from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedFormat {
+    /// RSS 2.0, 0.9x, or 1.0 (RDF).
+    Rss,
+    /// Atom 1.0.
+    Atom,
+    /// JSON Feed 1.0 / 1.1.
+    JsonFeed,
+}
+
+impl FeedFormat {
+    /// Stable snake_case name, identical to the serde representation.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Rss => "rss",
+            Self::Atom => "atom",
+            Self::JsonFeed => "json_feed",
+        }
+    }
+}
+
+impl fmt::Display for FeedFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A media file attached to a [`FeedItem`] (RSS `<enclosure>`, Atom
+/// `rel="enclosure"` link, JSON Feed attachment).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedEnclosure {
+    /// Absolute URL of the file.
+    pub url: String,
+    /// Declared MIME type (`image/jpeg`, `audio/mpeg`, …).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    /// Declared size in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length: Option<u64>,
+}
+
+impl FeedEnclosure {
+    /// Whether the enclosure declares an `image/*` MIME type.
+    pub fn is_image(&self) -> bool {
+        self.mime_type
+            .as_deref()
+            .is_some_and(|mime| mime.trim().to_ascii_lowercase().starts_with("image/"))
+    }
+}
+
+/// One entry of a [`Feed`]. Empty fields are omitted from JSON.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedItem {
+    /// Absolute URL of the article.
+    pub link: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// RSS `guid`, Atom `id`, or JSON Feed `id`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guid: Option<String>,
+    /// Publication date: RFC 3339 UTC when an RFC 2822 `pubDate` could be
+    /// parsed, otherwise as written in the feed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub published: Option<String>,
+    /// Author names joined with `", "`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enclosure: Option<FeedEnclosure>,
+}
+
+impl FeedItem {
+    /// Fill the gaps in a scraped `post` from this item: the title, author,
+    /// and publication date when the page did not provide them, and the
+    /// featured image from an image enclosure. Values extracted from the
+    /// page itself always win.
+    pub fn merge_into(&self, post: &mut Post) {
+        if post.title.trim().is_empty() {
+            if let Some(title) = &self.title {
+                post.title = title.clone();
+            }
+        }
+        if post.author.is_none() {
+            post.author = self.author.clone();
+        }
+        if post.publication_date.is_none() {
+            post.publication_date = self.published.clone();
+        }
+        if post.featured_image_url.is_empty() {
+            if let Some(enclosure) = self.enclosure.as_ref().filter(|e| e.is_image()) {
+                post.featured_image_url = enclosure.url.clone();
+            }
+        }
+    }
+}
+
+/// A parsed RSS, Atom, or JSON Feed document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Feed {
+    pub format: FeedFormat,
+    /// The URL the feed document was read from (after redirects and
+    /// discovery).
+    pub url: String,
+    /// The feed's own title.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The site the feed belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// Items in feed order.
+    #[serde(default)]
+    pub items: Vec<FeedItem>,
+}
+
+/// Parse an RSS, Atom, or JSON Feed document. Relative links resolve
+/// against `feed_url`.
+///
+/// Fails with a `Not a feed: …` message for anything else, including
+/// HTML pages.
+///
+/// # Examples
+///
+/// ```
+/// use uninews::{parse_feed, FeedFormat};
+///
+/// let rss = r#"<rss version="2.0"><channel><title>Example</title>
+///   <item><title>Hello</title><link>/hello</link>
+///   <pubDate>Tue, 14 Oct 2025 08:00:00 +0200</pubDate></item>
+/// </channel></rss>"#;
+/// let feed = parse_feed(rss, "https://example.com/feed.xml").unwrap();
+/// assert_eq!(feed.format, FeedFormat::Rss);
+/// assert_eq!(feed.items[0].link, "https://example.com/hello");
+/// assert_eq!(feed.items[0].published.as_deref(), Some("2025-10-14T06:00:00Z"));
+/// ```
+pub fn parse_feed(body: &str, feed_url: &str) -> Result<Feed, String> {
+    let body = body.trim_start_matches('\u{feff}').trim_start();
+    if body.starts_with('{') {
+        parse_json_feed(body, feed_url)
+    } else {
+        parse_xml_feed(body, feed_url)
+    }
+}
+
+/// Feed URLs declared by an HTML page's
+/// `<link rel="alternate" type="application/rss+xml" href="…">` elements
+/// (RSS, Atom, RDF, or JSON Feed types), resolved against `page_url`, in
+/// document order and without duplicates.
+pub fn discover_feed_urls(html: &str, page_url: &str) -> Vec<String> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("link[rel][type][href]").expect("valid feed link selector");
+    let mut urls: Vec<String> = Vec::new();
+    for link in document.select(&selector) {
+        let element = link.value();
+        let alternate = element.attr("rel").is_some_and(|rel| {
+            rel.split_ascii_whitespace()
+                .any(|token| token.eq_ignore_ascii_case("alternate"))
+        });
+        let mime = element
+            .attr("type")
+            .and_then(|value| value.split(';').next())
+            .unwrap_or("")
+            .trim()
+            .to_ascii_lowercase();
+        if !alternate || !FEED_LINK_TYPES.contains(&mime.as_str()) {
+            continue;
+        }
+        if let Some(url) = element
+            .attr("href")
+            .and_then(|href| resolve_href(page_url, href))
+        {
+            if !urls.contains(&url) {
+                urls.push(url);
+            }
+        }
+    }
+    urls
+}
+
+impl Scraper {
+    /// Fetch and parse the feed at `url` with this scraper's web client.
+    ///
+    /// When `url` serves an HTML page instead, the feeds it declares
+    /// ([`discover_feed_urls`]) are tried in order and the first one that
+    /// parses is returned. Fails with [`ScrapeError::Network`] /
+    /// [`ScrapeError::HttpStatus`] when the fetch fails, or
+    /// [`ScrapeError::Other`] when no feed is found.
+    pub async fn fetch_feed(&self, url: &str) -> Result<Feed, ScrapeError> {
+        let page = fetch_feed_document(self, url).await?;
+        let not_a_feed = match parse_feed(&page.body, &page.final_url) {
+            Ok(feed) => return Ok(parsed(feed)),
+            Err(error) => error,
+        };
+
+        let candidates = discover_feed_urls(&page.body, &page.final_url);
+        let mut last_error =
+            ScrapeError::other(format!("No feed found at {}: {}", url, not_a_feed));
+        for feed_url in candidates.into_iter().take(MAX_DISCOVERED_FEEDS) {
+            emit_event(ScrapeEvent::FeedDiscovered {
+                url: page.final_url.clone(),
+                feed_url: feed_url.clone(),
+            });
+            let feed = fetch_feed_document(self, &feed_url).await.and_then(|page| {
+                parse_feed(&page.body, &page.final_url)
+                    .map_err(|error| ScrapeError::other(format!("{}: {}", feed_url, error)))
+            });
+            match feed {
+                Ok(feed) => return Ok(parsed(feed)),
+                Err(error) => last_error = error,
+            }
+        }
+        Err(last_error)
+    }
+
+    /// Fetch the feed at `url` ([`Scraper::fetch_feed`]) and scrape its
+    /// items ([`Scraper::scrape_feed_items`]).
+    pub async fn scrape_feed(
+        &self,
+        url: &str,
+        language: &str,
+    ) -> Result<BoxStream<'static, (FeedItem, Post)>, ScrapeError> {
+        let feed = self.fetch_feed(url).await?;
+        Ok(self.scrape_feed_items(feed.items, language))
+    }
+
+    /// Scrape each item's link as a [`Scraper::scrape_many`] batch and
+    /// stream `(item, post)` pairs in completion order, with the item's
+    /// metadata merged into the post ([`FeedItem::merge_into`]). Items
+    /// sharing a link are scraped once, under the first of them.
+    pub fn scrape_feed_items<I>(
+        &self,
+        items: I,
+        language: &str,
+    ) -> BoxStream<'static, (FeedItem, Post)>
+    where
+        I: IntoIterator<Item = FeedItem>,
+    {
+        self.scrape_many_items(
+            items.into_iter().map(|item| (item.link.clone(), item)),
+            language,
+            FeedItem::merge_into,
+        )
+    }
+}
+
+/// Fetch the feed at `url` and scrape its items with a [`Scraper`] built
+/// from `options`; see [`Scraper::scrape_feed`].
+pub async fn scrape_feed(
+    url: &str,
+    language: &str,
+    options: ScrapeOptions,
+) -> Result<BoxStream<'static, (FeedItem, Post)>, ScrapeError> {
+    Scraper::new(options).scrape_feed(url, language).await
+}
+
+/// GET a feed (or homepage) document, failing on non-2xx statuses.
+async fn fetch_feed_document(scraper: &Scraper, url: &str) -> Result<FetchedPage, ScrapeError> {
+    let page = fetch_page(scraper, url)
+        .await
+        .map_err(ScrapeError::network)?;
+    if !page.status.is_success() {
+        return Err(ScrapeError::http_status(
+            page.status.as_u16(),
+            format!("Feed request for {} returned HTTP {}", url, page.status),
+        ));
+    }
+    Ok(page)
+}
+
+/// Emit [`ScrapeEvent::FeedParsed`] for `feed` and return it.
+fn parsed(feed: Feed) -> Feed {
+    emit_event(ScrapeEvent::FeedParsed {
+        url: feed.url.clone(),
+        format: feed.format,
+        items: feed.items.len(),
+    });
+    feed
+}
+
+fn parse_xml_feed(body: &str, feed_url: &str) -> Result<Feed, String> {
+    let options = ParsingOptions {
+        allow_dtd: true,
+        ..ParsingOptions::default()
+    };
+    let document = roxmltree::Document::parse_with_options(body, options)
+        .map_err(|error| format!("Not a feed: invalid XML ({})", error))?;
+    let root = document.root_element();
+    match root.tag_name().name() {
+        "rss" => {
+            let channel = elements(root, "channel", &CORE_NAMESPACES)
+                .into_iter()
+                .next()
+                .ok_or("Not a feed: <rss> without a <channel>")?;
+            Ok(rss_feed(channel, channel, feed_url))
+        }
+        // RSS 1.0 keeps its items next to the channel, not inside it.
+        "RDF" => {
+            let channel = elements(root, "channel", &CORE_NAMESPACES)
+                .into_iter()
+                .next()
+                .unwrap_or(root);
+            Ok(rss_feed(channel, root, feed_url))
+        }
+        "feed" => Ok(atom_feed(root, feed_url)),
+        other => Err(format!("Not a feed: unexpected root element <{}>", other)),
+    }
+}
+
+fn rss_feed(channel: Node, item_parent: Node, feed_url: &str) -> Feed {
+    let items = elements(item_parent, "item", &CORE_NAMESPACES)
+        .into_iter()
+        .filter_map(|item| rss_item(item, feed_url))
+        .collect();
+    Feed {
+        format: FeedFormat::Rss,
+        url: feed_url.to_string(),
+        title: child_text(channel, "title", &CORE_NAMESPACES),
+        link: child_text(channel, "link", &CORE_NAMESPACES)
+            .and_then(|link| resolve_href(feed_url, &link)),
+        items,
+    }
+}
+
+fn rss_item(item: Node, feed_url: &str) -> Option<FeedItem> {
+    let guid_node = elements(item, "guid", &CORE_NAMESPACES).into_iter().next();
+    let guid = guid_node.and_then(node_text);
+    let permalink_guid = guid_node
+        .filter(|node| node.attribute("isPermaLink") != Some("false"))
+        .and_then(node_text);
+    let link = child_text(item, "link", &CORE_NAMESPACES)
+        .or_else(|| item.attribute((RDF_NS, "about")).map(str::to_string))
+        .or(permalink_guid)
+        .and_then(|link| resolve_href(feed_url, &link))?;
+
+    let published = child_text(item, "pubDate", &CORE_NAMESPACES)
+        .or_else(|| child_text(item, "date", &DC_NAMESPACES))
+        .map(|date| normalize_date(&date));
+    let authors: Vec<String> = elements(item, "author", &CORE_NAMESPACES)
+        .into_iter()
+        .chain(elements(item, "creator", &DC_NAMESPACES))
+        .filter_map(node_text)
+        .collect();
+    let enclosure = elements(item, "enclosure", &CORE_NAMESPACES)
+        .into_iter()
+        .find_map(|node| {
+            enclosure(
+                feed_url,
+                node.attribute("url")?,
+                node.attribute("type"),
+                node.attribute("length"),
+            )
+        });
+
+    Some(FeedItem {
+        link,
+        title: child_text(item, "title", &CORE_NAMESPACES),
+        guid,
+        published,
+        author: join_names(authors),
+        enclosure,
+    })
+}
+
+fn atom_feed(feed: Node, feed_url: &str) -> Feed {
+    let feed_authors = atom_authors(feed);
+    let items = elements(feed, "entry", &CORE_NAMESPACES)
+        .into_iter()
+        .filter_map(|entry| atom_entry(entry, feed_url, feed_authors.as_deref()))
+        .collect();
+    Feed {
+        format: FeedFormat::Atom,
+        url: feed_url.to_string(),
+        title: child_text(feed, "title", &CORE_NAMESPACES),
+        link: atom_link(feed, "alternate").and_then(|href| resolve_href(feed_url, href)),
+        items,
+    }
+}
+
+fn atom_entry(entry: Node, feed_url: &str, feed_authors: Option<&str>) -> Option<FeedItem> {
+    let guid = child_text(entry, "id", &CORE_NAMESPACES);
+    let link = atom_link(entry, "alternate")
+        .map(str::to_string)
+        .or_else(|| guid.clone())
+        .and_then(|link| resolve_href(feed_url, &link))?;
+    let published = child_text(entry, "published", &CORE_NAMESPACES)
+        .or_else(|| child_text(entry, "updated", &CORE_NAMESPACES))
+        .map(|date| normalize_date(&date));
+    let enclosure = elements(entry, "link", &CORE_NAMESPACES)
+        .into_iter()
+        .filter(|link| link.attribute("rel") == Some("enclosure"))
+        .find_map(|link| {
+            enclosure(
+                feed_url,
+                link.attribute("href")?,
+                link.attribute("type"),
+                link.attribute("length"),
+            )
+        });
+
+    Some(FeedItem {
+        link,
+        title: child_text(entry, "title", &CORE_NAMESPACES),
+        guid,
+        published,
+        author: atom_authors(entry).or_else(|| feed_authors.map(str::to_string)),
+        enclosure,
+    })
+}
+
+/// `href` of the first Atom `<link>` with `rel` (a missing `rel` means
+/// `alternate`).
+fn atom_link<'a>(node: Node<'a, '_>, rel: &str) -> Option<&'a str> {
+    elements(node, "link", &CORE_NAMESPACES)
+        .into_iter()
+        .find(|link| link.attribute("rel").unwrap_or("alternate") == rel)
+        .and_then(|link| link.attribute("href"))
+}
+
+fn atom_authors(node: Node) -> Option<String> {
+    join_names(
+        elements(node, "author", &CORE_NAMESPACES)
+            .into_iter()
+            .filter_map(|author| child_text(author, "name", &CORE_NAMESPACES))
+            .collect(),
+    )
+}
+
+fn parse_json_feed(body: &str, feed_url: &str) -> Result<Feed, String> {
+    let value: Value = serde_json::from_str(body)
+        .map_err(|error| format!("Not a feed: invalid JSON ({})", error))?;
+    let is_json_feed = value
+        .get("version")
+        .and_then(Value::as_str)
+        .is_some_and(|version| version.starts_with("https://jsonfeed.org/version/"));
+    if !is_json_feed {
+        return Err("Not a feed: JSON without a jsonfeed.org version".to_string());
+    }
+
+    let feed_authors = json_authors(&value);
+    let items = value
+        .get("items")
+        .and_then(Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| json_feed_item(item, feed_url, feed_authors.as_deref()))
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(Feed {
+        format: FeedFormat::JsonFeed,
+        url: feed_url.to_string(),
+        title: json_string(&value, "title"),
+        link: json_string(&value, "home_page_url").and_then(|link| resolve_href(feed_url, &link)),
+        items,
+    })
+}
+
+fn json_feed_item(item: &Value, feed_url: &str, feed_authors: Option<&str>) -> Option<FeedItem> {
+    let link = json_string(item, "url")
+        .or_else(|| json_string(item, "external_url"))
+        .and_then(|link| resolve_href(feed_url, &link))?;
+    let guid = match item.get("id") {
+        Some(Value::Number(id)) => Some(id.to_string()),
+        _ => json_string(item, "id"),
+    };
+    let enclosure = item
+        .get("attachments")
+        .and_then(Value::as_array)
+        .and_then(|attachments| attachments.first())
+        .and_then(|attachment| {
+            let length = attachment
+                .get("size_in_bytes")
+                .and_then(Value::as_u64)
+                .map(|size| size.to_string());
+            enclosure(
+                feed_url,
+                attachment.get("url")?.as_str()?,
+                attachment.get("mime_type").and_then(Value::as_str),
+                length.as_deref(),
+            )
+        });
+
+    Some(FeedItem {
+        link,
+        title: json_string(item, "title"),
+        guid,
+        published: json_string(item, "date_published"),
+        author: json_authors(item).or_else(|| feed_authors.map(str::to_string)),
+        enclosure,
+    })
+}
+
+/// JSON Feed 1.1 `authors[].name`, else 1.0 `author.name`.
+fn json_authors(value: &Value) -> Option<String> {
+    let authors = match value.get("authors").and_then(Value::as_array) {
+        Some(authors) => authors
+            .iter()
+            .filter_map(|author| json_string(author, "name"))
+            .collect(),
+        None => value
+            .get("author")
+            .and_then(|author| json_string(author, "name"))
+            .into_iter()
+            .collect(),
+    };
+    join_names(authors)
+}
+
+fn json_string(value: &Value, key: &str) -> Option<String> {
+    let text = value.get(key)?.as_str()?.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn enclosure(
+    feed_url: &str,
+    url: &str,
+    mime_type: Option<&str>,
+    length: Option<&str>,
+) -> Option<FeedEnclosure> {
+    Some(FeedEnclosure {
+        url: resolve_href(feed_url, url)?,
+        mime_type: mime_type
+            .map(str::trim)
+            .filter(|mime| !mime.is_empty())
+            .map(str::to_string),
+        length: length.and_then(|length| length.trim().parse().ok()),
+    })
+}
+
+/// Child elements of `node` named `name` in one of `namespaces`.
+fn elements<'a, 'input>(
+    node: Node<'a, 'input>,
+    name: &str,
+    namespaces: &[Option<&str>],
+) -> Vec<Node<'a, 'input>> {
+    node.children()
+        .filter(|child| {
+            child.is_element()
+                && child.tag_name().name() == name
+                && namespaces.contains(&child.tag_name().namespace())
+        })
+        .collect()
+}
+
+/// Text of the first non-empty child element named `name`.
+fn child_text(node: Node, name: &str, namespaces: &[Option<&str>]) -> Option<String> {
+    elements(node, name, namespaces)
+        .into_iter()
+        .find_map(node_text)
+}
+
+/// All text (including CDATA) under `node`, trimmed; `None` when empty.
+fn node_text(node: Node) -> Option<String> {
+    let text: String = node
+        .descendants()
+        .filter(|descendant| descendant.is_text())
+        .filter_map(|descendant| descendant.text())
+        .collect();
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Deduplicated `names` joined with `", "`.
+fn join_names(names: Vec<String>) -> Option<String> {
+    let mut unique: Vec<String> = Vec::with_capacity(names.len());
+    for name in names {
+        if !unique.contains(&name) {
+            unique.push(name);
+        }
+    }
+    (!unique.is_empty()).then(|| unique.join(", "))
+}
+
+/// RFC 2822 dates as RFC 3339 UTC; anything else (Atom and JSON Feed
+/// dates are already RFC 3339) as written.
+fn normalize_date(date: &str) -> String {
+    rfc2822_to_rfc3339(date).unwrap_or_else(|| date.trim().to_string())
+}
+
+/// Parse an RFC 2822 date (`Tue, 14 Oct 2025 08:00:00 +0200`, with
+/// optional weekday and seconds, two-digit years, and named US zones) into
+/// an RFC 3339 UTC timestamp.
+fn rfc2822_to_rfc3339(date: &str) -> Option<String> {
+    let date = date.trim();
+    let date = date.split_once(',').map_or(date, |(_, rest)| rest);
+    let mut parts = date.split_whitespace();
+    let day: u32 = parts.next()?.parse().ok()?;
+    let month = month_number(parts.next()?)?;
+    let year: i64 = match parts.next()?.parse().ok()? {
+        year @ 0..=49 => year + 2000,
+        year @ 50..=99 => year + 1900,
+        year => year,
+    };
+    let mut time = parts.next()?.split(':');
+    let hour: i64 = time.next()?.parse().ok()?;
+    let minute: i64 = time.next()?.parse().ok()?;
+    let second: i64 = match time.next() {
+        Some(second) => second.parse().ok()?,
+        None => 0,
+    };
+    if !(1..=31).contains(&day) || hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+    let offset = zone_offset_seconds(parts.next().unwrap_or("GMT"))?;
+
+    let local = days_from_civil(year, month, day) * 86_400 + hour * 3_600 + minute * 60 + second;
+    Some(format_rfc3339_utc(u64::try_from(local - offset).ok()?))
+}
+
+fn month_number(name: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ];
+    let prefix = name.get(..3)?.to_ascii_lowercase();
+    MONTHS
+        .iter()
+        .position(|month| *month == prefix)
+        .map(|index| index as u32 + 1)
+}
+
+/// UTC offset of an RFC 2822 zone in seconds. Unknown named zones count as
+/// UTC, as RFC 2822 prescribes.
+fn zone_offset_seconds(zone: &str) -> Option<i64> {
+    let sign = match zone.as_bytes().first() {
+        Some(b'+') => Some(1),
+        Some(b'-') => Some(-1),
+        _ => None,
+    };
+    if let Some(sign) = sign {
+        let digits = &zone[1..];
+        if digits.len() != 4 || !digits.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+        let hours: i64 = digits[..2].parse().ok()?;
+        let minutes: i64 = digits[2..].parse().ok()?;
+        return Some(sign * (hours * 3_600 + minutes * 60));
+    }
+    let hours = match zone.to_ascii_uppercase().as_str() {
+        "EDT" => -4,
+        "EST" | "CDT" => -5,
+        "CST" | "MDT" => -6,
+        "MST" | "PDT" => -7,
+        "PST" => -8,
+        _ => 0,
+    };
+    Some(hours * 3_600)
+}
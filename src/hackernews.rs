@@ -0,0 +1,395 @@
+//! Hacker News items through the Firebase API.
+//!
+//! `news.ycombinator.com/item?id=…` pages are the discussion, not the
+//! story: scraping them yields the comment page HTML. The built-in
+//! [`HackerNewsHandler`] reads the item from the official Firebase API
+//! (`/v0/item/:id.json`) instead:
+//!
+//! - **Link stories** are followed to the story URL, the way link-only
+//!   tweets are ([`crate::x::x_post_is_link_only`]).
+//! - **Text items** (Ask HN, Show HN with a description, job posts, polls)
+//!   are converted from their `text` field, with the story URL (if any)
+//!   linked above it.
+//!
+//! With [`HackerNewsHandler::comment_tree`] a condensed comment tree,
+//! limited in depth and count, is appended as a Markdown section.
+
+use futures::future::BoxFuture;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use crate::error::FallbackStage;
+use crate::events::{emit_event, ScrapeEvent};
+use crate::handler::{HandlerFuture, ScrapeContext, SourceHandler};
+use crate::html::{push_escaped_attribute, push_escaped_text};
+use crate::markdown::html_to_markdown;
+use crate::util::format_rfc3339_utc;
+use crate::web::{convert_scraped_post, fetch_api_page, scrape_linked_article};
+use crate::{Post, PostMetadata, Provenance, ScrapeError, Scraper};
+
+/// The official Hacker News Firebase API.
+pub const HACKER_NEWS_API_BASE: &str = "https://hacker-news.firebaseio.com/v0";
+
+/// The heading of the comment-tree appendix.
+const COMMENTS_HEADING: &str = "## Comments";
+
+/// The built-in [`SourceHandler`] for Hacker News item URLs.
+///
+/// Appends no comments by default; see
+/// [`HackerNewsHandler::comment_tree`].
+#[derive(Debug, Clone)]
+pub struct HackerNewsHandler {
+    api_base: String,
+    max_comments: usize,
+    max_depth: usize,
+}
+
+impl Default for HackerNewsHandler {
+    fn default() -> Self {
+        Self {
+            api_base: HACKER_NEWS_API_BASE.to_string(),
+            max_comments: 0,
+            max_depth: 0,
+        }
+    }
+}
+
+impl HackerNewsHandler {
+    /// Read items from `api_base` instead of [`HACKER_NEWS_API_BASE`].
+    pub fn api_base(mut self, api_base: impl Into<String>) -> Self {
+        self.api_base = api_base.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Append up to `max_comments` comments, at most `max_depth` levels
+    /// deep (`1` is top-level comments only), to each post. Comments are
+    /// taken depth first in Hacker News' ranking order; dead and deleted
+    /// comments are skipped. `0` for either (the default) appends none.
+    pub fn comment_tree(mut self, max_comments: usize, max_depth: usize) -> Self {
+        self.max_comments = max_comments;
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Fetch item `id`.
+    ///
+    /// Fails with [`ScrapeError::Network`] / [`ScrapeError::HttpStatus`]
+    /// when the API cannot be reached or rejects the request, or
+    /// [`ScrapeError::Other`] when the item does not exist.
+    pub async fn fetch_item(
+        &self,
+        scraper: &Scraper,
+        id: u64,
+    ) -> Result<HackerNewsItem, ScrapeError> {
+        let url = format!("{}/item/{}.json", self.api_base, id);
+        let page = fetch_api_page(scraper, &url, "application/json")
+            .await
+            .map_err(ScrapeError::network)?;
+        match page.json_body() {
+            Ok(body) => parse_hacker_news_item(body).map_err(ScrapeError::other),
+            Err(error) if page.status.is_success() => Err(ScrapeError::other(error)),
+            Err(error) => Err(ScrapeError::http_status(page.status.as_u16(), error)),
+        }
+    }
+
+    /// Fetch the condensed comment tree under `item`, within the
+    /// [`HackerNewsHandler::comment_tree`] limits. Comments that fail to
+    /// load are skipped.
+    pub async fn fetch_comments(
+        &self,
+        scraper: &Scraper,
+        item: &HackerNewsItem,
+    ) -> Vec<HackerNewsComment> {
+        let mut remaining = self.max_comments;
+        self.fetch_replies(scraper, &item.kids, 1, &mut remaining)
+            .await
+    }
+
+    /// Fetch the comments `ids` at `depth` and their replies, depth first,
+    /// until `remaining` runs out.
+    fn fetch_replies<'a>(
+        &'a self,
+        scraper: &'a Scraper,
+        ids: &'a [u64],
+        depth: usize,
+        remaining: &'a mut usize,
+    ) -> BoxFuture<'a, Vec<HackerNewsComment>> {
+        Box::pin(async move {
+            let mut comments = Vec::new();
+            if depth > self.max_depth {
+                return comments;
+            }
+            for &id in ids {
+                if *remaining == 0 {
+                    break;
+                }
+                let Ok(item) = self.fetch_item(scraper, id).await else {
+                    continue;
+                };
+                if item.dead || item.deleted || item.text.is_none() {
+                    continue;
+                }
+                *remaining -= 1;
+                let replies = self
+                    .fetch_replies(scraper, &item.kids, depth + 1, remaining)
+                    .await;
+                comments.push(HackerNewsComment {
+                    id: item.id,
+                    by: item.by.unwrap_or_default(),
+                    time: item.time,
+                    text: item.text.unwrap_or_default(),
+                    replies,
+                });
+            }
+            comments
+        })
+    }
+}
+
+impl SourceHandler for HackerNewsHandler {
+    fn name(&self) -> &str {
+        "hackernews"
+    }
+
+    fn matches(&self, url: &str) -> bool {
+        hacker_news_item_id(url).is_some()
+    }
+
+    fn scrape<'a>(&'a self, url: &'a str, ctx: &'a ScrapeContext<'a>) -> HandlerFuture<'a> {
+        Box::pin(scrape_hacker_news_url(
+            self,
+            ctx.scraper(),
+            url,
+            ctx.language(),
+        ))
+    }
+
+    fn cache_fingerprint(&self) -> String {
+        format!("{} {} {}", self.api_base, self.max_comments, self.max_depth)
+    }
+}
+
+/// A Hacker News item, as the Firebase API serves it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HackerNewsItem {
+    pub id: u64,
+    /// `story`, `comment`, `job`, `poll`, or `pollopt`.
+    #[serde(rename = "type", default)]
+    pub kind: String,
+    /// The author's username.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub by: Option<String>,
+    /// Seconds since the Unix epoch.
+    #[serde(default)]
+    pub time: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// The story URL; `None` for Ask HN and other text items.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// The item text, as HTML.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score: Option<i64>,
+    /// The total comment count of a story.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub descendants: Option<u64>,
+    /// Child comment ids, in ranked order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kids: Vec<u64>,
+    /// The parent of a comment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<u64>,
+    #[serde(default)]
+    pub dead: bool,
+    #[serde(default)]
+    pub deleted: bool,
+}
+
+/// A comment in the condensed tree.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HackerNewsComment {
+    pub id: u64,
+    pub by: String,
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    /// The comment text, as HTML.
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub replies: Vec<HackerNewsComment>,
+}
+
+impl HackerNewsItem {
+    /// The item's discussion page.
+    pub fn discussion_url(&self) -> String {
+        format!("https://news.ycombinator.com/item?id={}", self.id)
+    }
+
+    /// The story URL to scrape: set for link stories, `None` for items
+    /// with a `text` field (Ask HN, Show HN with a description), whose
+    /// text is the content.
+    pub fn linked_article_url(&self) -> Option<&str> {
+        if self
+            .text
+            .as_deref()
+            .is_some_and(|text| !text.trim().is_empty())
+        {
+            return None;
+        }
+        self.url
+            .as_deref()
+            .filter(|url| Url::parse(url).is_ok_and(|url| matches!(url.scheme(), "http" | "https")))
+    }
+
+    /// The item as [`PostMetadata`]: a `DiscussionForumPosting` with the
+    /// title, author, and time.
+    pub fn metadata(&self) -> PostMetadata {
+        PostMetadata {
+            schema_type: Some("DiscussionForumPosting".to_string()),
+            headline: self.title.clone(),
+            authors: self.by.iter().cloned().collect(),
+            date_published: Some(format_rfc3339_utc(self.time)),
+            publisher: Some("Hacker News".to_string()),
+            ..PostMetadata::default()
+        }
+    }
+
+    /// The text item as a [`Post`] scraped from `url`: the story URL (if
+    /// any) linked above the text.
+    fn to_post(&self, url: &str) -> Post {
+        let mut content = String::new();
+        if let Some(story_url) = &self.url {
+            content.push_str("<p><a href=\"");
+            push_escaped_attribute(&mut content, story_url);
+            content.push_str("\">");
+            push_escaped_text(&mut content, story_url);
+            content.push_str("</a></p>");
+        }
+        // The API's HTML leaves the first paragraph bare and opens each
+        // following one with `<p>`.
+        content.push_str("<p>");
+        content.push_str(self.text.as_deref().unwrap_or_default());
+        let title = self.title.clone().unwrap_or_else(|| match &self.by {
+            Some(by) => format!("Comment by {}", by),
+            None => format!("Hacker News item {}", self.id),
+        });
+        Post {
+            title,
+            content,
+            publication_date: Some(format_rfc3339_utc(self.time)),
+            author: self.by.clone(),
+            provenance: Some(Provenance {
+                canonical_url: Some(self.discussion_url()),
+                ..Provenance::new(FallbackStage::HackerNews, url)
+            }),
+            metadata: Some(self.metadata()),
+            ..Post::default()
+        }
+    }
+}
+
+/// The id of a Hacker News item URL
+/// (`https://news.ycombinator.com/item?id=:id`); `None` for other URLs.
+///
+/// ```
+/// use uninews::hacker_news_item_id;
+/// assert_eq!(hacker_news_item_id("https://news.ycombinator.com/item?id=8863"), Some(8863));
+/// assert_eq!(hacker_news_item_id("https://news.ycombinator.com/news"), None);
+/// ```
+pub fn hacker_news_item_id(url: &str) -> Option<u64> {
+    let url = Url::parse(url).ok()?;
+    if url.host_str()? != "news.ycombinator.com" || url.path() != "/item" {
+        return None;
+    }
+    url.query_pairs()
+        .find(|(name, _)| name == "id")
+        .and_then(|(_, id)| id.parse().ok())
+}
+
+/// Parse a Firebase `/v0/item/:id.json` response. The API answers `null`
+/// for items that do not exist; that fails with a message saying so.
+pub fn parse_hacker_news_item(json: &str) -> Result<HackerNewsItem, String> {
+    let item: Option<HackerNewsItem> =
+        serde_json::from_str(json).map_err(|error| format!("Not a Hacker News item: {}", error))?;
+    let item = item.ok_or("The Hacker News item does not exist")?;
+    if item.deleted {
+        return Err(format!("Hacker News item {} was deleted", item.id));
+    }
+    Ok(item)
+}
+
+/// The comment-tree appendix: a Markdown section with one nested list item
+/// per comment; empty when there are no comments.
+fn comments_markdown(comments: &[HackerNewsComment]) -> String {
+    if comments.is_empty() {
+        return String::new();
+    }
+    let mut markdown = format!("{}\n\n", COMMENTS_HEADING);
+    push_comments(&mut markdown, comments, 0);
+    markdown
+}
+
+fn push_comments(markdown: &mut String, comments: &[HackerNewsComment], depth: usize) {
+    let indent = "  ".repeat(depth);
+    for comment in comments {
+        let text = html_to_markdown(&format!("<p>{}", comment.text), None);
+        let mut lines = text.lines();
+        markdown.push_str(&format!(
+            "{}- **{}**: {}\n",
+            indent,
+            comment.by,
+            lines.next().unwrap_or_default()
+        ));
+        for line in lines {
+            if line.trim().is_empty() {
+                markdown.push('\n');
+            } else {
+                markdown.push_str(&format!("{}  {}\n", indent, line));
+            }
+        }
+        push_comments(markdown, &comment.replies, depth + 1);
+    }
+}
+
+/// Scrape a Hacker News item: the linked story, or the item's own text
+/// converted to Markdown, with the comment-tree appendix.
+async fn scrape_hacker_news_url(
+    handler: &HackerNewsHandler,
+    scraper: &Scraper,
+    url: &str,
+    language: &str,
+) -> Post {
+    let fallbacks = [FallbackStage::HackerNews];
+    let item = match hacker_news_item_id(url) {
+        Some(id) => handler.fetch_item(scraper, id).await,
+        None => Err(ScrapeError::other(format!(
+            "Not a Hacker News item URL: {}",
+            url
+        ))),
+    };
+    let item = match item {
+        Ok(item) => item,
+        Err(error) => return Post::from_error(error).with_fallbacks(&fallbacks),
+    };
+
+    let mut post = match item.linked_article_url() {
+        Some(article_url) => scrape_linked_article(scraper, article_url, language, None).await,
+        None => {
+            let post = item.to_post(url);
+            emit_event(ScrapeEvent::ContentExtracted {
+                url: url.to_string(),
+                content_bytes: post.content.len(),
+            });
+            convert_scraped_post(scraper, post, language, &fallbacks).await
+        }
+    };
+
+    if post.error.is_empty() {
+        let comments = comments_markdown(&handler.fetch_comments(scraper, &item).await);
+        if !comments.is_empty() {
+            post.content = format!("{}\n\n{}", post.content.trim_end(), comments);
+        }
+    }
+    post
+}
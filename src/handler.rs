@@ -0,0 +1,251 @@
+//! Pluggable domain-specific scraping pipelines.
+//!
+//! Most URLs go through the generic web pipeline (fetch → fallbacks →
+//! extraction → Markdown conversion), but some sources are better served
+//! by a dedicated pipeline: X posts come from the X API, YouTube videos
+//! from their captions, Mastodon statuses from the instance's API,
+//! Bluesky posts from the AppView, Reddit threads from their `.json`
+//! representation, Hacker News items from the Firebase API, Telegram
+//! posts from the channel web preview, and an internal wiki or a partner
+//! site may have an API of its own. A [`SourceHandler`]
+//! claims URLs ([`SourceHandler::matches`]) and produces their [`Post`]
+//! ([`SourceHandler::scrape`]); a [`SourceHandlers`] registry, carried by
+//! the [`ScrapeOptions`], picks the handler for each URL. URLs no handler
+//! claims take the generic web pipeline.
+//!
+//! Handlers receive a [`ScrapeContext`] exposing the scraper's shared HTTP
+//! clients and options, the event emitter, the LLM conversion step, and
+//! the generic web pipeline (for URLs a handler decides to pass through).
+//!
+//! [`SourceHandlers::builtin`] (what [`ScrapeOptions::from_env`] uses)
+//! holds the built-in X, YouTube, Mastodon, Bluesky, Reddit, Hacker News,
+//! and Telegram handlers ([`crate::XHandler`], [`crate::YouTubeHandler`],
+//! [`crate::MastodonHandler`], [`crate::BlueskyHandler`],
+//! [`crate::RedditHandler`], [`crate::HackerNewsHandler`],
+//! [`crate::TelegramHandler`]). Handlers
+//! registered later are consulted first, so a registered handler can
+//! take over URLs from a built-in one.
+//!
+//! ```rust
+//! use uninews::{
+//!     HandlerFuture, Post, ScrapeContext, ScrapeOptions, Scraper, SourceHandler,
+//! };
+//!
+//! struct Wiki;
+//!
+//! impl SourceHandler for Wiki {
+//!     fn name(&self) -> &str {
+//!         "wiki"
+//!     }
+//!
+//!     fn matches(&self, url: &str) -> bool {
+//!         url.starts_with("https://wiki.internal/")
+//!     }
+//!
+//!     fn scrape<'a>(&'a self, url: &'a str, ctx: &'a ScrapeContext<'a>) -> HandlerFuture<'a> {
+//!         Box::pin(async move {
+//!             let page = Post {
+//!                 title: format!("Wiki page {url}"),
+//!                 content: "<p>Fetched with ctx.api_client()</p>".to_string(),
+//!                 ..Post::default()
+//!             };
+//!             match ctx.convert(page).await {
+//!                 Ok(post) => post,
+//!                 Err(error) => Post::from_error(error),
+//!             }
+//!         })
+//!     }
+//! }
+//!
+//! let scraper = Scraper::new(ScrapeOptions::from_env().handler(Wiki));
+//! assert_eq!(
+//!     scraper.options().source_handlers().names(),
+//!     ["x", "youtube", "mastodon", "bluesky", "reddit", "hackernews", "telegram", "wiki"]
+//! );
+//! ```
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use reqwest::Client;
+
+use crate::bluesky::BlueskyHandler;
+use crate::events::{emit_event, ScrapeEvent};
+use crate::hackernews::HackerNewsHandler;
+use crate::llm::convert_with_options;
+use crate::mastodon::MastodonHandler;
+use crate::reddit::RedditHandler;
+use crate::telegram::TelegramHandler;
+use crate::web::scrape_web_url;
+use crate::x::XHandler;
+use crate::youtube::YouTubeHandler;
+use crate::{Post, ScrapeError, ScrapeOptions, Scraper};
+
+/// Boxed future returned by [`SourceHandler::scrape`].
+pub type HandlerFuture<'a> = Pin<Box<dyn Future<Output = Post> + Send + 'a>>;
+
+/// A domain-specific scraping pipeline.
+///
+/// [`SourceHandler::scrape`] follows the same contract as
+/// [`Scraper::scrape`]: it always returns a [`Post`], with failures
+/// reported in [`Post::error`] (see [`Post::from_error`]).
+pub trait SourceHandler: Send + Sync + 'static {
+    /// Short, stable name reported in [`ScrapeEvent::SourceHandlerSelected`]
+    /// (`"x"`, `"wiki"`, …).
+    fn name(&self) -> &str;
+
+    /// Whether this handler scrapes `url`.
+    fn matches(&self, url: &str) -> bool;
+
+    /// Scrape `url` and convert it to Markdown in [`ScrapeContext::language`].
+    fn scrape<'a>(&'a self, url: &'a str, ctx: &'a ScrapeContext<'a>) -> HandlerFuture<'a>;
+
+    /// This handler's settings that change what it returns (an API base
+    /// URL, a comment limit). They are part of the [`crate::ScrapeCache`]
+    /// post key, so scrapers configured differently never share cached
+    /// posts. Empty by default.
+    fn cache_fingerprint(&self) -> String {
+        String::new()
+    }
+}
+
+/// What a [`SourceHandler`] gets to work with: the [`Scraper`] running the
+/// scrape and the requested output language.
+pub struct ScrapeContext<'a> {
+    scraper: &'a Scraper,
+    language: &'a str,
+}
+
+impl<'a> ScrapeContext<'a> {
+    pub(crate) fn new(scraper: &'a Scraper, language: &'a str) -> Self {
+        Self { scraper, language }
+    }
+
+    /// The scraper running this scrape.
+    pub fn scraper(&self) -> &'a Scraper {
+        self.scraper
+    }
+
+    /// The scraper's options.
+    pub fn options(&self) -> &'a ScrapeOptions {
+        self.scraper.options()
+    }
+
+    /// The requested output language.
+    pub fn language(&self) -> &'a str {
+        self.language
+    }
+
+    /// The shared client for page fetches (HTTP/1.1 + browser
+    /// User-Agent), honoring the scraper's timeouts.
+    pub fn web_client(&self) -> &'a Client {
+        self.scraper.web_client()
+    }
+
+    /// The shared client for JSON API calls, honoring the scraper's
+    /// timeouts.
+    pub fn api_client(&self) -> &'a Client {
+        self.scraper.api_client()
+    }
+
+    /// Deliver `event` to the registered event listener, if any.
+    pub fn emit(&self, event: ScrapeEvent) {
+        emit_event(event);
+    }
+
+    /// Convert `post.content` (HTML or plain text) to Markdown in the
+    /// requested language with the scraper's conversion settings, exactly
+    /// as the built-in pipelines do.
+    pub async fn convert(&self, post: Post) -> Result<Post, ScrapeError> {
+        convert_with_options(post, self.language, self.scraper.options()).await
+    }
+
+    /// Scrape `url` with the generic web pipeline, bypassing every
+    /// handler (and the post cache).
+    pub async fn scrape_web(&self, url: &str) -> Post {
+        scrape_web_url(self.scraper, url, self.language).await
+    }
+}
+
+/// Registry of [`SourceHandler`]s. Cheap to clone.
+///
+/// Handlers are consulted newest first; the first whose
+/// [`SourceHandler::matches`] accepts a URL scrapes it.
+#[derive(Clone, Default)]
+pub struct SourceHandlers {
+    handlers: Vec<Arc<dyn SourceHandler>>,
+}
+
+impl SourceHandlers {
+    /// An empty registry: every URL takes the generic web pipeline.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The built-in handlers: X / Twitter ([`crate::XHandler`]),
+    /// YouTube ([`crate::YouTubeHandler`]), Mastodon
+    /// ([`crate::MastodonHandler`]), Bluesky ([`crate::BlueskyHandler`]),
+    /// Reddit ([`crate::RedditHandler`]), Hacker News
+    /// ([`crate::HackerNewsHandler`]), and Telegram
+    /// ([`crate::TelegramHandler`]).
+    pub fn builtin() -> Self {
+        let mut handlers = Self::new();
+        handlers
+            .register(XHandler)
+            .register(YouTubeHandler)
+            .register(MastodonHandler)
+            .register(BlueskyHandler::default())
+            .register(RedditHandler::default())
+            .register(HackerNewsHandler::default())
+            .register(TelegramHandler::default());
+        handlers
+    }
+
+    /// Add `handler`, ahead of every handler registered before it.
+    pub fn register(&mut self, handler: impl SourceHandler) -> &mut Self {
+        self.handlers.push(Arc::new(handler));
+        self
+    }
+
+    /// The name and [`SourceHandler::cache_fingerprint`] of every handler,
+    /// in registration order.
+    pub(crate) fn cache_fingerprint(&self) -> String {
+        self.handlers
+            .iter()
+            .map(|handler| format!("{}={}", handler.name(), handler.cache_fingerprint()))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The handler that scrapes `url`, if any.
+    pub fn handler_for(&self, url: &str) -> Option<&dyn SourceHandler> {
+        self.handlers
+            .iter()
+            .rev()
+            .find(|handler| handler.matches(url))
+            .map(|handler| handler.as_ref())
+    }
+
+    /// Handler names, in registration order.
+    pub fn names(&self) -> Vec<&str> {
+        self.handlers.iter().map(|handler| handler.name()).collect()
+    }
+
+    /// Number of handlers.
+    pub fn len(&self) -> usize {
+        self.handlers.len()
+    }
+
+    /// Whether the registry holds no handlers.
+    pub fn is_empty(&self) -> bool {
+        self.handlers.is_empty()
+    }
+}
+
+impl fmt::Debug for SourceHandlers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
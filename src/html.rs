@@ -4,13 +4,26 @@
 //! main article body inside a parsed HTML document, strips unwanted elements
 //! (scripts, ads, navigation, …), and pulls metadata (`<title>`, Open Graph
 //! tags) out of the page.
+//!
+//! The article body is located by a readability-style scorer (see
+//! [`extract_scored_content`]) that ranks candidate containers by paragraph
+//! text, link density, and class / id hints; the older "longest `<article>`,
+//! else `<body>`" strategy ([`extract_article_or_body`]) remains as the
+//! fallback when scoring finds nothing usable.
+//!
+//! Per-domain [`ExtractionRules`] are consulted before all of the generic
+//! heuristics (see [`parse_scraped_post_with_rules`]).
 
+use std::collections::{HashMap, HashSet};
 use std::sync::OnceLock;
 
 use scraper::{ElementRef, Html, Selector};
 
+use crate::canonical::canonical_url_in;
+use crate::metadata::{extract_json_ld_article, JsonLdArticle};
+use crate::rules::{bundled_rules, CompiledRule};
 use crate::x::{is_x_article_url, x_article_body_unavailable};
-use crate::Post;
+use crate::{ExtractionRules, FallbackStage, Post, Provenance, ScrapeError};
 
 /// Tag names that are stripped from the extracted content entirely
 /// (scripts, ads, navigation, form controls, media wrappers).
@@ -41,7 +54,7 @@ fn cached_selector(slot: &'static OnceLock<Selector>, css: &str) -> &'static Sel
 /// The `&` arm is listed first by convention: replacements introduce `&`
 /// themselves, and escaping ampersands first is what prevents
 /// double-escaping in single-pass escapers.
-fn push_escaped_text(out: &mut String, text: &str) {
+pub(crate) fn push_escaped_text(out: &mut String, text: &str) {
     for ch in text.chars() {
         match ch {
             '&' => out.push_str("&amp;"),
@@ -52,6 +65,67 @@ fn push_escaped_text(out: &mut String, text: &str) {
     }
 }
 
+/// Appends `value` as a double-quoted attribute value, escaping `&`, `"`,
+/// `<`, and `>` (see [`push_escaped_text`]).
+pub(crate) fn push_escaped_attribute(out: &mut String, value: &str) {
+    for ch in value.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '"' => out.push_str("&quot;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            _ => out.push(ch),
+        }
+    }
+}
+
+/// Whether a link / image URL may survive cleaning: relative URLs and the
+/// `http`, `https`, and `mailto` schemes. `javascript:`, `data:`, and other
+/// schemes are dropped.
+pub(crate) fn is_kept_url(value: &str) -> bool {
+    let value = value.trim();
+    if value.is_empty() {
+        return false;
+    }
+    match value.find([':', '/', '?', '#']) {
+        Some(index) if value[index..].starts_with(':') => {
+            let scheme = value[..index].to_ascii_lowercase();
+            matches!(scheme.as_str(), "http" | "https" | "mailto")
+        }
+        _ => true,
+    }
+}
+
+/// Append the attributes [`clean_element`] keeps for `elem` — `href` on
+/// `<a>`, `src`/`alt` on `<img>`, `start` on `<ol>` — so links, images,
+/// and list numbering survive into the cleaned HTML. Everything else
+/// (classes, inline styles, event handlers) is dropped.
+fn push_kept_attributes(out: &mut String, elem: ElementRef) {
+    let kept: &[&str] = match elem.value().name() {
+        "a" => &["href"],
+        "img" => &["src", "alt"],
+        "ol" => &["start"],
+        _ => return,
+    };
+    for name in kept {
+        let Some(value) = elem.value().attr(name) else {
+            continue;
+        };
+        let keep = match *name {
+            "href" | "src" => is_kept_url(value),
+            "start" => value.trim().parse::<usize>().is_ok(),
+            _ => true,
+        };
+        if keep {
+            out.push(' ');
+            out.push_str(name);
+            out.push_str("=\"");
+            push_escaped_attribute(out, value.trim());
+            out.push('"');
+        }
+    }
+}
+
 /// One unit of pending work for the iterative [`clean_element`] traversal.
 ///
 /// Pushing explicit work items onto a heap-allocated stack keeps DOM nesting
@@ -81,6 +155,11 @@ enum CleanWork<'a> {
 
 /// Cleans an element by skipping unwanted tags and empty content.
 ///
+/// With `prune_boilerplate`, descendants that look like boilerplate inside
+/// an otherwise good container — related-story rails, share bars,
+/// newsletter boxes (see [`is_boilerplate`]) — are dropped as well; the
+/// root element itself is always kept.
+///
 /// This private function is the core of the content extraction pipeline. It removes
 /// unwanted HTML elements (like scripts and ads) while preserving meaningful content.
 ///
@@ -95,6 +174,9 @@ enum CleanWork<'a> {
 /// - Child nodes are processed in document order
 /// - Only non-empty children (or non-whitespace text) are kept; text-node
 ///   content is HTML-escaped (see [`push_escaped_text`])
+/// - All attributes are dropped except link targets, image sources and alt
+///   text, and list numbering (see [`push_kept_attributes`]); an `<img>`
+///   with a usable `src` is kept even though it has no children
 /// - Elements with no content after cleaning are elided entirely, open tag
 ///   included, by truncating the buffer back to the length recorded before
 ///   the open tag was appended
@@ -119,12 +201,13 @@ enum CleanWork<'a> {
 ///
 /// - `element`: The HTML element to clean
 /// - `skip_tags`: Tag names to completely remove
+/// - `prune_boilerplate`: Also remove boilerplate-looking descendants
 ///
 /// # Returns
 ///
 /// Cleaned HTML as a string, or empty string if no content remains
 #[must_use]
-fn clean_element(element: ElementRef, skip_tags: &[&str]) -> String {
+fn clean_element(element: ElementRef, skip_tags: &[&str], prune_boilerplate: bool) -> String {
     let mut out = String::new();
     let mut stack = vec![CleanWork::Enter(element)];
 
@@ -136,12 +219,25 @@ fn clean_element(element: ElementRef, skip_tags: &[&str]) -> String {
             }
             CleanWork::Enter(elem) => {
                 let tag = elem.value().name();
-                if skip_tags.contains(&tag) {
+                if skip_tags.contains(&tag)
+                    || (prune_boilerplate && elem != element && is_boilerplate(elem, skip_tags))
+                {
+                    continue;
+                }
+                if tag == "img" {
+                    // Void element: no children, no close tag, and no
+                    // content to elide on — keep it only with a usable src.
+                    if elem.value().attr("src").is_some_and(is_kept_url) {
+                        out.push_str("<img");
+                        push_kept_attributes(&mut out, elem);
+                        out.push_str("> ");
+                    }
                     continue;
                 }
                 let start = out.len();
                 out.push('<');
                 out.push_str(tag);
+                push_kept_attributes(&mut out, elem);
                 out.push('>');
                 let content_start = out.len();
                 stack.push(CleanWork::Exit {
@@ -203,11 +299,11 @@ fn has_article_ancestor(element: ElementRef) -> bool {
     })
 }
 
-/// Extracts and cleans main content from an HTML document.
+/// Extracts and cleans main content with the original, structure-only
+/// strategy: the longest `<article>`, else the whole `<body>`.
 ///
-/// This function implements the content extraction strategy used by the scraper.
-/// It prioritizes the `<article>` tag (standard for news sites) but falls back
-/// to `<body>` if no article is found.
+/// Used by [`extract_clean_content`] when the scoring extractor finds no
+/// candidate or only a thin one.
 ///
 /// # Strategy
 ///
@@ -236,7 +332,7 @@ fn has_article_ancestor(element: ElementRef) -> bool {
 /// content. (`Html::parse_document` is error-correcting, so malformed markup
 /// does not by itself produce an empty result.)
 #[must_use]
-fn extract_clean_content(document: &Html, skip_tags: &[&str]) -> String {
+fn extract_article_or_body(document: &Html, skip_tags: &[&str]) -> String {
     static ARTICLE_SELECTOR: OnceLock<Selector> = OnceLock::new();
     static BODY_SELECTOR: OnceLock<Selector> = OnceLock::new();
 
@@ -248,7 +344,7 @@ fn extract_clean_content(document: &Html, skip_tags: &[&str]) -> String {
     let best_article = document
         .select(cached_selector(&ARTICLE_SELECTOR, "article"))
         .filter(|article| !has_article_ancestor(*article))
-        .map(|article| clean_element(article, skip_tags))
+        .map(|article| clean_element(article, skip_tags, false))
         .filter(|cleaned| !cleaned.trim().is_empty())
         .max_by_key(|cleaned| cleaned.len());
     if let Some(content) = best_article {
@@ -260,14 +356,488 @@ fn extract_clean_content(document: &Html, skip_tags: &[&str]) -> String {
         .select(cached_selector(&BODY_SELECTOR, "body"))
         .next()
     {
-        return clean_element(body, skip_tags);
+        return clean_element(body, skip_tags, false);
     }
     String::new()
 }
 
+/// Class / id / role tokens that mark a likely article container.
+const POSITIVE_HINTS: &[&str] = &[
+    "article", "blog", "body", "content", "entry", "main", "page", "post", "prose", "story", "text",
+];
+
+/// Class / id / role tokens that mark boilerplate around the article:
+/// navigation, related-story rails, share bars, sign-up boxes, ads.
+const NEGATIVE_HINTS: &[&str] = &[
+    "ad",
+    "ads",
+    "advert",
+    "advertisement",
+    "banner",
+    "breadcrumb",
+    "breadcrumbs",
+    "comment",
+    "comments",
+    "complementary",
+    "contentinfo",
+    "cookie",
+    "footer",
+    "masthead",
+    "menu",
+    "modal",
+    "nav",
+    "navigation",
+    "newsletter",
+    "outbrain",
+    "popup",
+    "promo",
+    "recirc",
+    "recommended",
+    "related",
+    "share",
+    "sharing",
+    "sidebar",
+    "signup",
+    "social",
+    "sponsor",
+    "sponsored",
+    "subscribe",
+    "taboola",
+    "teaser",
+    "widget",
+];
+
+/// Tags whose presence among a `<div>`'s children means the `<div>` is a
+/// container rather than a bare paragraph of text.
+const BLOCK_TAGS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "div",
+    "dl",
+    "figure",
+    "footer",
+    "form",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "main",
+    "nav",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "ul",
+];
+
+/// Paragraphs shorter than this (in chars) do not contribute to scores.
+const MIN_PARAGRAPH_CHARS: usize = 25;
+
+/// Visible and link text under an element, in chars.
+#[derive(Default)]
+struct TextStats {
+    text_chars: usize,
+    link_chars: usize,
+}
+
+impl TextStats {
+    /// Share of the visible text that sits inside links (`0.0` for no text).
+    fn link_density(&self) -> f64 {
+        if self.text_chars == 0 {
+            0.0
+        } else {
+            self.link_chars as f64 / self.text_chars as f64
+        }
+    }
+}
+
+/// Count the visible and link text under `element`, skipping `skip_tags`
+/// subtrees. Iterative, like [`clean_element`], so deep markup cannot
+/// overflow the call stack.
+fn text_stats(element: ElementRef, skip_tags: &[&str]) -> TextStats {
+    let mut stats = TextStats::default();
+    let mut stack = vec![(element, false)];
+    while let Some((elem, in_link)) = stack.pop() {
+        let in_link = in_link || elem.value().name() == "a";
+        for child in elem.children() {
+            if let Some(child_elem) = ElementRef::wrap(child) {
+                if !skip_tags.contains(&child_elem.value().name()) {
+                    stack.push((child_elem, in_link));
+                }
+            } else if let Some(text) = child.value().as_text() {
+                let chars = text.trim().chars().count();
+                stats.text_chars += chars;
+                if in_link {
+                    stats.link_chars += chars;
+                }
+            }
+        }
+    }
+    stats
+}
+
+/// `+25` per class / id / role attribute carrying a [`POSITIVE_HINTS`]
+/// token, `-25` per one carrying a [`NEGATIVE_HINTS`] token. Attributes are
+/// split into lowercase alphanumeric tokens, so `story-body` matches `story`
+/// and `body` but `shadow` does not match `ad`.
+fn hint_weight(element: ElementRef) -> f64 {
+    let mut weight = 0.0;
+    for attr in ["class", "id", "role"] {
+        let Some(value) = element.value().attr(attr) else {
+            continue;
+        };
+        let tokens: Vec<String> = value
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|token| !token.is_empty())
+            .map(str::to_ascii_lowercase)
+            .collect();
+        if tokens
+            .iter()
+            .any(|token| NEGATIVE_HINTS.contains(&token.as_str()))
+        {
+            weight -= 25.0;
+        }
+        if tokens
+            .iter()
+            .any(|token| POSITIVE_HINTS.contains(&token.as_str()))
+        {
+            weight += 25.0;
+        }
+    }
+    weight
+}
+
+/// Starting score of a candidate container by tag: semantic article
+/// containers and generic `<div>`s are likely, lists and headings unlikely.
+fn tag_weight(tag: &str) -> f64 {
+    match tag {
+        "article" => 10.0,
+        "div" | "main" | "section" => 5.0,
+        "blockquote" | "pre" | "td" => 3.0,
+        "address" | "dd" | "dl" | "dt" | "form" | "li" | "ol" | "ul" => -3.0,
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.0,
+        _ => 0.0,
+    }
+}
+
+/// True for descendants of the chosen container that should be dropped:
+/// anything with a negative class / id hint, and link-dominated blocks
+/// (more than half their text in links) without a positive one.
+fn is_boilerplate(element: ElementRef, skip_tags: &[&str]) -> bool {
+    let weight = hint_weight(element);
+    if weight < 0.0 {
+        return true;
+    }
+    weight == 0.0
+        && matches!(
+            element.value().name(),
+            "div" | "section" | "ul" | "ol" | "table"
+        )
+        && text_stats(element, skip_tags).link_density() > 0.5
+}
+
+/// True when `element` is a text block worth scoring: a `<p>`, `<pre>`, or
+/// `<td>`, or a `<div>` holding only inline content.
+fn is_paragraph_like(element: ElementRef) -> bool {
+    match element.value().name() {
+        "p" | "pre" | "td" => true,
+        "div" => !element
+            .children()
+            .filter_map(ElementRef::wrap)
+            .any(|child| BLOCK_TAGS.contains(&child.value().name())),
+        _ => false,
+    }
+}
+
+/// True when `element` sits inside a `skip_tags` element (navigation,
+/// header, footer, …) whose text must not count toward any candidate.
+fn has_skipped_ancestor(element: ElementRef, skip_tags: &[&str]) -> bool {
+    element.ancestors().any(|node| {
+        node.value()
+            .as_element()
+            .is_some_and(|e| skip_tags.contains(&e.name()))
+    })
+}
+
+/// A sibling of the top candidate that is a self-contained paragraph of
+/// prose: long with few links, or short, link-free, and sentence-final.
+fn is_standalone_paragraph(element: ElementRef, skip_tags: &[&str]) -> bool {
+    if element.value().name() != "p" {
+        return false;
+    }
+    let stats = text_stats(element, skip_tags);
+    if stats.text_chars >= 80 {
+        return stats.link_density() < 0.25;
+    }
+    stats.text_chars > 0
+        && stats.link_chars == 0
+        && element.text().collect::<String>().trim_end().ends_with('.')
+}
+
+/// Extracts and cleans main content by scoring candidate containers, in
+/// the manner of Mozilla's Readability.
+///
+/// # Strategy
+///
+/// 1. **Paragraph scores**: every paragraph-like block ([`is_paragraph_like`])
+///    outside `skip_tags` with at least [`MIN_PARAGRAPH_CHARS`] of text
+///    scores `1 + commas + min(chars / 100, 3)`.
+/// 2. **Propagation**: that score is added to the block's parent in full,
+///    its grandparent at half, and its great-grandparent at a third. Each
+///    container starts from its tag weight ([`tag_weight`]) plus its class /
+///    id / role hints ([`hint_weight`]: "content", "story-body" vs
+///    "sidebar", "related", "newsletter", …).
+/// 3. **Link density**: each container's score is scaled by
+///    `1 - link_density`, so link rails lose to prose.
+/// 4. **Siblings**: the highest-scoring container is emitted together with
+///    its siblings that score at least a fifth of it (minimum 10), and
+///    sibling `<p>`s that read as standalone prose, in document order —
+///    stories split across several sibling blocks stay whole.
+///
+/// The emitted containers are cleaned with boilerplate pruning (see
+/// [`is_boilerplate`]).
+///
+/// # Returns
+///
+/// The cleaned content, or `None` when no block scored or cleaning left
+/// nothing.
+fn extract_scored_content(document: &Html, skip_tags: &[&str]) -> Option<String> {
+    static PARAGRAPH_SELECTOR: OnceLock<Selector> = OnceLock::new();
+
+    // Candidates in first-seen order (so ties resolve deterministically),
+    // plus an index by node id.
+    let mut candidates: Vec<(ElementRef, f64)> = Vec::new();
+    let mut index = HashMap::new();
+
+    for paragraph in document
+        .select(cached_selector(&PARAGRAPH_SELECTOR, "p, pre, td, div"))
+        .filter(|element| is_paragraph_like(*element))
+        .filter(|element| !has_skipped_ancestor(*element, skip_tags))
+    {
+        let text = paragraph.text().collect::<String>();
+        let chars = text.trim().chars().count();
+        if chars < MIN_PARAGRAPH_CHARS {
+            continue;
+        }
+        let score = 1.0 + text.matches(',').count() as f64 + (chars / 100).min(3) as f64;
+
+        let ancestors = paragraph
+            .ancestors()
+            .filter_map(ElementRef::wrap)
+            .take_while(|ancestor| ancestor.value().name() != "html")
+            .take(3);
+        for (level, ancestor) in ancestors.enumerate() {
+            let slot = *index.entry(ancestor.id()).or_insert_with(|| {
+                candidates.push((
+                    ancestor,
+                    tag_weight(ancestor.value().name()) + hint_weight(ancestor),
+                ));
+                candidates.len() - 1
+            });
+            candidates[slot].1 += score / (level + 1) as f64;
+        }
+    }
+
+    for (candidate, score) in &mut candidates {
+        *score *= 1.0 - text_stats(*candidate, skip_tags).link_density();
+    }
+
+    let mut top: Option<(ElementRef, f64)> = None;
+    for &(candidate, score) in &candidates {
+        if top.is_none_or(|(_, top_score)| score > top_score) {
+            top = Some((candidate, score));
+        }
+    }
+    let (top, top_score) = top?;
+
+    let parent = top
+        .parent()
+        .and_then(ElementRef::wrap)
+        .filter(|_| top.value().name() != "body");
+    let content = match parent {
+        Some(parent) => {
+            let threshold = (top_score * 0.2).max(10.0);
+            let score_of = |element: ElementRef| {
+                index
+                    .get(&element.id())
+                    .map(|&slot: &usize| candidates[slot].1)
+            };
+            parent
+                .children()
+                .filter_map(ElementRef::wrap)
+                .filter(|sibling| {
+                    *sibling == top
+                        || (hint_weight(*sibling) >= 0.0
+                            && (score_of(*sibling).is_some_and(|score| score >= threshold)
+                                || is_standalone_paragraph(*sibling, skip_tags)))
+                })
+                .map(|sibling| clean_element(sibling, skip_tags, true))
+                .filter(|cleaned| !cleaned.is_empty())
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+        None => clean_element(top, skip_tags, true),
+    };
+    (!content.trim().is_empty()).then_some(content)
+}
+
+/// Extracts and cleans main content from an HTML document.
+///
+/// Runs the scoring extractor ([`extract_scored_content`]) first. Its result
+/// is used unless it is missing, or thin ([`is_content_insufficient`]) while
+/// the structural strategy ([`extract_article_or_body`]) yields more visible
+/// text — pages made of short fragments (listings, galleries, tiny test
+/// fixtures) score poorly but still have usable content.
+///
+/// # Returns
+///
+/// Cleaned HTML content string, or empty string when neither strategy finds
+/// usable content.
+#[must_use]
+fn extract_clean_content(document: &Html, skip_tags: &[&str]) -> String {
+    let scored = extract_scored_content(document, skip_tags);
+    if scored
+        .as_deref()
+        .is_some_and(|scored| !is_content_insufficient(scored))
+    {
+        return scored.unwrap_or_default();
+    }
+    let fallback = extract_article_or_body(document, skip_tags);
+    match scored {
+        Some(scored)
+            if visible_text_from_cleaned_html(&scored).len()
+                >= visible_text_from_cleaned_html(&fallback).len() =>
+        {
+            scored
+        }
+        _ => fallback,
+    }
+}
+
+/// Delete every element matching one of `selectors` from `document`.
+fn remove_matching(document: &mut Html, selectors: &[Selector]) {
+    let ids: Vec<_> = selectors
+        .iter()
+        .flat_map(|selector| document.select(selector).map(|element| element.id()))
+        .collect();
+    for id in ids {
+        if let Some(mut node) = document.tree.get_mut(id) {
+            node.detach();
+        }
+    }
+}
+
+/// Content from the first of `rule`'s content selectors whose matches
+/// survive cleaning: every outermost match, cleaned, in document order.
+fn extract_rule_content(document: &Html, rule: &CompiledRule) -> Option<String> {
+    rule.content.iter().find_map(|selector| {
+        let matches: Vec<ElementRef> = document.select(selector).collect();
+        let ids: HashSet<_> = matches.iter().map(|element| element.id()).collect();
+        let content = matches
+            .iter()
+            .filter(|element| !element.ancestors().any(|node| ids.contains(&node.id())))
+            .map(|element| clean_element(*element, SKIP_TAGS, false))
+            .filter(|cleaned| !cleaned.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        (!visible_text_from_cleaned_html(&content).is_empty()).then_some(content)
+    })
+}
+
+/// Whitespace-collapsed text of `element`.
+fn element_text(element: ElementRef) -> String {
+    element
+        .text()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The first non-empty `attrs` value of the first element matching
+/// `selector`, falling back to its text when `text_fallback` is set.
+fn rule_value(
+    document: &Html,
+    selector: Option<&Selector>,
+    attrs: &[&str],
+    text_fallback: bool,
+) -> Option<String> {
+    let element = document.select(selector?).next()?;
+    attrs
+        .iter()
+        .filter_map(|attr| element.value().attr(attr))
+        .map(str::trim)
+        .find(|value| !value.is_empty())
+        .map(String::from)
+        .or_else(|| text_fallback.then(|| element_text(element)))
+        .filter(|value| !value.is_empty())
+}
+
+/// Every byline matched by `rule`'s author selector (a `content`
+/// attribute, else the text), deduplicated and joined with `", "`.
+fn rule_author(document: &Html, rule: &CompiledRule) -> Option<String> {
+    let mut authors: Vec<String> = Vec::new();
+    for element in document.select(rule.author.as_ref()?) {
+        let author = element
+            .value()
+            .attr("content")
+            .map(|value| value.trim().to_string())
+            .unwrap_or_else(|| element_text(element));
+        if !author.is_empty() && !authors.contains(&author) {
+            authors.push(author);
+        }
+    }
+    (!authors.is_empty()).then(|| authors.join(", "))
+}
+
+/// Resolve `href` against `base_url`; `None` when either does not parse or
+/// the result is not an http(s) URL.
+pub(crate) fn resolve_href(base_url: &str, href: &str) -> Option<String> {
+    let href = href.trim();
+    if href.is_empty() {
+        return None;
+    }
+    let resolved = match reqwest::Url::parse(base_url) {
+        Ok(base) => base.join(href).ok()?,
+        Err(_) => reqwest::Url::parse(href).ok()?,
+    };
+    matches!(resolved.scheme(), "http" | "https").then(|| resolved.to_string())
+}
+
+/// Wrap plain `text` (a JSON-LD `articleBody`) in escaped `<p>` elements,
+/// one per non-empty line, so it can stand in for cleaned DOM content.
+pub(crate) fn article_body_to_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 64);
+    for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        out.push_str("<p>");
+        push_escaped_text(&mut out, line);
+        out.push_str("</p>");
+    }
+    out
+}
+
 /// Parse a raw HTML body into a [`Post`], extracting the title, cleaned
 /// content, featured image, publication date, and author.
 ///
+/// The page's JSON-LD article node (schema.org `NewsArticle`,
+/// `BlogPosting`, …) fills [`Post::metadata`], and supplies the author,
+/// publication date, and featured image when the `<meta>` tags do not.
+/// When the DOM extraction is thin ([`is_content_insufficient`]) and the
+/// JSON-LD `articleBody` has more visible text, the `articleBody` becomes
+/// the content.
+///
+/// Successful posts carry a [`Provenance`] with `source_url` as the final
+/// URL, the resolved `<link rel="canonical">`, and
+/// [`FallbackStage::PlainFetch`] as the source; callers that obtained the
+/// HTML another way (browser render, archive.org) overwrite the source.
+///
 /// `title_override` wins over the `<title>` tag when provided (used by the
 /// X pipeline, where the tweet's article title is more accurate than the
 /// guest-page `<title>`).
@@ -275,10 +845,30 @@ fn extract_clean_content(document: &Html, skip_tags: &[&str]) -> String {
 /// X article guest pages that withhold the article body are detected up
 /// front and reported as an error instead of returning the "this page is
 /// not supported" boilerplate as content.
+///
+/// Uses the bundled extraction rules ([`ExtractionRules::bundled`]); see
+/// [`parse_scraped_post_with_rules`] to supply others.
 pub fn parse_scraped_post_from_html(
     source_url: &str,
     body_text: &str,
     title_override: Option<&str>,
+) -> Post {
+    parse_scraped_post_with_rules(source_url, body_text, title_override, bundled_rules())
+}
+
+/// [`parse_scraped_post_from_html`] with an explicit rule registry.
+///
+/// The [`crate::SiteRule`] matching `source_url` is applied before the
+/// generic heuristics: its removal selectors delete elements up front, its
+/// content selectors replace the scored / structural extraction when they
+/// match, and its title, author, date, and image selectors win over the
+/// `<title>` / `<meta>` tags and JSON-LD. `title_override` still wins over
+/// everything.
+pub fn parse_scraped_post_with_rules(
+    source_url: &str,
+    body_text: &str,
+    title_override: Option<&str>,
+    rules: &ExtractionRules,
 ) -> Post {
     if is_x_article_url(source_url) && x_article_body_unavailable(body_text) {
         return Post {
@@ -288,16 +878,28 @@ pub fn parse_scraped_post_from_html(
             publication_date: None,
             author: None,
             error: "X article body is not available in the guest HTML response.".to_string(),
+            error_kind: Some(ScrapeError::blocked_content(
+                "X article body is not available in the guest HTML response.",
+            )),
+            provenance: None,
+            metadata: None,
+            canonical_url: None,
         };
     }
 
-    let document = Html::parse_document(body_text);
+    let rule = rules.compiled_for_url(source_url);
+    let mut document = Html::parse_document(body_text);
+    if let Some(rule) = rule {
+        remove_matching(&mut document, &rule.remove);
+    }
 
     static TITLE_SELECTOR: OnceLock<Selector> = OnceLock::new();
     static OG_IMAGE_SELECTOR: OnceLock<Selector> = OnceLock::new();
     static PUBLISHED_TIME_SELECTOR: OnceLock<Selector> = OnceLock::new();
     static AUTHOR_SELECTOR: OnceLock<Selector> = OnceLock::new();
+    static CANONICAL_SELECTOR: OnceLock<Selector> = OnceLock::new();
 
+    let rule_title = rule.and_then(|rule| rule_value(&document, rule.title.as_ref(), &[], true));
     let extracted_title = document
         .select(cached_selector(&TITLE_SELECTOR, "title"))
         .next()
@@ -317,36 +919,90 @@ pub fn parse_scraped_post_from_html(
     let title = title_override
         .filter(|title| !title.trim().is_empty())
         .map(|title| title.trim().to_string())
+        .or(rule_title)
         .unwrap_or(extracted_title);
 
-    let content = extract_clean_content(&document, SKIP_TAGS);
+    let JsonLdArticle {
+        metadata,
+        article_body,
+    } = extract_json_ld_article(&document).unwrap_or_else(|| JsonLdArticle {
+        metadata: Default::default(),
+        article_body: None,
+    });
 
-    let featured_image_url = document
-        .select(cached_selector(
-            &OG_IMAGE_SELECTOR,
-            r#"meta[property="og:image"]"#,
-        ))
-        .next()
-        .and_then(|meta| meta.value().attr("content"))
-        .unwrap_or("")
-        .to_string();
+    let mut content = rule
+        .and_then(|rule| extract_rule_content(&document, rule))
+        .unwrap_or_else(|| extract_clean_content(&document, SKIP_TAGS));
+    if let Some(article_body) = article_body.filter(|_| is_content_insufficient(&content)) {
+        let body_html = article_body_to_html(&article_body);
+        if visible_text_from_cleaned_html(&body_html).len()
+            > visible_text_from_cleaned_html(&content).len()
+        {
+            content = body_html;
+        }
+    }
 
-    let publication_date = document
+    let featured_image_url = rule
+        .and_then(|rule| rule_value(&document, rule.image.as_ref(), &["src", "content"], false))
+        .and_then(|src| resolve_href(source_url, &src))
+        .or_else(|| {
+            document
+                .select(cached_selector(
+                    &OG_IMAGE_SELECTOR,
+                    r#"meta[property="og:image"]"#,
+                ))
+                .next()
+                .and_then(|meta| meta.value().attr("content"))
+                .map(String::from)
+        })
+        .or_else(|| metadata.image_url.clone())
+        .unwrap_or_default();
+
+    let publication_date = rule
+        .and_then(|rule| {
+            rule_value(
+                &document,
+                rule.date.as_ref(),
+                &["datetime", "content"],
+                true,
+            )
+        })
+        .or_else(|| {
+            document
+                .select(cached_selector(
+                    &PUBLISHED_TIME_SELECTOR,
+                    r#"meta[property="article:published_time"]"#,
+                ))
+                .next()
+                .and_then(|meta| meta.value().attr("content"))
+                .map(String::from)
+        })
+        .or_else(|| metadata.date_published.clone());
+
+    let author = rule
+        .and_then(|rule| rule_author(&document, rule))
+        .or_else(|| {
+            document
+                .select(cached_selector(&AUTHOR_SELECTOR, r#"meta[name="author"]"#))
+                .next()
+                .and_then(|meta| meta.value().attr("content"))
+                .map(String::from)
+        })
+        .or_else(|| metadata.author_line());
+
+    let metadata = (metadata != Default::default()).then_some(metadata);
+
+    let canonical_url = document
         .select(cached_selector(
-            &PUBLISHED_TIME_SELECTOR,
-            r#"meta[property="article:published_time"]"#,
+            &CANONICAL_SELECTOR,
+            r#"link[rel="canonical"]"#,
         ))
         .next()
-        .and_then(|meta| meta.value().attr("content"))
-        .map(String::from);
-
-    let author = document
-        .select(cached_selector(&AUTHOR_SELECTOR, r#"meta[name="author"]"#))
-        .next()
-        .and_then(|meta| meta.value().attr("content"))
-        .map(String::from);
+        .and_then(|link| link.value().attr("href"))
+        .and_then(|href| resolve_href(source_url, href));
 
-    if content.trim().is_empty() {
+    let visible_text = visible_text_from_cleaned_html(&content);
+    if visible_text.is_empty() {
         return Post {
             title,
             content: String::new(),
@@ -354,20 +1010,33 @@ pub fn parse_scraped_post_from_html(
             publication_date,
             author,
             error: "Could not extract meaningful content from the page.".into(),
+            error_kind: Some(ScrapeError::insufficient_content(
+                "Could not extract meaningful content from the page.",
+            )),
+            provenance: None,
+            metadata,
+            canonical_url: None,
         };
     }
 
-    if let Some(marker) = looks_like_blocked_content(&content) {
+    // Match markers against the visible text only, so a link target such
+    // as `/paywall-faq` does not flag a real article.
+    if let Some(marker) = looks_like_blocked_content(&visible_text) {
+        let error = format!(
+            "BlockedContent: the page appears to require a subscription, paywall, or bot check (matched \"{}\"). The extracted content is likely not the real article body.",
+            marker
+        );
         return Post {
             title,
             content: String::new(),
             featured_image_url,
             publication_date,
             author,
-            error: format!(
-                "BlockedContent: the page appears to require a subscription, paywall, or bot check (matched \"{}\"). The extracted content is likely not the real article body.",
-                marker
-            ),
+            error_kind: Some(ScrapeError::blocked_content(error.clone())),
+            error,
+            provenance: None,
+            metadata,
+            canonical_url: None,
         };
     }
 
@@ -378,6 +1047,13 @@ pub fn parse_scraped_post_from_html(
         publication_date,
         author,
         error: String::new(),
+        error_kind: None,
+        provenance: Some(Provenance {
+            canonical_url,
+            ..Provenance::new(FallbackStage::PlainFetch, source_url)
+        }),
+        metadata,
+        canonical_url: Some(canonical_url_in(source_url, &document)),
     }
 }
 
@@ -24,21 +24,43 @@ use reqwest::Client;
 use crate::util::BROWSER_USER_AGENT;
 
 /// Maximum time to wait for the TCP+TLS handshake to complete.
-const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+pub(crate) const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// Maximum total time for a single request, including the response body.
-const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+pub(crate) const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Build a client for article HTML fetches (HTTP/1.1 + browser UA) with
+/// the given timeouts. Used by [`crate::Scraper`] when its options override
+/// the defaults; everything else goes through [`web_client`].
+pub(crate) fn build_web_client(
+    connect_timeout: Duration,
+    request_timeout: Duration,
+) -> reqwest::Result<Client> {
+    Client::builder()
+        .user_agent(BROWSER_USER_AGENT)
+        .http1_only()
+        .connect_timeout(connect_timeout)
+        .timeout(request_timeout)
+        .build()
+}
+
+/// Build a client for JSON API calls with the given timeouts.
+pub(crate) fn build_api_client(
+    connect_timeout: Duration,
+    request_timeout: Duration,
+) -> reqwest::Result<Client> {
+    Client::builder()
+        .user_agent(BROWSER_USER_AGENT)
+        .connect_timeout(connect_timeout)
+        .timeout(request_timeout)
+        .build()
+}
 
 /// Process-wide client for article HTML fetches (HTTP/1.1 + browser UA).
 pub(crate) fn web_client() -> &'static Client {
     static CLIENT: OnceLock<Client> = OnceLock::new();
     CLIENT.get_or_init(|| {
-        Client::builder()
-            .user_agent(BROWSER_USER_AGENT)
-            .http1_only()
-            .connect_timeout(CONNECT_TIMEOUT)
-            .timeout(REQUEST_TIMEOUT)
-            .build()
+        build_web_client(CONNECT_TIMEOUT, REQUEST_TIMEOUT)
             .expect("static reqwest web client configuration must be valid")
     })
 }
@@ -47,11 +69,7 @@ pub(crate) fn web_client() -> &'static Client {
 pub(crate) fn api_client() -> &'static Client {
     static CLIENT: OnceLock<Client> = OnceLock::new();
     CLIENT.get_or_init(|| {
-        Client::builder()
-            .user_agent(BROWSER_USER_AGENT)
-            .connect_timeout(CONNECT_TIMEOUT)
-            .timeout(REQUEST_TIMEOUT)
-            .build()
+        build_api_client(CONNECT_TIMEOUT, REQUEST_TIMEOUT)
             .expect("static reqwest API client configuration must be valid")
     })
 }
@@ -7,8 +7,85 @@
 //! - **Intelligent HTML Parsing**: Extracts article content from complex HTML structures
 //! - **Smart Content Cleaning**: Automatically removes ads, scripts, navigation, and other noise
 //! - **AI-Powered Formatting**: Converts raw HTML to near-lossless Markdown using pluggable LLM providers
+//! - **LLM-Free Conversion**: A deterministic HTML → Markdown converter
+//!   ([`html_to_markdown`]) usable instead of the LLM or as a pre-pass that
+//!   shrinks its payload ([`ConversionMode`])
 //! - **Metadata Extraction**: Captures title, author, publication date, and featured images
+//! - **Charset Decoding**: Non-UTF-8 pages (Shift_JIS, EUC-KR, GBK,
+//!   windows-1251, ISO-8859-1, …) are decoded with the encoding named by
+//!   the BOM, `Content-Type`, or `<meta>` declaration, else a statistical
+//!   guess ([`detect_charset`])
+//! - **PDF Documents**: PDF responses (by `Content-Type` or magic bytes) are
+//!   read with a pure-Rust text extractor ([`parse_pdf`]); the document
+//!   information fills title, author, and date, and page boundaries are
+//!   kept in the converted Markdown
+//! - **WordPress & Ghost APIs**: Pages that identify a WordPress or Ghost
+//!   backend ([`detect_cms_api`]) are read from the CMS REST API, whose
+//!   rendered body, authors, dates, categories, tags, and featured image
+//!   replace the DOM extraction
+//! - **Multi-Page Articles**: Features split across `?page=2` or `/2/`
+//!   pages ([`next_page_url`]) are followed up to a per-scraper page cap
+//!   and their bodies concatenated in order before conversion
+//! - **AMP & Print Alternates**: Walled and JS-heavy pages that link a
+//!   server-rendered AMP document (also tried on the Google AMP cache) or
+//!   a print / text-only view ([`alternate_pages`]) are read from it
+//!   before any browser render
+//! - **Per-Domain Extraction Rules**: [`ExtractionRules`] map host patterns
+//!   to content, removal, and metadata selectors consulted before the
+//!   generic heuristics; a default ruleset is bundled and
+//!   `UNINEWS_RULES_FILE` layers a JSON rules file on top
+//! - **Structured Metadata**: Reads schema.org `NewsArticle` / `BlogPosting`
+//!   JSON-LD into [`PostMetadata`] (all authors, modified date, publisher,
+//!   section, keywords); its `articleBody` rescues thin DOM extractions
+//! - **Provenance**: Every post records which fallback produced it, the
+//!   final / canonical URL, archive snapshot, LLM, and timestamps
 //! - **Multilingual Support**: Translates content to any language during processing
+//! - **Per-Instance Configuration**: [`Scraper`] + [`ScrapeOptions`] carry the
+//!   LLM provider, API key, fallback toggles, and timeouts per scraper, so
+//!   one process can serve differently configured scrapes concurrently
+//! - **Batch Scraping**: [`Scraper::scrape_many`] / [`scrape_many`] stream
+//!   results for many URLs under global, per-host, and browser-render
+//!   concurrency limits
+//! - **Feed Ingestion**: [`Scraper::scrape_feed`] / [`scrape_feed`] read an
+//!   RSS, Atom, or JSON Feed (discovered from a homepage's
+//!   `<link rel="alternate">` when needed) and scrape every item, merging
+//!   the feed's title, author, date, and image into each [`Post`]
+//! - **Sitemap Ingestion**: [`Scraper::scrape_sitemap`] /
+//!   [`scrape_sitemap`] read XML and Google News sitemaps (indexes and
+//!   gzip'd files included, optionally only recent entries) and scrape
+//!   every page, merging the news title, date, and keywords into each
+//!   [`Post`]
+//! - **Link Discovery**: [`Scraper::discover_article_links`] /
+//!   [`discover_article_links`] rank a section page's or homepage's
+//!   anchors as probable article URLs (rendering JS shells with
+//!   Playwright), ready for [`Scraper::scrape_many`]
+//! - **Source Handlers**: Domain-specific pipelines ([`SourceHandler`])
+//!   registered on [`ScrapeOptions`] claim URLs before the generic web
+//!   pipeline; X / Twitter ([`XHandler`]), YouTube ([`YouTubeHandler`]),
+//!   Mastodon ([`MastodonHandler`]), Bluesky ([`BlueskyHandler`]), Reddit
+//!   ([`RedditHandler`]), Hacker News ([`HackerNewsHandler`]), and Telegram
+//!   ([`TelegramHandler`]) are built in
+//! - **YouTube Transcripts**: Videos are scraped from their description and
+//!   captions (manual preferred over auto-generated, in the target language
+//!   when available) read from the watch page's player response
+//! - **Mastodon / ActivityPub**: Status URLs on any instance are read from
+//!   the Mastodon API (or ActivityStreams JSON), with the author's
+//!   self-reply thread assembled oldest first and link-only statuses
+//!   followed to their article
+//! - **Bluesky**: `bsky.app` post URLs are read from the public AppView
+//!   API, with the author's thread, link cards, images, and quoted posts
+//!   assembled and link-only posts followed to their article
+//! - **Reddit**: Submissions are read from their `.json` representation;
+//!   self posts are converted from their text, link posts follow the
+//!   linked article, and the top comments can be appended
+//! - **Hacker News**: Item URLs are read from the Firebase API; stories
+//!   follow their URL, Ask / Show HN posts use their text, and a condensed
+//!   comment tree can be appended
+//! - **Telegram**: Public channel posts are read from the `t.me/s/` web
+//!   preview with their text, media, forward source, and date; channel
+//!   URLs give the last few posts and link-only posts follow their article
+//! - **Response & Result Cache**: Optional on-disk [`ScrapeCache`] for raw
+//!   responses and converted posts, keyed by URL, language, and model
 //! - **Progress Events**: Optional single-listener event stream ([`events`]) for
 //!   live scraping feedback in agents, harnesses, and UIs
 //! - **Playwright Fallback**: Bot-protection walls (Cloudflare & co.) are first
@@ -46,6 +123,30 @@
 //! }
 //! ```
 //!
+//! ## Per-Instance Configuration
+//!
+//! [`universal_scrape`] configures itself from environment variables. When
+//! different callers need different settings in the same process, build a
+//! [`Scraper`] from a [`ScrapeOptions`] instead — environment variables only
+//! seed the defaults:
+//!
+//! ```rust,no_run
+//! use uninews::{ScrapeOptions, Scraper};
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let scraper = Scraper::new(
+//!     ScrapeOptions::from_env()
+//!         .llm_client("claude")
+//!         .llm_api_key("sk-ant-...")
+//!         .playwright(false)
+//!         .archive_fallback(false),
+//! );
+//! let post = scraper.scrape("https://example.com/article", "english").await;
+//! # let _ = post;
+//! # }
+//! ```
+//!
 //! ## Requirements
 //!
 //! - Set the API-key env var for your chosen `UNINEWS_LLM_CLIENT`
@@ -57,19 +158,29 @@
 //!
 //! The scraper automatically extracts:
 //! - **Title**: From `<title>` tag or `og:title` meta tag
-//! - **Featured Image**: From `og:image` meta property
-//! - **Publication Date**: From `article:published_time` meta property
-//! - **Author**: From `author` meta tag
+//! - **Featured Image**: From `og:image` meta property, else JSON-LD `image`
+//! - **Publication Date**: From `article:published_time` meta property, else
+//!   JSON-LD `datePublished`
+//! - **Author**: From `author` meta tag, else the JSON-LD authors
+//! - **Structured Metadata** ([`Post::metadata`]): the page's schema.org
+//!   article JSON-LD, including `@graph` documents with `@id` references
+//! - **Canonical URL** ([`Post::canonical_url`]): the normalized
+//!   `<link rel="canonical">`, else `og:url`, else the final URL
 //!
 //! ## Content Extraction Strategy
 //!
 //! The library uses a multi-step approach:
 //! 1. Downloads HTML content from the provided URL
-//! 2. Attempts to locate main content in `<article>` tags (priority) or `<body>` fallback
+//! 2. Locates the main content by scoring candidate containers on paragraph
+//!    text, link density, and class / id hints ("story-body" vs "sidebar",
+//!    "related", "newsletter"), keeping sibling blocks of a split story and
+//!    pruning boilerplate inside it; the longest `<article>` (or the
+//!    `<body>`) is the fallback when scoring finds too little
 //! 3. Removes unwanted elements (scripts, styles, ads, navigation, etc.)
 //! 4. Cleans empty nodes and whitespace
 //! 5. Converts remaining HTML to Markdown using AI while preserving article wording and structure
-//! 6. Optionally translates to the requested language
+//!    (or deterministically, without an LLM — see [`ConversionMode`])
+//! 6. Optionally translates to the requested language (LLM modes only)
 //!
 //! ## Fallback Chain
 //!
@@ -78,7 +189,12 @@
 //!
 //! 1. **Plain HTTP** ([`web`](mod@web) pipeline): a single reqwest fetch,
 //!    HTML cleaning, and metadata extraction.
-//! 2. **Playwright Chromium** (headless, via `playwright-rs`): attempted
+//! 2. **AMP / print alternates** ([`alternate_pages`]): walled and
+//!    thin-content pages that link a server-rendered AMP document (tried on
+//!    the origin, then on the Google AMP cache) or a print / text-only view
+//!    are read from it — a few plain fetches instead of a browser render.
+//!    Disable with `UNINEWS_ALTERNATE_FALLBACK=0`.
+//! 3. **Playwright Chromium** (headless, via `playwright-rs`): attempted
 //!    when the plain fetch trips bot-protection heuristics (challenge
 //!    interstitials such as Cloudflare, detected from status code, headers,
 //!    and body markers) **or** fails hard (network errors / timeouts, 5xx) —
@@ -91,7 +207,7 @@
 //!    `UNINEWS_PLAYWRIGHT_TIMEOUT_MS` (default 45,000 ms). The host content
 //!    fallback ([`set_content_fallback`]) runs around this render and may
 //!    be consulted first via `UNINEWS_CONTENT_FALLBACK_FIRST`.
-//! 3. **archive.org Wayback Machine** ([`archive`]): the LAST fallback,
+//! 4. **archive.org Wayback Machine** ([`archive`]): the LAST fallback,
 //!    attempted only for the bot walls and hard failures (HTTP 5xx and
 //!    network errors: connect/read timeouts, DNS, TLS) that survived
 //!    rendering. The latest archived snapshot of the URL is fetched and
@@ -103,15 +219,24 @@
 //!
 //! ## Environment Variables
 //!
+//! These seed [`ScrapeOptions::from_env`] (and therefore
+//! [`universal_scrape`]); a [`Scraper`] built from explicit options ignores
+//! the variables it overrides.
+//!
 //! | Variable | Purpose | Default |
 //! |---|---|---|
 //! | `UNINEWS_LLM_CLIENT` | LLM provider for HTML → Markdown (`openai`, `openrouter`, `xai`, `grok`, `gemini`, `claude`) | `openai` |
 //! | `UNINEWS_LLM_MODEL` | Model override for the selected provider | provider default |
 //! | `UNINEWS_LLM_CONTEXT_WINDOW` | Context-window budget in tokens | 256,000 |
+//! | `UNINEWS_CONVERSION_MODE` | Markdown conversion: `llm`, `deterministic` (no LLM, no API key), or `llm_prepass` | `llm` |
 //! | `OPEN_AI_SECRET` / `OPENROUTER_API_KEY` / `XAI_API_KEY` / `GEMINI_API_KEY` / `CLAUDE_API_KEY` | API key for the selected `UNINEWS_LLM_CLIENT` | — (required) |
 //! | `UNINEWS_PLAYWRIGHT` | Toggle the Playwright fallback (`0`/`false`/`no`/`off` disables) | enabled |
 //! | `UNINEWS_PLAYWRIGHT_TIMEOUT_MS` | Playwright navigation / content-wait budget in ms | 45,000 |
 //! | `UNINEWS_ARCHIVE_FALLBACK` | Toggle the archive.org Wayback fallback (`0` disables) | enabled |
+//! | `UNINEWS_ALTERNATE_FALLBACK` | Toggle the AMP / print alternates tried before Playwright (`0` disables) | enabled |
+//! | `UNINEWS_CACHE_DIR` | Directory of the on-disk response / post cache ([`ScrapeCache`]) | — (disabled) |
+//! | `UNINEWS_CACHE_TTL_SECS` | Age after which cache entries are ignored, in seconds | 86,400 |
+//! | `UNINEWS_RULES_FILE` | JSON file of per-domain extraction rules layered on the bundled set ([`ExtractionRules`]) | — (bundled rules only) |
 //! | `UNINEWS_CHROME_BINARY` | Chrome/Chromium executable for the headless `--dump-dom` path (trusted input — see Security Notes) | auto-detected |
 //! | `UNINEWS_CHROME_USER_DATA_DIR` / `UNINEWS_CHROME_PROFILE_DIR` | Clone a logged-in Chrome profile for guest-walled X Articles | — |
 //! | `X_API_KEY` / `X_API_SECRET` | X API v2 OAuth 2.0 app credentials for tweets/threads (compat fallback: `DBTC_TWITTER_API_KEY` / `DBTC_TWITTER_API_SECRET`) | — (required for X URLs) |
@@ -121,15 +246,62 @@
 //!
 //! - `llm` — LLM provider selection, context-window budgeting, and the
 //!   HTML → Markdown conversion (re-exported at the crate root).
+//! - `scrape` — [`Scraper`] and [`ScrapeOptions`], the per-instance
+//!   configuration threaded through every pipeline.
+//! - `batch` — [`Scraper::scrape_many`], concurrent batch scraping with
+//!   per-host politeness.
+//! - `feed` — [`Feed`] / [`FeedItem`], RSS / Atom / JSON Feed parsing,
+//!   feed discovery, and [`Scraper::scrape_feed`].
+//! - `sitemap` — [`Sitemap`] / [`SitemapEntry`], XML and Google News
+//!   sitemap parsing and [`Scraper::scrape_sitemap`].
+//! - `discover` — [`ArticleLink`], section-page article-link ranking and
+//!   [`Scraper::discover_article_links`].
+//! - `handler` — [`SourceHandler`] / [`SourceHandlers`], pluggable
+//!   domain-specific pipelines and the [`ScrapeContext`] they run with.
+//! - `cache` — [`ScrapeCache`], the optional on-disk response and post
+//!   cache.
 //! - `web` — plain-HTTP scraping pipeline for non-X URLs.
 //! - `x` — X.com / Twitter tweets, threads, and articles.
+//! - `youtube` — [`YouTubeHandler`], YouTube video details and caption
+//!   transcripts.
+//! - `mastodon` — [`MastodonHandler`], Mastodon / ActivityPub statuses
+//!   and self-reply threads.
+//! - `bluesky` — [`BlueskyHandler`], Bluesky posts and threads from the
+//!   AppView API.
+//! - `reddit` — [`RedditHandler`], Reddit submissions and top comments
+//!   from the `.json` endpoints.
+//! - `hackernews` — [`HackerNewsHandler`], Hacker News stories and comment
+//!   trees from the Firebase API.
+//! - `telegram` — [`TelegramHandler`], Telegram public channel posts from
+//!   the `t.me/s/` web preview.
 //! - `html` — HTML cleaning and metadata extraction.
+//! - `pdf` — [`PdfDocument`], PDF text and document-information
+//!   extraction for the web pipeline.
+//! - `cms` — [`CmsApi`] / [`CmsPost`], the WordPress and Ghost REST API
+//!   shortcut for the web pipeline.
+//! - `pagination` — [`next_page_url`], multi-page article detection for
+//!   the web pipeline.
+//! - `alternate` — [`alternate_pages`] / [`amp_cache_url`], AMP and
+//!   print-view variants tried before the Playwright render.
+//! - `canonical` — [`normalize_url`] / [`canonical_url`], tracking
+//!   parameter stripping, AMP and archive unwrapping, and redirect
+//!   resolution for cache keys and batch dedup.
+//! - `metadata` — [`PostMetadata`], schema.org article JSON-LD extraction.
+//! - `rules` — [`ExtractionRules`] / [`SiteRule`], per-domain content,
+//!   removal, and metadata selectors.
+//! - `markdown` — deterministic HTML → Markdown converter and
+//!   [`ConversionMode`].
 //! - `browser` — headless-Chrome (`--dump-dom`) and Playwright Chromium
 //!   rendering fallbacks.
 //! - [`archive`] — archive.org Wayback Machine fallback for protected or
 //!   unreachable pages.
 //! - [`events`] — typed progress events with a single-listener emitter.
+//! - `error` — [`ScrapeError`] / [`FallbackStage`] failure classification.
+//! - `provenance` — [`Provenance`], the origin metadata on every
+//!   successful [`Post`].
 //! - `http` — shared, timeout-hardened `reqwest` clients.
+//! - `charset` — [`detect_charset`], character-set detection and decoding
+//!   of fetched pages.
 //! - `util` — small shared helpers.
 //!
 //! ## Security Notes
@@ -141,8 +313,10 @@
 //!   not just the original URL. Note also that the Playwright fallback
 //!   **executes the target page's JavaScript** and issues subresource
 //!   requests that bypass the plain `web_client` timeout policy.
-//! - **Secrets**: API keys are only read from environment variables and are
-//!   never written to logs, stderr, or [`Post`] fields.
+//! - **Secrets**: API keys are read from environment variables or
+//!   [`ScrapeOptions::llm_api_key`] and are never written to logs, stderr,
+//!   events, or [`Post`] fields (the `Debug` impl of [`ScrapeOptions`]
+//!   redacts them).
 //! - **Trusted env vars**: `UNINEWS_CHROME_BINARY` names an executable that
 //!   gets spawned; only set it to a binary you trust. Target URLs are passed
 //!   to Chrome as plain process arguments (no shell), so they cannot inject
@@ -152,41 +326,78 @@
 //!
 //! ## Error Handling
 //!
-//! Errors are non-fatal and returned in the [`Post::error`] field. Always check this field:
+//! Errors are non-fatal and returned in the [`Post::error`] field, with a
+//! typed [`ScrapeError`] in [`Post::error_kind`] (see
+//! [`Post::into_result`]). Always check one of them:
 //!
 //! ```rust,no_run
-//! # use uninews::universal_scrape;
+//! # use uninews::{universal_scrape, ScrapeError};
 //! # #[tokio::main]
 //! # async fn main() {
 //! let post = universal_scrape("https://invalid-url-example", "english", None).await;
 //!
-//! if !post.error.is_empty() {
-//!     match post.error.as_str() {
-//!         e if e.contains("Failed to fetch") => println!("Network error"),
-//!         e if e.contains("Could not extract meaningful content") => println!("Page structure not supported"),
-//!         e if e.contains("LLM Error") => println!("AI processing error"),
-//!         e => println!("Unknown error: {}", e),
-//!     }
+//! match post.into_result() {
+//!     Ok(post) => println!("{}", post.content),
+//!     Err(ScrapeError::Network { .. }) => println!("Network error"),
+//!     Err(ScrapeError::InsufficientContent { .. }) => println!("Page structure not supported"),
+//!     Err(ScrapeError::LlmProvider { .. }) => println!("AI processing error"),
+//!     Err(e) => println!("Other error ({:?}): {}", e.fallbacks(), e),
 //! }
 //! # }
 //! ```
 
+mod alternate;
 pub mod archive;
+mod batch;
+mod bluesky;
 mod browser;
+mod cache;
+mod canonical;
+mod charset;
+mod cms;
+mod discover;
+mod error;
 pub mod events;
 mod fallback;
+mod feed;
+mod hackernews;
+mod handler;
 #[doc(hidden)]
 pub mod html;
 mod http;
 pub mod llm;
+mod markdown;
+mod mastodon;
+mod metadata;
+mod pagination;
+mod pdf;
+mod provenance;
+mod reddit;
+mod rules;
+mod scrape;
+mod sitemap;
+mod telegram;
 mod util;
 mod web;
 #[doc(hidden)]
 pub mod x;
+mod youtube;
+
+use std::sync::{Arc, OnceLock};
 
 use serde::{Deserialize, Serialize};
+use tokio::sync::Semaphore;
 
+pub use alternate::{
+    alternate_fallback_enabled, alternate_pages, amp_cache_url, AlternateKind, AlternatePage,
+    UNINEWS_ALTERNATE_FALLBACK_ENV,
+};
 pub use archive::{archive_fallback_enabled, ArchiveSnapshot, UNINEWS_ARCHIVE_FALLBACK_ENV};
+pub use batch::scrape_many;
+pub use bluesky::{
+    bluesky_post_ref, parse_bluesky_thread, BlueskyAuthor, BlueskyExternal, BlueskyHandler,
+    BlueskyImage, BlueskyLink, BlueskyPost, BlueskyQuote, BlueskyThread, BLUESKY_PUBLIC_APPVIEW,
+};
 // Re-export Playwright toggles from the private `browser` module so operators
 // and tests can configure the bot-protection browser path without reaching
 // into crate-private modules.
@@ -203,6 +414,19 @@ pub use browser::{
 pub use browser::{
     playwright_overall_budget_ms, CHROME_DUMP_DOM_DEADLINE_MS, PLAYWRIGHT_OVERALL_GRACE_MS,
 };
+pub use cache::{
+    CacheKind, ScrapeCache, DEFAULT_CACHE_TTL, UNINEWS_CACHE_DIR_ENV, UNINEWS_CACHE_TTL_SECS_ENV,
+};
+pub use canonical::{canonical_url, is_short_link, is_tracking_parameter, normalize_url};
+pub use charset::{decode_body, detect_charset, CharsetSource, DetectedCharset, PRESCAN_BYTES};
+pub use cms::{
+    cms_api_enabled, detect_cms_api, parse_ghost_post, parse_wordpress_post, CmsApi, CmsKind,
+    CmsPost, UNINEWS_CMS_API_ENV,
+};
+pub use discover::{
+    discover_article_links, rank_article_links, ArticleLink, MIN_ARTICLE_LINK_SCORE,
+};
+pub use error::{FallbackStage, ScrapeError};
 /// Re-exported event API. New [`ScrapeEvent`] variants are **additive** in
 /// minor releases — listeners must `match` with a wildcard arm to stay
 /// forward-compatible.
@@ -211,14 +435,55 @@ pub use fallback::{
     content_fallback_first, set_content_fallback, ContentFallback, ContentFallbackFuture,
     ContentFallbackHook, UNINEWS_CONTENT_FALLBACK_FIRST_ENV,
 };
+pub use feed::{
+    discover_feed_urls, parse_feed, scrape_feed, Feed, FeedEnclosure, FeedFormat, FeedItem,
+};
+pub use hackernews::{
+    hacker_news_item_id, parse_hacker_news_item, HackerNewsComment, HackerNewsHandler,
+    HackerNewsItem, HACKER_NEWS_API_BASE,
+};
+pub use handler::{HandlerFuture, ScrapeContext, SourceHandler, SourceHandlers};
 pub use llm::{
     active_llm_client, active_provider_label, convert_content_to_markdown, llm_context_window,
     resolve_llm_context_window, uninews_llm_context_window, LLMClientInfo,
-    DEFAULT_LLM_CONTEXT_WINDOW, UNINEWS_LLM_CONTEXT_WINDOW_ENV,
+    DEFAULT_LLM_CONTEXT_WINDOW, MARKDOWN_PROMPT_VERSION, UNINEWS_LLM_CONTEXT_WINDOW_ENV,
+};
+pub use markdown::{html_to_markdown, ConversionMode, UNINEWS_CONVERSION_MODE_ENV};
+pub use mastodon::{
+    mastodon_status_id, parse_activity_note, parse_mastodon_context, parse_mastodon_status,
+    MastodonAccount, MastodonCard, MastodonHandler, MastodonMedia, MastodonSource, MastodonStatus,
+    MastodonThread,
+};
+pub use metadata::PostMetadata;
+pub use pagination::{next_page_url, DEFAULT_MAX_ARTICLE_PAGES};
+pub use pdf::{is_pdf, parse_pdf, PdfDocument};
+pub use provenance::Provenance;
+pub use reddit::{
+    parse_reddit_thread, reddit_post_id, RedditComment, RedditHandler, RedditImage,
+    RedditSubmission, RedditThread, REDDIT_BASE_URL,
+};
+pub use rules::{ExtractionRules, SiteRule, UNINEWS_RULES_FILE_ENV};
+pub use scrape::{
+    ScrapeOptions, Scraper, DEFAULT_BATCH_CONCURRENCY, DEFAULT_MAX_CONCURRENT_RENDERS,
+    DEFAULT_PER_HOST_CONCURRENCY, DEFAULT_PER_HOST_DELAY,
+};
+pub use sitemap::{
+    parse_sitemap, scrape_sitemap, Sitemap, SitemapDocument, SitemapEntry, SitemapNews,
+    MAX_SITEMAP_DEPTH, MAX_SITEMAP_DOCUMENTS,
+};
+pub use telegram::{
+    parse_telegram_channel, telegram_target, TelegramChannel, TelegramForward, TelegramHandler,
+    TelegramLinkPreview, TelegramPost, TelegramTarget, DEFAULT_TELEGRAM_RECENT_POSTS,
+    TELEGRAM_BASE_URL,
 };
 pub use util::is_youtube_url;
 #[doc(hidden)]
-pub use util::summarize_body;
+pub use util::{format_rfc3339_utc, summarize_body};
+pub use x::XHandler;
+pub use youtube::{
+    parse_timedtext, parse_youtube_watch_page, youtube_video_id, CaptionTrack, TranscriptCue,
+    YouTubeHandler, YouTubeVideo,
+};
 
 /// Represents a scraped news post with all extracted metadata.
 ///
@@ -233,6 +498,10 @@ pub use util::summarize_body;
 /// - **publication_date**: ISO 8601 formatted publication date if available
 /// - **author**: Article author extracted from meta tags
 /// - **error**: Empty string on success, contains error message if scraping failed
+/// - **error_kind**: Typed classification of the failure ([`ScrapeError`]);
+///   `None` on success
+/// - **provenance**: Which pipeline stage produced the content, from which
+///   URL, and when ([`Provenance`]); `None` when no page was read
 ///
 /// # Examples
 ///
@@ -246,6 +515,10 @@ pub use util::summarize_body;
 ///     publication_date: Some("2024-01-15T10:30:00Z".to_string()),
 ///     author: Some("Jane Doe".to_string()),
 ///     error: String::new(),
+///     error_kind: None,
+///     provenance: None,
+///     metadata: None,
+///     canonical_url: None,
 /// };
 ///
 /// // Check if scraping was successful
@@ -266,13 +539,14 @@ pub use util::summarize_body;
 ///     publication_date: None,
 ///     author: None,
 ///     error: "Failed to fetch URL: connection timeout".to_string(),
+///     ..Post::default()
 /// };
 ///
 /// if !failed_post.error.is_empty() {
 ///     eprintln!("Scraping failed: {}", failed_post.error);
 /// }
 /// ```
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct Post {
     /// The article title
     pub title: String,
@@ -286,6 +560,86 @@ pub struct Post {
     pub author: Option<String>,
     /// Error message; empty string if no error
     pub error: String,
+    /// Typed classification of [`Post::error`], including the fallback
+    /// stages that were tried. `None` on success; omitted from JSON when
+    /// `None`, so successful output is unchanged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<ScrapeError>,
+    /// Where the content came from: source stage, final and canonical URL,
+    /// HTTP status, archive snapshot, LLM, and timestamps. Omitted from
+    /// JSON when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Provenance>,
+    /// Article metadata from the page's JSON-LD (schema.org
+    /// `NewsArticle` / `BlogPosting`): all authors, published / modified
+    /// dates, publisher, section, keywords. Omitted from JSON when `None`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<PostMetadata>,
+    /// The article's canonical URL ([`canonical_url`]): the page's
+    /// `<link rel="canonical">`, else `og:url`, else the final URL, with
+    /// tracking parameters and AMP variants folded away
+    /// ([`normalize_url`]). Set on success; omitted from JSON when `None`.
+    /// The post is cached under it too, and batches of feed or sitemap
+    /// items yield one post per canonical URL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_url: Option<String>,
+}
+
+impl Post {
+    /// Build a [`Post`] carrying only `error` (message and kind), as a
+    /// failed scrape reports it.
+    pub fn from_error(error: ScrapeError) -> Self {
+        Self {
+            error: error.to_string(),
+            error_kind: Some(error),
+            ..Self::default()
+        }
+    }
+
+    /// Record the fallback `stages` that ran on this post's
+    /// [`Post::error_kind`]. Failed posts without a recorded kind are
+    /// classified as [`ScrapeError::Other`]; successful posts are returned
+    /// unchanged.
+    pub(crate) fn with_fallbacks(mut self, stages: &[FallbackStage]) -> Self {
+        if !self.error.is_empty() {
+            let kind = self
+                .error_kind
+                .take()
+                .unwrap_or_else(|| ScrapeError::Other {
+                    message: self.error.clone(),
+                    fallbacks: Vec::new(),
+                });
+            self.error_kind = Some(kind.with_fallbacks(stages));
+        }
+        self
+    }
+
+    /// Record `stage` ahead of the fallback stages this post's error
+    /// already lists (a pipeline run after `stage` gave up).
+    pub(crate) fn after_fallback(self, stage: FallbackStage) -> Self {
+        let stages: Vec<FallbackStage> = std::iter::once(stage)
+            .chain(
+                self.error_kind
+                    .iter()
+                    .flat_map(|error| error.fallbacks().iter().copied()),
+            )
+            .collect();
+        self.with_fallbacks(&stages)
+    }
+
+    /// Convert into a `Result`: `Ok(self)` when [`Post::error`] is empty,
+    /// otherwise the typed [`Post::error_kind`] (or
+    /// [`ScrapeError::Other`] wrapping the message when no kind was
+    /// recorded, e.g. for a post deserialized from older JSON).
+    pub fn into_result(self) -> Result<Post, ScrapeError> {
+        if self.error.is_empty() {
+            return Ok(self);
+        }
+        Err(self.error_kind.unwrap_or(ScrapeError::Other {
+            message: self.error,
+            fallbacks: Vec::new(),
+        }))
+    }
 }
 
 /// The main API function - scrapes a URL and returns structured article data.
@@ -331,7 +685,10 @@ pub struct Post {
 /// See [`convert_content_to_markdown`] for the full list of supported
 /// `UNINEWS_LLM_CLIENT` / `UNINEWS_LLM_MODEL` /
 /// `UNINEWS_LLM_CONTEXT_WINDOW` values and the provider-specific API key
-/// env vars.
+/// env vars. All of them are read once per call through
+/// [`ScrapeOptions::from_env`]; concurrent calls share one browser-render
+/// budget of [`DEFAULT_MAX_CONCURRENT_RENDERS`].
+/// Use a [`Scraper`] to configure scrapes without touching the environment.
 ///
 /// # Performance Considerations
 ///
@@ -468,27 +825,15 @@ pub async fn universal_scrape(
     language: &str,
     context_window_tokens: Option<usize>,
 ) -> Post {
-    events::emit_event(ScrapeEvent::ScrapeStarted {
-        url: url.to_string(),
-    });
-
-    // Delegate to the X.com handler for X / Twitter URLs.
-    let post = if x::is_x_url(url) {
-        x::scrape_x_url(url, language, context_window_tokens).await
-    } else {
-        web::scrape_web_url(url, language, context_window_tokens).await
-    };
-
-    if post.error.is_empty() {
-        events::emit_event(ScrapeEvent::ScrapeCompleted {
-            url: url.to_string(),
-        });
-    } else {
-        events::emit_event(ScrapeEvent::ScrapeFailed {
-            url: url.to_string(),
-            error: post.error.clone(),
-        });
+    static RENDER_PERMITS: OnceLock<Arc<Semaphore>> = OnceLock::new();
+    let mut options = ScrapeOptions::from_env();
+    if let Some(tokens) = context_window_tokens {
+        options = options.context_window(resolve_llm_context_window(Some(tokens)));
     }
-
-    post
+    let render_permits = RENDER_PERMITS
+        .get_or_init(|| Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_RENDERS)))
+        .clone();
+    Scraper::with_render_permits(options, render_permits)
+        .scrape(url, language)
+        .await
 }
@@ -3,8 +3,8 @@
 //! This module owns everything related to the CloudLLM-powered
 //! HTML → Markdown conversion step:
 //!
-//! - provider / model resolution from `UNINEWS_LLM_CLIENT` and
-//!   `UNINEWS_LLM_MODEL`,
+//! - provider / model resolution from a [`crate::ScrapeOptions`] (seeded
+//!   from `UNINEWS_LLM_CLIENT` and `UNINEWS_LLM_MODEL`),
 //! - the context-window budget (`UNINEWS_LLM_CONTEXT_WINDOW`,
 //!   [`DEFAULT_LLM_CONTEXT_WINDOW`]),
 //! - the near-lossless Markdown-conversion prompts,
@@ -22,7 +22,9 @@ use cloudllm::clients::openrouter::OpenRouterClient;
 use cloudllm::LLMSession;
 
 use crate::events::{emit_event, ScrapeEvent};
-use crate::Post;
+use crate::markdown::content_to_markdown;
+use crate::util::rfc3339_now;
+use crate::{ConversionMode, Post, Provenance, ScrapeError, ScrapeOptions};
 
 /// Default LLM client when `UNINEWS_LLM_CLIENT` is unset.
 const DEFAULT_LLM_CLIENT: &str = "openai";
@@ -48,7 +50,7 @@ fn default_llm_model_for(client_name: &str) -> &'static str {
 
 /// Read the LLM client name from `UNINEWS_LLM_CLIENT`, defaulting to
 /// [`DEFAULT_LLM_CLIENT`].
-fn uninews_llm_client_name() -> String {
+pub(crate) fn uninews_llm_client_name() -> String {
     env::var("UNINEWS_LLM_CLIENT")
         .ok()
         .map(|value| value.trim().to_string())
@@ -57,16 +59,34 @@ fn uninews_llm_client_name() -> String {
         .to_ascii_lowercase()
 }
 
-/// Read the LLM model slug from `UNINEWS_LLM_MODEL`, falling back to the
-/// per-client default returned by [`default_llm_model_for`] when unset.
-fn uninews_llm_model(client_name: &str) -> String {
+/// Read the LLM model slug from `UNINEWS_LLM_MODEL`, or `None` when unset
+/// or empty (the per-client default then applies).
+pub(crate) fn uninews_llm_model_override() -> Option<String> {
     env::var("UNINEWS_LLM_MODEL")
         .ok()
         .map(|value| value.trim().to_string())
         .filter(|value| !value.is_empty())
+}
+
+/// Resolve the model slug for `client_name`: the explicit override when
+/// present, otherwise the per-client default returned by
+/// [`default_llm_model_for`].
+pub(crate) fn resolve_llm_model(client_name: &str, model_override: Option<&str>) -> String {
+    model_override
+        .map(str::to_string)
         .unwrap_or_else(|| default_llm_model_for(client_name).to_string())
 }
 
+/// Return the explicitly configured API key, or read the provider-specific
+/// `env_name` when none was configured.
+fn provider_api_key(api_key: Option<&str>, env_name: &str) -> Result<String, String> {
+    match api_key {
+        Some(key) => Ok(key.to_string()),
+        None => env::var(env_name)
+            .map_err(|_| format!("Please set the {} environment variable.", env_name)),
+    }
+}
+
 /// Environment variable consulted when no explicit context window is passed
 /// to [`convert_content_to_markdown`] / [`crate::universal_scrape`].
 pub const UNINEWS_LLM_CONTEXT_WINDOW_ENV: &str = "UNINEWS_LLM_CONTEXT_WINDOW";
@@ -146,24 +166,37 @@ pub fn resolve_llm_context_window(context_window_tokens: Option<usize>) -> usize
 }
 
 /// Build the CloudLLM client selected by `UNINEWS_LLM_CLIENT` / `UNINEWS_LLM_MODEL`.
+fn build_uninews_llm_client() -> Result<Arc<dyn ClientWrapper>, String> {
+    build_llm_client(
+        &uninews_llm_client_name(),
+        uninews_llm_model_override().as_deref(),
+        None,
+    )
+}
+
+/// Build the CloudLLM client for `client_name`, using `model_override` (or
+/// the per-client default) and `api_key`.
 ///
-/// Each client reads its API key from a provider-specific environment variable:
+/// When `api_key` is `None`, each client reads its key from a
+/// provider-specific environment variable:
 /// - `openai`      → `OPEN_AI_SECRET`
 /// - `openrouter`  → `OPENROUTER_API_KEY`
 /// - `grok`        → `XAI_API_KEY`
 /// - `gemini`      → `GEMINI_API_KEY`
 /// - `claude`      → `CLAUDE_API_KEY`  (Anthropic Claude)
 ///
-/// If `UNINEWS_LLM_MODEL` is unset, the per-client default from
+/// If no model is pinned, the per-client default from
 /// [`default_llm_model_for`] is used (see the README's "LLM Providers" table).
-fn build_uninews_llm_client() -> Result<Arc<dyn ClientWrapper>, String> {
-    let client_name = uninews_llm_client_name();
-    let model = uninews_llm_model(&client_name);
+fn build_llm_client(
+    client_name: &str,
+    model_override: Option<&str>,
+    api_key: Option<&str>,
+) -> Result<Arc<dyn ClientWrapper>, String> {
+    let model = resolve_llm_model(client_name, model_override);
 
-    match client_name.as_str() {
+    match client_name {
         "openai" => {
-            let key = env::var("OPEN_AI_SECRET")
-                .map_err(|_| "Please set the OPEN_AI_SECRET environment variable.".to_string())?;
+            let key = provider_api_key(api_key, "OPEN_AI_SECRET")?;
             // Prefer strong-typed enums for the stock defaults; any other slug
             // falls through to the string constructor (escape hatch).
             let client = match model.as_str() {
@@ -182,16 +215,13 @@ fn build_uninews_llm_client() -> Result<Arc<dyn ClientWrapper>, String> {
             Ok(Arc::new(client))
         }
         "openrouter" => {
-            let key = env::var("OPENROUTER_API_KEY").map_err(|_| {
-                "Please set the OPENROUTER_API_KEY environment variable.".to_string()
-            })?;
+            let key = provider_api_key(api_key, "OPENROUTER_API_KEY")?;
             Ok(Arc::new(OpenRouterClient::new_with_model_str(
                 &key, &model,
             )))
         }
         "grok" => {
-            let key = env::var("XAI_API_KEY")
-                .map_err(|_| "Please set the XAI_API_KEY environment variable.".to_string())?;
+            let key = provider_api_key(api_key, "XAI_API_KEY")?;
             let client = match model.as_str() {
                 "grok-4.5" => GrokClient::new_with_model_enum(&key, GrokModel::Grok45),
                 "grok-4.5-latest" => {
@@ -202,15 +232,13 @@ fn build_uninews_llm_client() -> Result<Arc<dyn ClientWrapper>, String> {
             Ok(Arc::new(client))
         }
         "gemini" => {
-            let key = env::var("GEMINI_API_KEY")
-                .map_err(|_| "Please set the GEMINI_API_KEY environment variable.".to_string())?;
+            let key = provider_api_key(api_key, "GEMINI_API_KEY")?;
             Ok(Arc::new(GeminiClient::new_with_model_string(
                 &key, &model,
             )))
         }
         "claude" => {
-            let key = env::var("CLAUDE_API_KEY")
-                .map_err(|_| "Please set the CLAUDE_API_KEY environment variable.".to_string())?;
+            let key = provider_api_key(api_key, "CLAUDE_API_KEY")?;
             Ok(Arc::new(ClaudeClient::new_with_model_str(&key, &model)))
         }
         other => Err(format!(
@@ -275,7 +303,7 @@ pub fn active_provider_label() -> String {
             // Fall back to the env-derived defaults so chat notifications
             // still render something useful even when the API key is missing.
             let client_name = uninews_llm_client_name();
-            let model = uninews_llm_model(&client_name);
+            let model = resolve_llm_model(&client_name, uninews_llm_model_override().as_deref());
             format!("{} ({})", client_name, model)
         }
     }
@@ -315,6 +343,12 @@ pub fn normalized_output_language(language: &str) -> &str {
     }
 }
 
+/// Version of the Markdown conversion prompts ([`markdown_system_prompt`],
+/// [`markdown_user_prompt`]). Part of the converted-post cache key
+/// ([`crate::ScrapeCache`]): bump it whenever the prompts change so stale
+/// conversions are not served.
+pub const MARKDOWN_PROMPT_VERSION: u32 = 1;
+
 /// System prompt for the near-lossless HTML → Markdown conversion.
 #[doc(hidden)]
 pub fn markdown_system_prompt(language: &str) -> String {
@@ -395,6 +429,9 @@ pub fn markdown_user_prompt(language: &str, post_json: &str) -> String {
 /// - `UNINEWS_LLM_CONTEXT_WINDOW` - Optional LLM context-window budget (in
 ///   tokens) used when `context_window_tokens` is `None`. Falls back to
 ///   [`DEFAULT_LLM_CONTEXT_WINDOW`] when unset or unparseable.
+/// - `UNINEWS_CONVERSION_MODE` - `deterministic` skips the LLM entirely
+///   (see [`crate::ConversionMode`]); `llm_prepass` shrinks the payload
+///   with the built-in converter first.
 /// - One provider-specific API key env var is required:
 ///   - `openai`     → `OPEN_AI_SECRET`
 ///   - `openrouter` → `OPENROUTER_API_KEY`
@@ -424,6 +461,10 @@ pub fn markdown_user_prompt(language: &str, post_json: &str) -> String {
 ///         publication_date: None,
 ///         author: None,
 ///         error: String::new(),
+///         error_kind: None,
+///         provenance: None,
+///         metadata: None,
+///         canonical_url: None,
 ///     };
 ///
 ///     // Convert with the provider selected via UNINEWS_LLM_CLIENT (default: openai / gpt-5.6-sol)
@@ -445,34 +486,75 @@ pub fn markdown_user_prompt(language: &str, post_json: &str) -> String {
 ///
 /// If the specified language is not recognized, the output defaults to English.
 pub async fn convert_content_to_markdown(
-    mut post: Post,
+    post: Post,
     language: &str,
     context_window_tokens: Option<usize>,
 ) -> Result<Post, String> {
-    // Build the CloudLLM client selected by UNINEWS_LLM_CLIENT / UNINEWS_LLM_MODEL.
-    // Client-build failure (missing API key, unsupported UNINEWS_LLM_CLIENT)
-    // is the most common config error: emit LlmConversionFailed before
-    // returning so listeners are not left hanging on a conversion that never
-    // started. The provider label falls back to the env-derived defaults,
+    let options =
+        ScrapeOptions::from_env().context_window(resolve_llm_context_window(context_window_tokens));
+    convert_with_options(post, language, &options)
+        .await
+        .map_err(|error| error.to_string())
+}
+
+/// Markdown conversion driven by a [`ScrapeOptions`]: provider, model, API
+/// key, and context window all come from `options` instead of the
+/// environment. [`convert_content_to_markdown`] and [`crate::Scraper`] both
+/// funnel through here. Failures are classified as
+/// [`ScrapeError::LlmConfig`], [`ScrapeError::LlmContextOverflow`], or
+/// [`ScrapeError::LlmProvider`]; their messages match the historical
+/// `String` errors.
+///
+/// [`ConversionMode::Deterministic`] converts with
+/// [`crate::html_to_markdown`] and returns before any LLM client is built
+/// (so no API key is needed and `language` is ignored);
+/// [`ConversionMode::LlmPrepass`] runs that converter first and sends the
+/// resulting Markdown to the LLM.
+pub(crate) async fn convert_with_options(
+    mut post: Post,
+    language: &str,
+    options: &ScrapeOptions,
+) -> Result<Post, ScrapeError> {
+    let mode = options.conversion_mode();
+    if mode != ConversionMode::Llm {
+        let base_url = post
+            .provenance
+            .as_ref()
+            .map(|provenance| provenance.final_url.clone());
+        post.content = content_to_markdown(&post.content, base_url.as_deref());
+        if mode == ConversionMode::Deterministic {
+            if let Some(provenance) = post.provenance.as_mut() {
+                provenance.converted_at = Some(rfc3339_now());
+            }
+            return Ok(post);
+        }
+    }
+
+    // Client-build failure (missing API key, unsupported provider) is the
+    // most common config error: emit LlmConversionFailed before returning
+    // so listeners are not left hanging on a conversion that never started.
+    // The provider label falls back to the configured name and model,
     // mirroring `active_provider_label`'s error branch.
-    let client = match build_uninews_llm_client() {
+    let client_name = options.llm_client_name();
+    let client = match build_llm_client(
+        client_name,
+        options.llm_model_override(),
+        options.llm_api_key_override(),
+    ) {
         Ok(client) => client,
         Err(error) => {
-            let client_name = uninews_llm_client_name();
-            let model = uninews_llm_model(&client_name);
+            let model = resolve_llm_model(client_name, options.llm_model_override());
             emit_event(ScrapeEvent::LlmConversionFailed {
                 provider: format!("{} ({})", client_name, model),
                 error: error.clone(),
             });
-            return Err(error);
+            return Err(ScrapeError::llm_config(error));
         }
     };
 
-    let provider = format!(
-        "{} ({})",
-        client.llm_provider_name().unwrap_or("unknown"),
-        client.llm_model_name().unwrap_or("unknown")
-    );
+    let provider_name = client.llm_provider_name().unwrap_or("unknown").to_string();
+    let model_name = client.llm_model_name().unwrap_or("unknown").to_string();
+    let provider = format!("{} ({})", provider_name, model_name);
     emit_event(ScrapeEvent::LlmConversionStarted {
         provider: provider.clone(),
         content_bytes: post.content.len(),
@@ -481,16 +563,18 @@ pub async fn convert_content_to_markdown(
     // Normalize language: if empty, default to "english".
     let lang = normalized_output_language(language);
 
-    // Resolve the LLM context window: explicit override wins, then the env
-    // var, then DEFAULT_LLM_CONTEXT_WINDOW. See `resolve_llm_context_window`.
-    let context_window = resolve_llm_context_window(context_window_tokens);
+    // Already resolved by the options (explicit override, then the env var,
+    // then DEFAULT_LLM_CONTEXT_WINDOW); never 0.
+    let context_window = options.context_window_tokens();
 
     // Define a system prompt that instructs the LLM on its role.
     let system_prompt = markdown_system_prompt(lang);
 
-    // Serialize the entire Post to JSON.
+    // Serialize the Post to JSON. Provenance is pipeline metadata, not
+    // article data: keep it out of the prompt and restore it afterwards.
+    let provenance = post.provenance.take();
     let post_json = serde_json::to_string(&post)
-        .map_err(|e| format!("Failed to serialize Post to JSON: {}", e))?;
+        .map_err(|e| ScrapeError::other(format!("Failed to serialize Post to JSON: {}", e)))?;
     let user_prompt = markdown_user_prompt(lang, &post_json);
 
     // Pre-flight size check. cloudllm trims history at MESSAGE granularity:
@@ -508,7 +592,12 @@ pub async fn convert_content_to_markdown(
             provider,
             error: error.clone(),
         });
-        return Err(error);
+        return Err(ScrapeError::LlmContextOverflow {
+            estimated_tokens,
+            context_window,
+            message: error,
+            fallbacks: Vec::new(),
+        });
     }
 
     // Create a new LLMSession.
@@ -518,6 +607,12 @@ pub async fn convert_content_to_markdown(
     match session.send_message(Role::User, user_prompt, None).await {
         Ok(response) => {
             post.content = response.content.to_string();
+            post.provenance = provenance.map(|provenance| Provenance {
+                llm_provider: Some(provider_name),
+                llm_model: Some(model_name),
+                converted_at: Some(rfc3339_now()),
+                ..provenance
+            });
             emit_event(ScrapeEvent::LlmConversionSucceeded {
                 provider,
                 markdown_bytes: post.content.len(),
@@ -529,7 +624,7 @@ pub async fn convert_content_to_markdown(
                 provider,
                 error: err.to_string(),
             });
-            Err(format!("LLM Error: {}", err))
+            Err(ScrapeError::llm_provider(format!("LLM Error: {}", err)))
         }
     }
 }
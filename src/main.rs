@@ -24,12 +24,36 @@
 //! uninews "https://www.example.com/article" -l french -j
 //! ```
 //!
+//! ### Convert without an LLM (no API key needed)
+//! ```bash
+//! uninews "https://www.example.com/article" --no-llm
+//! ```
+//!
+//! ### Scrape every item of a feed (or a homepage that declares one)
+//! ```bash
+//! uninews feed "https://www.example.com/feed.xml" --limit 10 --json
+//! ```
+//!
+//! ### Scrape the last 48 hours of a Google News sitemap
+//! ```bash
+//! uninews sitemap "https://www.example.com/news-sitemap.xml" --max-age-hours 48
+//! ```
+//!
+//! ### List the article links of a section page, or scrape the top 5
+//! ```bash
+//! uninews discover "https://www.example.com/technology"
+//! uninews discover "https://www.example.com/technology" --scrape --limit 5
+//! ```
+//!
 //! ## Features
 //!
 //! - 🔗 Scrape any news article from its URL
 //! - 📝 Automatic conversion to clean Markdown format
 //! - 🌍 Support for 100+ languages via AI translation
 //! - 📊 JSON output for programmatic use
+//! - 📰 RSS / Atom / JSON Feed ingestion (`uninews feed <url>`)
+//! - 🗺️ XML / Google News sitemap ingestion (`uninews sitemap <url>`)
+//! - 🧭 Article-link discovery on section pages (`uninews discover <url>`)
 //! - 🚀 Pluggable LLM backend (OpenAI, OpenRouter, Grok, Gemini, Claude)
 //! - 🛡️ Graceful error handling with user-friendly messages
 //!
@@ -78,8 +102,11 @@
 //! }
 //! ```
 
-use clap::Parser;
-use uninews::universal_scrape;
+use std::time::Duration;
+
+use clap::{Parser, Subcommand};
+use futures::StreamExt;
+use uninews::{ArticleLink, ConversionMode, Post, ScrapeOptions, Scraper};
 
 /// Command line arguments for the Uninews scraper.
 ///
@@ -93,9 +120,14 @@ use uninews::universal_scrape;
     long_about = "Uninews is a powerful CLI tool for scraping news articles from any website \
                   and automatically converting them to beautifully formatted Markdown. \
                   It supports translation to 100+ languages using AI-powered processing. \
-                  Requires OPEN_AI_SECRET environment variable to be set."
+                  Requires OPEN_AI_SECRET environment variable to be set (unless --no-llm is used).",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
 )]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// The URL of the news article to scrape
     ///
     /// Must be a complete, valid HTTP(S) URL.
@@ -103,7 +135,8 @@ struct Args {
     /// - https://www.bbc.com/news/world
     /// - https://news.ycombinator.com/item?id=123
     /// - https://medium.com/publication/article-title
-    url: String,
+    #[arg(required = true)]
+    url: Option<String>,
 
     /// Target language for output (default: english)
     ///
@@ -119,7 +152,7 @@ struct Args {
     /// - And 80+ more languages
     ///
     /// Example: `--language spanish` or `-l français`
-    #[arg(short, long, default_value = "english")]
+    #[arg(short, long, default_value = "english", global = true)]
     language: String,
 
     /// Output the result as JSON instead of formatted text
@@ -136,15 +169,92 @@ struct Args {
     /// - error: Error message (empty if successful)
     ///
     /// Example: `--json` or `-j`
-    #[arg(short = 'j', long = "json", default_value_t = false)]
+    #[arg(short = 'j', long = "json", default_value_t = false, global = true)]
     json: bool,
+
+    /// Convert to Markdown with the built-in deterministic converter
+    /// instead of the LLM
+    ///
+    /// No API key is needed and no LLM call is made. The output is not
+    /// translated, so `--language` is ignored.
+    ///
+    /// Example: `--no-llm`
+    #[arg(long = "no-llm", default_value_t = false, global = true)]
+    no_llm: bool,
+}
+
+/// Subcommands beyond the default single-URL scrape.
+#[derive(Subcommand)]
+enum Command {
+    /// Scrape every item of an RSS, Atom, or JSON Feed
+    ///
+    /// The URL may point at the feed itself or at a homepage that declares
+    /// one with `<link rel="alternate" type="application/rss+xml">`. Items
+    /// are scraped concurrently and printed in feed order; the feed's
+    /// title, author, date, and image fill whatever the article page
+    /// lacks. With `--json` the output is a JSON array of posts.
+    ///
+    /// Example: `uninews feed https://example.com/ --limit 5`
+    Feed {
+        /// Feed or homepage URL
+        url: String,
+
+        /// Scrape only the first N items of the feed
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+    },
+
+    /// Scrape every page listed in an XML or Google News sitemap
+    ///
+    /// Sitemap indexes are followed and gzip'd sitemaps are decompressed.
+    /// Pages are scraped concurrently and printed in sitemap order; the
+    /// `<news:news>` title, publication date, and keywords fill whatever
+    /// the page lacks. With `--json` the output is a JSON array of posts.
+    ///
+    /// Example: `uninews sitemap https://example.com/news-sitemap.xml --max-age-hours 48`
+    Sitemap {
+        /// Sitemap or sitemap index URL
+        url: String,
+
+        /// Scrape only the first N pages of the sitemap
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+
+        /// Skip pages published (or last modified) more than H hours ago
+        #[arg(long = "max-age-hours", value_name = "H")]
+        max_age_hours: Option<u64>,
+    },
+
+    /// List the probable article links of a section page or homepage
+    ///
+    /// Anchors are ranked by path depth, date segments, slug shape, and
+    /// placement; off-site links, tag / author / utility pages, and
+    /// assets are dropped. JavaScript shells are rendered with Playwright
+    /// when it is enabled. Prints one `score  url  text` line per link
+    /// (a JSON array of links with `--json`), or, with `--scrape`,
+    /// scrapes the links and prints the posts in rank order.
+    ///
+    /// Example: `uninews discover https://www.axios.com/technology --scrape -n 5`
+    Discover {
+        /// Section page or homepage URL
+        url: String,
+
+        /// Keep only the N best-ranked links
+        #[arg(short = 'n', long)]
+        limit: Option<usize>,
+
+        /// Scrape the discovered links instead of listing them
+        #[arg(long, default_value_t = false)]
+        scrape: bool,
+    },
 }
 
 /// Main entry point for the Uninews CLI application.
 ///
 /// This async function:
 /// 1. Parses command-line arguments
-/// 2. Calls the library's `universal_scrape` function
+/// 2. Scrapes the URL with a `Scraper` configured from the environment
+///    (and `--no-llm`)
 /// 3. Handles any errors gracefully
 /// 4. Formats and outputs the results based on user preferences
 ///
@@ -171,10 +281,33 @@ async fn main() {
     let args = Args::parse();
 
     // Scrape the URL and convert its content to Markdown in the requested language.
-    // The LLM provider is selected via UNINEWS_LLM_CLIENT / UNINEWS_LLM_MODEL env vars.
-    // Pass `None` so the context window is resolved from UNINEWS_LLM_CONTEXT_WINDOW
+    // The LLM provider is selected via UNINEWS_LLM_CLIENT / UNINEWS_LLM_MODEL env vars,
+    // and the context window via UNINEWS_LLM_CONTEXT_WINDOW
     // (default: 256,000 tokens, see `uninews::DEFAULT_LLM_CONTEXT_WINDOW`).
-    let post = universal_scrape(&args.url, &args.language, None).await;
+    let mut options = ScrapeOptions::from_env();
+    if args.no_llm {
+        options = options.conversion(ConversionMode::Deterministic);
+    }
+    let scraper = Scraper::new(options);
+
+    let url = match args.command {
+        Some(Command::Feed { url, limit }) => {
+            scrape_feed(&scraper, &url, limit, &args.language, args.json).await
+        }
+        Some(Command::Sitemap {
+            url,
+            limit,
+            max_age_hours,
+        }) => {
+            let max_age = max_age_hours.map(|hours| Duration::from_secs(hours * 3_600));
+            scrape_sitemap(&scraper, &url, limit, max_age, &args.language, args.json).await
+        }
+        Some(Command::Discover { url, limit, scrape }) => {
+            discover(&scraper, &url, limit, scrape, &args.language, args.json).await
+        }
+        None => args.url.unwrap_or_default(),
+    };
+    let post = scraper.scrape(&url, &args.language).await;
 
     if args.json {
         // Serialize the Post to JSON even when scraping failed: the `error`
@@ -200,3 +333,146 @@ async fn main() {
         std::process::exit(1);
     }
 }
+
+/// Run `uninews feed`: scrape the first `limit` items of the feed at `url`
+/// and print them in feed order, then exit.
+async fn scrape_feed(
+    scraper: &Scraper,
+    url: &str,
+    limit: Option<usize>,
+    language: &str,
+    json: bool,
+) -> ! {
+    let feed = match scraper.fetch_feed(url).await {
+        Ok(feed) => feed,
+        Err(err) => {
+            eprintln!("❌ Error reading feed: {}", err);
+            std::process::exit(1);
+        }
+    };
+    let items: Vec<_> = feed
+        .items
+        .into_iter()
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
+    let order: Vec<String> = items.iter().map(|item| item.link.clone()).collect();
+    let results = scraper
+        .scrape_feed_items(items, language)
+        .map(|(item, post)| (item.link, post))
+        .collect()
+        .await;
+    print_batch(order, results, json)
+}
+
+/// Run `uninews sitemap`: scrape the first `limit` pages of the sitemap at
+/// `url` (only those newer than `max_age`, when set) and print them in
+/// sitemap order, then exit.
+async fn scrape_sitemap(
+    scraper: &Scraper,
+    url: &str,
+    limit: Option<usize>,
+    max_age: Option<Duration>,
+    language: &str,
+    json: bool,
+) -> ! {
+    let sitemap = match scraper.fetch_sitemap(url, max_age).await {
+        Ok(sitemap) => sitemap,
+        Err(err) => {
+            eprintln!("❌ Error reading sitemap: {}", err);
+            std::process::exit(1);
+        }
+    };
+    let entries: Vec<_> = sitemap
+        .entries
+        .into_iter()
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
+    let order: Vec<String> = entries.iter().map(|entry| entry.loc.clone()).collect();
+    let results = scraper
+        .scrape_sitemap_entries(entries, language)
+        .map(|(entry, post)| (entry.loc, post))
+        .collect()
+        .await;
+    print_batch(order, results, json)
+}
+
+/// Run `uninews discover`: rank the article links of the section page at
+/// `url` and print the best `limit` of them — or, with `scrape`, scrape
+/// them and print the posts in rank order — then exit.
+async fn discover(
+    scraper: &Scraper,
+    url: &str,
+    limit: Option<usize>,
+    scrape: bool,
+    language: &str,
+    json: bool,
+) -> ! {
+    let links = match scraper.discover_article_links(url).await {
+        Ok(links) => links,
+        Err(err) => {
+            eprintln!("❌ Error discovering links: {}", err);
+            std::process::exit(1);
+        }
+    };
+    let links: Vec<ArticleLink> = links
+        .into_iter()
+        .take(limit.unwrap_or(usize::MAX))
+        .collect();
+
+    if scrape {
+        let order: Vec<String> = links.into_iter().map(|link| link.url).collect();
+        let results = scraper.scrape_many(order.clone(), language).collect().await;
+        print_batch(order, results, json)
+    }
+
+    if json {
+        match serde_json::to_string_pretty(&links) {
+            Ok(json) => println!("{}", json),
+            Err(err) => {
+                eprintln!("❌ Error serializing to JSON: {}", err);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        for link in &links {
+            println!("{:>3}  {}  {}", link.score, link.url, link.text);
+        }
+    }
+    std::process::exit(0);
+}
+
+/// Print batch `results` in `order` (as a JSON array of posts with
+/// `json`), then exit — non-zero when any page failed.
+fn print_batch(order: Vec<String>, mut results: Vec<(String, Post)>, json: bool) -> ! {
+    results.sort_by_key(|(url, _)| order.iter().position(|ordered| ordered == url));
+    let failed = results
+        .iter()
+        .filter(|(_, post)| !post.error.is_empty())
+        .count();
+
+    if json {
+        let posts: Vec<&Post> = results.iter().map(|(_, post)| post).collect();
+        match serde_json::to_string_pretty(&posts) {
+            Ok(json) => println!("{}", json),
+            Err(err) => {
+                eprintln!("❌ Error serializing to JSON: {}", err);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        let mut first = true;
+        for (url, post) in &results {
+            if !post.error.is_empty() {
+                eprintln!("❌ Error scraping {}: {}", url, post.error);
+                continue;
+            }
+            if !first {
+                println!("\n---\n");
+            }
+            first = false;
+            println!("{}\n\n{}", post.title, post.content);
+        }
+    }
+
+    std::process::exit(if failed == 0 { 0 } else { 1 });
+}
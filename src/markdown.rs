@@ -0,0 +1,605 @@
+//! Deterministic HTML → Markdown conversion.
+//!
+//! The default pipeline hands the cleaned article HTML to an LLM, which
+//! needs an API key and costs money per article. [`html_to_markdown`] is a
+//! built-in, LLM-free converter for the same cleaned HTML (see the `html`
+//! module): headings, paragraphs, ordered / unordered lists (nested),
+//! links, images, blockquotes, tables, inline code and `<pre>` blocks, and
+//! emphasis. The output is a pure function of the input — no network, no
+//! randomness — so it suits tests, key-less deployments, and bulk ingestion.
+//!
+//! [`ConversionMode`] selects how a [`crate::Scraper`] converts content:
+//! through the LLM (the default), deterministically, or deterministically
+//! first with the LLM polishing (and translating) the much smaller Markdown
+//! payload. The deterministic converter never translates.
+
+use std::env;
+use std::fmt;
+use std::str::FromStr;
+
+use scraper::{ElementRef, Html};
+use serde::{Deserialize, Serialize};
+
+use crate::html::is_kept_url;
+
+/// Environment variable that selects the [`ConversionMode`] used by
+/// [`crate::ScrapeOptions::from_env`] (`llm`, `deterministic`, or
+/// `llm_prepass`; default `llm`).
+///
+/// # Examples
+///
+/// ```
+/// use uninews::UNINEWS_CONVERSION_MODE_ENV;
+/// assert_eq!(UNINEWS_CONVERSION_MODE_ENV, "UNINEWS_CONVERSION_MODE");
+/// ```
+pub const UNINEWS_CONVERSION_MODE_ENV: &str = "UNINEWS_CONVERSION_MODE";
+
+/// How scraped content is converted to Markdown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConversionMode {
+    /// Send the cleaned HTML to the configured LLM (translation supported).
+    #[default]
+    Llm,
+    /// Convert with [`html_to_markdown`] only: no LLM call, no API key, no
+    /// translation.
+    Deterministic,
+    /// Convert with [`html_to_markdown`] first, then send the (smaller)
+    /// Markdown to the LLM for cleanup and translation.
+    LlmPrepass,
+}
+
+impl ConversionMode {
+    /// Stable snake_case name, identical to the serde representation.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Llm => "llm",
+            Self::Deterministic => "deterministic",
+            Self::LlmPrepass => "llm_prepass",
+        }
+    }
+
+    /// Whether this mode calls the LLM.
+    pub fn uses_llm(self) -> bool {
+        self != Self::Deterministic
+    }
+}
+
+impl fmt::Display for ConversionMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ConversionMode {
+    type Err = String;
+
+    /// Case-insensitive; `-` is accepted in place of `_`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "llm" => Ok(Self::Llm),
+            "deterministic" => Ok(Self::Deterministic),
+            "llm_prepass" => Ok(Self::LlmPrepass),
+            other => Err(format!("Unknown conversion mode '{}'", other)),
+        }
+    }
+}
+
+/// The conversion mode selected by [`UNINEWS_CONVERSION_MODE_ENV`].
+/// Unset or empty selects [`ConversionMode::Llm`]; unknown values log a
+/// warning to stderr and fall back to it.
+pub(crate) fn conversion_mode_from_env() -> ConversionMode {
+    match env::var(UNINEWS_CONVERSION_MODE_ENV) {
+        Ok(raw) if !raw.trim().is_empty() => raw.parse().unwrap_or_else(|error| {
+            eprintln!(
+                "uninews: invalid {}={:?} ({}); using {}",
+                UNINEWS_CONVERSION_MODE_ENV,
+                raw,
+                error,
+                ConversionMode::Llm
+            );
+            ConversionMode::Llm
+        }),
+        _ => ConversionMode::Llm,
+    }
+}
+
+/// Convert a post body with [`html_to_markdown`] when it is HTML (starts
+/// with a tag, as the cleaner's output always does). Plain-text bodies —
+/// X threads, host-fallback extractions — are returned unchanged.
+pub(crate) fn content_to_markdown(content: &str, base_url: Option<&str>) -> String {
+    if content.trim_start().starts_with('<') {
+        html_to_markdown(content, base_url)
+    } else {
+        content.to_string()
+    }
+}
+
+/// Subtrees that never contribute text (the cleaner already drops them;
+/// listed again so raw HTML passed to [`html_to_markdown`] stays sane).
+const IGNORED_TAGS: &[&str] = &["script", "style", "noscript", "template", "head", "title"];
+
+/// Per-element rendering state for the iterative converter.
+enum Frame {
+    /// Inline wrapper with no Markdown of its own (`span`, unknown tags).
+    Transparent,
+    /// Paragraph-level container (`p`, `div`, `section`, …).
+    Block,
+    Heading(usize),
+    Blockquote,
+    Pre,
+    List {
+        ordered: bool,
+        next: usize,
+    },
+    ListItem,
+    Table {
+        rows: Vec<Vec<String>>,
+    },
+    Row {
+        cells: Vec<String>,
+    },
+    Cell,
+    Link {
+        href: Option<String>,
+    },
+    Emphasis(&'static str),
+    Code,
+}
+
+impl Frame {
+    /// Whether text inside this frame is emitted verbatim (no escaping or
+    /// whitespace collapsing).
+    fn is_raw(&self) -> bool {
+        matches!(self, Self::Pre | Self::Code)
+    }
+}
+
+/// One unit of pending work, mirroring the cleaner's explicit stack so deep
+/// markup cannot overflow the call stack.
+enum Work<'a> {
+    Enter(ElementRef<'a>),
+    Text(&'a str),
+    Exit,
+}
+
+/// Convert cleaned article HTML to Markdown without an LLM.
+///
+/// Relative link and image URLs are resolved against `base_url` when given;
+/// links with non-web schemes (`javascript:`, `data:`, …) keep their text
+/// and lose the URL. Text is Markdown-escaped — inline markers anywhere,
+/// block markers (`#`, `-`, `+`, `1.`, `|`) at the start of a line — so
+/// scraped prose can never turn into live formatting or inline HTML.
+///
+/// # Examples
+///
+/// ```
+/// use uninews::html_to_markdown;
+///
+/// let markdown = html_to_markdown(
+///     r#"<article><h2>Title</h2><p>Read <a href="/more">more</a>.</p></article>"#,
+///     Some("https://example.com/news/"),
+/// );
+/// assert_eq!(markdown, "## Title\n\nRead [more](https://example.com/more).");
+/// ```
+pub fn html_to_markdown(html: &str, base_url: Option<&str>) -> String {
+    let fragment = Html::parse_fragment(html);
+    let base = base_url.and_then(|base| reqwest::Url::parse(base).ok());
+
+    // Frames pair up with `Exit` markers; the root frame is never popped.
+    let mut frames: Vec<(Frame, String)> = vec![(Frame::Transparent, String::new())];
+    let mut raw_depth = 0usize;
+    let mut stack: Vec<Work> = Vec::new();
+    push_children(&mut stack, fragment.root_element());
+
+    while let Some(work) = stack.pop() {
+        match work {
+            Work::Text(text) => {
+                let out = &mut frames.last_mut().expect("root frame").1;
+                if raw_depth > 0 {
+                    out.push_str(text);
+                } else {
+                    push_markdown_text(out, text);
+                }
+            }
+            Work::Enter(element) => {
+                let tag = element.value().name();
+                if IGNORED_TAGS.contains(&tag) {
+                    continue;
+                }
+                let out = &mut frames.last_mut().expect("root frame").1;
+                match tag {
+                    "img" => {
+                        if let Some(src) = element
+                            .value()
+                            .attr("src")
+                            .and_then(|src| resolve_url(base.as_ref(), src))
+                        {
+                            let alt = element.value().attr("alt").unwrap_or_default();
+                            out.push_str("![");
+                            push_markdown_text(out, alt.trim());
+                            out.push_str("](");
+                            out.push_str(&src);
+                            out.push(')');
+                        }
+                        continue;
+                    }
+                    "br" => {
+                        out.push('\n');
+                        continue;
+                    }
+                    "hr" => {
+                        push_block(out, "---");
+                        continue;
+                    }
+                    _ => {}
+                }
+
+                let frame = match tag {
+                    "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                        Frame::Heading(usize::from(tag.as_bytes()[1] - b'0'))
+                    }
+                    "p" | "div" | "section" | "article" | "main" | "header" | "footer"
+                    | "figure" | "figcaption" | "address" | "details" | "summary" | "dl" | "dt"
+                    | "dd" | "caption" | "center" => Frame::Block,
+                    "blockquote" => Frame::Blockquote,
+                    "pre" => Frame::Pre,
+                    "ul" | "menu" => Frame::List {
+                        ordered: false,
+                        next: 1,
+                    },
+                    "ol" => Frame::List {
+                        ordered: true,
+                        next: element
+                            .value()
+                            .attr("start")
+                            .and_then(|start| start.trim().parse().ok())
+                            .unwrap_or(1),
+                    },
+                    "li" => Frame::ListItem,
+                    "table" => Frame::Table { rows: Vec::new() },
+                    "tr" => Frame::Row { cells: Vec::new() },
+                    "td" | "th" => Frame::Cell,
+                    "a" => Frame::Link {
+                        href: element
+                            .value()
+                            .attr("href")
+                            .and_then(|href| resolve_url(base.as_ref(), href)),
+                    },
+                    "strong" | "b" => Frame::Emphasis("**"),
+                    "em" | "i" | "cite" => Frame::Emphasis("*"),
+                    "del" | "s" | "strike" => Frame::Emphasis("~~"),
+                    "code" | "kbd" | "samp" if raw_depth == 0 => Frame::Code,
+                    _ => Frame::Transparent,
+                };
+                if frame.is_raw() {
+                    raw_depth += 1;
+                }
+                frames.push((frame, String::new()));
+                stack.push(Work::Exit);
+                push_children(&mut stack, element);
+            }
+            Work::Exit => {
+                let (frame, out) = frames.pop().expect("frame for every exit");
+                if frame.is_raw() {
+                    raw_depth -= 1;
+                }
+                finish_frame(&mut frames, frame, out);
+            }
+        }
+    }
+
+    let (_, out) = frames.pop().expect("root frame");
+    collapse_blank_lines(&out)
+}
+
+/// Schedule `element`'s children so they pop in document order.
+fn push_children<'a>(stack: &mut Vec<Work<'a>>, element: ElementRef<'a>) {
+    for child in element.children().rev() {
+        if let Some(child_element) = ElementRef::wrap(child) {
+            stack.push(Work::Enter(child_element));
+        } else if let Some(text) = child.value().as_text() {
+            stack.push(Work::Text(text));
+        }
+    }
+}
+
+/// Render a finished frame into its parent's buffer.
+fn finish_frame(frames: &mut [(Frame, String)], frame: Frame, out: String) {
+    let Some(parent_index) = frames.len().checked_sub(1) else {
+        return;
+    };
+    match frame {
+        Frame::Transparent => frames[parent_index].1.push_str(&out),
+        Frame::Block => {
+            let text = out.trim();
+            if !text.is_empty() {
+                push_block(&mut frames[parent_index].1, text);
+            }
+        }
+        Frame::Heading(level) => {
+            let text = single_line(&out);
+            if !text.is_empty() {
+                push_block(
+                    &mut frames[parent_index].1,
+                    &format!("{} {}", "#".repeat(level), text),
+                );
+            }
+        }
+        Frame::Blockquote => {
+            let text = collapse_blank_lines(&out);
+            if !text.is_empty() {
+                let quoted = text
+                    .lines()
+                    .map(|line| {
+                        if line.is_empty() {
+                            ">".to_string()
+                        } else {
+                            format!("> {}", line)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                push_block(&mut frames[parent_index].1, &quoted);
+            }
+        }
+        Frame::Pre => {
+            let text = out.trim_matches('\n');
+            if !text.trim().is_empty() {
+                let fence = if text.contains("```") { "~~~~" } else { "```" };
+                push_block(
+                    &mut frames[parent_index].1,
+                    &format!("{}\n{}\n{}", fence, text, fence),
+                );
+            }
+        }
+        Frame::List { .. } => {
+            let text = out.trim_end_matches(['\n', ' ']);
+            if text.trim().is_empty() {
+                return;
+            }
+            let (parent, parent_out) = &mut frames[parent_index];
+            if matches!(parent, Frame::ListItem) {
+                // Nested list: keep it tight under the item's first line.
+                let trimmed_len = parent_out.trim_end().len();
+                parent_out.truncate(trimmed_len);
+                parent_out.push('\n');
+                parent_out.push_str(text);
+                parent_out.push('\n');
+            } else {
+                push_block(parent_out, text);
+            }
+        }
+        Frame::ListItem => {
+            let text = collapse_blank_lines(&out);
+            if text.is_empty() {
+                return;
+            }
+            let (parent, parent_out) = &mut frames[parent_index];
+            let marker = match parent {
+                Frame::List {
+                    ordered: true,
+                    next,
+                } => {
+                    let marker = format!("{}. ", next);
+                    *next += 1;
+                    marker
+                }
+                _ => "- ".to_string(),
+            };
+            let indent = " ".repeat(marker.len());
+            let mut item = marker;
+            for (index, line) in text.lines().enumerate() {
+                if index > 0 {
+                    item.push('\n');
+                    if !line.is_empty() {
+                        item.push_str(&indent);
+                    }
+                }
+                item.push_str(line);
+            }
+            if matches!(parent, Frame::List { .. }) {
+                parent_out.push_str(&item);
+                parent_out.push('\n');
+            } else {
+                push_block(parent_out, &item);
+            }
+        }
+        Frame::Table { rows } => {
+            let caption = out.trim();
+            let parent_out = &mut frames[parent_index].1;
+            if !caption.is_empty() {
+                push_block(parent_out, caption);
+            }
+            if let Some(table) = render_table(&rows) {
+                push_block(parent_out, &table);
+            }
+        }
+        Frame::Row { cells } => {
+            if cells.is_empty() {
+                return;
+            }
+            match frames.iter_mut().rev().find_map(|(frame, _)| match frame {
+                Frame::Table { rows } => Some(rows),
+                _ => None,
+            }) {
+                Some(rows) => rows.push(cells),
+                None => push_block(&mut frames[parent_index].1, &cells.join(" | ")),
+            }
+        }
+        Frame::Cell => {
+            let text = single_line(&out).replace('|', "\\|");
+            match frames.iter_mut().rev().find_map(|(frame, _)| match frame {
+                Frame::Row { cells } => Some(cells),
+                _ => None,
+            }) {
+                Some(cells) => cells.push(text),
+                None => frames[parent_index].1.push_str(&text),
+            }
+        }
+        Frame::Link { href } => {
+            let text = single_line(&out);
+            if text.is_empty() {
+                return;
+            }
+            let parent_out = &mut frames[parent_index].1;
+            match href {
+                Some(href) => parent_out.push_str(&format!("[{}]({})", text, href)),
+                None => parent_out.push_str(&text),
+            }
+        }
+        Frame::Emphasis(marker) => {
+            let text = single_line(&out);
+            if !text.is_empty() {
+                frames[parent_index]
+                    .1
+                    .push_str(&format!("{}{}{}", marker, text, marker));
+            }
+        }
+        Frame::Code => {
+            let text = single_line(&out);
+            if !text.is_empty() {
+                let ticks = if text.contains('`') { "``" } else { "`" };
+                let pad = if text.contains('`') { " " } else { "" };
+                frames[parent_index]
+                    .1
+                    .push_str(&format!("{}{}{}{}{}", ticks, pad, text, pad, ticks));
+            }
+        }
+    }
+}
+
+/// Append whitespace-collapsed, Markdown-escaped text.
+///
+/// Inline markers are escaped everywhere. At the start of a line, so are
+/// the markers that would open a block there: an ATX heading (`#`), a
+/// bullet or thematic break (`-`, `+`), an ordered-list delimiter (`1.`,
+/// `1)`), and a table row (`|`).
+fn push_markdown_text(out: &mut String, text: &str) {
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch.is_whitespace() {
+            if !out.is_empty() && !out.ends_with([' ', '\n']) {
+                out.push(' ');
+            }
+            continue;
+        }
+        if out.is_empty() || out.ends_with('\n') {
+            let opens_block =
+                |next: Option<&char>| next.is_none_or(|next| next.is_whitespace() || *next == ch);
+            if ch == '|' || (matches!(ch, '#' | '-' | '+') && opens_block(chars.peek())) {
+                out.push('\\');
+            } else if ch.is_ascii_digit() {
+                out.push(ch);
+                while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+                    out.push(digit);
+                }
+                let mut lookahead = chars.clone();
+                if matches!(lookahead.next(), Some('.' | ')'))
+                    && lookahead.next().is_none_or(char::is_whitespace)
+                {
+                    out.push('\\');
+                }
+                continue;
+            }
+        }
+        if matches!(ch, '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '>') {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+}
+
+/// Append `text` as its own block, separated by a blank line.
+fn push_block(out: &mut String, text: &str) {
+    out.push_str("\n\n");
+    out.push_str(text);
+    out.push_str("\n\n");
+}
+
+/// Collapse internal whitespace (newlines included) to single spaces.
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Trim trailing spaces, collapse runs of blank lines to one, and trim the
+/// result; fenced code blocks are left untouched.
+fn collapse_blank_lines(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_fence = false;
+    let mut blank_pending = false;
+    for line in text.lines() {
+        let fence_line =
+            line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~~");
+        if in_fence {
+            out.push_str(line);
+            out.push('\n');
+            in_fence = !fence_line;
+            continue;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            blank_pending = !out.is_empty();
+            continue;
+        }
+        if blank_pending {
+            out.push('\n');
+            blank_pending = false;
+        }
+        out.push_str(line);
+        out.push('\n');
+        in_fence = fence_line;
+    }
+    let trimmed_len = out.trim_end().len();
+    out.truncate(trimmed_len);
+    out
+}
+
+/// Render collected rows as a GitHub-flavored Markdown table; the first row
+/// is the header. `None` when there are no rows.
+fn render_table(rows: &[Vec<String>]) -> Option<String> {
+    let columns = rows
+        .iter()
+        .map(Vec::len)
+        .max()
+        .filter(|&columns| columns > 0)?;
+    let render_row = |cells: &[String]| {
+        let mut line = String::from("|");
+        for index in 0..columns {
+            let cell = cells.get(index).map(String::as_str).unwrap_or_default();
+            line.push(' ');
+            line.push_str(cell);
+            line.push_str(" |");
+        }
+        line
+    };
+    let mut table = render_row(&rows[0]);
+    table.push('\n');
+    table.push('|');
+    table.push_str(&" --- |".repeat(columns));
+    for row in &rows[1..] {
+        table.push('\n');
+        table.push_str(&render_row(row));
+    }
+    Some(table)
+}
+
+/// Resolve a link / image URL against `base`; `None` for empty values and
+/// non-web schemes. Spaces and parentheses are percent-encoded so the URL
+/// cannot break out of the Markdown link syntax.
+fn resolve_url(base: Option<&reqwest::Url>, value: &str) -> Option<String> {
+    let value = value.trim();
+    if !is_kept_url(value) {
+        return None;
+    }
+    let resolved = match base {
+        Some(base) => base.join(value).ok()?.to_string(),
+        None => value.to_string(),
+    };
+    Some(
+        resolved
+            .replace(' ', "%20")
+            .replace('(', "%28")
+            .replace(')', "%29"),
+    )
+}
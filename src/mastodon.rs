@@ -0,0 +1,920 @@
+//! Mastodon / ActivityPub statuses and self-reply threads.
+//!
+//! Mastodon's web UI is a JavaScript application: the HTML of a status
+//! page is an app shell the generic pipeline can only read through a
+//! browser render. The built-in [`MastodonHandler`] reads the status as
+//! data instead, from the instance that serves the URL:
+//!
+//! 1. **Mastodon API** — `GET /api/v1/statuses/:id` for the status and
+//!    `GET /api/v1/statuses/:id/context` for its conversation. Pleroma,
+//!    Akkoma, and GoToSocial implement the same endpoints.
+//! 2. **ActivityStreams** — when the API is unavailable (instances in
+//!    limited-federation mode, servers without the Mastodon API), the
+//!    status URL itself fetched with `Accept: application/activity+json`.
+//!    The thread is walked through each note's `inReplyTo` and the first
+//!    page of its `replies` collection.
+//!
+//! From either source, the author's self-reply thread around the status is
+//! assembled oldest first ([`MastodonThread`]): the author's replies to
+//! their own statuses, without other accounts' replies. Link-only statuses
+//! are followed to the article they link to, the way link-only tweets are
+//! ([`crate::x::x_post_is_link_only`]).
+//!
+//! Status URLs are recognized by shape (`/@user/:id`,
+//! `/users/:user/statuses/:id`, `/web/statuses/:id`) on any host. When
+//! neither the API nor ActivityStreams yields the status (a host that is
+//! not a fediverse server, an instance that refuses unsigned fetches, an
+//! error or a network failure), the URL is scraped by the generic web
+//! pipeline, with its browser render, alternates, and archive.org
+//! fallbacks.
+
+use std::collections::HashSet;
+
+use reqwest::Url;
+use scraper::{ElementRef, Html, Node};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::FallbackStage;
+use crate::events::{emit_event, ScrapeEvent};
+use crate::handler::{HandlerFuture, ScrapeContext, SourceHandler};
+use crate::html::{push_escaped_attribute, push_escaped_text};
+use crate::util::{array, parse_w3c_datetime, summarize_body, text, TITLE_TEXT_CHARS};
+use crate::web::{convert_scraped_post, fetch_api_page, scrape_linked_article, scrape_web_url};
+use crate::{Post, PostMetadata, Provenance, ScrapeError, Scraper};
+
+/// The `Accept` header for ActivityStreams objects.
+const ACTIVITY_JSON: &str =
+    "application/activity+json, application/ld+json; profile=\"https://www.w3.org/ns/activitystreams\"";
+
+/// At most this many statuses are fetched one by one when walking a
+/// thread through ActivityStreams.
+const MAX_ACTIVITY_FETCHES: usize = 50;
+
+/// The built-in [`SourceHandler`] for Mastodon (and other
+/// Mastodon-API or ActivityPub) status URLs on any instance.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MastodonHandler;
+
+impl SourceHandler for MastodonHandler {
+    fn name(&self) -> &str {
+        "mastodon"
+    }
+
+    fn matches(&self, url: &str) -> bool {
+        mastodon_status_id(url).is_some()
+    }
+
+    fn scrape<'a>(&'a self, url: &'a str, ctx: &'a ScrapeContext<'a>) -> HandlerFuture<'a> {
+        Box::pin(scrape_mastodon_url(ctx.scraper(), url, ctx.language()))
+    }
+}
+
+/// The account that posted a status.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MastodonAccount {
+    /// The instance's account id, or the ActivityPub actor id (a URL).
+    pub id: String,
+    /// The full `user@host` handle.
+    pub acct: String,
+    /// Display name; empty when the account has none (or it is unknown).
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub display_name: String,
+    /// The profile page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar: Option<String>,
+}
+
+/// A media attachment of a status.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MastodonMedia {
+    /// `image`, `gifv`, `video`, `audio`, or `unknown`.
+    pub kind: String,
+    pub url: String,
+    /// The alt text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// The link preview card of a status.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MastodonCard {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+/// One status (toot), from the Mastodon API or an ActivityStreams note.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MastodonStatus {
+    /// The instance's status id, or the ActivityStreams object id (a URL).
+    pub id: String,
+    /// The status page, on the instance the status was posted on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// RFC 3339 timestamp.
+    pub created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edited_at: Option<String>,
+    /// The id of the status this one replies to (same id space as `id`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_reply_to_id: Option<String>,
+    pub account: MastodonAccount,
+    /// The status text, as HTML.
+    pub content: String,
+    /// The content warning shown before the text.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spoiler_text: Option<String>,
+    /// ISO 639 language code.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub media: Vec<MastodonMedia>,
+    /// Hashtags, without the `#`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub card: Option<MastodonCard>,
+}
+
+/// Where a [`MastodonThread`] was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MastodonSource {
+    /// The Mastodon REST API (`/api/v1/statuses/:id` and `/context`).
+    Api,
+    /// ActivityStreams JSON (`Accept: application/activity+json`).
+    ActivityStreams,
+}
+
+/// A status and the self-reply thread around it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MastodonThread {
+    /// The author's self-reply thread, oldest first. Holds at least the
+    /// requested status.
+    pub statuses: Vec<MastodonStatus>,
+    /// The index of the requested status in `statuses`.
+    pub focus: usize,
+    pub source: MastodonSource,
+}
+
+impl MastodonStatus {
+    /// The visible text of the status, whitespace-collapsed.
+    pub fn text(&self) -> String {
+        visible_text(&self.content, false)
+    }
+
+    /// Whether the status text is nothing but links (mentions and hashtags
+    /// count as text).
+    pub fn is_link_only(&self) -> bool {
+        !self.linked_urls().is_empty() && visible_text(&self.content, true).is_empty()
+    }
+
+    /// The external article the status links to: its first link that is
+    /// not a mention, hashtag, or another status, else its preview card.
+    pub fn linked_article_url(&self) -> Option<String> {
+        self.linked_urls()
+            .into_iter()
+            .next()
+            .or_else(|| self.card.as_ref().map(|card| card.url.clone()))
+    }
+
+    /// The external links of the status text, in document order.
+    fn linked_urls(&self) -> Vec<String> {
+        let fragment = Html::parse_fragment(&self.content);
+        let mut urls = Vec::new();
+        for node in fragment.root_element().descendants() {
+            if let Some(href) = ElementRef::wrap(node).and_then(external_link) {
+                if !urls.contains(&href) {
+                    urls.push(href);
+                }
+            }
+        }
+        urls
+    }
+}
+
+impl MastodonThread {
+    /// The requested status.
+    pub fn status(&self) -> &MastodonStatus {
+        &self.statuses[self.focus]
+    }
+
+    /// The thread as a [`Post`] scraped from `url`: every status's HTML,
+    /// separated by `<hr>` and stamped with its time when there are
+    /// several, followed by its images and media links.
+    fn to_post(&self, url: &str) -> Post {
+        let first = &self.statuses[0];
+        let account = &first.account;
+        let author = if account.display_name.is_empty() {
+            format!("@{}", account.acct)
+        } else {
+            format!("@{} ({})", account.acct, account.display_name)
+        };
+
+        let mut content = String::new();
+        for (index, status) in self.statuses.iter().enumerate() {
+            if index > 0 {
+                content.push_str("<hr>");
+            }
+            if self.statuses.len() > 1 {
+                content.push_str("<p><em>");
+                push_escaped_text(&mut content, &status.created_at);
+                content.push_str("</em></p>");
+            }
+            if let Some(spoiler) = &status.spoiler_text {
+                content.push_str("<p><strong>Content warning: ");
+                push_escaped_text(&mut content, spoiler);
+                content.push_str("</strong></p>");
+            }
+            content.push_str(&status.content);
+            for media in &status.media {
+                push_media_html(&mut content, media);
+            }
+        }
+
+        let text = first.text();
+        let summary = if text.is_empty() {
+            first
+                .spoiler_text
+                .clone()
+                .unwrap_or_else(|| "Mastodon post".to_string())
+        } else {
+            text.chars().take(TITLE_TEXT_CHARS).collect()
+        };
+        let image = self
+            .statuses
+            .iter()
+            .flat_map(|status| &status.media)
+            .find(|media| media.kind == "image")
+            .map(|media| media.url.clone())
+            .or_else(|| account.avatar.clone());
+        let mut keywords: Vec<String> = Vec::new();
+        for tag in self.statuses.iter().flat_map(|status| &status.tags) {
+            if !keywords.contains(tag) {
+                keywords.push(tag.clone());
+            }
+        }
+        let metadata = PostMetadata {
+            schema_type: Some("SocialMediaPosting".to_string()),
+            authors: vec![if account.display_name.is_empty() {
+                account.acct.clone()
+            } else {
+                account.display_name.clone()
+            }],
+            date_published: Some(first.created_at.clone()),
+            date_modified: self.status().edited_at.clone(),
+            publisher: account
+                .acct
+                .split_once('@')
+                .map(|(_, host)| host.to_string()),
+            keywords,
+            image_url: image.clone(),
+            ..PostMetadata::default()
+        };
+        Post {
+            title: format!("{}: {}", author, summary),
+            content,
+            featured_image_url: image.unwrap_or_default(),
+            publication_date: Some(first.created_at.clone()),
+            author: Some(author),
+            provenance: Some(Provenance {
+                canonical_url: self.status().url.clone(),
+                ..Provenance::new(FallbackStage::Mastodon, url)
+            }),
+            metadata: Some(metadata),
+            ..Post::default()
+        }
+    }
+}
+
+/// The status id in a Mastodon status URL (`/@user/:id`,
+/// `/@user@host/:id`, `/users/:user/statuses/:id`, `/web/statuses/:id`,
+/// `/web/@user/:id`); `None` for other URLs.
+///
+/// ```
+/// use uninews::mastodon_status_id;
+/// assert_eq!(
+///     mastodon_status_id("https://mastodon.social/@Gargron/113579484624538435").as_deref(),
+///     Some("113579484624538435")
+/// );
+/// assert_eq!(mastodon_status_id("https://mastodon.social/@Gargron"), None);
+/// assert_eq!(mastodon_status_id("https://medium.com/@writer/a-story-1f2e3d"), None);
+/// ```
+pub fn mastodon_status_id(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    let segments: Vec<&str> = url
+        .path_segments()
+        .map(|segments| segments.filter(|segment| !segment.is_empty()).collect())
+        .unwrap_or_default();
+    let id = match segments.as_slice() {
+        [user, id] | ["web", user, id] if user.len() > 1 && user.starts_with('@') => id,
+        ["users", _, "statuses", id] | ["users", _, "statuses", id, "activity"] => id,
+        ["web", "statuses", id] => id,
+        _ => return None,
+    };
+    (id.len() <= 20 && !id.is_empty() && id.bytes().all(|byte| byte.is_ascii_digit()))
+        .then(|| id.to_string())
+}
+
+/// Parse a Mastodon API status (`GET /api/v1/statuses/:id`). A boost is
+/// read as the status it boosts. Fails with a message starting
+/// `Not a Mastodon status:`.
+pub fn parse_mastodon_status(json: &str) -> Result<MastodonStatus, String> {
+    let value: Value = serde_json::from_str(json)
+        .map_err(|error| format!("Not a Mastodon status: invalid JSON ({})", error))?;
+    api_status(&value)
+}
+
+/// Parse a Mastodon API status context (`GET /api/v1/statuses/:id/context`)
+/// into its statuses: the ancestors, then the descendants. Fails with a
+/// message starting `Not a Mastodon status context:`.
+pub fn parse_mastodon_context(json: &str) -> Result<Vec<MastodonStatus>, String> {
+    let value: Value = serde_json::from_str(json)
+        .map_err(|error| format!("Not a Mastodon status context: invalid JSON ({})", error))?;
+    let (Some(ancestors), Some(descendants)) = (
+        value["ancestors"].as_array(),
+        value["descendants"].as_array(),
+    ) else {
+        return Err("Not a Mastodon status context: no ancestors / descendants".to_string());
+    };
+    // Unreadable entries (deleted statuses, filtered placeholders) are
+    // skipped rather than failing the whole context.
+    Ok(ancestors
+        .iter()
+        .chain(descendants)
+        .filter_map(|status| api_status(status).ok())
+        .collect())
+}
+
+/// Parse an ActivityStreams note (or a `Create` activity wrapping one),
+/// as served for a status URL requested with
+/// `Accept: application/activity+json`. Fails with a message starting
+/// `Not an ActivityStreams note:`.
+pub fn parse_activity_note(json: &str) -> Result<MastodonStatus, String> {
+    let value: Value = serde_json::from_str(json)
+        .map_err(|error| format!("Not an ActivityStreams note: invalid JSON ({})", error))?;
+    activity_note(&value)
+}
+
+impl Scraper {
+    /// Fetch the Mastodon status at `url` and the author's self-reply
+    /// thread around it, from the instance's API or, failing that, its
+    /// ActivityStreams JSON (see the `mastodon` module docs).
+    ///
+    /// Fails with [`ScrapeError::Network`] / [`ScrapeError::HttpStatus`]
+    /// when neither source answers, or [`ScrapeError::Other`] when the URL
+    /// is not a status URL, the responses are not statuses, or the host is
+    /// not a fediverse server.
+    pub async fn fetch_mastodon_thread(&self, url: &str) -> Result<MastodonThread, ScrapeError> {
+        fetch_thread(self, url).await
+    }
+}
+
+/// Scrape a Mastodon status: the self-reply thread converted to Markdown,
+/// or the linked article of a link-only status. When neither the API nor
+/// ActivityStreams yields the status, the URL takes the generic web
+/// pipeline.
+async fn scrape_mastodon_url(scraper: &Scraper, url: &str, language: &str) -> Post {
+    let fallbacks = [FallbackStage::Mastodon];
+    let thread = match fetch_thread(scraper, url).await {
+        Ok(thread) => thread,
+        // Any site can have `/@user/<digits>` paths, and instances with
+        // authorized fetch refuse unsigned requests: the page itself may
+        // still be readable, with the web pipeline's own fallbacks.
+        Err(error) => {
+            emit_event(ScrapeEvent::FetchFailed {
+                url: url.to_string(),
+                error: error.to_string(),
+            });
+            return scrape_web_url(scraper, url, language)
+                .await
+                .after_fallback(FallbackStage::Mastodon);
+        }
+    };
+
+    let status = thread.status();
+    if status.is_link_only() {
+        if let Some(article_url) = status.linked_article_url() {
+            let title_override = status
+                .card
+                .as_ref()
+                .filter(|card| card.url == article_url && !card.title.trim().is_empty())
+                .map(|card| card.title.trim());
+            return scrape_linked_article(scraper, &article_url, language, title_override).await;
+        }
+    }
+
+    let post = thread.to_post(url);
+    emit_event(ScrapeEvent::ContentExtracted {
+        url: url.to_string(),
+        content_bytes: post.content.len(),
+    });
+    convert_scraped_post(scraper, post, language, &fallbacks).await
+}
+
+/// Fetch the thread around the status at `url`: the API first, then
+/// ActivityStreams.
+async fn fetch_thread(scraper: &Scraper, url: &str) -> Result<MastodonThread, ScrapeError> {
+    let status_id = mastodon_status_id(url)
+        .ok_or_else(|| ScrapeError::other(format!("Not a Mastodon status URL: {}", url)))?;
+    let parsed = Url::parse(url).map_err(|error| ScrapeError::other(error.to_string()))?;
+    let origin = parsed.origin().ascii_serialization();
+    let host = parsed.host_str().unwrap_or_default().to_string();
+
+    let api_error = match fetch_api_thread(scraper, &origin, &host, &status_id).await {
+        Ok(thread) => return Ok(thread),
+        Err(error) => error,
+    };
+
+    let page = fetch_api_page(scraper, url, ACTIVITY_JSON)
+        .await
+        .map_err(|error| ScrapeError::network(format!("{} (API: {})", error, api_error)))?;
+    if !page.status.is_success() {
+        return Err(ScrapeError::http_status(
+            page.status.as_u16(),
+            format!(
+                "Mastodon status {} returned HTTP {} (API: {})",
+                url, page.status, api_error
+            ),
+        ));
+    }
+    if !page.is_json() {
+        return Err(ScrapeError::other(format!(
+            "{} is not served by a Mastodon or ActivityPub server",
+            url
+        )));
+    }
+    let status = parse_activity_note(&page.body).map_err(|error| {
+        ScrapeError::other(format!(
+            "{}: {} ({})",
+            url,
+            error,
+            summarize_body(&page.body, 400)
+        ))
+    })?;
+    Ok(fetch_activity_thread(scraper, status, &page.body).await)
+}
+
+/// Read the status and its context from the Mastodon API at `origin`.
+async fn fetch_api_thread(
+    scraper: &Scraper,
+    origin: &str,
+    host: &str,
+    status_id: &str,
+) -> Result<MastodonThread, String> {
+    let status_url = format!("{}/api/v1/statuses/{}", origin, status_id);
+    let page = fetch_api_page(scraper, &status_url, "application/json").await?;
+    let mut status = page.json_body().and_then(parse_mastodon_status)?;
+    qualify_acct(&mut status, host);
+
+    let context_url = format!("{}/context", status_url);
+    let context = match fetch_api_page(scraper, &context_url, "application/json").await {
+        Ok(page) => page.json_body().and_then(parse_mastodon_context),
+        Err(error) => Err(error),
+    };
+    let mut context = context.unwrap_or_else(|error| {
+        emit_event(ScrapeEvent::FetchFailed {
+            url: context_url.clone(),
+            error,
+        });
+        Vec::new()
+    });
+    for status in &mut context {
+        qualify_acct(status, host);
+    }
+    Ok(self_reply_thread(status, context, MastodonSource::Api))
+}
+
+/// Walk the thread around the ActivityStreams note `status` (parsed from
+/// `body`): up through `inReplyTo` while the author stays the same, and
+/// down through the first page of each note's `replies` collection (the
+/// author's own replies, on Mastodon). Also reads the author's actor for
+/// their display name and avatar. Fetch failures end the walk early.
+async fn fetch_activity_thread(
+    scraper: &Scraper,
+    mut status: MastodonStatus,
+    body: &str,
+) -> MastodonThread {
+    let author = status.account.id.clone();
+    let mut fetches = 0;
+    let mut fetch_note = |url: String| {
+        fetches += 1;
+        let allowed = fetches <= MAX_ACTIVITY_FETCHES;
+        async move {
+            if !allowed {
+                return None;
+            }
+            let page = fetch_api_page(scraper, &url, ACTIVITY_JSON).await.ok()?;
+            if !page.status.is_success() {
+                return None;
+            }
+            serde_json::from_str::<Value>(&page.body).ok()
+        }
+    };
+
+    if let Some(actor) = fetch_note(author.clone()).await {
+        let mut account = activity_actor(&actor);
+        if !account.acct.is_empty() {
+            account.id = author.clone();
+            status.account = account;
+        }
+    }
+
+    let mut context = Vec::new();
+    let mut seen: HashSet<String> = HashSet::from([status.id.clone()]);
+    let mut parent = status.in_reply_to_id.clone();
+    while let Some(parent_id) = parent.take() {
+        if !seen.insert(parent_id.clone()) {
+            break;
+        }
+        let Some(note) = fetch_note(parent_id).await else {
+            break;
+        };
+        let Ok(mut ancestor) = activity_note(&note) else {
+            break;
+        };
+        if ancestor.account.id != author {
+            break;
+        }
+        ancestor.account = status.account.clone();
+        parent = ancestor.in_reply_to_id.clone();
+        context.push(ancestor);
+    }
+
+    let mut pending: Vec<Value> = serde_json::from_str::<Value>(body)
+        .map(|note| reply_items(&note))
+        .unwrap_or_default();
+    while let Some(item) = pending.pop() {
+        let note = match item {
+            Value::String(id) => {
+                if seen.contains(&id) {
+                    continue;
+                }
+                match fetch_note(id).await {
+                    Some(note) => note,
+                    None => continue,
+                }
+            }
+            note => note,
+        };
+        let Ok(mut reply) = activity_note(&note) else {
+            continue;
+        };
+        if reply.account.id != author || !seen.insert(reply.id.clone()) {
+            continue;
+        }
+        pending.extend(reply_items(&note));
+        reply.account = status.account.clone();
+        context.push(reply);
+    }
+
+    self_reply_thread(status, context, MastodonSource::ActivityStreams)
+}
+
+/// The author's self-reply thread around `status` within `context`: the
+/// chain of the author's statuses `status` replies to, plus every status
+/// of the author replying to one already in the thread, oldest first.
+fn self_reply_thread(
+    status: MastodonStatus,
+    context: Vec<MastodonStatus>,
+    source: MastodonSource,
+) -> MastodonThread {
+    let author = status.account.id.clone();
+    let focus_id = status.id.clone();
+    let by_author: Vec<MastodonStatus> = context
+        .into_iter()
+        .filter(|candidate| candidate.account.id == author && candidate.id != focus_id)
+        .collect();
+
+    let mut ids: HashSet<String> = HashSet::from([focus_id.clone()]);
+    let mut parent = status.in_reply_to_id.clone();
+    while let Some(parent_id) = parent {
+        match by_author.iter().find(|candidate| candidate.id == parent_id) {
+            Some(ancestor) if ids.insert(ancestor.id.clone()) => {
+                parent = ancestor.in_reply_to_id.clone();
+            }
+            _ => break,
+        }
+    }
+    loop {
+        let before = ids.len();
+        for candidate in &by_author {
+            if candidate
+                .in_reply_to_id
+                .as_ref()
+                .is_some_and(|parent_id| ids.contains(parent_id))
+            {
+                ids.insert(candidate.id.clone());
+            }
+        }
+        if ids.len() == before {
+            break;
+        }
+    }
+
+    let mut statuses: Vec<MastodonStatus> = by_author
+        .into_iter()
+        .filter(|candidate| ids.contains(&candidate.id))
+        .collect();
+    statuses.push(status);
+    // Mastodon ids are time-ordered snowflakes: break same-second ties
+    // numerically.
+    statuses.sort_by_key(|status| {
+        (
+            parse_w3c_datetime(&status.created_at),
+            status.id.len(),
+            status.id.clone(),
+        )
+    });
+    let focus = statuses
+        .iter()
+        .position(|status| status.id == focus_id)
+        .unwrap_or_default();
+    MastodonThread {
+        statuses,
+        focus,
+        source,
+    }
+}
+
+/// A status from a Mastodon API status object.
+fn api_status(value: &Value) -> Result<MastodonStatus, String> {
+    let value = if value["reblog"].is_object() {
+        &value["reblog"]
+    } else {
+        value
+    };
+    let id = json_id(&value["id"]).ok_or("Not a Mastodon status: no id")?;
+    let created_at = text(&value["created_at"]).ok_or("Not a Mastodon status: no created_at")?;
+    let account = &value["account"];
+    let acct = text(&account["acct"]).ok_or("Not a Mastodon status: no account")?;
+    let card = &value["card"];
+
+    Ok(MastodonStatus {
+        id,
+        url: text(&value["url"]).or_else(|| text(&value["uri"])),
+        created_at,
+        edited_at: text(&value["edited_at"]),
+        in_reply_to_id: json_id(&value["in_reply_to_id"]),
+        account: MastodonAccount {
+            id: json_id(&account["id"]).unwrap_or_else(|| acct.clone()),
+            acct,
+            display_name: text(&account["display_name"]).unwrap_or_default(),
+            url: text(&account["url"]),
+            avatar: text(&account["avatar"]),
+        },
+        content: text(&value["content"]).unwrap_or_default(),
+        spoiler_text: text(&value["spoiler_text"]),
+        language: text(&value["language"]),
+        media: array(&value["media_attachments"])
+            .iter()
+            .filter_map(|media| {
+                Some(MastodonMedia {
+                    kind: text(&media["type"]).unwrap_or_else(|| "unknown".to_string()),
+                    url: text(&media["url"]).or_else(|| text(&media["remote_url"]))?,
+                    description: text(&media["description"]),
+                })
+            })
+            .collect(),
+        tags: array(&value["tags"])
+            .iter()
+            .filter_map(|tag| text(&tag["name"]))
+            .collect(),
+        card: text(&card["url"]).map(|url| MastodonCard {
+            url,
+            title: text(&card["title"]).unwrap_or_default(),
+            description: text(&card["description"]),
+            image: text(&card["image"]),
+        }),
+    })
+}
+
+/// A status from an ActivityStreams `Note` (or a `Create` wrapping one).
+fn activity_note(value: &Value) -> Result<MastodonStatus, String> {
+    let note = if value["type"] == "Create" && value["object"].is_object() {
+        &value["object"]
+    } else {
+        value
+    };
+    if !matches!(
+        note["type"].as_str(),
+        Some("Note" | "Article" | "Page" | "Question")
+    ) {
+        return Err(format!(
+            "Not an ActivityStreams note: type {}",
+            note["type"]
+        ));
+    }
+    let id = text(&note["id"]).ok_or("Not an ActivityStreams note: no id")?;
+    let actor = match &note["attributedTo"] {
+        Value::Array(actors) => actors.first().and_then(object_id),
+        actor => object_id(actor),
+    }
+    .ok_or("Not an ActivityStreams note: no attributedTo")?;
+    let content = text(&note["content"]).or_else(|| {
+        note["contentMap"]
+            .as_object()
+            .and_then(|map| map.values().find_map(text))
+    });
+
+    Ok(MastodonStatus {
+        url: match &note["url"] {
+            Value::Array(urls) => urls.first().and_then(object_href),
+            url => object_href(url),
+        }
+        .or_else(|| Some(id.clone())),
+        id,
+        created_at: text(&note["published"]).unwrap_or_default(),
+        edited_at: text(&note["updated"]),
+        in_reply_to_id: object_id(&note["inReplyTo"]),
+        account: MastodonAccount {
+            acct: actor_acct(&actor, None).unwrap_or_else(|| actor.clone()),
+            url: Some(actor.clone()),
+            id: actor,
+            ..MastodonAccount::default()
+        },
+        content: content.unwrap_or_default(),
+        spoiler_text: text(&note["summary"]),
+        language: note["contentMap"]
+            .as_object()
+            .and_then(|map| map.keys().next().cloned()),
+        media: array(&note["attachment"])
+            .iter()
+            .filter_map(|attachment| {
+                let url = object_href(&attachment["url"])?;
+                let media_type = attachment["mediaType"].as_str().unwrap_or_default();
+                let kind = match media_type.split('/').next() {
+                    Some("image") => "image",
+                    Some("video") => "video",
+                    Some("audio") => "audio",
+                    _ => "unknown",
+                };
+                Some(MastodonMedia {
+                    kind: kind.to_string(),
+                    url,
+                    description: text(&attachment["name"]),
+                })
+            })
+            .collect(),
+        tags: array(&note["tag"])
+            .iter()
+            .filter(|tag| tag["type"] == "Hashtag")
+            .filter_map(|tag| text(&tag["name"]))
+            .map(|name| name.trim_start_matches('#').to_string())
+            .collect(),
+        card: None,
+    })
+}
+
+/// The account of an ActivityStreams actor (`Person`, `Service`, …).
+fn activity_actor(actor: &Value) -> MastodonAccount {
+    let id = text(&actor["id"]).unwrap_or_default();
+    let acct = actor_acct(&id, text(&actor["preferredUsername"]).as_deref()).unwrap_or_default();
+    MastodonAccount {
+        acct,
+        display_name: text(&actor["name"]).unwrap_or_default(),
+        url: object_href(&actor["url"]).or_else(|| Some(id.clone())),
+        avatar: object_href(&actor["icon"]["url"]).or_else(|| object_href(&actor["icon"])),
+        id,
+    }
+}
+
+/// The `user@host` handle for the actor `actor_id`: `username` (or the
+/// last path segment of the actor URL) at the actor's host.
+fn actor_acct(actor_id: &str, username: Option<&str>) -> Option<String> {
+    let url = Url::parse(actor_id).ok()?;
+    let host = url.host_str()?;
+    let username = match username {
+        Some(username) => username.to_string(),
+        None => url
+            .path_segments()?
+            .rfind(|segment| !segment.is_empty())?
+            .trim_start_matches('@')
+            .to_string(),
+    };
+    (!username.is_empty()).then(|| format!("{}@{}", username, host))
+}
+
+/// Complete a local account's `acct` (`alice`) with the instance `host`.
+fn qualify_acct(status: &mut MastodonStatus, host: &str) {
+    let account = &mut status.account;
+    if !account.acct.contains('@') {
+        let host = account
+            .url
+            .as_deref()
+            .and_then(|url| Url::parse(url).ok())
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| host.to_string());
+        account.acct = format!("{}@{}", account.acct, host);
+    }
+}
+
+/// The items of the first page of a note's `replies` collection: note
+/// ids (URLs) or embedded notes.
+fn reply_items(note: &Value) -> Vec<Value> {
+    let note = if note["type"] == "Create" {
+        &note["object"]
+    } else {
+        note
+    };
+    let first = &note["replies"]["first"];
+    let items = if first["items"].is_array() {
+        &first["items"]
+    } else {
+        &first["orderedItems"]
+    };
+    array(items).to_vec()
+}
+
+/// The visible text of status HTML, whitespace-collapsed. With
+/// `without_links`, the text of external links is left out.
+fn visible_text(content: &str, without_links: bool) -> String {
+    let fragment = Html::parse_fragment(content);
+    let mut text = String::new();
+    for node in fragment.root_element().descendants() {
+        match node.value() {
+            Node::Text(chunk) => {
+                let in_link = without_links
+                    && node
+                        .ancestors()
+                        .filter_map(ElementRef::wrap)
+                        .any(|element| external_link(element).is_some());
+                if !in_link {
+                    text.push_str(chunk);
+                }
+            }
+            Node::Element(element) if matches!(element.name(), "p" | "br") => text.push(' '),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The target of an `<a>` linking out of a status: an http(s) link that
+/// is not a mention, a hashtag, or a link to another status.
+fn external_link(element: ElementRef<'_>) -> Option<String> {
+    let value = element.value();
+    if value.name() != "a" {
+        return None;
+    }
+    let href = value.attr("href")?.trim();
+    let classes = value.attr("class").unwrap_or_default();
+    let rel = value.attr("rel").unwrap_or_default();
+    let internal = classes
+        .split_whitespace()
+        .any(|class| matches!(class, "mention" | "hashtag"))
+        || rel.split_whitespace().any(|rel| rel == "tag");
+    let external = (href.starts_with("https://") || href.starts_with("http://"))
+        && !internal
+        && mastodon_status_id(href).is_none();
+    external.then(|| href.to_string())
+}
+
+/// Append `media` as an `<img>` (images) or a link (other media).
+fn push_media_html(out: &mut String, media: &MastodonMedia) {
+    if media.kind == "image" {
+        out.push_str("<p><img src=\"");
+        push_escaped_attribute(out, &media.url);
+        out.push_str("\" alt=\"");
+        push_escaped_attribute(out, media.description.as_deref().unwrap_or_default());
+        out.push_str("\"></p>");
+    } else {
+        out.push_str("<p><a href=\"");
+        push_escaped_attribute(out, &media.url);
+        out.push_str("\">");
+        push_escaped_text(out, &format!("[{}]", media.kind));
+        if let Some(description) = &media.description {
+            out.push(' ');
+            push_escaped_text(out, description);
+        }
+        out.push_str("</a></p>");
+    }
+}
+
+/// A Mastodon id: a JSON string (or, from some servers, a number).
+fn json_id(value: &Value) -> Option<String> {
+    match value {
+        Value::Number(number) => Some(number.to_string()),
+        value => text(value),
+    }
+}
+
+/// An ActivityStreams reference: a URL string or an object's `id`.
+fn object_id(value: &Value) -> Option<String> {
+    text(value).or_else(|| text(&value["id"]))
+}
+
+/// An ActivityStreams link: a URL string or a `Link`'s `href`.
+fn object_href(value: &Value) -> Option<String> {
+    text(value).or_else(|| text(&value["href"]))
+}
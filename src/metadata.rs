@@ -0,0 +1,279 @@
+//! JSON-LD / schema.org article metadata.
+//!
+//! Most publishers embed a `<script type="application/ld+json">` block
+//! describing the page as a schema.org `NewsArticle`, `BlogPosting`, or
+//! another `Article` subtype. It carries metadata the `<meta>` tags do not:
+//! every author, `dateModified`, publisher, section, keywords, and often the
+//! full `articleBody`. [`extract_json_ld_article`] finds the best article
+//! node across all such blocks — top-level objects, arrays, `@graph`
+//! arrays, and `mainEntity` wrappers — resolves `{"@id": …}` references to
+//! other nodes (Yoast-style graphs link authors and publishers that way),
+//! and returns it as [`PostMetadata`] plus the body text.
+//!
+//! Malformed blocks are skipped; JSON-LD is optional enrichment and never
+//! fails a parse.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// JSON-LD nodes by `@id`.
+type NodesById<'a> = HashMap<&'a str, &'a Map<String, Value>>;
+
+/// Structured article metadata from the page's JSON-LD, attached to
+/// [`crate::Post::metadata`].
+///
+/// Empty fields are omitted from JSON. [`crate::Post::author`],
+/// [`crate::Post::publication_date`], and
+/// [`crate::Post::featured_image_url`] fall back to these values when the
+/// page's `<meta>` tags do not provide them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PostMetadata {
+    /// The schema.org type of the article node (`NewsArticle`,
+    /// `BlogPosting`, …).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_type: Option<String>,
+    /// `headline`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub headline: Option<String>,
+    /// `description`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Every `author` name, in document order, deduplicated.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+    /// `datePublished`, as written by the publisher.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_published: Option<String>,
+    /// `dateModified`, as written by the publisher.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_modified: Option<String>,
+    /// The publisher's name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<String>,
+    /// `articleSection` (the first one when several are listed).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub section: Option<String>,
+    /// `keywords`, split on commas when given as one string.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    /// The first `image` URL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
+}
+
+impl PostMetadata {
+    /// The authors joined with `", "`, or `None` when there are none.
+    pub fn author_line(&self) -> Option<String> {
+        (!self.authors.is_empty()).then(|| self.authors.join(", "))
+    }
+}
+
+/// The article node found in a page's JSON-LD.
+pub(crate) struct JsonLdArticle {
+    pub(crate) metadata: PostMetadata,
+    /// `articleBody` as plain text, when present.
+    pub(crate) article_body: Option<String>,
+}
+
+/// Find the best schema.org article node in `document`'s JSON-LD blocks.
+pub(crate) fn extract_json_ld_article(document: &Html) -> Option<JsonLdArticle> {
+    static SCRIPT_SELECTOR: OnceLock<Selector> = OnceLock::new();
+    let selector = SCRIPT_SELECTOR.get_or_init(|| {
+        Selector::parse("script[type]").expect("hard-coded CSS selector must be valid")
+    });
+
+    let blocks: Vec<Value> = document
+        .select(selector)
+        .filter(|script| {
+            script.value().attr("type").is_some_and(|kind| {
+                kind.trim()
+                    .to_ascii_lowercase()
+                    .starts_with("application/ld+json")
+            })
+        })
+        .filter_map(|script| parse_block(&script.text().collect::<String>()))
+        .collect();
+
+    let nodes = collect_nodes(&blocks);
+    let ids: NodesById = nodes
+        .iter()
+        // Bare `{"@id": …}` references must not shadow the full node.
+        .filter(|node| node.len() > 1)
+        .filter_map(|node| Some((node.get("@id")?.as_str()?, *node)))
+        .collect();
+
+    // Highest-ranked article type wins; document order breaks ties.
+    let mut best: Option<(u8, &Map<String, Value>)> = None;
+    for node in &nodes {
+        let rank = article_rank(node);
+        if rank > best.map_or(0, |(best_rank, _)| best_rank) {
+            best = Some((rank, node));
+        }
+    }
+    let (_, article) = best?;
+    Some(article_from_node(article, &ids))
+}
+
+/// Parse one script body, tolerating CDATA wrappers and HTML comments some
+/// CMSes put around the JSON.
+fn parse_block(text: &str) -> Option<Value> {
+    let mut json = text.trim();
+    for (open, close) in [
+        ("<!--", "-->"),
+        ("//<![CDATA[", "//]]>"),
+        ("<![CDATA[", "]]>"),
+    ] {
+        if let Some(inner) = json.strip_prefix(open) {
+            json = inner.strip_suffix(close).unwrap_or(inner).trim();
+        }
+    }
+    serde_json::from_str(json).ok()
+}
+
+/// Every JSON-LD node reachable through top-level arrays, `@graph`, and
+/// `mainEntity`, in document order.
+fn collect_nodes(blocks: &[Value]) -> Vec<&Map<String, Value>> {
+    let mut nodes = Vec::new();
+    let mut stack: Vec<&Value> = blocks.iter().rev().collect();
+    while let Some(value) = stack.pop() {
+        match value {
+            Value::Array(items) => stack.extend(items.iter().rev()),
+            Value::Object(object) => {
+                nodes.push(object);
+                for key in ["mainEntity", "@graph"] {
+                    if let Some(child) = object.get(key) {
+                        stack.push(child);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    nodes
+}
+
+/// The schema.org type names of `node`, without any `schema:` or
+/// `https://schema.org/` prefix.
+fn type_names(node: &Map<String, Value>) -> Vec<&str> {
+    let names: Vec<&str> = match node.get("@type") {
+        Some(Value::String(name)) => vec![name.as_str()],
+        Some(Value::Array(names)) => names.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    names
+        .into_iter()
+        .map(|name| name.rsplit(['/', ':']).next().unwrap_or(name))
+        .collect()
+}
+
+/// How good an article candidate `node` is: news articles beat blog posts,
+/// which beat generic `Article` subtypes; `0` means not an article.
+fn article_rank(node: &Map<String, Value>) -> u8 {
+    type_names(node)
+        .into_iter()
+        .map(|name| match name {
+            _ if name.ends_with("NewsArticle") => 3,
+            "BlogPosting" | "LiveBlogPosting" => 2,
+            "Article"
+            | "TechArticle"
+            | "ScholarlyArticle"
+            | "Report"
+            | "SocialMediaPosting"
+            | "DiscussionForumPosting" => 1,
+            _ => 0,
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Follow a `{"@id": …}` reference to the full node when one exists.
+fn resolve<'a>(value: &'a Value, ids: &NodesById<'a>) -> Option<&'a Map<String, Value>> {
+    let object = value.as_object()?;
+    match object.get("@id").and_then(Value::as_str) {
+        Some(id) if object.len() == 1 => ids.get(id).copied().or(Some(object)),
+        _ => Some(object),
+    }
+}
+
+/// Trimmed, non-empty text of a JSON string.
+fn text(value: Option<&Value>) -> Option<String> {
+    let text = value?.as_str()?.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// The value itself when it is an array, otherwise a one-element list.
+fn as_list(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(items) => items.iter().collect(),
+        other => vec![other],
+    }
+}
+
+/// Names from a `Person` / `Organization` value: a string, an object with
+/// `name`, an `@id` reference, or an array of those.
+fn names<'a>(value: Option<&'a Value>, ids: &NodesById<'a>) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for item in value.map(as_list).unwrap_or_default() {
+        let name = match item {
+            Value::String(_) => text(Some(item)),
+            _ => resolve(item, ids).and_then(|node| text(node.get("name"))),
+        };
+        if let Some(name) = name {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// The first image URL: a string, an `ImageObject` (`url` / `contentUrl`),
+/// or an array of those.
+fn first_image<'a>(value: Option<&'a Value>, ids: &NodesById<'a>) -> Option<String> {
+    value.map(as_list)?.into_iter().find_map(|item| match item {
+        Value::String(_) => text(Some(item)),
+        _ => resolve(item, ids)
+            .and_then(|node| text(node.get("url")).or_else(|| text(node.get("contentUrl")))),
+    })
+}
+
+fn article_from_node<'a>(node: &'a Map<String, Value>, ids: &NodesById<'a>) -> JsonLdArticle {
+    let mut keywords: Vec<String> = Vec::new();
+    for item in node.get("keywords").map(as_list).unwrap_or_default() {
+        for keyword in item.as_str().unwrap_or_default().split(',') {
+            let keyword = keyword.trim();
+            if !keyword.is_empty() && !keywords.iter().any(|known| known == keyword) {
+                keywords.push(keyword.to_string());
+            }
+        }
+    }
+
+    let metadata = PostMetadata {
+        schema_type: type_names(node)
+            .into_iter()
+            .find(|name| !name.is_empty())
+            .map(str::to_string),
+        headline: text(node.get("headline")).or_else(|| text(node.get("name"))),
+        description: text(node.get("description")),
+        authors: names(node.get("author"), ids),
+        date_published: text(node.get("datePublished")),
+        date_modified: text(node.get("dateModified")),
+        publisher: names(node.get("publisher"), ids).into_iter().next(),
+        section: node
+            .get("articleSection")
+            .map(as_list)
+            .unwrap_or_default()
+            .into_iter()
+            .find_map(|section| text(Some(section))),
+        keywords,
+        image_url: first_image(node.get("image"), ids),
+    };
+    JsonLdArticle {
+        metadata,
+        article_body: text(node.get("articleBody")),
+    }
+}
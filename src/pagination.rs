@@ -0,0 +1,179 @@
+//! Multi-page article detection.
+//!
+//! Some outlets split long features across pages (`?page=2`, `/2/`,
+//! `/page/2/`) linked with `<link rel="next">` or "Next page" anchors. The
+//! web pipeline follows them up to [`crate::ScrapeOptions::max_article_pages`]
+//! and concatenates the extracted bodies in order before conversion.
+//!
+//! [`next_page_url`] only accepts a link that continues the *same* article:
+//! same host, same path and query once the page number is set aside, and
+//! exactly the next page number. That keeps `rel="next"` links to the next
+//! blog post, section archives, and comment pages out, and since page
+//! numbers only go up, a pagination loop cannot form. A bare number as the
+//! last path segment (`/2/`) is as often an id or a date (`/story/43`,
+//! `/2024/05/16`), so such a link must also be marked as the next page:
+//! `rel="next"`, or text reading "Next", "Next page", "Page N", or N.
+
+use reqwest::Url;
+use scraper::{ElementRef, Html, Selector};
+
+/// Default cap on the pages of one article the web pipeline reads
+/// (the first page included).
+pub const DEFAULT_MAX_ARTICLE_PAGES: usize = 10;
+
+/// Query parameters that carry a page number (not `p`, which is
+/// WordPress's post id).
+const PAGE_QUERY_KEYS: &[&str] = &[
+    "page", "pg", "paged", "pagenum", "pagina", "seite", "strona",
+];
+
+/// Page numbers above this are treated as ids, not pages
+/// (`/news/12345`).
+const MAX_PAGE_NUMBER: u64 = 200;
+
+/// Where a page URL sits in a paginated article: everything but the page
+/// number, and the page number (1 when the URL carries none).
+#[derive(Debug, PartialEq, Eq)]
+struct PageLocation {
+    host: String,
+    path: String,
+    query: Vec<(String, String)>,
+    number: u64,
+    /// The number is a bare path segment (`/2/`), not a page parameter or
+    /// a `page`-prefixed segment.
+    bare: bool,
+}
+
+/// The URL of the page after `page_url` in a paginated article, found in
+/// the page's `html` (a `<link rel="next">` or any anchor; one ending in a
+/// bare page number must be marked as the next page), or `None` on the last
+/// page and on articles that are not paginated.
+///
+/// ```
+/// use uninews::next_page_url;
+/// let html = r#"<html><head>
+///   <link rel="next" href="https://news.example/feature/transit?page=2">
+/// </head><body></body></html>"#;
+/// assert_eq!(
+///     next_page_url("https://news.example/feature/transit", html).as_deref(),
+///     Some("https://news.example/feature/transit?page=2")
+/// );
+/// ```
+pub fn next_page_url(page_url: &str, html: &str) -> Option<String> {
+    let page = Url::parse(page_url).ok()?;
+    let current = page_location(&page)?;
+    let document = Html::parse_document(html);
+    let selector =
+        Selector::parse(r#"link[rel~="next"][href], a[href]"#).expect("valid pagination selector");
+    document
+        .select(&selector)
+        .filter_map(|link| Some((link, page.join(link.value().attr("href")?.trim()).ok()?)))
+        .find(|(link, candidate)| {
+            page_location(candidate).is_some_and(|next| {
+                next.number == current.number + 1
+                    && (!next.bare || marked_as_next(*link, next.number))
+                    && PageLocation {
+                        number: current.number,
+                        bare: current.bare,
+                        ..next
+                    } == current
+            })
+        })
+        .map(|(_, mut url)| {
+            url.set_fragment(None);
+            url.to_string()
+        })
+}
+
+/// Split `url` into its article location and page number. `None` for
+/// non-HTTP URLs.
+fn page_location(url: &Url) -> Option<PageLocation> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return None;
+    }
+    let mut number = None;
+    let mut bare = false;
+    let mut query: Vec<(String, String)> = Vec::new();
+    for (key, value) in url.query_pairs() {
+        let key = key.to_ascii_lowercase();
+        if key.starts_with("utm_") {
+            continue;
+        }
+        match page_number(&value) {
+            Some(page) if number.is_none() && PAGE_QUERY_KEYS.contains(&key.as_str()) => {
+                number = Some(page);
+            }
+            _ => query.push((key, value.into_owned())),
+        }
+    }
+    query.sort();
+
+    let mut segments: Vec<&str> = url
+        .path_segments()?
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    if number.is_none() {
+        if let Some(last) = segments.last().copied() {
+            let lower = last.to_ascii_lowercase();
+            let suffix = lower
+                .strip_prefix("page-")
+                .or_else(|| lower.strip_prefix("page"))
+                .or_else(|| lower.strip_prefix('p'))
+                .unwrap_or(&lower);
+            if let Some(page) = page_number(suffix) {
+                number = Some(page);
+                bare = suffix == lower;
+                segments.pop();
+                if bare
+                    && segments
+                        .last()
+                        .is_some_and(|segment| segment.eq_ignore_ascii_case("page"))
+                {
+                    segments.pop();
+                    bare = false;
+                }
+            }
+        }
+    }
+
+    Some(PageLocation {
+        host: url.host_str()?.to_ascii_lowercase(),
+        path: segments.join("/"),
+        query,
+        number: number.unwrap_or(1),
+        bare,
+    })
+}
+
+/// Whether `link` says it leads to page `number`: `rel="next"`, or text
+/// reading "Next", "Next page", "Page N", or N (trailing arrows aside).
+fn marked_as_next(link: ElementRef, number: u64) -> bool {
+    if link.value().attr("rel").is_some_and(|rel| {
+        rel.split_ascii_whitespace()
+            .any(|rel| rel.eq_ignore_ascii_case("next"))
+    }) {
+        return true;
+    }
+    let text = link
+        .text()
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    let text = text.trim_end_matches(['»', '›', '→', '>', ' ']);
+    let number = number.to_string();
+    matches!(text, "next" | "next page")
+        || text == number
+        || text.strip_prefix("page ") == Some(number.as_str())
+}
+
+/// A plausible page number: digits only, 1 to [`MAX_PAGE_NUMBER`].
+fn page_number(value: &str) -> Option<u64> {
+    if value.is_empty() || !value.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+    value
+        .parse()
+        .ok()
+        .filter(|page| (1..=MAX_PAGE_NUMBER).contains(page))
+}
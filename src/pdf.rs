@@ -0,0 +1,301 @@
+//! PDF document support for the web pipeline.
+//!
+//! Press releases, court filings, and research papers are often linked as
+//! PDFs. A fetched body is routed here instead of the HTML parser when the
+//! response's `Content-Type` says `application/pdf` or the body starts
+//! with the `%PDF-` magic bytes ([`is_pdf`]):
+//!
+//! 1. [`parse_pdf`] extracts the text of every page with the pure-Rust
+//!    [`pdf_extract`] crate and reads the document information dictionary
+//!    (title, author, subject, keywords, creation / modification dates).
+//! 2. The pages become the post's HTML content — one `<p>` per paragraph,
+//!    with an `<hr>` at every page boundary — which then goes through the
+//!    normal Markdown conversion.
+//!
+//! PDFs with no extractable text (scanned, image-only documents) fail as
+//! [`crate::ScrapeError::InsufficientContent`]. Browser renders cannot
+//! improve a PDF, so they never get the thin-content Playwright retry.
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use pdf_extract::{Dictionary, Document, Object};
+use serde::{Deserialize, Serialize};
+
+use crate::error::FallbackStage;
+use crate::html::article_body_to_html;
+use crate::{Post, PostMetadata, Provenance, ScrapeError};
+
+/// The magic bytes every PDF file starts with.
+const PDF_MAGIC: &[u8] = b"%PDF-";
+
+/// How far into the body the magic bytes may appear (PDF readers accept
+/// leading garbage up to 1 KiB).
+const PDF_MAGIC_WINDOW: usize = 1024;
+
+/// Titles taken from the first line of text are cut to this many
+/// characters.
+const MAX_FALLBACK_TITLE_CHARS: usize = 200;
+
+/// Text and metadata extracted from a PDF document.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PdfDocument {
+    /// `/Title` from the document information dictionary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// `/Author`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// `/Subject`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    /// `/Keywords`, split on commas and semicolons.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    /// `/CreationDate`, as RFC 3339 (`2026-10-16T09:30:00+02:00`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub creation_date: Option<String>,
+    /// `/ModDate`, as RFC 3339.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modification_date: Option<String>,
+    /// The text of each page, in page order.
+    pub pages: Vec<String>,
+}
+
+impl PdfDocument {
+    /// Whether no page has any visible text.
+    pub fn is_empty(&self) -> bool {
+        self.pages.iter().all(|page| page.trim().is_empty())
+    }
+
+    /// The document as a [`Post`] read from `url`: HTML paragraphs with an
+    /// `<hr>` between pages, and the information dictionary as title,
+    /// author, date, and [`PostMetadata`]. `title_override` wins over the
+    /// PDF's `/Title`; without either, the first line of text is used.
+    pub(crate) fn to_post(&self, url: &str, title_override: Option<&str>) -> Post {
+        if self.is_empty() {
+            return Post::from_error(ScrapeError::insufficient_content(
+                "The PDF has no extractable text (scanned or image-only document).",
+            ));
+        }
+
+        let mut content = String::new();
+        for (index, page) in self.pages.iter().enumerate() {
+            if index > 0 {
+                content.push_str("<hr>");
+            }
+            content.push_str(&article_body_to_html(&page_paragraphs(page)));
+        }
+
+        let title = title_override
+            .map(str::to_string)
+            .or_else(|| self.title.clone())
+            .or_else(|| {
+                self.pages
+                    .iter()
+                    .flat_map(|page| page.lines())
+                    .map(str::trim)
+                    .find(|line| !line.is_empty())
+                    .map(|line| line.chars().take(MAX_FALLBACK_TITLE_CHARS).collect())
+            })
+            .unwrap_or_default();
+        let metadata = PostMetadata {
+            schema_type: Some("DigitalDocument".to_string()),
+            headline: self.title.clone(),
+            description: self.subject.clone(),
+            authors: self.author.iter().cloned().collect(),
+            date_published: self.creation_date.clone(),
+            date_modified: self.modification_date.clone(),
+            keywords: self.keywords.clone(),
+            ..PostMetadata::default()
+        };
+        Post {
+            title,
+            content,
+            publication_date: self.creation_date.clone(),
+            author: self.author.clone(),
+            provenance: Some(Provenance::new(FallbackStage::PlainFetch, url)),
+            metadata: Some(metadata),
+            ..Post::default()
+        }
+    }
+}
+
+/// Whether a response is a PDF document: its `content_type` is
+/// `application/pdf` (or the legacy `application/x-pdf`), or `body` starts
+/// with the `%PDF-` magic bytes (servers often send PDFs as
+/// `application/octet-stream`). Bodies declared as text or HTML are never
+/// sniffed.
+///
+/// ```
+/// use uninews::is_pdf;
+/// assert!(is_pdf(Some("application/pdf; qs=0.9"), b""));
+/// assert!(is_pdf(Some("application/octet-stream"), b"%PDF-1.7\n..."));
+/// assert!(!is_pdf(Some("text/html"), b"<p>%PDF-1.7 explained</p>"));
+/// ```
+pub fn is_pdf(content_type: Option<&str>, body: &[u8]) -> bool {
+    let mime = content_type
+        .and_then(|value| value.split(';').next())
+        .map(|mime| mime.trim().to_ascii_lowercase());
+    match mime.as_deref() {
+        Some("application/pdf" | "application/x-pdf") => return true,
+        Some(mime) if mime.starts_with("text/") || mime.contains("html") => return false,
+        _ => {}
+    }
+    body[..body.len().min(PDF_MAGIC_WINDOW)]
+        .windows(PDF_MAGIC.len())
+        .any(|window| window == PDF_MAGIC)
+}
+
+/// Extract the page text and document information of a PDF.
+///
+/// Fails with a message starting `Not a readable PDF:` for malformed,
+/// truncated, or password-protected documents.
+pub fn parse_pdf(bytes: &[u8]) -> Result<PdfDocument, String> {
+    let unreadable = |error: String| format!("Not a readable PDF: {}", error);
+    // pdf-extract panics on some malformed fonts and content streams.
+    let pages = catch_unwind(AssertUnwindSafe(|| {
+        pdf_extract::extract_text_from_mem_by_pages(bytes)
+    }))
+    .map_err(|_| unreadable("the text extractor crashed".to_string()))?
+    .map_err(|error| unreadable(error.to_string()))?;
+    let document = Document::load_mem(bytes).map_err(|error| unreadable(error.to_string()))?;
+
+    let info = document
+        .trailer
+        .get(b"Info")
+        .and_then(|info| document.dereference(info))
+        .and_then(|(_, info)| info.as_dict());
+    let Ok(info) = info else {
+        return Ok(PdfDocument {
+            pages,
+            ..PdfDocument::default()
+        });
+    };
+    let field = |key: &[u8]| info_string(&document, info, key);
+
+    Ok(PdfDocument {
+        title: field(b"Title"),
+        author: field(b"Author"),
+        subject: field(b"Subject"),
+        keywords: field(b"Keywords")
+            .map(|keywords| {
+                keywords
+                    .split([',', ';'])
+                    .map(str::trim)
+                    .filter(|keyword| !keyword.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        creation_date: field(b"CreationDate").and_then(|date| parse_pdf_date(&date)),
+        modification_date: field(b"ModDate").and_then(|date| parse_pdf_date(&date)),
+        pages,
+    })
+}
+
+/// A text string entry of the information dictionary, trimmed; `None`
+/// when missing or blank.
+fn info_string(document: &Document, info: &Dictionary, key: &[u8]) -> Option<String> {
+    let (_, value) = document.dereference(info.get(key).ok()?).ok()?;
+    let Object::String(bytes, _) = value else {
+        return None;
+    };
+    let text = decode_text_string(bytes);
+    let text = text.trim_matches(|c: char| c.is_whitespace() || c == '\0');
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Decode a PDF text string: UTF-16BE or UTF-8 when marked with a byte
+/// order mark, else PDFDocEncoding (read as Latin-1, which it matches for
+/// every printable character news metadata uses).
+fn decode_text_string(bytes: &[u8]) -> String {
+    if let Some(utf16) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        let units: Vec<u16> = utf16
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect();
+        return String::from_utf16_lossy(&units);
+    }
+    if let Some(utf8) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return String::from_utf8_lossy(utf8).into_owned();
+    }
+    bytes.iter().map(|&byte| byte as char).collect()
+}
+
+/// Convert a PDF date (`D:YYYYMMDDHHmmSSOHH'mm'`, every part after the
+/// year optional) to RFC 3339. Dates without a time zone are read as UTC.
+fn parse_pdf_date(value: &str) -> Option<String> {
+    let value = value.trim();
+    let value = value.strip_prefix("D:").unwrap_or(value);
+    let digits: String = value.chars().take_while(char::is_ascii_digit).collect();
+    if digits.len() < 4 {
+        return None;
+    }
+    let part = |start: usize, default: u32| -> u32 {
+        digits
+            .get(start..start + 2)
+            .and_then(|part| part.parse().ok())
+            .unwrap_or(default)
+    };
+    let year = &digits[..4];
+    let (month, day) = (part(4, 1), part(6, 1));
+    let (hour, minute, second) = (part(8, 0), part(10, 0), part(12, 0));
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) || hour > 23 || minute > 59 {
+        return None;
+    }
+
+    let zone = &value[digits.len()..];
+    let offset = match zone.chars().next() {
+        Some(sign @ ('+' | '-')) => {
+            let zone_digits: String = zone[1..]
+                .chars()
+                .filter(char::is_ascii_digit)
+                .take(4)
+                .collect();
+            let hours = zone_digits.get(..2).unwrap_or("00");
+            let minutes = zone_digits.get(2..4).unwrap_or("00");
+            format!("{}{}:{}", sign, hours, minutes)
+        }
+        _ => "Z".to_string(),
+    };
+    Some(format!(
+        "{}-{:02}-{:02}T{:02}:{:02}:{:02}{}",
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second.min(59),
+        offset
+    ))
+}
+
+/// Reflow a page's extracted text into one line per paragraph: blank lines
+/// separate paragraphs, wrapped lines are joined, and words hyphenated
+/// across a line break are rejoined.
+fn page_paragraphs(page: &str) -> String {
+    let mut paragraphs = Vec::new();
+    let mut current = String::new();
+    for line in page.lines().map(str::trim) {
+        if line.is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        let hyphenated = current
+            .strip_suffix('-')
+            .is_some_and(|rest| rest.ends_with(|c: char| c.is_alphabetic()))
+            && line.starts_with(|c: char| c.is_lowercase());
+        if hyphenated {
+            current.pop();
+        } else if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(line);
+    }
+    if !current.is_empty() {
+        paragraphs.push(current);
+    }
+    paragraphs.join("\n")
+}
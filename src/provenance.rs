@@ -0,0 +1,78 @@
+//! Where a [`Post`](crate::Post)'s content came from.
+//!
+//! Every successful scrape records a [`Provenance`] on
+//! [`Post::provenance`](crate::Post::provenance): which pipeline stage
+//! produced the content (plain fetch, Chrome / Playwright render, the host
+//! content fallback, an archive.org snapshot, or the X API), the final URL
+//! after redirects, the page's `<link rel="canonical">`, the HTTP status,
+//! the archive snapshot (when one was used), the LLM that converted it, and
+//! RFC 3339 UTC timestamps for the fetch and the conversion.
+//!
+//! Content served from an archive snapshot may be stale; check
+//! [`Provenance::is_archived`] (and `snapshot_timestamp`) before publishing
+//! it as current.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::FallbackStage;
+
+/// Origin metadata for a scraped [`Post`](crate::Post).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// The pipeline stage whose output became the post's content.
+    pub source: FallbackStage,
+    /// URL the content was read from, after redirects (the snapshot URL
+    /// for archive.org content).
+    pub final_url: String,
+    /// The page's `<link rel="canonical">`, resolved against `final_url`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_url: Option<String>,
+    /// HTTP status of the response the content was read from (`None` for
+    /// browser renders and host fallbacks, which do not expose it).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_status: Option<u16>,
+    /// archive.org snapshot URL, when `source` is
+    /// [`FallbackStage::Archive`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_url: Option<String>,
+    /// archive.org snapshot timestamp (`yyyyMMddhhmmss`), when `source` is
+    /// [`FallbackStage::Archive`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_timestamp: Option<String>,
+    /// LLM provider that converted the content to Markdown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub llm_provider: Option<String>,
+    /// LLM model that converted the content to Markdown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub llm_model: Option<String>,
+    /// When the content was fetched (RFC 3339, UTC).
+    pub fetched_at: String,
+    /// When the Markdown conversion finished (RFC 3339, UTC).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub converted_at: Option<String>,
+}
+
+impl Provenance {
+    /// Provenance for content produced by `source` from `final_url`,
+    /// stamped with the current time.
+    pub(crate) fn new(source: FallbackStage, final_url: &str) -> Self {
+        Self {
+            source,
+            final_url: final_url.to_string(),
+            canonical_url: None,
+            http_status: None,
+            snapshot_url: None,
+            snapshot_timestamp: None,
+            llm_provider: None,
+            llm_model: None,
+            fetched_at: crate::util::rfc3339_now(),
+            converted_at: None,
+        }
+    }
+
+    /// Whether the content came from an archive.org snapshot (and may
+    /// therefore be stale).
+    pub fn is_archived(&self) -> bool {
+        self.source == FallbackStage::Archive
+    }
+}
@@ -0,0 +1,585 @@
+//! Reddit submissions through the `.json` endpoints.
+//!
+//! Reddit pages are a heavy JavaScript application and often answer the
+//! plain fetch with a bot wall, but every public submission is also served
+//! as JSON: `/comments/:id.json` returns the submission and its comment
+//! tree as two listings. The built-in [`RedditHandler`] scrapes submission
+//! URLs (`reddit.com/r/:sub/comments/:id/…`, `redd.it/:id`) from that
+//! representation:
+//!
+//! - **Self posts** (and image, gallery, and video posts) are converted
+//!   from their `selftext` and media.
+//! - **Link posts** are followed to the linked article, the way link-only
+//!   tweets are ([`crate::x::x_post_is_link_only`]).
+//!
+//! Either way [`Post::metadata`] describes the submission (title, author,
+//! subreddit, and `created_utc`), and with
+//! [`RedditHandler::top_comments`] the highest-scored top-level comments
+//! are appended as a Markdown section.
+
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::FallbackStage;
+use crate::events::{emit_event, ScrapeEvent};
+use crate::handler::{HandlerFuture, ScrapeContext, SourceHandler};
+use crate::html::{push_escaped_attribute, push_escaped_text};
+use crate::util::{format_rfc3339_utc, summarize_body, text};
+use crate::web::{convert_scraped_post, fetch_api_page, scrape_linked_article};
+use crate::{Post, PostMetadata, Provenance, ScrapeError, Scraper};
+
+/// Where submissions are read from unless
+/// [`RedditHandler::base_url`] says otherwise.
+pub const REDDIT_BASE_URL: &str = "https://www.reddit.com";
+
+/// The heading of the comments appendix.
+const COMMENTS_HEADING: &str = "## Top comments";
+
+/// The built-in [`SourceHandler`] for Reddit submission URLs.
+///
+/// Appends no comments by default; see [`RedditHandler::top_comments`].
+#[derive(Debug, Clone)]
+pub struct RedditHandler {
+    base_url: String,
+    top_comments: usize,
+}
+
+impl Default for RedditHandler {
+    fn default() -> Self {
+        Self {
+            base_url: REDDIT_BASE_URL.to_string(),
+            top_comments: 0,
+        }
+    }
+}
+
+impl RedditHandler {
+    /// Read submissions from `base_url` instead of [`REDDIT_BASE_URL`].
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Append the `count` highest-scored top-level comments (with their
+    /// author and score) to each post; `0` (the default) appends none.
+    /// Stickied and deleted comments are skipped.
+    pub fn top_comments(mut self, count: usize) -> Self {
+        self.top_comments = count;
+        self
+    }
+
+    /// Fetch the submission at `url` and its top-level comments.
+    ///
+    /// Fails with [`ScrapeError::Network`] / [`ScrapeError::HttpStatus`]
+    /// when Reddit cannot be reached or rejects the request (removed
+    /// submission, private subreddit), [`ScrapeError::BotWall`] when it
+    /// answers with an HTML block page instead of JSON, or
+    /// [`ScrapeError::Other`] when `url` is not a submission URL.
+    pub async fn fetch_thread(
+        &self,
+        scraper: &Scraper,
+        url: &str,
+    ) -> Result<RedditThread, ScrapeError> {
+        let id = reddit_post_id(url)
+            .ok_or_else(|| ScrapeError::other(format!("Not a Reddit submission URL: {}", url)))?;
+        let json_url = format!(
+            "{}/comments/{}.json?raw_json=1&sort=top&depth=1&limit={}",
+            self.base_url,
+            id,
+            self.top_comments.max(1)
+        );
+        let page = fetch_api_page(scraper, &json_url, "application/json")
+            .await
+            .map_err(ScrapeError::network)?;
+        if let Err(error) = page.json_body() {
+            return Err(if !page.is_json() {
+                ScrapeError::bot_wall(format!(
+                    "Reddit answered {} with HTTP {} instead of JSON: {}",
+                    json_url,
+                    page.status,
+                    summarize_body(&page.body, 200)
+                ))
+            } else if page.status.is_success() {
+                ScrapeError::other(error)
+            } else {
+                ScrapeError::http_status(
+                    page.status.as_u16(),
+                    format!(
+                        "Reddit returned HTTP {}: {}",
+                        page.status,
+                        reddit_error(&page.body)
+                    ),
+                )
+            });
+        }
+        parse_reddit_thread(&page.body).map_err(ScrapeError::other)
+    }
+}
+
+impl SourceHandler for RedditHandler {
+    fn name(&self) -> &str {
+        "reddit"
+    }
+
+    fn matches(&self, url: &str) -> bool {
+        reddit_post_id(url).is_some()
+    }
+
+    fn scrape<'a>(&'a self, url: &'a str, ctx: &'a ScrapeContext<'a>) -> HandlerFuture<'a> {
+        Box::pin(scrape_reddit_url(self, ctx.scraper(), url, ctx.language()))
+    }
+
+    fn cache_fingerprint(&self) -> String {
+        format!("{} {}", self.base_url, self.top_comments)
+    }
+}
+
+/// An image in a submission: the linked image, a gallery item, or a video
+/// thumbnail.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedditImage {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub caption: Option<String>,
+}
+
+/// A Reddit submission (a `t3` thing).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedditSubmission {
+    /// The base-36 submission id (`1g4x2ab`).
+    pub id: String,
+    pub title: String,
+    /// The author's username, without `u/` (`[deleted]` when deleted).
+    pub author: String,
+    /// The subreddit name, without `r/`.
+    pub subreddit: String,
+    /// The submission's full `reddit.com` URL.
+    pub permalink: String,
+    /// The link target; the permalink for self posts.
+    pub url: String,
+    /// Seconds since the Unix epoch.
+    pub created_utc: u64,
+    pub score: i64,
+    pub num_comments: u64,
+    /// Whether this is a self (text) post.
+    pub is_self: bool,
+    /// The self-post body, as Markdown.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub selftext: String,
+    /// The self-post body, as HTML.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selftext_html: Option<String>,
+    /// Reddit's classification of the link (`self`, `link`, `image`,
+    /// `hosted:video`, `rich:video`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_hint: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub flair: Option<String>,
+    pub over_18: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<RedditImage>,
+    /// The playback URL of a Reddit-hosted video.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub video: Option<String>,
+    /// The submission this one crossposts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crosspost_parent: Option<Box<RedditSubmission>>,
+}
+
+/// A top-level comment (a `t1` thing).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedditComment {
+    pub id: String,
+    /// The author's username, without `u/`.
+    pub author: String,
+    /// The comment body, as Markdown.
+    pub body: String,
+    pub score: i64,
+    /// Seconds since the Unix epoch.
+    pub created_utc: u64,
+    /// The comment's full `reddit.com` URL.
+    pub permalink: String,
+    /// Whether moderators pinned the comment to the top.
+    pub stickied: bool,
+}
+
+/// A submission and its top-level comments.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedditThread {
+    pub submission: RedditSubmission,
+    /// Top-level comments, in the order Reddit returned them.
+    #[serde(default)]
+    pub comments: Vec<RedditComment>,
+}
+
+impl RedditSubmission {
+    /// The submission's content: the crossposted submission for
+    /// crossposts, else the submission itself.
+    pub fn content_source(&self) -> &RedditSubmission {
+        self.crosspost_parent.as_deref().unwrap_or(self)
+    }
+
+    /// The external article a link post points at; `None` for self posts
+    /// and for images, galleries, and videos hosted on Reddit.
+    pub fn linked_article_url(&self) -> Option<String> {
+        let source = self.content_source();
+        if source.is_self
+            || !source.images.is_empty()
+            || source.video.is_some()
+            || matches!(
+                source.post_hint.as_deref(),
+                Some("self" | "image" | "hosted:video")
+            )
+        {
+            return None;
+        }
+        let url = Url::parse(&source.url).ok()?;
+        let host = url.host_str()?;
+        let on_reddit = ["reddit.com", "redd.it", "redditmedia.com"]
+            .iter()
+            .any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)));
+        (!on_reddit && matches!(url.scheme(), "http" | "https")).then(|| source.url.clone())
+    }
+
+    /// `created_utc` as RFC 3339 UTC.
+    pub fn created_at(&self) -> String {
+        format_rfc3339_utc(self.created_utc)
+    }
+
+    /// The submission as [`PostMetadata`]: a `DiscussionForumPosting`
+    /// with the title, author, subreddit (as the section), and creation
+    /// time.
+    pub fn metadata(&self) -> PostMetadata {
+        PostMetadata {
+            schema_type: Some("DiscussionForumPosting".to_string()),
+            headline: Some(self.title.clone()),
+            authors: vec![format!("u/{}", self.author)],
+            date_published: Some(self.created_at()),
+            publisher: Some("Reddit".to_string()),
+            section: Some(format!("r/{}", self.subreddit)),
+            keywords: self.flair.iter().cloned().collect(),
+            image_url: self
+                .content_source()
+                .images
+                .first()
+                .map(|image| image.url.clone()),
+            ..PostMetadata::default()
+        }
+    }
+
+    /// The submission body as HTML: the self text, then its images and
+    /// video.
+    fn to_html(&self) -> String {
+        let source = self.content_source();
+        let mut html = match &source.selftext_html {
+            Some(selftext_html) => selftext_html.clone(),
+            None => {
+                let mut html = String::new();
+                for paragraph in source.selftext.split("\n\n").map(str::trim) {
+                    if !paragraph.is_empty() {
+                        html.push_str("<p>");
+                        push_escaped_text(&mut html, paragraph);
+                        html.push_str("</p>");
+                    }
+                }
+                html
+            }
+        };
+        for image in &source.images {
+            html.push_str("<p><img src=\"");
+            push_escaped_attribute(&mut html, &image.url);
+            html.push_str("\" alt=\"");
+            push_escaped_attribute(&mut html, image.caption.as_deref().unwrap_or_default());
+            html.push_str("\"></p>");
+        }
+        if let Some(video) = &source.video {
+            html.push_str("<p><a href=\"");
+            push_escaped_attribute(&mut html, video);
+            html.push_str("\">[video]</a></p>");
+        }
+        html
+    }
+}
+
+impl RedditThread {
+    /// The `count` highest-scored comments worth showing: not stickied,
+    /// deleted, or removed.
+    pub fn top_comments(&self, count: usize) -> Vec<&RedditComment> {
+        let mut comments: Vec<&RedditComment> = self
+            .comments
+            .iter()
+            .filter(|comment| {
+                !comment.stickied && !matches!(comment.body.trim(), "" | "[deleted]" | "[removed]")
+            })
+            .collect();
+        comments.sort_by_key(|comment| std::cmp::Reverse(comment.score));
+        comments.truncate(count);
+        comments
+    }
+
+    /// The submission as a [`Post`] scraped from `url`.
+    fn to_post(&self, url: &str) -> Post {
+        let submission = &self.submission;
+        let metadata = submission.metadata();
+        Post {
+            title: submission.title.clone(),
+            content: submission.to_html(),
+            featured_image_url: metadata.image_url.clone().unwrap_or_default(),
+            publication_date: Some(submission.created_at()),
+            author: Some(format!("u/{}", submission.author)),
+            provenance: Some(Provenance {
+                canonical_url: Some(submission.permalink.clone()),
+                ..Provenance::new(FallbackStage::Reddit, url)
+            }),
+            metadata: Some(metadata),
+            ..Post::default()
+        }
+    }
+
+    /// The comments appendix: a Markdown section quoting the `count`
+    /// [`RedditThread::top_comments`] with their author and score; empty
+    /// when there are none.
+    fn comments_markdown(&self, count: usize) -> String {
+        let comments = self.top_comments(count);
+        if comments.is_empty() {
+            return String::new();
+        }
+        let mut markdown = format!("{}\n", COMMENTS_HEADING);
+        for comment in comments {
+            let points = if comment.score == 1 {
+                "point"
+            } else {
+                "points"
+            };
+            markdown.push_str(&format!(
+                "\n**u/{}** · {} {}\n\n",
+                comment.author, comment.score, points
+            ));
+            for line in comment.body.trim().lines() {
+                markdown.push_str(if line.trim().is_empty() { ">" } else { "> " });
+                markdown.push_str(line.trim_end());
+                markdown.push('\n');
+            }
+        }
+        markdown
+    }
+}
+
+/// The base-36 submission id of a Reddit submission URL
+/// (`reddit.com/r/:sub/comments/:id/…`, `reddit.com/comments/:id`,
+/// `reddit.com/user/:name/comments/:id`, `redd.it/:id`) on any Reddit
+/// host (`www`, `old`, `new`, `np`, `m`); `None` for other URLs.
+///
+/// ```
+/// use uninews::reddit_post_id;
+/// assert_eq!(
+///     reddit_post_id("https://www.reddit.com/r/rust/comments/1g4x2ab/announcing_rust/").as_deref(),
+///     Some("1g4x2ab")
+/// );
+/// assert_eq!(reddit_post_id("https://redd.it/1g4x2ab").as_deref(), Some("1g4x2ab"));
+/// assert_eq!(reddit_post_id("https://www.reddit.com/r/rust/"), None);
+/// ```
+pub fn reddit_post_id(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    let host = url.host_str()?.to_ascii_lowercase();
+    let segments: Vec<&str> = url
+        .path_segments()?
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let id = if host == "redd.it" {
+        match segments.as_slice() {
+            [id] => *id,
+            _ => return None,
+        }
+    } else if host == "reddit.com" || host.ends_with(".reddit.com") {
+        match segments.as_slice() {
+            ["r" | "user" | "u", _, "comments", id, ..] | ["comments", id, ..] => *id,
+            _ => return None,
+        }
+    } else {
+        return None;
+    };
+    let id = id.to_ascii_lowercase();
+    (!id.is_empty() && id.len() <= 16 && id.bytes().all(|byte| byte.is_ascii_alphanumeric()))
+        .then_some(id)
+}
+
+/// Parse a `/comments/:id.json` response: the submission listing and the
+/// comment listing. Fails with a message starting `Not a Reddit thread:`.
+pub fn parse_reddit_thread(json: &str) -> Result<RedditThread, String> {
+    let value: Value = serde_json::from_str(json)
+        .map_err(|error| format!("Not a Reddit thread: invalid JSON ({})", error))?;
+    let listings = value
+        .as_array()
+        .ok_or("Not a Reddit thread: expected the submission and comment listings")?;
+    let submission = listings
+        .first()
+        .and_then(|listing| children(listing).first())
+        .filter(|thing| thing["kind"] == "t3")
+        .ok_or("Not a Reddit thread: no submission")
+        .map(|thing| submission(&thing["data"]))?;
+    let comments = listings
+        .get(1)
+        .map(children)
+        .unwrap_or_default()
+        .iter()
+        .filter(|thing| thing["kind"] == "t1")
+        .map(|thing| comment(&thing["data"]))
+        .collect();
+    Ok(RedditThread {
+        submission,
+        comments,
+    })
+}
+
+fn children(listing: &Value) -> &[Value] {
+    listing["data"]["children"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+/// A submission from a `t3` thing's `data`.
+fn submission(data: &Value) -> RedditSubmission {
+    let mut images = Vec::new();
+    if let Some(items) = data["gallery_data"]["items"].as_array() {
+        for item in items {
+            let Some(media_id) = item["media_id"].as_str() else {
+                continue;
+            };
+            let media = &data["media_metadata"][media_id];
+            if let Some(url) = text(&media["s"]["u"]).or_else(|| text(&media["s"]["gif"])) {
+                images.push(RedditImage {
+                    url,
+                    caption: text(&item["caption"]),
+                });
+            }
+        }
+    }
+    let post_hint = text(&data["post_hint"]);
+    if images.is_empty() {
+        let preview = text(&data["preview"]["images"][0]["source"]["url"]);
+        let image = match post_hint.as_deref() {
+            Some("image") => text(&data["url_overridden_by_dest"])
+                .or_else(|| text(&data["url"]))
+                .or(preview),
+            Some("hosted:video") => preview,
+            _ => None,
+        };
+        images.extend(image.map(|url| RedditImage { url, caption: None }));
+    }
+
+    RedditSubmission {
+        id: text(&data["id"]).unwrap_or_default(),
+        title: text(&data["title"]).unwrap_or_default(),
+        author: text(&data["author"]).unwrap_or_else(|| "[deleted]".to_string()),
+        subreddit: text(&data["subreddit"]).unwrap_or_default(),
+        permalink: permalink(data),
+        url: text(&data["url_overridden_by_dest"])
+            .or_else(|| text(&data["url"]))
+            .unwrap_or_default(),
+        created_utc: seconds(&data["created_utc"]),
+        score: data["score"].as_i64().unwrap_or_default(),
+        num_comments: data["num_comments"].as_u64().unwrap_or_default(),
+        is_self: data["is_self"].as_bool().unwrap_or_default(),
+        selftext: data["selftext"].as_str().unwrap_or_default().to_string(),
+        selftext_html: text(&data["selftext_html"]),
+        post_hint,
+        flair: text(&data["link_flair_text"]),
+        over_18: data["over_18"].as_bool().unwrap_or_default(),
+        images,
+        video: text(&data["media"]["reddit_video"]["fallback_url"])
+            .or_else(|| text(&data["secure_media"]["reddit_video"]["fallback_url"])),
+        crosspost_parent: data["crosspost_parent_list"]
+            .get(0)
+            .map(|parent| Box::new(submission(parent))),
+    }
+}
+
+/// A comment from a `t1` thing's `data`.
+fn comment(data: &Value) -> RedditComment {
+    RedditComment {
+        id: text(&data["id"]).unwrap_or_default(),
+        author: text(&data["author"]).unwrap_or_else(|| "[deleted]".to_string()),
+        body: data["body"].as_str().unwrap_or_default().to_string(),
+        score: data["score"].as_i64().unwrap_or_default(),
+        created_utc: seconds(&data["created_utc"]),
+        permalink: permalink(data),
+        stickied: data["stickied"].as_bool().unwrap_or_default(),
+    }
+}
+
+/// The full URL of a thing's site-relative `permalink`.
+fn permalink(data: &Value) -> String {
+    match data["permalink"].as_str() {
+        Some(path) if path.starts_with('/') => format!("{}{}", REDDIT_BASE_URL, path),
+        Some(url) => url.to_string(),
+        None => String::new(),
+    }
+}
+
+/// A `created_utc` timestamp (a float in Reddit's JSON) as whole seconds.
+fn seconds(value: &Value) -> u64 {
+    value
+        .as_u64()
+        .or_else(|| {
+            value
+                .as_f64()
+                .filter(|seconds| *seconds >= 0.0)
+                .map(|seconds| seconds as u64)
+        })
+        .unwrap_or_default()
+}
+
+/// The `message` / `reason` of a Reddit error body, or the body itself.
+fn reddit_error(body: &str) -> String {
+    let value: Value = serde_json::from_str(body).unwrap_or_default();
+    match (text(&value["message"]), text(&value["reason"])) {
+        (Some(message), Some(reason)) => format!("{} ({})", message, reason),
+        (Some(message), None) => message,
+        _ => summarize_body(body, 200),
+    }
+}
+
+/// Scrape a Reddit submission: the self post converted to Markdown, or the
+/// linked article of a link post, with the submission's metadata and the
+/// comments appendix.
+async fn scrape_reddit_url(
+    handler: &RedditHandler,
+    scraper: &Scraper,
+    url: &str,
+    language: &str,
+) -> Post {
+    let fallbacks = [FallbackStage::Reddit];
+    let thread = match handler.fetch_thread(scraper, url).await {
+        Ok(thread) => thread,
+        Err(error) => return Post::from_error(error).with_fallbacks(&fallbacks),
+    };
+
+    let mut post = match thread.submission.linked_article_url() {
+        Some(article_url) => {
+            let mut post = scrape_linked_article(scraper, &article_url, language, None).await;
+            if post.error.is_empty() {
+                post.metadata = Some(thread.submission.metadata());
+            }
+            post
+        }
+        None => {
+            let post = thread.to_post(url);
+            emit_event(ScrapeEvent::ContentExtracted {
+                url: url.to_string(),
+                content_bytes: post.content.len(),
+            });
+            convert_scraped_post(scraper, post, language, &fallbacks).await
+        }
+    };
+
+    if post.error.is_empty() {
+        let comments = thread.comments_markdown(handler.top_comments);
+        if !comments.is_empty() {
+            post.content = format!("{}\n\n{}", post.content.trim_end(), comments);
+        }
+    }
+    post
+}
@@ -0,0 +1,392 @@
+//! Per-domain extraction rules.
+//!
+//! The generic extractor (see the `html` module) handles most pages, but
+//! some sites need specific selectors: the body container, elements to
+//! strip, a byline, a date. A [`SiteRule`] maps host patterns to content
+//! selectors, removal selectors, and metadata selectors; an
+//! [`ExtractionRules`] registry holds them and picks the rule for a URL.
+//! The HTML parser consults the matching rule before its generic
+//! heuristics:
+//!
+//! 1. **`remove`** — every matching element is deleted from the document
+//!    before anything else is extracted.
+//! 2. **`content`** — tried in order; the first selector that matches
+//!    anything supplies the content (all of its matches, outermost only,
+//!    in document order). When none matches, or the matches are empty, the
+//!    generic extractor runs as usual.
+//! 3. **`title`**, **`author`**, **`date`**, **`image`** — win over the
+//!    `<title>` / `<meta>` tags and JSON-LD when they match.
+//!
+//! Rules are JSON:
+//!
+//! ```json
+//! {
+//!   "rules": [
+//!     {
+//!       "hosts": ["example.com"],
+//!       "content": ["div.story-body", "main"],
+//!       "remove": [".newsletter-promo", ".related-links"],
+//!       "title": "h1.headline",
+//!       "author": ".byline a",
+//!       "date": "time[datetime]",
+//!       "image": "figure.lead img"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! [`ExtractionRules::from_env`] (what [`crate::ScrapeOptions::from_env`]
+//! uses) starts from the bundled default ruleset
+//! ([`ExtractionRules::bundled`]) and layers the file named by
+//! [`UNINEWS_RULES_FILE_ENV`] on top; rules can also be added
+//! programmatically with [`ExtractionRules::add_rule`].
+
+use std::env;
+use std::fmt;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use scraper::Selector;
+use serde::{Deserialize, Serialize};
+
+/// Environment variable naming a JSON rules file layered on top of the
+/// bundled ruleset by [`ExtractionRules::from_env`].
+///
+/// # Examples
+///
+/// ```
+/// use uninews::UNINEWS_RULES_FILE_ENV;
+/// assert_eq!(UNINEWS_RULES_FILE_ENV, "UNINEWS_RULES_FILE");
+/// ```
+pub const UNINEWS_RULES_FILE_ENV: &str = "UNINEWS_RULES_FILE";
+
+/// The bundled default ruleset, in the rules-file format.
+const BUNDLED_RULES: &str = include_str!("default_rules.json");
+
+/// Extraction rule for one site: which hosts it applies to and the CSS
+/// selectors to use there. Every selector field is optional.
+///
+/// ```
+/// use uninews::{ExtractionRules, SiteRule};
+///
+/// let mut rules = ExtractionRules::bundled();
+/// rules
+///     .add_rule(
+///         SiteRule::new(["example.com"])
+///             .content("div.story-body")
+///             .remove(".newsletter-promo")
+///             .author(".byline a")
+///             .date("time[datetime]"),
+///     )
+///     .unwrap();
+/// assert!(rules.rule_for_url("https://www.example.com/a").is_some());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SiteRule {
+    /// Host patterns. `example.com` matches that host and every subdomain
+    /// (a leading `*.` is accepted and ignored); `*` matches every host.
+    pub hosts: Vec<String>,
+    /// Content selectors, in order of preference.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub content: Vec<String>,
+    /// Selectors of elements removed before extraction.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub remove: Vec<String>,
+    /// Title element (its text).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Byline element(s): the text of every match, or a `content`
+    /// attribute.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Date element: its `datetime` or `content` attribute, else its text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    /// Featured image element: its `src` or `content` attribute.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+impl SiteRule {
+    /// A rule for `hosts` with no selectors yet.
+    pub fn new<I, S>(hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            hosts: hosts.into_iter().map(Into::into).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Append a content selector (tried after the ones already set).
+    pub fn content(mut self, selector: &str) -> Self {
+        self.content.push(selector.to_string());
+        self
+    }
+
+    /// Append a removal selector.
+    pub fn remove(mut self, selector: &str) -> Self {
+        self.remove.push(selector.to_string());
+        self
+    }
+
+    /// Set the title selector.
+    pub fn title(mut self, selector: &str) -> Self {
+        self.title = Some(selector.to_string());
+        self
+    }
+
+    /// Set the byline selector.
+    pub fn author(mut self, selector: &str) -> Self {
+        self.author = Some(selector.to_string());
+        self
+    }
+
+    /// Set the date selector.
+    pub fn date(mut self, selector: &str) -> Self {
+        self.date = Some(selector.to_string());
+        self
+    }
+
+    /// Set the featured-image selector.
+    pub fn image(mut self, selector: &str) -> Self {
+        self.image = Some(selector.to_string());
+        self
+    }
+}
+
+/// A [`SiteRule`] with its host patterns normalized and selectors parsed.
+#[derive(Clone)]
+pub(crate) struct CompiledRule {
+    rule: SiteRule,
+    /// Normalized host patterns; `*` is kept as-is.
+    hosts: Vec<String>,
+    pub(crate) content: Vec<Selector>,
+    pub(crate) remove: Vec<Selector>,
+    pub(crate) title: Option<Selector>,
+    pub(crate) author: Option<Selector>,
+    pub(crate) date: Option<Selector>,
+    pub(crate) image: Option<Selector>,
+}
+
+impl CompiledRule {
+    fn compile(rule: SiteRule) -> Result<Self, String> {
+        let hosts: Vec<String> = rule
+            .hosts
+            .iter()
+            .map(|host| {
+                let host = host.trim().to_ascii_lowercase();
+                let host = host.strip_prefix("*.").unwrap_or(&host);
+                host.trim_matches('.').to_string()
+            })
+            .collect();
+        if hosts.is_empty() || hosts.iter().any(String::is_empty) {
+            return Err(format!(
+                "rule needs at least one non-empty host pattern (got {:?})",
+                rule.hosts
+            ));
+        }
+        let parse = |css: &String| {
+            Selector::parse(css).map_err(|_| {
+                format!(
+                    "invalid CSS selector {:?} in the rule for {}",
+                    css,
+                    rule.hosts.join(", ")
+                )
+            })
+        };
+        let content = rule.content.iter().map(parse).collect::<Result<_, _>>()?;
+        let remove = rule.remove.iter().map(parse).collect::<Result<_, _>>()?;
+        let title = rule.title.as_ref().map(parse).transpose()?;
+        let author = rule.author.as_ref().map(parse).transpose()?;
+        let date = rule.date.as_ref().map(parse).transpose()?;
+        let image = rule.image.as_ref().map(parse).transpose()?;
+        Ok(Self {
+            hosts,
+            content,
+            remove,
+            title,
+            author,
+            date,
+            image,
+            rule,
+        })
+    }
+
+    /// How specifically this rule matches `host`: `None` when it does not,
+    /// `0` for the `*` wildcard, otherwise the length of the longest
+    /// matching pattern.
+    fn specificity(&self, host: &str) -> Option<usize> {
+        self.hosts
+            .iter()
+            .filter_map(|pattern| {
+                if pattern == "*" {
+                    Some(0)
+                } else if host == pattern
+                    || host
+                        .strip_suffix(pattern.as_str())
+                        .is_some_and(|prefix| prefix.ends_with('.'))
+                {
+                    Some(pattern.len())
+                } else {
+                    None
+                }
+            })
+            .max()
+    }
+}
+
+/// Registry of [`SiteRule`]s.
+///
+/// For a given URL the rule with the most specific matching host pattern
+/// applies (`news.example.com` beats `example.com`, which beats `*`); on a
+/// tie the rule added last wins, so rules layered on top of the bundled
+/// set override it. Cheap to share: [`crate::ScrapeOptions`] keeps it
+/// behind an `Arc`.
+#[derive(Clone, Default)]
+pub struct ExtractionRules {
+    rules: Vec<CompiledRule>,
+}
+
+/// On-disk shape of a rules file.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile<R> {
+    rules: Vec<R>,
+}
+
+impl ExtractionRules {
+    /// An empty registry: only the generic heuristics apply.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The default ruleset bundled with the crate.
+    pub fn bundled() -> Self {
+        Self::from_json(BUNDLED_RULES).expect("bundled extraction rules must be valid")
+    }
+
+    /// The bundled ruleset, plus the rules file named by
+    /// [`UNINEWS_RULES_FILE_ENV`] when set. A file that cannot be read or
+    /// parsed is reported on stderr and ignored.
+    pub fn from_env() -> Self {
+        let mut rules = Self::bundled();
+        let Ok(path) = env::var(UNINEWS_RULES_FILE_ENV) else {
+            return rules;
+        };
+        let path = path.trim();
+        if path.is_empty() {
+            return rules;
+        }
+        match Self::from_file(path) {
+            Ok(file_rules) => rules.extend(file_rules),
+            Err(error) => eprintln!(
+                "uninews: invalid {}={:?} ({}); using the bundled rules only",
+                UNINEWS_RULES_FILE_ENV, path, error
+            ),
+        }
+        rules
+    }
+
+    /// Parse a rules file body (`{"rules": [...]}`). Unknown fields and
+    /// invalid selectors are errors.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let file: RulesFile<SiteRule> =
+            serde_json::from_str(json).map_err(|err| format!("invalid rules JSON: {}", err))?;
+        let mut rules = Self::new();
+        for (index, rule) in file.rules.into_iter().enumerate() {
+            rules
+                .add_rule(rule)
+                .map_err(|err| format!("rule {}: {}", index, err))?;
+        }
+        Ok(rules)
+    }
+
+    /// Read and parse a rules file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .map_err(|err| format!("cannot read {}: {}", path.display(), err))?;
+        Self::from_json(&json)
+    }
+
+    /// Add `rule`, taking precedence over existing rules with equally
+    /// specific host patterns. Fails when the rule has no host pattern or a
+    /// selector does not parse.
+    pub fn add_rule(&mut self, rule: SiteRule) -> Result<(), String> {
+        self.rules.push(CompiledRule::compile(rule)?);
+        Ok(())
+    }
+
+    /// Append every rule of `other`, which take precedence on ties.
+    pub fn extend(&mut self, other: ExtractionRules) {
+        self.rules.extend(other.rules);
+    }
+
+    /// The rules, in precedence order (later wins on ties).
+    pub fn rules(&self) -> impl Iterator<Item = &SiteRule> {
+        self.rules.iter().map(|compiled| &compiled.rule)
+    }
+
+    /// Number of rules.
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Whether the registry has no rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// The rule that applies to `url`, if any.
+    pub fn rule_for_url(&self, url: &str) -> Option<&SiteRule> {
+        self.compiled_for_url(url).map(|compiled| &compiled.rule)
+    }
+
+    /// The registry in the rules-file format.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(&RulesFile {
+            rules: self.rules().collect(),
+        })
+        .expect("extraction rules always serialize")
+    }
+
+    pub(crate) fn compiled_for_url(&self, url: &str) -> Option<&CompiledRule> {
+        let parsed = reqwest::Url::parse(url).ok()?;
+        let host = parsed
+            .host_str()?
+            .trim_end_matches('.')
+            .to_ascii_lowercase();
+        let mut best: Option<(usize, &CompiledRule)> = None;
+        for compiled in &self.rules {
+            if let Some(specificity) = compiled.specificity(&host) {
+                if best.is_none_or(|(best_specificity, _)| specificity >= best_specificity) {
+                    best = Some((specificity, compiled));
+                }
+            }
+        }
+        best.map(|(_, compiled)| compiled)
+    }
+}
+
+impl PartialEq for ExtractionRules {
+    fn eq(&self, other: &Self) -> bool {
+        self.rules().eq(other.rules())
+    }
+}
+
+impl fmt::Debug for ExtractionRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.rules()).finish()
+    }
+}
+
+/// The bundled ruleset, parsed once; used by
+/// [`crate::html::parse_scraped_post_from_html`].
+pub(crate) fn bundled_rules() -> &'static ExtractionRules {
+    static BUNDLED: OnceLock<ExtractionRules> = OnceLock::new();
+    BUNDLED.get_or_init(ExtractionRules::bundled)
+}
@@ -0,0 +1,698 @@
+//! Per-instance scraper configuration.
+//!
+//! Every knob uninews used to read from process-wide environment variables
+//! at call time — LLM provider, model, API key, context window, the
+//! Playwright / archive.org / host-fallback toggles, and the HTTP and
+//! render timeouts — lives in a [`ScrapeOptions`] value instead. A
+//! [`Scraper`] is built from one set of options and carries them (plus its
+//! own HTTP clients) through the whole pipeline, so a multi-tenant service
+//! can scrape one URL with Claude and no Playwright while another scrape
+//! on the same process uses OpenAI and the archive.org fallback.
+//!
+//! Environment variables only **seed** the defaults:
+//! [`ScrapeOptions::from_env`] (also the [`Default`]) reads them once, and
+//! the builder-style setters override individual fields afterwards.
+//! [`crate::universal_scrape`] is a thin wrapper over a [`Scraper`] built
+//! from `ScrapeOptions::from_env()`, so existing env-driven deployments
+//! behave exactly as before.
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//! use uninews::{ScrapeOptions, Scraper};
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let claude = Scraper::new(
+//!     ScrapeOptions::from_env()
+//!         .llm_client("claude")
+//!         .llm_api_key("sk-ant-...")
+//!         .playwright(false),
+//! );
+//! let openai = Scraper::new(
+//!     ScrapeOptions::from_env()
+//!         .llm_client("openai")
+//!         .llm_model("gpt-5.6-luna")
+//!         .archive_fallback(true)
+//!         .request_timeout(Duration::from_secs(20)),
+//! );
+//!
+//! let (a, b) = tokio::join!(
+//!     claude.scrape("https://example.com/a", "english"),
+//!     openai.scrape("https://example.com/b", "spanish"),
+//! );
+//! # let _ = (a, b);
+//! # }
+//! ```
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::Client;
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::alternate::alternate_fallback_enabled;
+use crate::archive::archive_fallback_enabled;
+use crate::browser::{playwright_enabled, playwright_timeout};
+use crate::canonical::{cache_key_url, is_archive_snapshot, is_short_link, normalize_url};
+use crate::cms::cms_api_enabled;
+use crate::events::{emit_event, ScrapeEvent};
+use crate::fallback::content_fallback_first;
+use crate::handler::ScrapeContext;
+use crate::http::{
+    api_client, build_api_client, build_web_client, web_client, CONNECT_TIMEOUT, REQUEST_TIMEOUT,
+};
+use crate::llm::{uninews_llm_client_name, uninews_llm_context_window, uninews_llm_model_override};
+use crate::markdown::conversion_mode_from_env;
+use crate::pagination::DEFAULT_MAX_ARTICLE_PAGES;
+use crate::{
+    web, ConversionMode, ExtractionRules, Post, ScrapeCache, SourceHandler, SourceHandlers,
+};
+
+/// Configuration for a [`Scraper`].
+///
+/// Built with [`ScrapeOptions::from_env`] (environment-seeded defaults)
+/// and refined with the consuming, builder-style setters below. Cheap to
+/// clone.
+///
+/// The `Debug` output never includes the API key.
+#[derive(Clone)]
+pub struct ScrapeOptions {
+    llm_client: String,
+    llm_model: Option<String>,
+    llm_api_key: Option<String>,
+    context_window: usize,
+    conversion: ConversionMode,
+    playwright: bool,
+    playwright_timeout: Duration,
+    archive_fallback: bool,
+    content_fallback_first: bool,
+    cms_api: bool,
+    alternate_fallback: bool,
+    connect_timeout: Duration,
+    request_timeout: Duration,
+    concurrency: usize,
+    per_host_concurrency: usize,
+    per_host_delay: Duration,
+    max_concurrent_renders: usize,
+    max_article_pages: usize,
+    cache: Option<ScrapeCache>,
+    rules: Arc<ExtractionRules>,
+    handlers: SourceHandlers,
+}
+
+/// Default global concurrency for [`Scraper::scrape_many`].
+pub const DEFAULT_BATCH_CONCURRENCY: usize = 8;
+
+/// Default number of simultaneous scrapes per host in
+/// [`Scraper::scrape_many`].
+pub const DEFAULT_PER_HOST_CONCURRENCY: usize = 2;
+
+/// Default minimum delay between scrape starts on the same host in
+/// [`Scraper::scrape_many`].
+pub const DEFAULT_PER_HOST_DELAY: Duration = Duration::from_millis(500);
+
+/// Default cap on simultaneous headless-browser renders per [`Scraper`].
+pub const DEFAULT_MAX_CONCURRENT_RENDERS: usize = 2;
+
+impl ScrapeOptions {
+    /// Options seeded from the environment, read once at call time:
+    ///
+    /// | Field | Environment variable |
+    /// |---|---|
+    /// | LLM provider | `UNINEWS_LLM_CLIENT` (default `openai`) |
+    /// | LLM model | `UNINEWS_LLM_MODEL` (default: per-provider) |
+    /// | Context window | `UNINEWS_LLM_CONTEXT_WINDOW` (default 256,000) |
+    /// | Conversion mode | `UNINEWS_CONVERSION_MODE` (default `llm`) |
+    /// | Playwright fallback | `UNINEWS_PLAYWRIGHT` (default on) |
+    /// | Playwright timeout | `UNINEWS_PLAYWRIGHT_TIMEOUT_MS` (default 45,000) |
+    /// | archive.org fallback | `UNINEWS_ARCHIVE_FALLBACK` (default on) |
+    /// | Host fallback first | `UNINEWS_CONTENT_FALLBACK_FIRST` (default off) |
+    /// | CMS API shortcut | `UNINEWS_CMS_API` (default on) |
+    /// | AMP / print alternates | `UNINEWS_ALTERNATE_FALLBACK` (default on) |
+    /// | Cache | `UNINEWS_CACHE_DIR` / `UNINEWS_CACHE_TTL_SECS` (default off / 24 h) |
+    /// | Extraction rules | bundled set + `UNINEWS_RULES_FILE` |
+    /// | Source handlers | built-in set ([`SourceHandlers::builtin`]) |
+    ///
+    /// The API key is left unset: when no key is configured explicitly via
+    /// [`ScrapeOptions::llm_api_key`], the provider-specific env var
+    /// (`OPEN_AI_SECRET`, `CLAUDE_API_KEY`, …) is read when the LLM client
+    /// is built, so switching providers with [`ScrapeOptions::llm_client`]
+    /// picks up the matching key.
+    pub fn from_env() -> Self {
+        Self {
+            llm_client: uninews_llm_client_name(),
+            llm_model: uninews_llm_model_override(),
+            llm_api_key: None,
+            context_window: uninews_llm_context_window(),
+            conversion: conversion_mode_from_env(),
+            playwright: playwright_enabled(),
+            playwright_timeout: playwright_timeout(),
+            archive_fallback: archive_fallback_enabled(),
+            content_fallback_first: content_fallback_first(),
+            cms_api: cms_api_enabled(),
+            alternate_fallback: alternate_fallback_enabled(),
+            connect_timeout: CONNECT_TIMEOUT,
+            request_timeout: REQUEST_TIMEOUT,
+            concurrency: DEFAULT_BATCH_CONCURRENCY,
+            per_host_concurrency: DEFAULT_PER_HOST_CONCURRENCY,
+            per_host_delay: DEFAULT_PER_HOST_DELAY,
+            max_concurrent_renders: DEFAULT_MAX_CONCURRENT_RENDERS,
+            max_article_pages: DEFAULT_MAX_ARTICLE_PAGES,
+            cache: ScrapeCache::from_env(),
+            rules: Arc::new(ExtractionRules::from_env()),
+            handlers: SourceHandlers::builtin(),
+        }
+    }
+
+    /// Select the LLM provider (`openai`, `openrouter`, `grok`, `gemini`,
+    /// `claude`; case-insensitive). An unsupported name is reported as an
+    /// LLM configuration error when the conversion runs, exactly like an
+    /// unsupported `UNINEWS_LLM_CLIENT`.
+    pub fn llm_client(mut self, client: &str) -> Self {
+        self.llm_client = client.trim().to_ascii_lowercase();
+        self
+    }
+
+    /// Pin the model slug forwarded to the provider. When never set (and
+    /// `UNINEWS_LLM_MODEL` is unset) the provider's built-in default is
+    /// used.
+    pub fn llm_model(mut self, model: &str) -> Self {
+        let model = model.trim();
+        self.llm_model = (!model.is_empty()).then(|| model.to_string());
+        self
+    }
+
+    /// Use `api_key` for the selected provider instead of its
+    /// provider-specific environment variable. The key is never written to
+    /// logs, events, or [`Post`] fields.
+    pub fn llm_api_key(mut self, api_key: &str) -> Self {
+        self.llm_api_key = Some(api_key.to_string());
+        self
+    }
+
+    /// Context-window budget (in tokens) for the Markdown conversion.
+    /// `0` is rejected (it would make every prompt overflow) and leaves the
+    /// current value in place.
+    pub fn context_window(mut self, tokens: usize) -> Self {
+        if tokens > 0 {
+            self.context_window = tokens;
+        }
+        self
+    }
+
+    /// How content is converted to Markdown: through the LLM, with the
+    /// built-in deterministic converter (no API key needed, no
+    /// translation), or deterministically first with the LLM finishing the
+    /// smaller Markdown payload. See [`ConversionMode`].
+    pub fn conversion(mut self, mode: ConversionMode) -> Self {
+        self.conversion = mode;
+        self
+    }
+
+    /// Toggle the Playwright Chromium render used for bot walls, thin
+    /// content, and hard failures.
+    pub fn playwright(mut self, enabled: bool) -> Self {
+        self.playwright = enabled;
+        self
+    }
+
+    /// Playwright navigation / content-wait budget. A zero duration is
+    /// rejected and leaves the current value in place.
+    pub fn playwright_timeout(mut self, timeout: Duration) -> Self {
+        if !timeout.is_zero() {
+            self.playwright_timeout = timeout;
+        }
+        self
+    }
+
+    /// Toggle the archive.org Wayback Machine fallback.
+    pub fn archive_fallback(mut self, enabled: bool) -> Self {
+        self.archive_fallback = enabled;
+        self
+    }
+
+    /// Consult the host content fallback hook before the local Playwright
+    /// render for walls and hard failures (see
+    /// [`crate::UNINEWS_CONTENT_FALLBACK_FIRST_ENV`]).
+    pub fn content_fallback_first(mut self, enabled: bool) -> Self {
+        self.content_fallback_first = enabled;
+        self
+    }
+
+    /// Toggle the WordPress / Ghost REST API shortcut for CMS-backed pages
+    /// (see [`crate::detect_cms_api`]).
+    pub fn cms_api(mut self, enabled: bool) -> Self {
+        self.cms_api = enabled;
+        self
+    }
+
+    /// Toggle the AMP / print-view alternates tried before the Playwright
+    /// render for walled and thin pages (see [`crate::alternate_pages`]).
+    pub fn alternate_fallback(mut self, enabled: bool) -> Self {
+        self.alternate_fallback = enabled;
+        self
+    }
+
+    /// Maximum time to wait for the TCP+TLS handshake of every HTTP request
+    /// (default 10 s). A zero duration is rejected.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        if !timeout.is_zero() {
+            self.connect_timeout = timeout;
+        }
+        self
+    }
+
+    /// Maximum total time for a single HTTP request, body included
+    /// (default 30 s). A zero duration is rejected.
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        if !timeout.is_zero() {
+            self.request_timeout = timeout;
+        }
+        self
+    }
+
+    /// Maximum number of URLs [`Scraper::scrape_many`] scrapes at once
+    /// (default [`DEFAULT_BATCH_CONCURRENCY`]). `0` is rejected.
+    pub fn concurrency(mut self, limit: usize) -> Self {
+        if limit > 0 {
+            self.concurrency = limit;
+        }
+        self
+    }
+
+    /// Maximum number of simultaneous scrapes against one host in
+    /// [`Scraper::scrape_many`] (default [`DEFAULT_PER_HOST_CONCURRENCY`]).
+    /// `0` is rejected.
+    pub fn per_host_concurrency(mut self, limit: usize) -> Self {
+        if limit > 0 {
+            self.per_host_concurrency = limit;
+        }
+        self
+    }
+
+    /// Minimum delay between two scrape starts on the same host in
+    /// [`Scraper::scrape_many`] (default [`DEFAULT_PER_HOST_DELAY`]; zero
+    /// disables the delay).
+    pub fn per_host_delay(mut self, delay: Duration) -> Self {
+        self.per_host_delay = delay;
+        self
+    }
+
+    /// Maximum number of headless-browser renders (Playwright and the
+    /// Chrome `--dump-dom` path) a [`Scraper`] and its clones run at once
+    /// (default [`DEFAULT_MAX_CONCURRENT_RENDERS`]). Renders beyond the cap
+    /// wait for a free slot. `0` is rejected.
+    pub fn max_concurrent_renders(mut self, limit: usize) -> Self {
+        if limit > 0 {
+            self.max_concurrent_renders = limit;
+        }
+        self
+    }
+
+    /// Maximum number of pages read from one paginated article, the first
+    /// included (default [`DEFAULT_MAX_ARTICLE_PAGES`]; see
+    /// [`crate::next_page_url`]). `1` reads the first page only; `0` is
+    /// rejected.
+    pub fn max_article_pages(mut self, limit: usize) -> Self {
+        if limit > 0 {
+            self.max_article_pages = limit;
+        }
+        self
+    }
+
+    /// Cache raw responses and converted posts on disk ([`ScrapeCache`]);
+    /// `None` disables caching.
+    pub fn cache(mut self, cache: Option<ScrapeCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Per-domain extraction rules consulted before the generic content
+    /// heuristics (see [`ExtractionRules`]). Replaces the whole registry;
+    /// start from [`ExtractionRules::bundled`] to keep the default rules.
+    pub fn rules(mut self, rules: ExtractionRules) -> Self {
+        self.rules = Arc::new(rules);
+        self
+    }
+
+    /// Domain-specific scraping pipelines consulted before the generic web
+    /// pipeline (see [`SourceHandlers`]). Replaces the whole registry;
+    /// start from [`SourceHandlers::builtin`] to keep the built-in handlers.
+    pub fn handlers(mut self, handlers: SourceHandlers) -> Self {
+        self.handlers = handlers;
+        self
+    }
+
+    /// Register `handler` on top of the current source handlers; it takes
+    /// precedence over every handler registered before it.
+    pub fn handler(mut self, handler: impl SourceHandler) -> Self {
+        self.handlers.register(handler);
+        self
+    }
+
+    /// The selected LLM provider name (lowercase).
+    pub fn llm_client_name(&self) -> &str {
+        &self.llm_client
+    }
+
+    /// The pinned model slug, if any (`None` → provider default).
+    pub fn llm_model_override(&self) -> Option<&str> {
+        self.llm_model.as_deref()
+    }
+
+    /// The explicitly configured API key, if any.
+    pub(crate) fn llm_api_key_override(&self) -> Option<&str> {
+        self.llm_api_key.as_deref()
+    }
+
+    /// The context-window budget in tokens.
+    pub fn context_window_tokens(&self) -> usize {
+        self.context_window
+    }
+
+    /// The Markdown conversion mode.
+    pub fn conversion_mode(&self) -> ConversionMode {
+        self.conversion
+    }
+
+    /// Whether the Playwright render fallback is enabled.
+    pub fn playwright_enabled(&self) -> bool {
+        self.playwright
+    }
+
+    /// The Playwright navigation / content-wait budget.
+    pub fn playwright_timeout_duration(&self) -> Duration {
+        self.playwright_timeout
+    }
+
+    /// Whether the archive.org fallback is enabled.
+    pub fn archive_fallback_enabled(&self) -> bool {
+        self.archive_fallback
+    }
+
+    /// Whether the host content fallback runs before Playwright on walls.
+    pub fn content_fallback_first_enabled(&self) -> bool {
+        self.content_fallback_first
+    }
+
+    /// Whether CMS-backed pages are read from their REST API.
+    pub fn cms_api_enabled(&self) -> bool {
+        self.cms_api
+    }
+
+    /// Whether AMP / print alternates are tried before Playwright.
+    pub fn alternate_fallback_enabled(&self) -> bool {
+        self.alternate_fallback
+    }
+
+    /// The HTTP connect timeout.
+    pub fn connect_timeout_duration(&self) -> Duration {
+        self.connect_timeout
+    }
+
+    /// The HTTP request timeout.
+    pub fn request_timeout_duration(&self) -> Duration {
+        self.request_timeout
+    }
+
+    /// The global batch concurrency limit.
+    pub fn concurrency_limit(&self) -> usize {
+        self.concurrency
+    }
+
+    /// The per-host batch concurrency limit.
+    pub fn per_host_concurrency_limit(&self) -> usize {
+        self.per_host_concurrency
+    }
+
+    /// The minimum delay between scrape starts on one host.
+    pub fn per_host_delay_duration(&self) -> Duration {
+        self.per_host_delay
+    }
+
+    /// The cap on simultaneous headless-browser renders.
+    pub fn render_concurrency_limit(&self) -> usize {
+        self.max_concurrent_renders
+    }
+
+    /// The cap on pages read per paginated article.
+    pub fn article_page_limit(&self) -> usize {
+        self.max_article_pages
+    }
+
+    /// The on-disk cache, if enabled.
+    pub fn scrape_cache(&self) -> Option<&ScrapeCache> {
+        self.cache.as_ref()
+    }
+
+    /// The per-domain extraction rules.
+    pub fn extraction_rules(&self) -> &ExtractionRules {
+        &self.rules
+    }
+
+    /// The domain-specific source handlers.
+    pub fn source_handlers(&self) -> &SourceHandlers {
+        &self.handlers
+    }
+}
+
+impl Default for ScrapeOptions {
+    /// Same as [`ScrapeOptions::from_env`].
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+impl fmt::Debug for ScrapeOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScrapeOptions")
+            .field("llm_client", &self.llm_client)
+            .field("llm_model", &self.llm_model)
+            .field(
+                "llm_api_key",
+                &self.llm_api_key.as_ref().map(|_| "<redacted>"),
+            )
+            .field("context_window", &self.context_window)
+            .field("conversion", &self.conversion)
+            .field("playwright", &self.playwright)
+            .field("playwright_timeout", &self.playwright_timeout)
+            .field("archive_fallback", &self.archive_fallback)
+            .field("content_fallback_first", &self.content_fallback_first)
+            .field("cms_api", &self.cms_api)
+            .field("alternate_fallback", &self.alternate_fallback)
+            .field("connect_timeout", &self.connect_timeout)
+            .field("request_timeout", &self.request_timeout)
+            .field("concurrency", &self.concurrency)
+            .field("per_host_concurrency", &self.per_host_concurrency)
+            .field("per_host_delay", &self.per_host_delay)
+            .field("max_concurrent_renders", &self.max_concurrent_renders)
+            .field("max_article_pages", &self.max_article_pages)
+            .field("cache", &self.cache)
+            .field("rules", &self.rules.len())
+            .field("handlers", &self.handlers)
+            .finish()
+    }
+}
+
+/// A configured scraper: one [`ScrapeOptions`] plus the HTTP clients that
+/// honor its timeouts.
+///
+/// Cheap to clone (the `reqwest` clients are reference-counted) and safe to
+/// share across tasks. Scrapers whose timeouts match the defaults reuse
+/// the process-wide clients, so building one per request does not defeat
+/// connection pooling. Clones share one browser-render budget
+/// ([`ScrapeOptions::max_concurrent_renders`]).
+#[derive(Debug, Clone)]
+pub struct Scraper {
+    options: ScrapeOptions,
+    web_client: Client,
+    api_client: Client,
+    render_permits: Arc<Semaphore>,
+}
+
+impl Scraper {
+    /// Build a scraper from `options`.
+    ///
+    /// # Panics
+    ///
+    /// Only if `reqwest` cannot initialize its TLS backend, the same
+    /// condition under which the process-wide clients panic.
+    pub fn new(options: ScrapeOptions) -> Self {
+        let default_timeouts = options.connect_timeout == CONNECT_TIMEOUT
+            && options.request_timeout == REQUEST_TIMEOUT;
+        let (web_client, api_client) = if default_timeouts {
+            (web_client().clone(), api_client().clone())
+        } else {
+            (
+                build_web_client(options.connect_timeout, options.request_timeout)
+                    .expect("reqwest web client configuration must be valid"),
+                build_api_client(options.connect_timeout, options.request_timeout)
+                    .expect("reqwest API client configuration must be valid"),
+            )
+        };
+        let render_permits = Arc::new(Semaphore::new(options.max_concurrent_renders));
+        Self {
+            options,
+            web_client,
+            api_client,
+            render_permits,
+        }
+    }
+
+    /// A scraper configured from the environment
+    /// (`Scraper::new(ScrapeOptions::from_env())`).
+    pub fn from_env() -> Self {
+        Self::new(ScrapeOptions::from_env())
+    }
+
+    /// The options this scraper was built with.
+    pub fn options(&self) -> &ScrapeOptions {
+        &self.options
+    }
+
+    /// A scraper from `options` that draws browser renders from
+    /// `render_permits`, a budget shared with other scrapers.
+    pub(crate) fn with_render_permits(
+        options: ScrapeOptions,
+        render_permits: Arc<Semaphore>,
+    ) -> Self {
+        Self {
+            render_permits,
+            ..Self::new(options)
+        }
+    }
+
+    /// Client for article HTML fetches (HTTP/1.1 + browser UA).
+    pub(crate) fn web_client(&self) -> &Client {
+        &self.web_client
+    }
+
+    /// Client for JSON API calls.
+    pub(crate) fn api_client(&self) -> &Client {
+        &self.api_client
+    }
+
+    /// Wait for a browser-render slot; hold the permit for the duration of
+    /// the render.
+    pub(crate) async fn render_permit(&self) -> SemaphorePermit<'_> {
+        self.render_permits
+            .acquire()
+            .await
+            .expect("render semaphore is never closed")
+    }
+
+    /// Scrape `url` and convert it to Markdown in `language`.
+    ///
+    /// Same pipeline and error contract as [`crate::universal_scrape`]
+    /// (which delegates here): always returns a [`Post`], with failures
+    /// reported in [`Post::error`]. With a [`ScrapeCache`] configured, a
+    /// fresh converted post for the same URL, language, and model is
+    /// returned without running the pipeline, and successful results are
+    /// stored. Posts are keyed by their canonical form
+    /// ([`crate::normalize_url`], archive.org snapshots kept apart), so
+    /// tracking parameters and AMP variants of one article share an entry.
+    /// A miss under the request URL is retried under where its redirects
+    /// end ([`Scraper::resolve_url`]; skipped for URLs a source handler
+    /// claims, except shortener links), and a converted post is stored
+    /// under the request URL, the redirect target, and its
+    /// [`Post::canonical_url`].
+    ///
+    /// Successful posts carry their [`Post::canonical_url`].
+    pub async fn scrape(&self, url: &str, language: &str) -> Post {
+        emit_event(ScrapeEvent::ScrapeStarted {
+            url: url.to_string(),
+        });
+
+        let cache = self.options.scrape_cache();
+        let handler = self.options.source_handlers().handler_for(url);
+        // The URLs a successful post is to be stored under: those it was
+        // looked up under and missed, plus its canonical URL.
+        let mut cache_urls = Vec::new();
+        let mut cached = None;
+        if let Some(cache) = cache {
+            cached = cache.load_post(url, language, &self.options).await;
+            if cached.is_none() {
+                cache_urls.push(url.to_string());
+                if handler.is_none() || is_short_link(url) {
+                    let resolved_url = self.resolve_url(url).await;
+                    if resolved_url != cache_key_url(url) {
+                        cached = cache
+                            .load_post(&resolved_url, language, &self.options)
+                            .await;
+                        if cached.is_none() {
+                            cache_urls.push(resolved_url);
+                        }
+                    }
+                }
+            }
+        }
+
+        let post = if let Some(post) = cached {
+            post
+        } else {
+            // Delegate to the first source handler that claims the URL (the
+            // built-in X handler for X / Twitter URLs); everything else
+            // takes the generic web pipeline.
+            let mut post = match handler {
+                Some(handler) => {
+                    emit_event(ScrapeEvent::SourceHandlerSelected {
+                        url: url.to_string(),
+                        handler: handler.name().to_string(),
+                    });
+                    handler
+                        .scrape(url, &ScrapeContext::new(self, language))
+                        .await
+                }
+                None => web::scrape_web_url(self, url, language).await,
+            };
+            if post.error.is_empty() && post.canonical_url.is_none() {
+                let declared = post.provenance.as_ref().map(|provenance| {
+                    provenance
+                        .canonical_url
+                        .as_deref()
+                        .unwrap_or(&provenance.final_url)
+                });
+                post.canonical_url = Some(normalize_url(declared.unwrap_or(url)));
+            }
+            // A snapshot's canonical URL is the live article's: keep the
+            // snapshot's post away from it.
+            if !cache_urls.iter().any(|url| is_archive_snapshot(url)) {
+                cache_urls.extend(post.canonical_url.clone());
+            }
+            post
+        };
+
+        if let Some(cache) = cache.filter(|_| post.error.is_empty()) {
+            let mut keys = HashSet::new();
+            for cache_url in &cache_urls {
+                if keys.insert(cache_key_url(cache_url)) {
+                    cache
+                        .store_post(cache_url, language, &self.options, &post)
+                        .await;
+                }
+            }
+        }
+
+        if post.error.is_empty() {
+            emit_event(ScrapeEvent::ScrapeCompleted {
+                url: url.to_string(),
+            });
+        } else {
+            emit_event(ScrapeEvent::ScrapeFailed {
+                url: url.to_string(),
+                error: post.error.clone(),
+            });
+        }
+
+        post
+    }
+}
+
+impl Default for Scraper {
+    /// Same as [`Scraper::from_env`].
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
@@ -0,0 +1,436 @@
+//! XML sitemap and Google News sitemap ingestion.
+//!
+//! Many publishers list the last 48 hours of articles in a
+//! `news-sitemap.xml`, which is usually more complete than their RSS feed.
+//! [`Scraper::fetch_sitemap`] reads a sitemap with the scraper's web
+//! client and collects its article URLs into a [`Sitemap`]:
+//!
+//! - **`<urlset>`** documents contribute one [`SitemapEntry`] per `<url>`:
+//!   `loc`, `lastmod`, and the Google News `<news:news>` extension
+//!   (publication name and language, publication date, title, keywords)
+//!   as [`SitemapNews`].
+//! - **`<sitemapindex>`** documents are followed to their child sitemaps,
+//!   up to [`MAX_SITEMAP_DEPTH`] levels and [`MAX_SITEMAP_DOCUMENTS`]
+//!   documents. A child that fails to load is skipped (its
+//!   `fetch_failed` event still fires); only a failure of the root
+//!   sitemap is an error.
+//! - **Gzip'd sitemaps** (`sitemap.xml.gz`) are decompressed on the fly.
+//!
+//! With a `max_age`, entries and child sitemaps dated (news publication
+//! date, else `lastmod`) before `now - max_age` are skipped; undated ones
+//! are kept. Sitemap documents bypass the [`crate::ScrapeCache`].
+//!
+//! [`Scraper::scrape_sitemap`] then scrapes every entry through the normal
+//! pipeline as a [`Scraper::scrape_many`] batch and merges the news
+//! metadata into each [`Post`] the page itself leaves empty
+//! ([`SitemapEntry::merge_into`]):
+//!
+//! ```rust,no_run
+//! use futures::StreamExt;
+//! use std::time::Duration;
+//! use uninews::{ScrapeOptions, Scraper};
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let scraper = Scraper::new(ScrapeOptions::from_env());
+//! let two_days = Some(Duration::from_secs(48 * 3_600));
+//! match scraper
+//!     .scrape_sitemap("https://example.com/news-sitemap.xml", "english", two_days)
+//!     .await
+//! {
+//!     Ok(mut posts) => {
+//!         while let Some((entry, post)) = posts.next().await {
+//!             println!("{} -> {}", entry.loc, post.title);
+//!         }
+//!     }
+//!     Err(error) => eprintln!("no sitemap: {error}"),
+//! }
+//! # }
+//! ```
+
+use std::collections::{HashSet, VecDeque};
+use std::io::Read;
+use std::time::Duration;
+
+use flate2::read::GzDecoder;
+use futures::stream::BoxStream;
+use roxmltree::{Node, ParsingOptions};
+use serde::{Deserialize, Serialize};
+
+use crate::events::{emit_event, ScrapeEvent};
+use crate::html::resolve_href;
+use crate::util::{parse_w3c_datetime, unix_now};
+use crate::web::{fetch_page_bytes, MAX_BODY_BYTES};
+use crate::{Post, PostMetadata, ScrapeError, ScrapeOptions, Scraper};
+
+/// Deepest chain of `<sitemapindex>` documents followed from the root
+/// (root index → child index → `<urlset>`).
+pub const MAX_SITEMAP_DEPTH: usize = 3;
+
+/// Most sitemap documents (indexes included) read by one
+/// [`Scraper::fetch_sitemap`] call.
+pub const MAX_SITEMAP_DOCUMENTS: usize = 50;
+
+/// Google News sitemap namespace.
+const NEWS_NS: &str = "http://www.google.com/schemas/sitemap-news/0.9";
+
+/// The Google News `<news:news>` block of a sitemap `<url>`. Empty fields
+/// are omitted from JSON.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SitemapNews {
+    /// `<news:publication><news:name>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publication_name: Option<String>,
+    /// `<news:publication><news:language>` (ISO 639 code).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publication_language: Option<String>,
+    /// `<news:publication_date>`, as written (W3C datetime).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publication_date: Option<String>,
+    /// `<news:title>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// `<news:keywords>`, split on commas.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+}
+
+/// One `<url>` of a `<urlset>`, or one `<sitemap>` of a
+/// `<sitemapindex>`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SitemapEntry {
+    /// Absolute URL of the page (or of the child sitemap).
+    pub loc: String,
+    /// `<lastmod>`, as written (W3C datetime).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lastmod: Option<String>,
+    /// The Google News extension, when present.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub news: Option<SitemapNews>,
+}
+
+impl SitemapEntry {
+    /// Fill the gaps in a scraped `post` from the entry's news metadata:
+    /// the title and publication date when the page did not provide them,
+    /// and the keywords and publisher in [`Post::metadata`] when its
+    /// JSON-LD had none. Values extracted from the page itself always win.
+    pub fn merge_into(&self, post: &mut Post) {
+        let Some(news) = &self.news else {
+            return;
+        };
+        if post.title.trim().is_empty() {
+            if let Some(title) = &news.title {
+                post.title = title.clone();
+            }
+        }
+        if post.publication_date.is_none() {
+            post.publication_date = news.publication_date.clone();
+        }
+        if news.keywords.is_empty() && news.publication_name.is_none() {
+            return;
+        }
+        let metadata = post.metadata.get_or_insert_with(PostMetadata::default);
+        if metadata.keywords.is_empty() {
+            metadata.keywords = news.keywords.clone();
+        }
+        if metadata.publisher.is_none() {
+            metadata.publisher = news.publication_name.clone();
+        }
+    }
+
+    /// Unix seconds of the news publication date, else of `lastmod`.
+    fn timestamp(&self) -> Option<i64> {
+        self.news
+            .as_ref()
+            .and_then(|news| news.publication_date.as_deref())
+            .or(self.lastmod.as_deref())
+            .and_then(parse_w3c_datetime)
+    }
+}
+
+/// A parsed sitemap document, before any child sitemap is followed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SitemapDocument {
+    /// A `<urlset>` of pages.
+    UrlSet(Vec<SitemapEntry>),
+    /// A `<sitemapindex>` of child sitemaps.
+    Index(Vec<SitemapEntry>),
+}
+
+/// The pages collected from a sitemap and the indexes it links to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Sitemap {
+    /// The root sitemap URL (after redirects).
+    pub url: String,
+    /// Every page entry, in document order, deduplicated by `loc`.
+    #[serde(default)]
+    pub entries: Vec<SitemapEntry>,
+    /// Every sitemap document that was read, root first.
+    #[serde(default)]
+    pub sitemaps: Vec<String>,
+}
+
+/// Parse a sitemap document; gzip'd bodies are decompressed first.
+/// Relative `loc`s resolve against `sitemap_url`.
+///
+/// Fails with a `Not a sitemap: …` message for anything but a `<urlset>`
+/// or `<sitemapindex>`.
+///
+/// # Examples
+///
+/// ```
+/// use uninews::{parse_sitemap, SitemapDocument};
+///
+/// let xml = br#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+///   <url><loc>https://example.com/a</loc><lastmod>2026-10-17</lastmod></url>
+/// </urlset>"#;
+/// let SitemapDocument::UrlSet(entries) =
+///     parse_sitemap(xml, "https://example.com/sitemap.xml").unwrap()
+/// else {
+///     panic!("expected a urlset");
+/// };
+/// assert_eq!(entries[0].loc, "https://example.com/a");
+/// ```
+pub fn parse_sitemap(body: &[u8], sitemap_url: &str) -> Result<SitemapDocument, String> {
+    let text = if body.starts_with(&[0x1f, 0x8b]) {
+        let mut decompressed = Vec::new();
+        GzDecoder::new(body)
+            .take(MAX_BODY_BYTES as u64 + 1)
+            .read_to_end(&mut decompressed)
+            .map_err(|error| format!("Not a sitemap: invalid gzip data ({})", error))?;
+        if decompressed.len() > MAX_BODY_BYTES {
+            return Err(format!(
+                "Decompressed sitemap exceeded the {} MiB limit",
+                MAX_BODY_BYTES / (1024 * 1024)
+            ));
+        }
+        String::from_utf8_lossy(&decompressed).into_owned()
+    } else {
+        String::from_utf8_lossy(body).into_owned()
+    };
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+
+    let options = ParsingOptions {
+        allow_dtd: true,
+        ..ParsingOptions::default()
+    };
+    let document = roxmltree::Document::parse_with_options(text, options)
+        .map_err(|error| format!("Not a sitemap: invalid XML ({})", error))?;
+    let root = document.root_element();
+    match root.tag_name().name() {
+        "urlset" => Ok(SitemapDocument::UrlSet(entries(root, "url", sitemap_url))),
+        "sitemapindex" => Ok(SitemapDocument::Index(entries(
+            root,
+            "sitemap",
+            sitemap_url,
+        ))),
+        other => Err(format!(
+            "Not a sitemap: unexpected root element <{}>",
+            other
+        )),
+    }
+}
+
+impl Scraper {
+    /// Fetch the sitemap at `url` with this scraper's web client and
+    /// collect its page entries, following `<sitemapindex>` documents.
+    ///
+    /// With `max_age`, entries and child sitemaps dated before
+    /// `now - max_age` are skipped. Fails with [`ScrapeError::Network`] /
+    /// [`ScrapeError::HttpStatus`] when the root sitemap cannot be
+    /// fetched, or [`ScrapeError::Other`] when it is not a sitemap.
+    pub async fn fetch_sitemap(
+        &self,
+        url: &str,
+        max_age: Option<Duration>,
+    ) -> Result<Sitemap, ScrapeError> {
+        let cutoff = max_age.map(|max_age| {
+            i64::try_from(unix_now().saturating_sub(max_age.as_secs())).unwrap_or(i64::MAX)
+        });
+        let is_fresh = |entry: &SitemapEntry| match (cutoff, entry.timestamp()) {
+            (Some(cutoff), Some(timestamp)) => timestamp >= cutoff,
+            _ => true,
+        };
+
+        let mut sitemap = Sitemap {
+            url: url.to_string(),
+            entries: Vec::new(),
+            sitemaps: Vec::new(),
+        };
+        let mut seen_pages: HashSet<String> = HashSet::new();
+        let mut seen_sitemaps: HashSet<String> = HashSet::from([url.to_string()]);
+        let mut queue = VecDeque::from([(url.to_string(), 1)]);
+
+        while let Some((sitemap_url, depth)) = queue.pop_front() {
+            if sitemap.sitemaps.len() >= MAX_SITEMAP_DOCUMENTS {
+                break;
+            }
+            let is_root = sitemap.sitemaps.is_empty();
+            let (final_url, document) = match fetch_sitemap_document(self, &sitemap_url).await {
+                Ok(fetched) => fetched,
+                Err(error) if is_root => return Err(error),
+                Err(_) => continue,
+            };
+            if is_root {
+                sitemap.url = final_url.clone();
+            }
+            sitemap.sitemaps.push(final_url.clone());
+
+            match document {
+                SitemapDocument::UrlSet(entries) => {
+                    emit_event(ScrapeEvent::SitemapParsed {
+                        url: final_url,
+                        entries: entries.len(),
+                        sitemaps: 0,
+                    });
+                    for entry in entries.into_iter().filter(|entry| is_fresh(entry)) {
+                        if seen_pages.insert(entry.loc.clone()) {
+                            sitemap.entries.push(entry);
+                        }
+                    }
+                }
+                SitemapDocument::Index(children) => {
+                    emit_event(ScrapeEvent::SitemapParsed {
+                        url: final_url,
+                        entries: 0,
+                        sitemaps: children.len(),
+                    });
+                    if depth >= MAX_SITEMAP_DEPTH {
+                        continue;
+                    }
+                    for child in children.into_iter().filter(|child| is_fresh(child)) {
+                        if seen_sitemaps.insert(child.loc.clone()) {
+                            queue.push_back((child.loc, depth + 1));
+                        }
+                    }
+                }
+            }
+        }
+        Ok(sitemap)
+    }
+
+    /// Fetch the sitemap at `url` ([`Scraper::fetch_sitemap`]) and scrape
+    /// its entries ([`Scraper::scrape_sitemap_entries`]).
+    pub async fn scrape_sitemap(
+        &self,
+        url: &str,
+        language: &str,
+        max_age: Option<Duration>,
+    ) -> Result<BoxStream<'static, (SitemapEntry, Post)>, ScrapeError> {
+        let sitemap = self.fetch_sitemap(url, max_age).await?;
+        Ok(self.scrape_sitemap_entries(sitemap.entries, language))
+    }
+
+    /// Scrape each entry's `loc` as a [`Scraper::scrape_many`] batch and
+    /// stream `(entry, post)` pairs in completion order, with the entry's
+    /// news metadata merged into the post ([`SitemapEntry::merge_into`]).
+    /// Entries sharing a `loc` are scraped once, under the first of them.
+    pub fn scrape_sitemap_entries<I>(
+        &self,
+        entries: I,
+        language: &str,
+    ) -> BoxStream<'static, (SitemapEntry, Post)>
+    where
+        I: IntoIterator<Item = SitemapEntry>,
+    {
+        self.scrape_many_items(
+            entries.into_iter().map(|entry| (entry.loc.clone(), entry)),
+            language,
+            SitemapEntry::merge_into,
+        )
+    }
+}
+
+/// Fetch the sitemap at `url` and scrape its entries with a [`Scraper`]
+/// built from `options`; see [`Scraper::scrape_sitemap`].
+pub async fn scrape_sitemap(
+    url: &str,
+    language: &str,
+    max_age: Option<Duration>,
+    options: ScrapeOptions,
+) -> Result<BoxStream<'static, (SitemapEntry, Post)>, ScrapeError> {
+    Scraper::new(options)
+        .scrape_sitemap(url, language, max_age)
+        .await
+}
+
+/// GET and parse one sitemap document, returning its final URL.
+async fn fetch_sitemap_document(
+    scraper: &Scraper,
+    url: &str,
+) -> Result<(String, SitemapDocument), ScrapeError> {
+    let page = fetch_page_bytes(scraper, url)
+        .await
+        .map_err(ScrapeError::network)?;
+    if !page.status.is_success() {
+        return Err(ScrapeError::http_status(
+            page.status.as_u16(),
+            format!("Sitemap request for {} returned HTTP {}", url, page.status),
+        ));
+    }
+    let document = parse_sitemap(&page.body, &page.final_url)
+        .map_err(|error| ScrapeError::other(format!("{}: {}", url, error)))?;
+    Ok((page.final_url, document))
+}
+
+/// The `<url>` / `<sitemap>` children of `root` with a usable `loc`.
+fn entries(root: Node, name: &str, sitemap_url: &str) -> Vec<SitemapEntry> {
+    children(root, name)
+        .filter_map(|node| {
+            let loc = child_text(node, "loc").and_then(|loc| resolve_href(sitemap_url, &loc))?;
+            Some(SitemapEntry {
+                loc,
+                lastmod: child_text(node, "lastmod"),
+                news: children(node, "news")
+                    .find(|news| news.tag_name().namespace() == Some(NEWS_NS))
+                    .map(news_block),
+            })
+        })
+        .collect()
+}
+
+fn news_block(news: Node) -> SitemapNews {
+    let publication = children(news, "publication").next();
+    SitemapNews {
+        publication_name: publication.and_then(|publication| child_text(publication, "name")),
+        publication_language: publication
+            .and_then(|publication| child_text(publication, "language")),
+        publication_date: child_text(news, "publication_date"),
+        title: child_text(news, "title"),
+        keywords: child_text(news, "keywords")
+            .map(|keywords| {
+                keywords
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|keyword| !keyword.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+/// Child elements of `node` with local name `name`. Sitemaps in the wild
+/// use several namespace URIs for the same vocabulary, so only the local
+/// name is compared; extension blocks (`image:image`, `video:video`) are
+/// nested one level deeper and never match.
+fn children<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.children()
+        .filter(move |child| child.is_element() && child.tag_name().name() == name)
+}
+
+/// Trimmed text (including CDATA) of the first non-empty child named
+/// `name`.
+fn child_text(node: Node, name: &str) -> Option<String> {
+    children(node, name).find_map(|child| {
+        let text: String = child
+            .descendants()
+            .filter_map(|descendant| descendant.text().filter(|_| descendant.is_text()))
+            .collect();
+        let text = text.trim();
+        (!text.is_empty()).then(|| text.to_string())
+    })
+}
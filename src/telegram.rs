@@ -0,0 +1,806 @@
+//! Telegram public channel posts through the `t.me/s/` web preview.
+//!
+//! A `t.me/<channel>/<id>` page is only an "open in Telegram" stub, but
+//! public channels also have a server-rendered web preview at
+//! `t.me/s/<channel>` listing their posts, twenty per page (`?before=<id>`
+//! pages back). The built-in [`TelegramHandler`] reads that preview in two
+//! modes:
+//!
+//! - **Single post** — `t.me/<channel>/<id>`: the post's text, media,
+//!   forward source, and date. Link-only posts are followed to the article
+//!   they link to, the way link-only tweets are
+//!   ([`crate::x::x_post_is_link_only`]).
+//! - **Recent posts** — `t.me/<channel>` or `t.me/s/<channel>`: the last
+//!   [`TelegramHandler::recent_posts`] posts, newest first.
+//!
+//! Usernames without a web preview (users, bots, private channels) take
+//! the generic web pipeline.
+
+use reqwest::Url;
+use scraper::{ElementRef, Html, Node, Selector};
+use serde::{Deserialize, Serialize};
+
+use crate::error::FallbackStage;
+use crate::events::{emit_event, ScrapeEvent};
+use crate::handler::{HandlerFuture, ScrapeContext, SourceHandler};
+use crate::html::{push_escaped_attribute, push_escaped_text};
+use crate::util::TITLE_TEXT_CHARS;
+use crate::web::{convert_scraped_post, fetch_page, scrape_linked_article, scrape_web_url};
+use crate::{Post, PostMetadata, Provenance, ScrapeError, Scraper};
+
+/// Where channel previews are read from unless
+/// [`TelegramHandler::base_url`] says otherwise.
+pub const TELEGRAM_BASE_URL: &str = "https://t.me";
+
+/// How many posts a channel URL is scraped with by default.
+pub const DEFAULT_TELEGRAM_RECENT_POSTS: usize = 10;
+
+/// First path segments of `t.me` links that are not channels.
+const RESERVED_PATHS: &[&str] = &[
+    "joinchat",
+    "addstickers",
+    "addemoji",
+    "addtheme",
+    "addlist",
+    "setlanguage",
+    "confirmphone",
+    "share",
+    "proxy",
+    "socks",
+    "login",
+    "invoice",
+    "boost",
+    "contact",
+];
+
+/// The built-in [`SourceHandler`] for Telegram channel and post URLs.
+#[derive(Debug, Clone)]
+pub struct TelegramHandler {
+    base_url: String,
+    recent_posts: usize,
+}
+
+impl Default for TelegramHandler {
+    fn default() -> Self {
+        Self {
+            base_url: TELEGRAM_BASE_URL.to_string(),
+            recent_posts: DEFAULT_TELEGRAM_RECENT_POSTS,
+        }
+    }
+}
+
+impl TelegramHandler {
+    /// Read channel previews from `base_url` instead of
+    /// [`TELEGRAM_BASE_URL`].
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Scrape channel URLs as their last `count` posts
+    /// ([`DEFAULT_TELEGRAM_RECENT_POSTS`] by default). `0` leaves channel
+    /// URLs to the generic web pipeline; post URLs are handled either way.
+    pub fn recent_posts(mut self, count: usize) -> Self {
+        self.recent_posts = count;
+        self
+    }
+
+    /// Fetch one page of `channel`'s web preview: its newest posts, or
+    /// with `before`, the posts before that id. `Ok(None)` when the
+    /// username has no public preview (a user, a bot, or a private
+    /// channel).
+    pub async fn fetch_channel(
+        &self,
+        scraper: &Scraper,
+        channel: &str,
+        before: Option<u64>,
+    ) -> Result<Option<TelegramChannel>, ScrapeError> {
+        let url = match before {
+            Some(before) => format!("{}/s/{}?before={}", self.base_url, channel, before),
+            None => format!("{}/s/{}", self.base_url, channel),
+        };
+        let page = fetch_page(scraper, &url)
+            .await
+            .map_err(ScrapeError::network)?;
+        if !page.status.is_success() {
+            return Err(ScrapeError::http_status(
+                page.status.as_u16(),
+                format!("Telegram returned HTTP {} for {}", page.status, url),
+            ));
+        }
+        Ok(parse_telegram_channel(&page.body).ok())
+    }
+
+    /// Fetch post `id` of `channel` with the channel's details.
+    /// `Ok(None)` when the channel has no public preview; fails with
+    /// [`ScrapeError::Other`] when the channel has no such post.
+    pub async fn fetch_post(
+        &self,
+        scraper: &Scraper,
+        channel: &str,
+        id: u64,
+    ) -> Result<Option<(TelegramChannel, TelegramPost)>, ScrapeError> {
+        let Some(mut page) = self.fetch_channel(scraper, channel, Some(id + 1)).await? else {
+            return Ok(None);
+        };
+        let index = page
+            .posts
+            .iter()
+            .position(|post| post.id == id)
+            .ok_or_else(|| {
+                ScrapeError::other(format!(
+                    "Telegram channel @{} has no post {} (deleted?)",
+                    channel, id
+                ))
+            })?;
+        let post = page.posts.remove(index);
+        page.posts.clear();
+        Ok(Some((page, post)))
+    }
+
+    /// Fetch `channel`'s last [`TelegramHandler::recent_posts`] posts,
+    /// newest first, paging back through the preview as needed.
+    /// `Ok(None)` when the channel has no public preview.
+    pub async fn fetch_recent_posts(
+        &self,
+        scraper: &Scraper,
+        channel: &str,
+    ) -> Result<Option<TelegramChannel>, ScrapeError> {
+        let Some(mut collected) = self.fetch_channel(scraper, channel, None).await? else {
+            return Ok(None);
+        };
+        collected.posts.reverse();
+        while collected.posts.len() < self.recent_posts {
+            let Some(oldest) = collected.posts.last().map(|post| post.id) else {
+                break;
+            };
+            let Some(page) = self.fetch_channel(scraper, channel, Some(oldest)).await? else {
+                break;
+            };
+            let before = collected.posts.len();
+            collected
+                .posts
+                .extend(page.posts.into_iter().rev().filter(|post| post.id < oldest));
+            if collected.posts.len() == before {
+                break;
+            }
+        }
+        collected.posts.truncate(self.recent_posts);
+        Ok(Some(collected))
+    }
+}
+
+impl SourceHandler for TelegramHandler {
+    fn name(&self) -> &str {
+        "telegram"
+    }
+
+    fn matches(&self, url: &str) -> bool {
+        match telegram_target(url) {
+            Some(TelegramTarget::Post { .. }) => true,
+            Some(TelegramTarget::Channel { .. }) => self.recent_posts > 0,
+            None => false,
+        }
+    }
+
+    fn scrape<'a>(&'a self, url: &'a str, ctx: &'a ScrapeContext<'a>) -> HandlerFuture<'a> {
+        Box::pin(scrape_telegram_url(
+            self,
+            ctx.scraper(),
+            url,
+            ctx.language(),
+        ))
+    }
+
+    fn cache_fingerprint(&self) -> String {
+        format!("{} {}", self.base_url, self.recent_posts)
+    }
+}
+
+/// What a Telegram URL points at.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TelegramTarget {
+    /// One post: `t.me/<channel>/<id>` or `t.me/s/<channel>/<id>`.
+    Post { channel: String, id: u64 },
+    /// A channel: `t.me/<channel>` or `t.me/s/<channel>`.
+    Channel { channel: String },
+}
+
+/// The channel a post was forwarded from.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TelegramForward {
+    pub name: String,
+    /// The original post or channel, when public.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+/// A link preview card under a post.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TelegramLinkPreview {
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub site_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+}
+
+/// One channel post from the web preview.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TelegramPost {
+    /// The channel username.
+    pub channel: String,
+    pub id: u64,
+    /// `https://t.me/<channel>/<id>`.
+    pub url: String,
+    /// The name the post is signed with (the channel title).
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub author: String,
+    /// The message text, as plain text.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub text: String,
+    /// The message text, as HTML (links and formatting kept, emoji
+    /// images replaced by their characters).
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub html: String,
+    /// The `datetime` of the post's timestamp (ISO 8601).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forwarded_from: Option<TelegramForward>,
+    /// External links in the text, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<String>,
+    /// Photos (and video thumbnails).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub videos: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link_preview: Option<TelegramLinkPreview>,
+}
+
+/// A channel's web preview: its details and a page of posts.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TelegramChannel {
+    /// The channel username.
+    pub username: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// Posts in page order (oldest first) from a parsed page; newest first
+    /// from [`TelegramHandler::fetch_recent_posts`].
+    #[serde(default)]
+    pub posts: Vec<TelegramPost>,
+}
+
+impl TelegramPost {
+    /// Whether the post is nothing but links: an external link and no
+    /// other text or media (a post with only a link preview counts too).
+    pub fn is_link_only(&self) -> bool {
+        let has_link = !self.links.is_empty() || self.link_preview.is_some();
+        has_link
+            && self.images.is_empty()
+            && self.videos.is_empty()
+            && message_text(&self.html, true).is_empty()
+    }
+
+    /// The external article the post links to: its first external link,
+    /// else its link preview.
+    pub fn linked_article_url(&self) -> Option<String> {
+        self.links
+            .first()
+            .or(self.link_preview.as_ref().map(|preview| &preview.url))
+            .cloned()
+    }
+
+    /// The post as HTML: the forward source, the text, the media, then
+    /// the link preview.
+    fn to_html(&self) -> String {
+        let mut html = String::new();
+        if let Some(forward) = &self.forwarded_from {
+            html.push_str("<p><em>Forwarded from ");
+            match &forward.url {
+                Some(url) => {
+                    html.push_str("<a href=\"");
+                    push_escaped_attribute(&mut html, url);
+                    html.push_str("\">");
+                    push_escaped_text(&mut html, &forward.name);
+                    html.push_str("</a>");
+                }
+                None => push_escaped_text(&mut html, &forward.name),
+            }
+            html.push_str("</em></p>");
+        }
+        if !self.html.is_empty() {
+            html.push_str("<p>");
+            html.push_str(&self.html.replace("<br><br>", "</p><p>"));
+            html.push_str("</p>");
+        }
+        for image in &self.images {
+            html.push_str("<p><img src=\"");
+            push_escaped_attribute(&mut html, image);
+            html.push_str("\" alt=\"\"></p>");
+        }
+        for video in &self.videos {
+            html.push_str("<p><a href=\"");
+            push_escaped_attribute(&mut html, video);
+            html.push_str("\">Video</a></p>");
+        }
+        if let Some(preview) = &self.link_preview {
+            html.push_str("<blockquote><p><a href=\"");
+            push_escaped_attribute(&mut html, &preview.url);
+            html.push_str("\">");
+            let title = preview
+                .title
+                .as_deref()
+                .or(preview.site_name.as_deref())
+                .unwrap_or(&preview.url);
+            push_escaped_text(&mut html, title);
+            html.push_str("</a></p>");
+            if let Some(description) = &preview.description {
+                html.push_str("<p>");
+                push_escaped_text(&mut html, description);
+                html.push_str("</p>");
+            }
+            html.push_str("</blockquote>");
+        }
+        html
+    }
+}
+
+impl TelegramChannel {
+    /// The channel's display name: its title, else `@username`.
+    fn display_name(&self) -> String {
+        if self.title.is_empty() {
+            format!("@{}", self.username)
+        } else {
+            self.title.clone()
+        }
+    }
+
+    /// `post` of this channel as a [`Post`] scraped from `url`.
+    fn post_to_post(&self, post: &TelegramPost, url: &str) -> Post {
+        let name = self.display_name();
+        let text = post.text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let summary = if text.is_empty() {
+            format!("Post {}", post.id)
+        } else {
+            text.chars().take(TITLE_TEXT_CHARS).collect()
+        };
+        let image = post
+            .images
+            .first()
+            .cloned()
+            .or_else(|| post.link_preview.as_ref()?.image.clone());
+        Post {
+            title: format!("{}: {}", name, summary),
+            content: post.to_html(),
+            featured_image_url: image.clone().unwrap_or_default(),
+            publication_date: post.date.clone(),
+            author: Some(name.clone()),
+            provenance: Some(Provenance {
+                canonical_url: Some(post.url.clone()),
+                ..Provenance::new(FallbackStage::Telegram, url)
+            }),
+            metadata: Some(PostMetadata {
+                schema_type: Some("SocialMediaPosting".to_string()),
+                authors: vec![name],
+                date_published: post.date.clone(),
+                publisher: Some("Telegram".to_string()),
+                image_url: image,
+                ..PostMetadata::default()
+            }),
+            ..Post::default()
+        }
+    }
+
+    /// The channel's posts as one [`Post`] scraped from `url`: every
+    /// post's date (linking to it) and HTML, separated by `<hr>`.
+    fn to_post(&self, url: &str) -> Post {
+        let name = self.display_name();
+        let mut content = String::new();
+        if let Some(description) = &self.description {
+            content.push_str("<p>");
+            push_escaped_text(&mut content, description);
+            content.push_str("</p>");
+        }
+        for post in &self.posts {
+            if !content.is_empty() {
+                content.push_str("<hr>");
+            }
+            content.push_str("<p><em><a href=\"");
+            push_escaped_attribute(&mut content, &post.url);
+            content.push_str("\">");
+            push_escaped_text(&mut content, post.date.as_deref().unwrap_or(&post.url));
+            content.push_str("</a></em></p>");
+            content.push_str(&post.to_html());
+        }
+        let newest = self.posts.first().and_then(|post| post.date.clone());
+        Post {
+            title: name.clone(),
+            content,
+            featured_image_url: self.image.clone().unwrap_or_default(),
+            publication_date: newest.clone(),
+            author: Some(name.clone()),
+            provenance: Some(Provenance {
+                canonical_url: Some(format!("{}/s/{}", TELEGRAM_BASE_URL, self.username)),
+                ..Provenance::new(FallbackStage::Telegram, url)
+            }),
+            metadata: Some(PostMetadata {
+                schema_type: Some("CollectionPage".to_string()),
+                headline: Some(name.clone()),
+                description: self.description.clone(),
+                authors: vec![name],
+                date_modified: newest,
+                publisher: Some("Telegram".to_string()),
+                image_url: self.image.clone(),
+                ..PostMetadata::default()
+            }),
+            ..Post::default()
+        }
+    }
+}
+
+/// What a `t.me` / `telegram.me` URL points at; `None` for other URLs and
+/// for invite, sticker, share, and other non-channel links.
+///
+/// ```
+/// use uninews::{telegram_target, TelegramTarget};
+/// assert_eq!(
+///     telegram_target("https://t.me/durov/142"),
+///     Some(TelegramTarget::Post { channel: "durov".to_string(), id: 142 })
+/// );
+/// assert_eq!(
+///     telegram_target("https://t.me/s/durov"),
+///     Some(TelegramTarget::Channel { channel: "durov".to_string() })
+/// );
+/// assert_eq!(telegram_target("https://t.me/joinchat/AbCdEf"), None);
+/// ```
+pub fn telegram_target(url: &str) -> Option<TelegramTarget> {
+    let url = Url::parse(url).ok()?;
+    let host = url.host_str()?.to_ascii_lowercase();
+    if !matches!(
+        host.as_str(),
+        "t.me" | "www.t.me" | "telegram.me" | "www.telegram.me"
+    ) {
+        return None;
+    }
+    let segments: Vec<&str> = url
+        .path_segments()?
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let segments = match segments.as_slice() {
+        ["s", rest @ ..] => rest,
+        rest => rest,
+    };
+    let (channel, id) = match segments {
+        [channel] => (*channel, None),
+        [channel, id] => (*channel, Some(id.parse().ok()?)),
+        _ => return None,
+    };
+    let valid = (4..=32).contains(&channel.len())
+        && channel.starts_with(|c: char| c.is_ascii_alphabetic())
+        && channel
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
+        && !RESERVED_PATHS.contains(&channel.to_ascii_lowercase().as_str());
+    if !valid {
+        return None;
+    }
+    let channel = channel.to_string();
+    Some(match id {
+        Some(id) => TelegramTarget::Post { channel, id },
+        None => TelegramTarget::Channel { channel },
+    })
+}
+
+/// Parse a `t.me/s/<channel>` web preview page: the channel details and
+/// its posts, oldest first. Fails with a message starting `Not a Telegram
+/// channel preview:` for pages without channel details or posts (the
+/// plain `t.me/<username>` page of a user, bot, or private channel).
+pub fn parse_telegram_channel(html: &str) -> Result<TelegramChannel, String> {
+    let document = Html::parse_document(html);
+    let root = document.root_element();
+    let posts: Vec<TelegramPost> = root
+        .select(&selector(".tgme_widget_message[data-post]"))
+        .filter_map(parse_post)
+        .collect();
+    let info = root.select(&selector(".tgme_channel_info")).next();
+    if info.is_none() && posts.is_empty() {
+        return Err("Not a Telegram channel preview: no channel info or posts".to_string());
+    }
+
+    let text_of = |css: &str| {
+        root.select(&selector(css))
+            .next()
+            .map(|element| collapse(&element.text().collect::<String>()))
+            .filter(|text| !text.is_empty())
+    };
+    let username = text_of(".tgme_channel_info_header_username")
+        .map(|username| username.trim_start_matches('@').to_string())
+        .or_else(|| posts.first().map(|post| post.channel.clone()))
+        .unwrap_or_default();
+    Ok(TelegramChannel {
+        username,
+        title: text_of(".tgme_channel_info_header_title").unwrap_or_default(),
+        description: text_of(".tgme_channel_info_description"),
+        image: root
+            .select(&selector(
+                ".tgme_channel_info .tgme_page_photo_image img[src]",
+            ))
+            .next()
+            .and_then(|image| image.value().attr("src"))
+            .map(absolute),
+        posts,
+    })
+}
+
+/// A post from its `.tgme_widget_message` element.
+fn parse_post(message: ElementRef<'_>) -> Option<TelegramPost> {
+    let (channel, id) = message.value().attr("data-post")?.split_once('/')?;
+    let id: u64 = id.parse().ok()?;
+    let first = |css: &str| message.select(&selector(css)).next();
+    let text_element = first(".tgme_widget_message_text.js-message_text")
+        .or_else(|| first(".tgme_widget_message_text:not(.js-message_reply_text)"));
+
+    let mut html = String::new();
+    let mut links = Vec::new();
+    if let Some(text_element) = text_element {
+        // Hashtag links are relative to the channel preview.
+        let base = Url::parse(&format!("{}/s/{}", TELEGRAM_BASE_URL, channel)).ok()?;
+        push_message_html(&mut html, text_element, &base);
+        for anchor in text_element.select(&selector("a[href]")) {
+            if let Some(link) = external_link(anchor) {
+                if !links.contains(&link) {
+                    links.push(link);
+                }
+            }
+        }
+    }
+
+    let forwarded_from =
+        first(".tgme_widget_message_forwarded_from_name").map(|name| TelegramForward {
+            name: collapse(&name.text().collect::<String>()),
+            url: name.value().attr("href").map(absolute),
+        });
+    let mut images: Vec<String> = message
+        .select(&selector(
+            ".tgme_widget_message_photo_wrap, .tgme_widget_message_video_thumb",
+        ))
+        .filter_map(background_image)
+        .collect();
+    images.dedup();
+    let videos = message
+        .select(&selector("video.tgme_widget_message_video[src]"))
+        .filter_map(|video| video.value().attr("src"))
+        .map(absolute)
+        .collect();
+    let link_preview = first("a.tgme_widget_message_link_preview[href]").map(|preview| {
+        let text_of = |css: &str| {
+            preview
+                .select(&selector(css))
+                .next()
+                .map(|element| collapse(&element.text().collect::<String>()))
+                .filter(|text| !text.is_empty())
+        };
+        TelegramLinkPreview {
+            url: preview.value().attr("href").unwrap_or_default().to_string(),
+            site_name: text_of(".link_preview_site_name"),
+            title: text_of(".link_preview_title"),
+            description: text_of(".link_preview_description"),
+            image: preview
+                .select(&selector(".link_preview_image, .link_preview_right_image"))
+                .find_map(background_image),
+        }
+    });
+
+    Some(TelegramPost {
+        channel: channel.to_string(),
+        id,
+        url: format!("{}/{}/{}", TELEGRAM_BASE_URL, channel, id),
+        author: first(".tgme_widget_message_owner_name")
+            .map(|owner| collapse(&owner.text().collect::<String>()))
+            .unwrap_or_default(),
+        text: message_text(&html, false),
+        html,
+        date: first(".tgme_widget_message_date time[datetime]")
+            .and_then(|time| time.value().attr("datetime"))
+            .map(str::to_string),
+        forwarded_from,
+        links,
+        images,
+        videos,
+        link_preview,
+    })
+}
+
+/// Append the message text element's content to `out` as clean HTML:
+/// text, line breaks, links, and inline formatting, with emoji images
+/// replaced by their characters, other markup unwrapped, and link targets
+/// resolved against `base`.
+fn push_message_html(out: &mut String, element: ElementRef<'_>, base: &Url) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => push_escaped_text(out, text),
+            Node::Element(_) => {
+                let Some(child) = ElementRef::wrap(child) else {
+                    continue;
+                };
+                let value = child.value();
+                let name = value.name();
+                let is_emoji = value.classes().any(|class| class == "emoji");
+                match name {
+                    "br" => out.push_str("<br>"),
+                    "a" => match value.attr("href") {
+                        Some(href) => {
+                            out.push_str("<a href=\"");
+                            let href = base
+                                .join(href.trim())
+                                .map(String::from)
+                                .unwrap_or_else(|_| href.to_string());
+                            push_escaped_attribute(out, &href);
+                            out.push_str("\">");
+                            push_message_html(out, child, base);
+                            out.push_str("</a>");
+                        }
+                        None => push_message_html(out, child, base),
+                    },
+                    "b" | "strong" | "i" | "em" | "u" | "s" | "del" | "code" | "pre"
+                    | "blockquote"
+                        if !is_emoji =>
+                    {
+                        out.push('<');
+                        out.push_str(name);
+                        out.push('>');
+                        push_message_html(out, child, base);
+                        out.push_str("</");
+                        out.push_str(name);
+                        out.push('>');
+                    }
+                    _ if is_emoji => {
+                        push_escaped_text(out, &child.text().collect::<String>());
+                    }
+                    _ => push_message_html(out, child, base),
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The plain text of message HTML, whitespace collapsed; without the text
+/// of external links when `without_links` is set.
+fn message_text(html: &str, without_links: bool) -> String {
+    let fragment = Html::parse_fragment(html);
+    let mut text = String::new();
+    for node in fragment.root_element().descendants() {
+        match node.value() {
+            Node::Text(chunk) => {
+                let in_link = without_links
+                    && node
+                        .ancestors()
+                        .filter_map(ElementRef::wrap)
+                        .any(|element| external_link(element).is_some());
+                if !in_link {
+                    text.push_str(chunk);
+                }
+            }
+            Node::Element(element) if element.name() == "br" => text.push('\n'),
+            _ => {}
+        }
+    }
+    if without_links {
+        collapse(&text)
+    } else {
+        text.lines()
+            .map(collapse)
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim()
+            .to_string()
+    }
+}
+
+/// The target of an `<a>` linking out of Telegram: an http(s) link that is
+/// not a mention, hashtag search, or other `t.me` link.
+fn external_link(element: ElementRef<'_>) -> Option<String> {
+    if element.value().name() != "a" {
+        return None;
+    }
+    let href = absolute(element.value().attr("href")?.trim());
+    let url = Url::parse(&href).ok()?;
+    let host = url.host_str()?.to_ascii_lowercase();
+    let internal = ["t.me", "telegram.me", "telegram.org"]
+        .iter()
+        .any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)));
+    (matches!(url.scheme(), "http" | "https") && !internal).then_some(href)
+}
+
+/// The `background-image: url(…)` of an element's `style`.
+fn background_image(element: ElementRef<'_>) -> Option<String> {
+    let style = element.value().attr("style")?;
+    let start = style.find("url(")? + "url(".len();
+    let rest = &style[start..];
+    let url = rest[..rest.find(')')?].trim().trim_matches(['\'', '"']);
+    (!url.is_empty()).then(|| absolute(url))
+}
+
+/// `url` with a protocol-relative (`//cdn…`) prefix made `https:`.
+fn absolute(url: &str) -> String {
+    match url.strip_prefix("//") {
+        Some(rest) => format!("https://{}", rest),
+        None => url.to_string(),
+    }
+}
+
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn selector(css: &str) -> Selector {
+    Selector::parse(css).expect("hard-coded CSS selector must be valid")
+}
+
+/// Scrape a Telegram URL: one post (or the article a link-only post links
+/// to), or a channel's recent posts, converted to Markdown. Usernames
+/// without a public preview take the generic web pipeline.
+async fn scrape_telegram_url(
+    handler: &TelegramHandler,
+    scraper: &Scraper,
+    url: &str,
+    language: &str,
+) -> Post {
+    let fallbacks = [FallbackStage::Telegram];
+    let post = match telegram_target(url) {
+        Some(TelegramTarget::Post { channel, id }) => {
+            match handler.fetch_post(scraper, &channel, id).await {
+                Ok(Some((channel, post))) => {
+                    if post.is_link_only() {
+                        if let Some(article_url) = post.linked_article_url() {
+                            let title_override = post
+                                .link_preview
+                                .as_ref()
+                                .filter(|preview| preview.url == article_url)
+                                .and_then(|preview| preview.title.as_deref());
+                            return scrape_linked_article(
+                                scraper,
+                                &article_url,
+                                language,
+                                title_override,
+                            )
+                            .await;
+                        }
+                    }
+                    channel.post_to_post(&post, url)
+                }
+                Ok(None) => return scrape_web_url(scraper, url, language).await,
+                Err(error) => return Post::from_error(error).with_fallbacks(&fallbacks),
+            }
+        }
+        Some(TelegramTarget::Channel { channel }) => {
+            match handler.fetch_recent_posts(scraper, &channel).await {
+                Ok(Some(channel)) => channel.to_post(url),
+                Ok(None) => return scrape_web_url(scraper, url, language).await,
+                Err(error) => return Post::from_error(error).with_fallbacks(&fallbacks),
+            }
+        }
+        None => {
+            let error = ScrapeError::other(format!("Not a Telegram URL: {}", url));
+            return Post::from_error(error).with_fallbacks(&fallbacks);
+        }
+    };
+
+    emit_event(ScrapeEvent::ContentExtracted {
+        url: url.to_string(),
+        content_bytes: post.content.len(),
+    });
+    convert_scraped_post(scraper, post, language, &fallbacks).await
+}
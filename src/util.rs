@@ -8,6 +8,8 @@
 
 use std::env;
 
+use serde_json::Value;
+
 /// Browser-like User-Agent header used for plain HTML fetches so news sites
 /// do not serve bot-wall responses to the scraper.
 pub(crate) const BROWSER_USER_AGENT: &str = "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/136.0.0.0 Safari/537.36";
@@ -41,6 +43,24 @@ pub fn summarize_body(body: &str, max_len: usize) -> String {
     format!("{}...", &trimmed[..end])
 }
 
+/// Titles built from a post's text (social handlers have no headline)
+/// quote this many characters of it.
+pub(crate) const TITLE_TEXT_CHARS: usize = 80;
+
+/// A non-empty, trimmed JSON string.
+pub(crate) fn text(value: &Value) -> Option<String> {
+    value
+        .as_str()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(str::to_string)
+}
+
+/// The elements of a JSON array, or nothing when `value` is not an array.
+pub(crate) fn array(value: &Value) -> &[Value] {
+    value.as_array().map(Vec::as_slice).unwrap_or_default()
+}
+
 /// Returns `true` when `url` points at YouTube — any `youtube.com` (or
 /// subdomain) path, or a `youtu.be` short link.
 ///
@@ -75,3 +95,128 @@ pub fn is_youtube_url(url: &str) -> bool {
         || host == "youtu.be"
         || host.ends_with(".youtu.be")
 }
+
+/// Format `unix_seconds` as an RFC 3339 UTC timestamp
+/// (`2026-10-16T09:30:00Z`).
+///
+/// Hand-rolled (Howard Hinnant's days-to-civil algorithm) to avoid a date
+/// crate dependency for one formatter. Exposed (as `pub` +
+/// `#[doc(hidden)]`) so the calendar arithmetic can be tested.
+#[doc(hidden)]
+pub fn format_rfc3339_utc(unix_seconds: u64) -> String {
+    let days = (unix_seconds / 86_400) as i64;
+    let seconds_of_day = unix_seconds % 86_400;
+
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3_600,
+        (seconds_of_day % 3_600) / 60,
+        seconds_of_day % 60
+    )
+}
+
+/// Days since 1970-01-01 for the proleptic Gregorian date
+/// `year`-`month`-`day` (the inverse of the conversion in
+/// [`format_rfc3339_utc`]).
+pub(crate) fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let shifted_month = i64::from((month + 9) % 12);
+    let day_of_year = (153 * shifted_month + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Parse a W3C datetime — the ISO 8601 profile used by sitemaps:
+/// `2026-10-17`, `2026-10-17T09:30Z`, `2026-10-17T09:30:00.5+02:00` —
+/// into Unix seconds. A date without a time is midnight UTC, and a time
+/// without a zone is read as UTC.
+pub(crate) fn parse_w3c_datetime(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let (date, time) = match value.find(['T', 't', ' ']) {
+        Some(index) => (&value[..index], Some(value[index + 1..].trim())),
+        None => (value, None),
+    };
+    let mut date_parts = date.split('-');
+    let year = date_parts.next().filter(|year| year.len() == 4)?;
+    let year: i64 = year.parse().ok()?;
+    let month: u32 = date_parts
+        .next()
+        .map_or(Some(1), |month| month.parse().ok())?;
+    let day: u32 = date_parts.next().map_or(Some(1), |day| day.parse().ok())?;
+    if date_parts.next().is_some() || !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let mut seconds = days_from_civil(year, month, day) * 86_400;
+
+    if let Some(time) = time {
+        let (clock, offset) = if let Some(clock) = time.strip_suffix(['Z', 'z']) {
+            (clock, 0)
+        } else if let Some(index) = time.rfind(['+', '-']) {
+            let zone = time[index + 1..].replace(':', "");
+            if zone.len() != 4 && zone.len() != 2 {
+                return None;
+            }
+            let hours: i64 = zone.get(..2)?.parse().ok()?;
+            let minutes: i64 = zone
+                .get(2..)
+                .filter(|m| !m.is_empty())
+                .map_or(Some(0), |m| m.parse().ok())?;
+            let sign = if time[index..].starts_with('-') {
+                -1
+            } else {
+                1
+            };
+            (&time[..index], sign * (hours * 3_600 + minutes * 60))
+        } else {
+            (time, 0)
+        };
+        let mut clock_parts = clock.split(':');
+        let hour: i64 = clock_parts.next()?.parse().ok()?;
+        let minute: i64 = clock_parts.next()?.parse().ok()?;
+        let second: f64 = clock_parts
+            .next()
+            .map_or(Some(0.0), |second| second.parse().ok())?;
+        if clock_parts.next().is_some()
+            || hour > 23
+            || minute > 59
+            || !(0.0..61.0).contains(&second)
+        {
+            return None;
+        }
+        seconds += hour * 3_600 + minute * 60 + second as i64 - offset;
+    }
+    Some(seconds)
+}
+
+/// The current time as Unix seconds.
+pub(crate) fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+/// The current time as an RFC 3339 UTC timestamp.
+pub(crate) fn rfc3339_now() -> String {
+    format_rfc3339_utc(unix_now())
+}
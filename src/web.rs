@@ -1,14 +1,16 @@
 //! Plain-web (non-X) scraping pipeline.
 //!
-//! This module fetches a URL over HTTP, parses the HTML into a [`Post`],
-//! and drives the fallback chain for difficult pages:
+//! This module fetches a URL over HTTP, parses the HTML (or a PDF, see
+//! [`crate::pdf`]) into a [`Post`], and drives the fallback chain for
+//! difficult pages:
 //!
 //! 1. Plain HTTP fetch with a browser User-Agent.
 //! 2. For X Article guest walls: headless-Chrome rendering
 //!    ([`crate::browser`]).
 //! 3. For bot-protection walls (Cloudflare & co.): Playwright Chromium
 //!    render ([`crate::browser::fetch_rendered_dom_with_playwright`]),
-//!    when enabled via `UNINEWS_PLAYWRIGHT` (default on).
+//!    when enabled in the [`crate::ScrapeOptions`] (seeded from
+//!    `UNINEWS_PLAYWRIGHT`, default on).
 //! 4. For thin content on an otherwise healthy page: the SAME Playwright
 //!    render, triggered when a successful (2xx), non-walled, non-X
 //!    response either fails content extraction, yields implausibly short
@@ -30,27 +32,45 @@
 //! 6. For failures that survive rendering: the archive.org Wayback Machine
 //!    fallback ([`crate::archive`]) — the LAST fallback, not the first.
 //! 7. LLM Markdown conversion of the extracted body ([`crate::llm`]).
+//!
+//! Before the Playwright render of steps 3 and 4, walled and thin pages
+//! that link a server-rendered AMP document (on the origin, then on the
+//! Google AMP cache) or a print / text-only view are read from it instead
+//! ([`crate::alternate`]); seeded from `UNINEWS_ALTERNATE_FALLBACK`,
+//! default on.
+//!
+//! Articles split across pages (`<link rel="next">`, `?page=2`, `/2/`)
+//! have their following pages fetched and appended to the first page's
+//! body before conversion ([`crate::pagination`]).
+//!
+//! Pages that identify a WordPress or Ghost backend skip the DOM
+//! extraction: their post is read from the CMS REST API
+//! ([`crate::cms`]), falling back to the DOM when the API cannot serve it.
 
 use std::error::Error as StdError;
 use std::fmt::Write as _;
 
-use reqwest::header::HeaderMap;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, CONTENT_TYPE};
+use reqwest::{RequestBuilder, StatusCode};
 
-use crate::archive::{archive_fallback_enabled, latest_snapshot, looks_like_bot_protection};
-use crate::browser::{
-    fetch_rendered_dom_with_chrome, fetch_rendered_dom_with_playwright, playwright_enabled,
-};
+use crate::alternate::{alternate_pages, AlternatePage};
+use crate::archive::{latest_snapshot, looks_like_bot_protection};
+use crate::browser::{fetch_rendered_dom_with_chrome, fetch_rendered_dom_with_playwright_timeout};
+use crate::cache::CachedResponse;
+use crate::charset::{decode_with, detect_charset, is_text_content, DetectedCharset};
+use crate::cms::{detect_cms_api, fetch_cms_post};
 use crate::events::{emit_event, ScrapeEvent};
-use crate::fallback::{content_fallback_first, content_fallback_hook, ContentFallback};
-use crate::html::parse_scraped_post_from_html;
-use crate::http::web_client;
-use crate::llm::convert_content_to_markdown;
-use crate::util::is_youtube_url;
+use crate::fallback::{content_fallback_hook, ContentFallback};
+use crate::html::parse_scraped_post_with_rules;
+use crate::llm::convert_with_options;
+use crate::pagination::next_page_url;
+use crate::pdf::{is_pdf, parse_pdf};
+use crate::util::{is_youtube_url, summarize_body};
 use crate::x::{
     is_x_article_url, is_x_url, x_article_body_unavailable, x_debug_dump,
     x_debug_dump_http_response,
 };
-use crate::Post;
+use crate::{FallbackStage, Post, Provenance, ScrapeError, Scraper};
 
 /// Outcome of a single raw fetch + parse attempt, with the failure
 /// classification needed to decide whether the archive.org fallback
@@ -73,17 +93,28 @@ struct RawFetch {
     /// before a body was read). Compared against [`JS_SHELL_MAX_BYTES`]
     /// by the thin-content trigger.
     body_bytes: usize,
+    /// The headless-Chrome render ran (X Article guest walls only).
+    chrome_rendered: bool,
+    /// The body was a PDF document ([`crate::pdf`]). Browser renders
+    /// cannot improve a PDF, so it is exempt from the thin-content
+    /// trigger.
+    pdf: bool,
+    /// The post was read from the page's CMS REST API ([`crate::cms`]).
+    /// The API body is authoritative, so it is exempt from the
+    /// thin-content trigger too.
+    cms_api: bool,
+    /// The raw HTML of an extracted page (empty for PDFs, CMS API posts,
+    /// and failed fetches), where walled and thin pages look for AMP and
+    /// print alternates ([`crate::alternate`]).
+    html: String,
+    /// The URL `html` was served from, after redirects.
+    html_url: String,
 }
 
-/// Build a [`Post`] carrying only an error message.
-fn error_post(error: String) -> Post {
-    Post {
-        title: String::new(),
-        content: String::new(),
-        featured_image_url: String::new(),
-        publication_date: None,
-        author: None,
-        error,
+/// Record `source` as the stage that produced `post`'s content.
+fn set_provenance_source(post: &mut Post, source: FallbackStage) {
+    if let Some(provenance) = post.provenance.as_mut() {
+        provenance.source = source;
     }
 }
 
@@ -93,7 +124,7 @@ fn error_post(error: String) -> Post {
 /// server cannot exhaust host memory before the request timeout fires;
 /// oversize bodies fail as a network failure, which keeps them eligible
 /// for the archive.org fallback like any other hard fetch failure.
-const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+pub(crate) const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
 
 /// Raw-body size under which a successful (2xx), non-walled page is
 /// treated as a JavaScript shell and given one Playwright render attempt
@@ -105,7 +136,7 @@ const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
 /// either recovers the JS-produced content or fails cleanly, in which
 /// case the original plain-fetch result is kept — the trigger can never
 /// make a scrape worse.
-const JS_SHELL_MAX_BYTES: usize = 16 * 1024;
+pub(crate) const JS_SHELL_MAX_BYTES: usize = 16 * 1024;
 
 /// Minimum extracted content (in bytes) accepted without a render retry.
 /// A real article body is never this short; sub-threshold "successful"
@@ -116,12 +147,7 @@ const JS_SHELL_MAX_BYTES: usize = 16 * 1024;
 const MIN_CONTENT_BYTES: usize = 512;
 
 /// Read a response body in bounded chunks, enforcing [`MAX_BODY_BYTES`].
-///
-/// Invalid UTF-8 is replaced with U+FFFD (browsers are equally tolerant);
-/// the previous `Response::text()` behavior differed only for pages
-/// declaring a non-UTF-8 charset, which the LLM conversion downstream
-/// handles equally well via replacement characters.
-async fn read_body_bounded(mut response: reqwest::Response) -> Result<String, String> {
+async fn read_body_bounded(mut response: reqwest::Response) -> Result<Vec<u8>, String> {
     let mut body: Vec<u8> = Vec::new();
     loop {
         match response.chunk().await {
@@ -138,20 +164,159 @@ async fn read_body_bounded(mut response: reqwest::Response) -> Result<String, St
             Err(err) => return Err(format!("Failed to read response body: {}", err)),
         }
     }
-    Ok(String::from_utf8_lossy(&body).into_owned())
+    Ok(body)
 }
 
-/// Fetch `url`, parse the HTML body into a [`Post`], and classify any
-/// failure for the archive.org fallback decision.
+/// An HTTP response with its body read, from the network or the response
+/// cache. The body is text ([`fetch_page`]) or raw bytes
+/// ([`fetch_page_bytes`]).
+pub(crate) struct FetchedPage<B = String> {
+    /// The URL after redirects.
+    pub(crate) final_url: String,
+    pub(crate) status: StatusCode,
+    pub(crate) headers: HeaderMap,
+    pub(crate) body: B,
+    /// The encoding a text body is decoded with ([`crate::charset`]);
+    /// `None` for binary bodies and cached responses (stored decoded).
+    pub(crate) charset: Option<DetectedCharset>,
+}
+
+impl FetchedPage<Vec<u8>> {
+    /// The page with its body decoded as text (see [`fetch_page`]).
+    fn into_text(self) -> FetchedPage {
+        let body = match self.charset {
+            Some(charset) => decode_with(&self.body, charset),
+            None => String::from_utf8_lossy(&self.body).into_owned(),
+        };
+        FetchedPage {
+            final_url: self.final_url,
+            status: self.status,
+            headers: self.headers,
+            body,
+            charset: self.charset,
+        }
+    }
+
+    /// The response's `Content-Type` header, when it is valid text.
+    fn content_type(&self) -> Option<&str> {
+        self.headers.get(CONTENT_TYPE)?.to_str().ok()
+    }
+}
+
+impl<B> FetchedPage<B> {
+    /// The MIME type of the response's `Content-Type` header, lowercased
+    /// and without parameters (`application/json`).
+    pub(crate) fn mime_type(&self) -> Option<String> {
+        let content_type = self.headers.get(CONTENT_TYPE)?.to_str().ok()?;
+        let mime = content_type.split(';').next()?.trim();
+        (!mime.is_empty()).then(|| mime.to_ascii_lowercase())
+    }
+
+    /// Whether the response is declared as JSON (`application/json`,
+    /// `application/activity+json`, …).
+    pub(crate) fn is_json(&self) -> bool {
+        self.mime_type().is_some_and(|mime| mime.contains("json"))
+    }
+}
+
+impl FetchedPage {
+    /// The body of a successful JSON API response, or why there is none
+    /// (the HTTP status and the start of the body, or the wrong content
+    /// type).
+    pub(crate) fn json_body(&self) -> Result<&str, String> {
+        if !self.status.is_success() {
+            return Err(format!(
+                "{} returned HTTP {}: {}",
+                self.final_url,
+                self.status,
+                summarize_body(&self.body, 200)
+            ));
+        }
+        if !self.is_json() {
+            return Err(format!("{} did not return JSON", self.final_url));
+        }
+        Ok(&self.body)
+    }
+}
+
+impl FetchedPage {
+    fn from_cached(cached: CachedResponse) -> Self {
+        let headers = cached
+            .headers
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((
+                    HeaderName::from_bytes(name.as_bytes()).ok()?,
+                    HeaderValue::from_str(value).ok()?,
+                ))
+            })
+            .collect();
+        Self {
+            final_url: cached.final_url,
+            status: StatusCode::from_u16(cached.status).unwrap_or(StatusCode::OK),
+            headers,
+            body: cached.body,
+            charset: None,
+        }
+    }
+
+    fn to_cached(&self) -> CachedResponse {
+        CachedResponse {
+            final_url: self.final_url.clone(),
+            status: self.status.as_u16(),
+            headers: self
+                .headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.as_str().to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect(),
+            body: self.body.clone(),
+        }
+    }
+}
+
+/// Send the GET for `url` and read its body as text, emitting the fetch
+/// events.
 ///
-/// For X Article URLs whose guest HTML withholds the body, a headless-Chrome
-/// render is attempted before giving up.
-async fn fetch_and_parse(url: &str, title_override: Option<&str>) -> RawFetch {
+/// The body is decoded with the encoding [`crate::detect_charset`] finds
+/// (BOM, `Content-Type`, `<meta>` declaration, then statistical
+/// detection); malformed sequences become U+FFFD, as in browsers.
+pub(crate) async fn fetch_page(scraper: &Scraper, url: &str) -> Result<FetchedPage, String> {
+    Ok(fetch_page_bytes(scraper, url).await?.into_text())
+}
+
+/// Send the GET for `url` and read its raw body, emitting the fetch
+/// events. The character encoding of text bodies is detected here, so
+/// `FetchSucceeded` can report it.
+pub(crate) async fn fetch_page_bytes(
+    scraper: &Scraper,
+    url: &str,
+) -> Result<FetchedPage<Vec<u8>>, String> {
+    send_and_read(url, scraper.web_client().get(url)).await
+}
+
+/// Send a GET for `url` with the scraper's API client, asking for
+/// `accept` (`application/json`, `application/activity+json`, …), and
+/// read its body as text, emitting the fetch events. Used by the source
+/// handlers that read a site's JSON API instead of its HTML.
+pub(crate) async fn fetch_api_page(
+    scraper: &Scraper,
+    url: &str,
+    accept: &str,
+) -> Result<FetchedPage, String> {
+    let request = scraper.api_client().get(url).header(ACCEPT, accept);
+    Ok(send_and_read(url, request).await?.into_text())
+}
+
+/// Send `request` (a GET for `url`) and read its raw body, emitting the
+/// fetch events.
+async fn send_and_read(url: &str, request: RequestBuilder) -> Result<FetchedPage<Vec<u8>>, String> {
     emit_event(ScrapeEvent::FetchStarted {
         url: url.to_string(),
     });
 
-    let response = match web_client().get(url).send().await {
+    let response = match request.send().await {
         Ok(response) => response,
         Err(err) => {
             // Walk the full error source chain so DNS/TLS/proxy causes are
@@ -166,66 +331,307 @@ async fn fetch_and_parse(url: &str, title_override: Option<&str>) -> RawFetch {
                 url: url.to_string(),
                 error: msg.clone(),
             });
-            return RawFetch {
-                post: error_post(msg),
-                network_failure: true,
-                server_error: false,
-                bot_protected: false,
-                status_success: false,
-                body_bytes: 0,
-            };
+            return Err(msg);
         }
     };
-    let response_url = response.url().to_string();
-    let is_x_article = is_x_article_url(&response_url) || is_x_article_url(url);
-    let response_status = response.status();
-    let response_headers = response.headers().clone();
-    let body_text = match read_body_bounded(response).await {
-        Ok(text) => text,
+    let final_url = response.url().to_string();
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = match read_body_bounded(response).await {
+        Ok(body) => body,
         Err(err) => {
             emit_event(ScrapeEvent::FetchFailed {
                 url: url.to_string(),
                 error: err.clone(),
             });
-            return RawFetch {
-                post: error_post(err),
-                network_failure: true,
-                server_error: false,
-                bot_protected: false,
-                status_success: false,
-                body_bytes: 0,
-            };
+            return Err(err);
         }
     };
 
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+    let charset = (is_text_content(content_type) && !is_pdf(content_type, &body))
+        .then(|| detect_charset(&body, content_type, Some(&final_url)));
+
     emit_event(ScrapeEvent::FetchSucceeded {
-        url: response_url.clone(),
-        status: response_status.as_u16(),
-        body_bytes: body_text.len(),
+        url: final_url.clone(),
+        status: status.as_u16(),
+        body_bytes: body.len(),
+        encoding: charset.map(|charset| charset.encoding.to_string()),
     });
+    Ok(FetchedPage {
+        final_url,
+        status,
+        headers,
+        body,
+        charset,
+    })
+}
+
+/// Extract a successfully fetched PDF into a [`Post`], off the async
+/// runtime.
+///
+/// PDFs are never bot walls and never browser-rendered, and they are not
+/// stored in the response cache, which holds text bodies; the converted
+/// post is still cached like any other. Unreadable PDFs fail as
+/// [`ScrapeError::Other`].
+async fn parse_pdf_response(page: FetchedPage<Vec<u8>>, title_override: Option<&str>) -> RawFetch {
+    let FetchedPage {
+        final_url,
+        status,
+        body,
+        ..
+    } = page;
+    let body_bytes = body.len();
+    let parsed = tokio::task::spawn_blocking(move || parse_pdf(&body))
+        .await
+        .unwrap_or_else(|error| Err(format!("Not a readable PDF: {}", error)));
+
+    let mut post = match parsed {
+        Ok(document) => {
+            emit_event(ScrapeEvent::PdfExtracted {
+                url: final_url.clone(),
+                pages: document.pages.len(),
+            });
+            document.to_post(&final_url, title_override)
+        }
+        Err(error) => Post::from_error(ScrapeError::other(error)),
+    };
+    if let Some(provenance) = post.provenance.as_mut() {
+        provenance.http_status = Some(status.as_u16());
+    }
+    if post.error.is_empty() {
+        emit_event(ScrapeEvent::ContentExtracted {
+            url: final_url,
+            content_bytes: post.content.len(),
+        });
+    } else {
+        emit_event(ScrapeEvent::ContentExtractionFailed {
+            url: final_url,
+            error: post.error.clone(),
+        });
+    }
+
+    RawFetch {
+        post,
+        network_failure: false,
+        server_error: false,
+        bot_protected: false,
+        status_success: true,
+        body_bytes,
+        chrome_rendered: false,
+        pdf: true,
+        cms_api: false,
+        html: String::new(),
+        html_url: String::new(),
+    }
+}
+
+/// Append the following pages of a paginated article to `post`, the
+/// extracted first page of `first_url` (whose HTML is `first_html`).
+///
+/// Not called for archive.org snapshots, whose links point back at the
+/// live site. Pages are found with [`next_page_url`] and fetched (reporting the usual
+/// fetch events) until the last page, the scraper's
+/// [`crate::ScrapeOptions::max_article_pages`] cap, a URL already read, or
+/// a page that fails to fetch, is walled, or yields no content. The pages
+/// read so far are kept either way.
+async fn follow_pagination(
+    scraper: &Scraper,
+    first_url: &str,
+    first_html: &str,
+    title_override: Option<&str>,
+    post: &mut Post,
+) {
+    let max_pages = scraper.options().article_page_limit();
+    let mut visited = vec![first_url.to_string()];
+    let mut pages = 1;
+    let mut next = next_page_url(first_url, first_html);
+    while let Some(page_url) = next.take() {
+        if visited.len() >= max_pages || visited.contains(&page_url) {
+            break;
+        }
+        visited.push(page_url.clone());
+        let Ok(page) = fetch_page(scraper, &page_url).await else {
+            break;
+        };
+        if !page.status.is_success()
+            || looks_like_bot_protection(page.status.as_u16(), &page.headers, &page.body)
+        {
+            break;
+        }
+        let page_post = parse_scraped_post_with_rules(
+            &page.final_url,
+            &page.body,
+            title_override,
+            scraper.options().extraction_rules(),
+        );
+        if !page_post.error.is_empty() {
+            break;
+        }
+        post.content.push('\n');
+        post.content.push_str(&page_post.content);
+        pages += 1;
+        next = next_page_url(&page.final_url, &page.body);
+    }
+    if pages > 1 {
+        emit_event(ScrapeEvent::ArticlePagesFollowed {
+            url: first_url.to_string(),
+            pages,
+        });
+    }
+}
+
+/// Read the post of a CMS-backed page from the CMS REST API
+/// ([`crate::cms`]). `None` when the page is not CMS-backed (or is an
+/// archive.org snapshot) or the API cannot serve the post, in which case
+/// the page goes through DOM extraction.
+async fn try_cms_api(
+    scraper: &Scraper,
+    page_url: &str,
+    html: &str,
+    status: StatusCode,
+    title_override: Option<&str>,
+) -> Option<Post> {
+    if page_url.starts_with("https://web.archive.org/") {
+        return None;
+    }
+    let api = detect_cms_api(page_url, html)?;
+    match fetch_cms_post(scraper, &api, page_url).await {
+        Ok((api_url, cms_post)) => {
+            emit_event(ScrapeEvent::CmsApiResolved {
+                url: page_url.to_string(),
+                cms: api.kind(),
+                api_url,
+            });
+            let mut post = cms_post.to_post(page_url, title_override);
+            if let Some(provenance) = post.provenance.as_mut() {
+                provenance.http_status = Some(status.as_u16());
+            }
+            emit_event(ScrapeEvent::ContentExtracted {
+                url: page_url.to_string(),
+                content_bytes: post.content.len(),
+            });
+            Some(post)
+        }
+        Err(error) => {
+            emit_event(ScrapeEvent::CmsApiFailed {
+                url: page_url.to_string(),
+                cms: api.kind(),
+                error,
+            });
+            None
+        }
+    }
+}
+
+/// Fetch `url`, parse the HTML body into a [`Post`], and classify any
+/// failure for the archive.org fallback decision. Successful PDF responses
+/// are extracted as documents instead ([`parse_pdf_response`]), and
+/// CMS-backed pages are read from their REST API when it can serve the
+/// post ([`try_cms_api`]).
+///
+/// With a [`crate::ScrapeCache`] configured, a fresh cached response
+/// replaces the network fetch, and successful (2xx, non-walled) HTML
+/// responses are stored.
+///
+/// For X Article URLs whose guest HTML withholds the body, a headless-Chrome
+/// render is attempted before giving up.
+async fn fetch_and_parse(scraper: &Scraper, url: &str, title_override: Option<&str>) -> RawFetch {
+    let cache = scraper.options().scrape_cache();
+    let cached = match cache {
+        Some(cache) => cache.load_response(url).await,
+        None => None,
+    };
+    let from_cache = cached.is_some();
+    let page = match cached {
+        Some(cached) => FetchedPage::from_cached(cached),
+        None => match fetch_page_bytes(scraper, url).await {
+            Ok(page) if page.status.is_success() && is_pdf(page.content_type(), &page.body) => {
+                return parse_pdf_response(page, title_override).await;
+            }
+            Ok(page) => page.into_text(),
+            Err(error) => {
+                return RawFetch {
+                    post: Post::from_error(ScrapeError::network(error)),
+                    network_failure: true,
+                    server_error: false,
+                    bot_protected: false,
+                    status_success: false,
+                    body_bytes: 0,
+                    chrome_rendered: false,
+                    pdf: false,
+                    cms_api: false,
+                    html: String::new(),
+                    html_url: String::new(),
+                };
+            }
+        },
+    };
+    let response_url = page.final_url.clone();
+    let is_x_article = is_x_article_url(&response_url) || is_x_article_url(url);
+    let response_status = page.status;
 
     if is_x_article {
         x_debug_dump_http_response(
             "X article page response",
             &response_url,
             response_status,
-            &response_headers,
-            &body_text,
+            &page.headers,
+            &page.body,
         );
     }
 
     let server_error = response_status.is_server_error();
     let status_success = response_status.is_success();
-    let body_bytes = body_text.len();
+    let body_bytes = page.body.len();
     let bot_protected =
-        looks_like_bot_protection(response_status.as_u16(), &response_headers, &body_text);
+        looks_like_bot_protection(response_status.as_u16(), &page.headers, &page.body);
     if bot_protected {
         emit_event(ScrapeEvent::BotProtectionDetected {
             url: response_url.clone(),
         });
     }
+    if let Some(cache) = cache {
+        if !from_cache && status_success && !bot_protected {
+            cache.store_response(url, &page.to_cached()).await;
+        }
+    }
+    let body_text = page.body;
+
+    if status_success && !bot_protected && scraper.options().cms_api_enabled() {
+        if let Some(post) = try_cms_api(
+            scraper,
+            &response_url,
+            &body_text,
+            response_status,
+            title_override,
+        )
+        .await
+        {
+            return RawFetch {
+                post,
+                network_failure: false,
+                server_error,
+                bot_protected,
+                status_success,
+                body_bytes,
+                chrome_rendered: false,
+                pdf: false,
+                cms_api: true,
+                html: String::new(),
+                html_url: String::new(),
+            };
+        }
+    }
 
-    let mut scraped_post = parse_scraped_post_from_html(&response_url, &body_text, title_override);
+    let mut scraped_post = parse_scraped_post_with_rules(
+        &response_url,
+        &body_text,
+        title_override,
+        scraper.options().extraction_rules(),
+    );
 
     // A challenge interstitial can still yield *some* extractable text; do
     // not mistake that for real article content. The override must happen
@@ -236,6 +642,35 @@ async fn fetch_and_parse(url: &str, title_override: Option<&str>) -> RawFetch {
             "The page appears to be behind a bot-protection wall (e.g. a Cloudflare challenge)."
                 .to_string();
     }
+    if let Some(provenance) = scraped_post.provenance.as_mut() {
+        provenance.http_status = Some(response_status.as_u16());
+    }
+
+    // Classify: a wall wins over whatever the extractor reported; otherwise
+    // a non-2xx response that yielded nothing is an HTTP status failure.
+    if bot_protected {
+        scraped_post.error_kind = Some(ScrapeError::bot_wall(scraped_post.error.clone()));
+    } else if !status_success && !scraped_post.error.is_empty() {
+        scraped_post.error_kind = Some(ScrapeError::http_status(
+            response_status.as_u16(),
+            scraped_post.error.clone(),
+        ));
+    }
+
+    if scraped_post.error.is_empty()
+        && status_success
+        && !is_x_article
+        && !response_url.starts_with("https://web.archive.org/")
+    {
+        follow_pagination(
+            scraper,
+            &response_url,
+            &body_text,
+            title_override,
+            &mut scraped_post,
+        )
+        .await;
+    }
 
     if scraped_post.error.is_empty() {
         emit_event(ScrapeEvent::ContentExtracted {
@@ -257,19 +692,29 @@ async fn fetch_and_parse(url: &str, title_override: Option<&str>) -> RawFetch {
             bot_protected,
             status_success,
             body_bytes,
+            chrome_rendered: false,
+            pdf: false,
+            cms_api: false,
+            html: body_text,
+            html_url: response_url,
         };
     }
 
-    let rendered_dom = match fetch_rendered_dom_with_chrome(&response_url).await {
+    let render_permit = scraper.render_permit().await;
+    let rendered_dom = fetch_rendered_dom_with_chrome(&response_url).await;
+    drop(render_permit);
+    let rendered_dom = match rendered_dom {
         Ok(rendered_dom) => rendered_dom,
         Err(browser_error) => {
             if x_article_body_unavailable(&body_text) {
+                let error = format!(
+                    "X article body is not available to guest sessions. Set UNINEWS_CHROME_USER_DATA_DIR and optionally UNINEWS_CHROME_PROFILE_DIR to a logged-in Chrome profile. Browser fallback failed: {}",
+                    browser_error
+                );
                 return RawFetch {
                     post: Post {
-                        error: format!(
-                            "X article body is not available to guest sessions. Set UNINEWS_CHROME_USER_DATA_DIR and optionally UNINEWS_CHROME_PROFILE_DIR to a logged-in Chrome profile. Browser fallback failed: {}",
-                            browser_error
-                        ),
+                        error_kind: Some(ScrapeError::blocked_content(error.clone())),
+                        error,
                         ..scraped_post
                     },
                     network_failure: false,
@@ -277,6 +722,11 @@ async fn fetch_and_parse(url: &str, title_override: Option<&str>) -> RawFetch {
                     bot_protected,
                     status_success,
                     body_bytes,
+                    chrome_rendered: true,
+                    pdf: false,
+                    cms_api: false,
+                    html: String::new(),
+                    html_url: String::new(),
                 };
             }
 
@@ -293,13 +743,24 @@ async fn fetch_and_parse(url: &str, title_override: Option<&str>) -> RawFetch {
                 bot_protected,
                 status_success,
                 body_bytes,
+                chrome_rendered: true,
+                pdf: false,
+                cms_api: false,
+                html: String::new(),
+                html_url: String::new(),
             };
         }
     };
 
     x_debug_dump("X article rendered DOM", &rendered_dom);
 
-    let rendered_post = parse_scraped_post_from_html(&response_url, &rendered_dom, title_override);
+    let mut rendered_post = parse_scraped_post_with_rules(
+        &response_url,
+        &rendered_dom,
+        title_override,
+        scraper.options().extraction_rules(),
+    );
+    set_provenance_source(&mut rendered_post, FallbackStage::ChromeRender);
     if rendered_post.error.is_empty() {
         return RawFetch {
             post: rendered_post,
@@ -308,13 +769,20 @@ async fn fetch_and_parse(url: &str, title_override: Option<&str>) -> RawFetch {
             bot_protected,
             status_success,
             body_bytes,
+            chrome_rendered: true,
+            pdf: false,
+            cms_api: false,
+            html: String::new(),
+            html_url: String::new(),
         };
     }
 
     if x_article_body_unavailable(&rendered_dom) {
+        let error = "X article body is not available to guest sessions. Set UNINEWS_CHROME_USER_DATA_DIR and optionally UNINEWS_CHROME_PROFILE_DIR to a logged-in Chrome profile.".to_string();
         return RawFetch {
             post: Post {
-                error: "X article body is not available to guest sessions. Set UNINEWS_CHROME_USER_DATA_DIR and optionally UNINEWS_CHROME_PROFILE_DIR to a logged-in Chrome profile.".to_string(),
+                error_kind: Some(ScrapeError::blocked_content(error.clone())),
+                error,
                 ..rendered_post
             },
             network_failure: false,
@@ -322,6 +790,11 @@ async fn fetch_and_parse(url: &str, title_override: Option<&str>) -> RawFetch {
             bot_protected,
             status_success,
             body_bytes,
+            chrome_rendered: true,
+            pdf: false,
+            cms_api: false,
+            html: String::new(),
+            html_url: String::new(),
         };
     }
 
@@ -338,27 +811,127 @@ async fn fetch_and_parse(url: &str, title_override: Option<&str>) -> RawFetch {
         bot_protected,
         status_success,
         body_bytes,
+        chrome_rendered: true,
+        pdf: false,
+        cms_api: false,
+        html: String::new(),
+        html_url: String::new(),
     }
 }
 
+/// Try the server-rendered AMP and print variants a walled or thin page
+/// (`html`, served from `url`) links ([`alternate_pages`]) before the
+/// Playwright render. Returns the
+/// first variant that is not walled itself and yields more article content
+/// than `prior` (the plain-fetch result) and at least [`MIN_CONTENT_BYTES`];
+/// `None` when the page links no variant or none helps.
+async fn try_alternate_pages(
+    scraper: &Scraper,
+    url: &str,
+    html: &str,
+    title_override: Option<&str>,
+    prior: &Post,
+    fallbacks: &mut Vec<FallbackStage>,
+) -> Option<Post> {
+    let alternates = alternate_pages(url, html);
+    if alternates.is_empty() {
+        return None;
+    }
+    fallbacks.push(FallbackStage::Alternate);
+    let prior_bytes = if prior.error.is_empty() {
+        prior.content.len()
+    } else {
+        0
+    };
+
+    for AlternatePage {
+        kind,
+        url: alternate_url,
+    } in alternates
+    {
+        emit_event(ScrapeEvent::AlternateFetchStarted {
+            url: url.to_string(),
+            alternate_url: alternate_url.clone(),
+            kind,
+        });
+        let error = match fetch_page(scraper, &alternate_url).await {
+            Err(error) => error,
+            Ok(page) if !page.status.is_success() => format!("HTTP {}", page.status),
+            Ok(page)
+                if looks_like_bot_protection(page.status.as_u16(), &page.headers, &page.body) =>
+            {
+                "the variant is behind a bot-protection wall too".to_string()
+            }
+            Ok(page) => {
+                let mut post = parse_scraped_post_with_rules(
+                    &page.final_url,
+                    &page.body,
+                    title_override,
+                    scraper.options().extraction_rules(),
+                );
+                if !post.error.is_empty() {
+                    post.error
+                } else if post.content.len() < MIN_CONTENT_BYTES.max(prior_bytes + 1) {
+                    format!(
+                        "the variant yielded {} content bytes, no more than the page",
+                        post.content.len()
+                    )
+                } else {
+                    set_provenance_source(&mut post, FallbackStage::Alternate);
+                    if let Some(provenance) = post.provenance.as_mut() {
+                        provenance.http_status = Some(page.status.as_u16());
+                    }
+                    emit_event(ScrapeEvent::AlternateFetchSucceeded {
+                        url: url.to_string(),
+                        alternate_url,
+                        kind,
+                        content_bytes: post.content.len(),
+                    });
+                    emit_event(ScrapeEvent::ContentExtracted {
+                        url: page.final_url,
+                        content_bytes: post.content.len(),
+                    });
+                    return Some(post);
+                }
+            }
+        };
+        emit_event(ScrapeEvent::AlternateFetchFailed {
+            url: url.to_string(),
+            alternate_url,
+            kind,
+            error,
+        });
+    }
+    None
+}
+
 /// Try Playwright Chromium for a bot-protected or thin-content page.
 /// Returns `Some(post)` when the rendered DOM yields usable article
 /// content; `None` when the fallback is skipped, fails, or still looks
 /// blocked (caller continues to archive.org or keeps the plain result).
 async fn try_playwright_fallback(
+    scraper: &Scraper,
     url: &str,
     title_override: Option<&str>,
     prior_error: &str,
+    fallbacks: &mut Vec<FallbackStage>,
 ) -> Option<Post> {
-    if !playwright_enabled() {
+    let options = scraper.options();
+    if !options.playwright_enabled() {
         return None;
     }
+    fallbacks.push(FallbackStage::Playwright);
 
     emit_event(ScrapeEvent::PlaywrightFallbackStarted {
         url: url.to_string(),
     });
 
-    let html = match fetch_rendered_dom_with_playwright(url).await {
+    let render_permit = scraper.render_permit().await;
+    let html =
+        fetch_rendered_dom_with_playwright_timeout(url, options.playwright_timeout_duration())
+            .await;
+    drop(render_permit);
+    let html = match html {
         Ok(html) => html,
         Err(err) => {
             emit_event(ScrapeEvent::PlaywrightFallbackFailed {
@@ -379,8 +952,10 @@ async fn try_playwright_fallback(
         return None;
     }
 
-    let rendered = parse_scraped_post_from_html(url, &html, title_override);
+    let mut rendered =
+        parse_scraped_post_with_rules(url, &html, title_override, options.extraction_rules());
     if rendered.error.is_empty() {
+        set_provenance_source(&mut rendered, FallbackStage::Playwright);
         emit_event(ScrapeEvent::PlaywrightFallbackSucceeded {
             url: url.to_string(),
             body_bytes: html.len(),
@@ -411,8 +986,14 @@ async fn try_playwright_fallback(
 /// is installed or the hook's output was unusable — callers continue
 /// their normal fallback chain, so the hook can never make a scrape
 /// worse.
-async fn try_host_content_fallback(url: &str, title_override: Option<&str>) -> Option<Post> {
+pub(crate) async fn try_host_content_fallback(
+    scraper: &Scraper,
+    url: &str,
+    title_override: Option<&str>,
+    fallbacks: &mut Vec<FallbackStage>,
+) -> Option<Post> {
     let hook = content_fallback_hook()?;
+    fallbacks.push(FallbackStage::ContentFallback);
 
     emit_event(ScrapeEvent::ContentFallbackStarted {
         url: url.to_string(),
@@ -442,6 +1023,10 @@ async fn try_host_content_fallback(url: &str, title_override: Option<&str>) -> O
                 publication_date: None,
                 author: None,
                 error: String::new(),
+                error_kind: None,
+                provenance: Some(Provenance::new(FallbackStage::ContentFallback, url)),
+                metadata: None,
+                canonical_url: None,
             })
         }
         Ok(ContentFallback::RenderedDom(html)) => {
@@ -454,8 +1039,14 @@ async fn try_host_content_fallback(url: &str, title_override: Option<&str>) -> O
                 });
                 return None;
             }
-            let rendered = parse_scraped_post_from_html(url, &html, title_override);
+            let mut rendered = parse_scraped_post_with_rules(
+                url,
+                &html,
+                title_override,
+                scraper.options().extraction_rules(),
+            );
             if rendered.error.is_empty() {
+                set_provenance_source(&mut rendered, FallbackStage::ContentFallback);
                 emit_event(ScrapeEvent::ContentFallbackSucceeded {
                     url: url.to_string(),
                     content_bytes: html.len(),
@@ -489,31 +1080,48 @@ async fn try_host_content_fallback(url: &str, title_override: Option<&str>) -> O
 /// conversion.
 ///
 /// On failure the returned post carries the error in [`Post::error`].
-/// Playwright Chromium is tried (unless disabled via `UNINEWS_PLAYWRIGHT=0`)
+/// Playwright Chromium is tried (unless disabled in the scraper's options)
 /// for bot-protection walls, for thin-content pages — a successful (2xx),
 /// non-walled, non-X response whose extraction failed or whose raw body is
 /// under `JS_SHELL_MAX_BYTES` — and for hard failures (network errors /
 /// timeouts, 5xx), where a silent Cloudflare drop is the likely cause. The
-/// host content fallback runs around this render (first via
-/// `UNINEWS_CONTENT_FALLBACK_FIRST` on known-doomed local renders). When
+/// host content fallback runs around this render (first when
+/// `content_fallback_first` is set, for known-doomed local renders). When
 /// the render does not yield usable content the original plain-fetch post is
 /// kept untouched. Remaining bot walls and hard failures then go through the
 /// archive.org Wayback Machine fallback as the LAST resort (unless disabled
-/// via `UNINEWS_ARCHIVE_FALLBACK=0`); thin-content pages are not
+/// in the options); thin-content pages are not
 /// archive-eligible, so Playwright is their only fallback.
-async fn scrape_web_url_raw_with_title_override(url: &str, title_override: Option<&str>) -> Post {
+///
+/// Every stage that actually ran is appended to `fallbacks`, in order.
+async fn scrape_web_url_raw_with_title_override(
+    scraper: &Scraper,
+    url: &str,
+    title_override: Option<&str>,
+    fallbacks: &mut Vec<FallbackStage>,
+) -> Post {
+    let options = scraper.options();
+
     // URLs whose real payload never appears in the page HTML (YouTube
     // videos: the article-equivalent content is the transcript) go
     // straight to the host content fallback when one is installed. When
     // no hook is installed — or it cannot serve the URL — the normal
     // fetch pipeline below runs unchanged.
     if is_youtube_url(url) {
-        if let Some(post) = try_host_content_fallback(url, title_override).await {
+        if let Some(post) = try_host_content_fallback(scraper, url, title_override, fallbacks).await
+        {
             return post;
         }
     }
 
-    let raw = fetch_and_parse(url, title_override).await;
+    fallbacks.push(FallbackStage::PlainFetch);
+    let raw = fetch_and_parse(scraper, url, title_override).await;
+    if raw.chrome_rendered {
+        fallbacks.push(FallbackStage::ChromeRender);
+    }
+    if raw.cms_api {
+        fallbacks.push(FallbackStage::CmsApi);
+    }
 
     // Thin-content trigger: a healthy, non-walled page whose extraction
     // failed (JS-gated body), whose extracted content is implausibly short
@@ -524,9 +1132,12 @@ async fn scrape_web_url_raw_with_title_override(url: &str, title_override: Optio
     // markers, so in practice the two conditions are mutually exclusive —
     // and if both could apply, the wall path below wins by construction
     // (a walled page never satisfies `thin_content`). X URLs keep their
-    // own dedicated chain.
+    // own dedicated chain, and PDFs and CMS API posts have nothing a
+    // browser could add.
     let thin_content = raw.status_success
         && !raw.bot_protected
+        && !raw.pdf
+        && !raw.cms_api
         && !is_x_url(url)
         && (!raw.post.error.is_empty()
             || raw.post.content.len() < MIN_CONTENT_BYTES
@@ -548,33 +1159,61 @@ async fn scrape_web_url_raw_with_title_override(url: &str, title_override: Optio
     let mut post_after_playwright = raw.post;
     let try_render = raw.bot_protected || thin_content || raw.network_failure || raw.server_error;
     if try_render {
+        // Walled and thin pages often link a server-rendered AMP or print
+        // variant: a few plain fetches, tried before any browser render.
+        if (raw.bot_protected || thin_content)
+            && !is_x_url(url)
+            && options.alternate_fallback_enabled()
+        {
+            if let Some(alternate) = try_alternate_pages(
+                scraper,
+                &raw.html_url,
+                &raw.html,
+                title_override,
+                &post_after_playwright,
+                fallbacks,
+            )
+            .await
+            {
+                return alternate;
+            }
+        }
+
         // Ordering for WALLS and hard failures: when the operator knows the
         // local render is doomed (datacenter IP the challenge will never
         // pass — the same cause behind a silent timeout / 5xx), the host
-        // content fallback goes FIRST via UNINEWS_CONTENT_FALLBACK_FIRST,
+        // content fallback goes FIRST via `content_fallback_first`,
         // saving the wasted ~60 s local attempt. Thin-content pages keep
         // local-Playwright-first ordering in every case — JS shells are
         // not IP-gated, so local renders work for them.
         let hook_first = (raw.bot_protected || raw.network_failure || raw.server_error)
             && !thin_content
-            && content_fallback_first()
+            && options.content_fallback_first_enabled()
             && content_fallback_hook().is_some();
 
         if hook_first {
-            if let Some(fallback_post) = try_host_content_fallback(url, title_override).await {
+            if let Some(fallback_post) =
+                try_host_content_fallback(scraper, url, title_override, fallbacks).await
+            {
                 return fallback_post;
             }
         }
 
-        if let Some(rendered) =
-            try_playwright_fallback(url, title_override, &post_after_playwright.error).await
+        if let Some(rendered) = try_playwright_fallback(
+            scraper,
+            url,
+            title_override,
+            &post_after_playwright.error,
+            fallbacks,
+        )
+        .await
         {
             return rendered;
         }
         // Annotate so the final error chain shows Playwright was attempted.
         // A thin-shell page whose plain extraction SUCCEEDED keeps its
         // empty-error result untouched when the render does not help.
-        if playwright_enabled() && !post_after_playwright.error.is_empty() {
+        if options.playwright_enabled() && !post_after_playwright.error.is_empty() {
             post_after_playwright.error = format!(
                 "{} (Playwright Chromium fallback did not yield usable content)",
                 post_after_playwright.error
@@ -589,7 +1228,9 @@ async fn scrape_web_url_raw_with_title_override(url: &str, title_override: Optio
         // and failed, this second consultation is skipped — the hook
         // already had its chance.)
         if !hook_first {
-            if let Some(fallback_post) = try_host_content_fallback(url, title_override).await {
+            if let Some(fallback_post) =
+                try_host_content_fallback(scraper, url, title_override, fallbacks).await
+            {
                 return fallback_post;
             }
         }
@@ -598,7 +1239,7 @@ async fn scrape_web_url_raw_with_title_override(url: &str, title_override: Optio
     // The archive.org fallback covers bot-protection walls and hard
     // failures. X URLs keep their own dedicated fallback chain.
     let eligible = raw.bot_protected || raw.network_failure || raw.server_error;
-    if !archive_fallback_enabled() || is_x_url(url) || !eligible {
+    if !options.archive_fallback_enabled() || is_x_url(url) || !eligible {
         return post_after_playwright;
     }
 
@@ -613,8 +1254,9 @@ async fn scrape_web_url_raw_with_title_override(url: &str, title_override: Optio
         url: url.to_string(),
         reason: reason.to_string(),
     });
+    fallbacks.push(FallbackStage::Archive);
 
-    match latest_snapshot(url).await {
+    match latest_snapshot(scraper.api_client(), url).await {
         Ok(Some(snapshot)) => {
             emit_event(ScrapeEvent::ArchiveSnapshotFound {
                 url: url.to_string(),
@@ -622,8 +1264,13 @@ async fn scrape_web_url_raw_with_title_override(url: &str, title_override: Optio
                 timestamp: snapshot.timestamp.clone(),
             });
 
-            let archived = fetch_and_parse(&snapshot.url, title_override).await;
+            let mut archived = fetch_and_parse(scraper, &snapshot.url, title_override).await;
             if archived.post.error.is_empty() {
+                if let Some(provenance) = archived.post.provenance.as_mut() {
+                    provenance.source = FallbackStage::Archive;
+                    provenance.snapshot_url = Some(snapshot.url.clone());
+                    provenance.snapshot_timestamp = Some(snapshot.timestamp.clone());
+                }
                 return archived.post;
             }
 
@@ -664,32 +1311,64 @@ async fn scrape_web_url_raw_with_title_override(url: &str, title_override: Optio
 }
 
 /// Fetch, parse, and Markdown-convert a web URL, honoring an optional title
-/// override (used when following links out of X posts).
+/// override (used when following links out of social posts).
 pub(crate) async fn scrape_web_url_with_title_override(
+    scraper: &Scraper,
     url: &str,
     language: &str,
     title_override: Option<&str>,
-    context_window_tokens: Option<usize>,
 ) -> Post {
-    let scraped_post = scrape_web_url_raw_with_title_override(url, title_override).await;
+    let mut fallbacks = Vec::new();
+    let scraped_post =
+        scrape_web_url_raw_with_title_override(scraper, url, title_override, &mut fallbacks).await;
     if !scraped_post.error.is_empty() {
-        return scraped_post;
+        return scraped_post.with_fallbacks(&fallbacks);
     }
+    convert_scraped_post(scraper, scraped_post, language, &fallbacks).await
+}
 
-    match convert_content_to_markdown(scraped_post.clone(), language, context_window_tokens).await {
+/// Scrape the article a link-only social post points to, naming the
+/// article in the error when it fails.
+pub(crate) async fn scrape_linked_article(
+    scraper: &Scraper,
+    article_url: &str,
+    language: &str,
+    title_override: Option<&str>,
+) -> Post {
+    let article_post =
+        scrape_web_url_with_title_override(scraper, article_url, language, title_override).await;
+    if article_post.error.is_empty() {
+        return article_post;
+    }
+    Post {
+        error: format!(
+            "Failed to scrape linked article {}: {}",
+            article_url, article_post.error
+        ),
+        ..article_post
+    }
+}
+
+/// Markdown-convert a successfully scraped post. A conversion failure is
+/// reported on the unconverted post, with `fallbacks` recorded on the
+/// error.
+pub(crate) async fn convert_scraped_post(
+    scraper: &Scraper,
+    scraped_post: Post,
+    language: &str,
+    fallbacks: &[FallbackStage],
+) -> Post {
+    match convert_with_options(scraped_post.clone(), language, scraper.options()).await {
         Ok(markdown_post) => markdown_post,
         Err(err) => Post {
-            error: err,
+            error: err.to_string(),
+            error_kind: Some(err.with_fallbacks(fallbacks)),
             ..scraped_post
         },
     }
 }
 
 /// Fetch, parse, and Markdown-convert a plain web URL.
-pub(crate) async fn scrape_web_url(
-    url: &str,
-    language: &str,
-    context_window_tokens: Option<usize>,
-) -> Post {
-    scrape_web_url_with_title_override(url, language, None, context_window_tokens).await
+pub(crate) async fn scrape_web_url(scraper: &Scraper, url: &str, language: &str) -> Post {
+    scrape_web_url_with_title_override(scraper, url, language, None).await
 }
@@ -17,12 +17,33 @@ use std::collections::HashSet;
 use reqwest::Client;
 use serde::Deserialize;
 
+use crate::canonical::follow_redirects;
 use crate::events::{emit_event, ScrapeEvent};
-use crate::http::api_client;
-use crate::llm::convert_content_to_markdown;
+use crate::handler::{HandlerFuture, ScrapeContext, SourceHandler};
+use crate::llm::convert_with_options;
 use crate::util::{first_non_empty_env_var, summarize_body};
-use crate::web::scrape_web_url_with_title_override;
-use crate::Post;
+use crate::web::{scrape_linked_article, scrape_web_url_with_title_override};
+use crate::{FallbackStage, Post, Provenance, ScrapeError, Scraper};
+
+/// The built-in [`SourceHandler`] for X / Twitter URLs
+/// ([`is_x_url`]): tweets, threads, and X Articles via the X API, with
+/// link-only posts followed to the linked article.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct XHandler;
+
+impl SourceHandler for XHandler {
+    fn name(&self) -> &str {
+        "x"
+    }
+
+    fn matches(&self, url: &str) -> bool {
+        is_x_url(url)
+    }
+
+    fn scrape<'a>(&'a self, url: &'a str, ctx: &'a ScrapeContext<'a>) -> HandlerFuture<'a> {
+        Box::pin(scrape_x_url(ctx.scraper(), url, ctx.language()))
+    }
+}
 
 /// Public, well-known bearer token embedded in X's own web client. Used for
 /// guest-token GraphQL requests; not a secret.
@@ -353,11 +374,9 @@ pub fn x_linked_article_url(tweet: &XTweet) -> Option<String> {
 }
 
 async fn resolve_url_redirect(client: &Client, url: &str) -> Option<String> {
-    let response = client.get(url).send().await.ok()?;
-    let final_url = response.url().as_str().trim().to_string();
+    let final_url = follow_redirects(client, url).await?;
 
-    if final_url.is_empty()
-        || final_url.starts_with("https://t.co/")
+    if final_url.starts_with("https://t.co/")
         || final_url.starts_with("http://t.co/")
         || x_url_is_status_link(&final_url)
     {
@@ -517,6 +536,10 @@ pub fn parse_x_web_article_post(
         publication_date,
         author,
         error: String::new(),
+        error_kind: None,
+        provenance: None,
+        metadata: None,
+        canonical_url: None,
     })
 }
 
@@ -561,6 +584,7 @@ async fn resolve_x_guest_token(client: &Client) -> Result<String, String> {
         url: GUEST_ACTIVATE_URL.to_string(),
         status: status.as_u16(),
         body_bytes: body.len(),
+        encoding: None,
     });
 
     if !status.is_success() {
@@ -668,6 +692,7 @@ async fn scrape_x_article_from_web_graphql(
         url: graphql_url,
         status: status.as_u16(),
         body_bytes: body.len(),
+        encoding: None,
     });
 
     if !status.is_success() {
@@ -753,33 +778,34 @@ pub fn x_bearer_token_parse_error(error: &serde_json::Error) -> String {
     format!("Failed to parse X bearer token response: {}", error)
 }
 
-/// Build a [`Post`] carrying only an error message (mirrors `web::error_post`).
+/// Build a [`Post`] carrying only an X API error message
+/// ([`ScrapeError::XApi`] without an HTTP status, after the X API stage).
 fn x_error_post(error: String) -> Post {
-    Post {
-        title: String::new(),
-        content: String::new(),
-        featured_image_url: String::new(),
-        publication_date: None,
-        author: None,
-        error,
-    }
+    Post::from_error(ScrapeError::x_api(None, error).with_fallbacks(&[FallbackStage::XApi]))
+}
+
+/// `post` with [`FallbackStage::XApi`] recorded ahead of the stages its
+/// error already lists (the web pipeline run for a linked article after
+/// the X API). Successful posts are returned unchanged.
+fn after_x_api(post: Post) -> Post {
+    post.after_fallback(FallbackStage::XApi)
 }
 
 /// Convert a scraped post to Markdown, attaching the conversion error to
 /// the pre-conversion post on failure (mirrors the `web.rs` pattern).
 ///
-/// `convert_content_to_markdown` consumes the post and drops it on error,
+/// `convert_with_options` consumes the post and drops it on error,
 /// so the clone below is the price of keeping the original available for
 /// the error arm; removing it entirely requires `llm.rs` to hand the post
 /// back on failure (out of scope here).
-async fn markdown_or_error_post(
-    post: Post,
-    language: &str,
-    context_window_tokens: Option<usize>,
-) -> Post {
-    match convert_content_to_markdown(post.clone(), language, context_window_tokens).await {
+async fn markdown_or_error_post(scraper: &Scraper, post: Post, language: &str) -> Post {
+    match convert_with_options(post.clone(), language, scraper.options()).await {
         Ok(markdown_post) => markdown_post,
-        Err(error) => Post { error, ..post },
+        Err(error) => Post {
+            error: error.to_string(),
+            error_kind: Some(error.with_fallbacks(&[FallbackStage::XApi])),
+            ..post
+        },
     }
 }
 
@@ -818,14 +844,15 @@ pub fn parse_x_search_tweets(body: &str) -> Result<Vec<XTweet>, String> {
 /// `None` when the tweet carries no resolvable external article URL, in
 /// which case the caller falls back to treating it as a regular tweet.
 async fn scrape_link_only_tweet(
-    client: &Client,
+    scraper: &Scraper,
+    root_provenance: Provenance,
     root_tweet: &XTweet,
     root_urls: &[String],
     author_display: Option<String>,
     profile_image: String,
     language: &str,
-    context_window_tokens: Option<usize>,
 ) -> Option<Post> {
+    let client = scraper.api_client();
     let article_title_override = root_tweet
         .article
         .as_ref()
@@ -844,11 +871,13 @@ async fn scrape_link_only_tweet(
             publication_date: root_tweet.created_at.clone(),
             author: author_display,
             error: String::new(),
+            error_kind: None,
+            provenance: Some(root_provenance),
+            metadata: None,
+            canonical_url: None,
         };
 
-        return Some(
-            markdown_or_error_post(scraped_article_post, language, context_window_tokens).await,
-        );
+        return Some(markdown_or_error_post(scraper, scraped_article_post, language).await);
     }
 
     let article_url = resolve_x_linked_article_url(client, root_urls).await?;
@@ -863,25 +892,24 @@ async fn scrape_link_only_tweet(
         )
         .await
         {
-            Ok(scraped_article_post) => {
-                return Some(
-                    markdown_or_error_post(scraped_article_post, language, context_window_tokens)
-                        .await,
-                );
+            Ok(mut scraped_article_post) => {
+                scraped_article_post.provenance =
+                    Some(Provenance::new(FallbackStage::XApi, &article_url));
+                return Some(markdown_or_error_post(scraper, scraped_article_post, language).await);
             }
             Err(graphql_error) => {
                 let article_post = scrape_web_url_with_title_override(
+                    scraper,
                     &article_url,
                     language,
                     article_title_override,
-                    context_window_tokens,
                 )
                 .await;
                 if article_post.error.is_empty() {
                     return Some(article_post);
                 }
 
-                return Some(Post {
+                return Some(after_x_api(Post {
                     title: article_post.title,
                     content: article_post.content,
                     featured_image_url: article_post.featured_image_url,
@@ -891,33 +919,18 @@ async fn scrape_link_only_tweet(
                         "Failed to scrape linked X article {} via X web GraphQL: {}. HTML fallback failed: {}",
                         article_url, graphql_error, article_post.error
                     ),
-                });
+                    error_kind: article_post.error_kind,
+                    provenance: article_post.provenance,
+                    metadata: article_post.metadata,
+                    canonical_url: None,
+                }));
             }
         }
     }
 
-    let article_post = scrape_web_url_with_title_override(
-        &article_url,
-        language,
-        article_title_override,
-        context_window_tokens,
-    )
-    .await;
-    if article_post.error.is_empty() {
-        return Some(article_post);
-    }
-
-    Some(Post {
-        title: article_post.title,
-        content: article_post.content,
-        featured_image_url: article_post.featured_image_url,
-        publication_date: article_post.publication_date,
-        author: article_post.author,
-        error: format!(
-            "Failed to scrape linked article {}: {}",
-            article_url, article_post.error
-        ),
-    })
+    Some(after_x_api(
+        scrape_linked_article(scraper, &article_url, language, article_title_override).await,
+    ))
 }
 
 /// Fetches a tweet or X thread via the Twitter/X API v2 and returns a [`Post`].
@@ -940,11 +953,7 @@ async fn scrape_link_only_tweet(
 /// # Errors
 ///
 /// All errors are non-fatal and are returned inside [`Post::error`].
-pub(crate) async fn scrape_x_url(
-    url: &str,
-    language: &str,
-    context_window_tokens: Option<usize>,
-) -> Post {
+pub(crate) async fn scrape_x_url(scraper: &Scraper, url: &str, language: &str) -> Post {
     // ── 1. Extract the tweet ID from the URL ─────────────────────────────────
     let tweet_id = match extract_tweet_id(url) {
         Some(id) => id,
@@ -957,7 +966,7 @@ pub(crate) async fn scrape_x_url(
     };
 
     // ── 2. Use the shared API HTTP client ───────────────────────────────────
-    let client = api_client();
+    let client = scraper.api_client();
 
     // ── 3. Resolve the Bearer Token ──────────────────────────────────────────
     let bearer_token = match resolve_x_bearer_token(client).await {
@@ -1007,12 +1016,19 @@ pub(crate) async fn scrape_x_url(
         url: root_tweet_url.clone(),
         status: root_status.as_u16(),
         body_bytes: root_body.len(),
+        encoding: None,
     });
 
     if !root_status.is_success() {
         let message =
             x_api_error_message(&root_body).unwrap_or_else(|| summarize_body(&root_body, 400));
-        return x_error_post(format!("X API returned HTTP {}: {}", root_status, message));
+        return Post::from_error(
+            ScrapeError::x_api(
+                Some(root_status.as_u16()),
+                format!("X API returned HTTP {}: {}", root_status, message),
+            )
+            .with_fallbacks(&[FallbackStage::XApi]),
+        );
     }
 
     let root_data: XTweetResponse = match serde_json::from_str(&root_body) {
@@ -1069,16 +1085,20 @@ pub(crate) async fn scrape_x_url(
     // Compute the tweet's URL list once for the link-only check and the
     // article resolution below (I9: was recomputed up to 3x per scrape).
     let root_urls = x_text_urls(&root_tweet);
+    let root_provenance = Provenance {
+        http_status: Some(root_status.as_u16()),
+        ..Provenance::new(FallbackStage::XApi, url)
+    };
 
     if x_post_is_link_only_with_urls(&root_tweet, &root_urls) {
         if let Some(post) = scrape_link_only_tweet(
-            client,
+            scraper,
+            root_provenance.clone(),
             &root_tweet,
             &root_urls,
             author_display.clone(),
             profile_image.clone(),
             language,
-            context_window_tokens,
         )
         .await
         {
@@ -1121,6 +1141,7 @@ pub(crate) async fn scrape_x_url(
                             url: search_url.clone(),
                             status: search_status.as_u16(),
                             body_bytes: search_body.len(),
+                            encoding: None,
                         });
                         if let Ok(tweets) = parse_x_search_tweets(&search_body) {
                             for t in tweets {
@@ -1190,8 +1211,12 @@ pub(crate) async fn scrape_x_url(
         publication_date: root_tweet.created_at,
         author: author_display,
         error: String::new(),
+        error_kind: None,
+        provenance: Some(root_provenance),
+        metadata: None,
+        canonical_url: None,
     };
 
     // ── 7. AI Markdown conversion & optional translation ──────────────────────
-    markdown_or_error_post(scraped_post, language, context_window_tokens).await
+    markdown_or_error_post(scraper, scraped_post, language).await
 }
@@ -0,0 +1,545 @@
+//! Native YouTube transcript extraction.
+//!
+//! The article-equivalent payload of a video is its transcript, which is
+//! not part of the watch-page HTML the generic pipeline would extract.
+//! The built-in [`YouTubeHandler`] instead reads the player response
+//! (`ytInitialPlayerResponse`) embedded in the watch page:
+//!
+//! 1. **Video details** — title, channel, publish date, description,
+//!    thumbnail, and keywords ([`YouTubeVideo`]).
+//! 2. **Caption tracks** — [`YouTubeVideo::caption_track`] picks one for
+//!    the target language, preferring manual captions over
+//!    auto-generated ones, then any manual track, then any track.
+//! 3. **Captions** — the track's timedtext document is fetched and parsed
+//!    into [`TranscriptCue`]s, grouped into one paragraph per minute.
+//!
+//! The description and transcript become the post's content, which then
+//! goes through the normal Markdown conversion like any extracted article
+//! body (the same path as [`crate::ContentFallback::Extracted`]). A video
+//! without captions is scraped from its description alone.
+//!
+//! A host content fallback hook ([`crate::set_content_fallback`]), when
+//! installed, is still consulted first; the native extraction runs when it
+//! has nothing for the video.
+
+use reqwest::Url;
+use scraper::Html;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::FallbackStage;
+use crate::events::{emit_event, ScrapeEvent};
+use crate::handler::{HandlerFuture, ScrapeContext, SourceHandler};
+use crate::html::article_body_to_html;
+use crate::util::is_youtube_url;
+use crate::web::{convert_scraped_post, fetch_page, try_host_content_fallback};
+use crate::{Post, PostMetadata, Provenance, ScrapeError, Scraper};
+
+/// Transcript paragraphs span this much video time (one minute).
+const PARAGRAPH_MS: u64 = 60_000;
+
+/// The built-in [`SourceHandler`] for YouTube videos (watch, `youtu.be`,
+/// Shorts, embed, and live URLs): the description and captions read from
+/// the watch page's player response.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct YouTubeHandler;
+
+impl SourceHandler for YouTubeHandler {
+    fn name(&self) -> &str {
+        "youtube"
+    }
+
+    fn matches(&self, url: &str) -> bool {
+        is_youtube_url(url) && youtube_video_id(url).is_some()
+    }
+
+    fn scrape<'a>(&'a self, url: &'a str, ctx: &'a ScrapeContext<'a>) -> HandlerFuture<'a> {
+        Box::pin(scrape_youtube_url(ctx.scraper(), url, ctx.language()))
+    }
+}
+
+/// One caption track listed in a video's player response.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CaptionTrack {
+    /// The timedtext URL of the captions.
+    pub base_url: String,
+    /// BCP 47-style language code (`en`, `pt-BR`, …).
+    pub language_code: String,
+    /// Display name (`English`, `English (auto-generated)`, …).
+    pub name: String,
+    /// Speech-recognition captions rather than uploaded ones.
+    pub auto_generated: bool,
+}
+
+/// A video's details and caption tracks, read from its watch page.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct YouTubeVideo {
+    /// The 11-character video id.
+    pub video_id: String,
+    /// The watch page the details were read from.
+    pub url: String,
+    pub title: String,
+    /// The channel name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+    /// Publish date, as YouTube reports it (`2026-10-16` or RFC 3339).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publish_date: Option<String>,
+    pub description: String,
+    /// The largest thumbnail.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thumbnail_url: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub caption_tracks: Vec<CaptionTrack>,
+    /// `playabilityStatus.status` (`OK`, `LOGIN_REQUIRED`, `ERROR`, …).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub playability_status: Option<String>,
+    /// Why the video is not playable, when it is not.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub playability_reason: Option<String>,
+}
+
+/// One caption cue.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranscriptCue {
+    /// Offset into the video, in milliseconds.
+    pub start_ms: u64,
+    /// The caption text, entity-decoded and whitespace-collapsed.
+    pub text: String,
+}
+
+impl YouTubeVideo {
+    /// The caption track to use for `language` (a name such as `spanish`
+    /// or a code such as `es`): a manual track in that language, else an
+    /// auto-generated one, else the first manual track, else the first
+    /// track.
+    pub fn caption_track(&self, language: &str) -> Option<&CaptionTrack> {
+        let wanted = language_code(language);
+        let in_language = |track: &&CaptionTrack| {
+            wanted
+                .as_deref()
+                .is_some_and(|wanted| primary_subtag(&track.language_code) == wanted)
+        };
+        let tracks = &self.caption_tracks;
+        tracks
+            .iter()
+            .filter(in_language)
+            .find(|track| !track.auto_generated)
+            .or_else(|| tracks.iter().find(in_language))
+            .or_else(|| tracks.iter().find(|track| !track.auto_generated))
+            .or_else(|| tracks.first())
+    }
+
+    /// The video as a [`Post`] whose content is the description and, when
+    /// given, the transcript, as HTML paragraphs ready for conversion.
+    fn to_post(&self, transcript: &[TranscriptCue]) -> Post {
+        let mut content = String::new();
+        if !self.description.trim().is_empty() {
+            content.push_str("<h2>Description</h2>");
+            content.push_str(&article_body_to_html(&self.description));
+        }
+        if !transcript.is_empty() {
+            content.push_str("<h2>Transcript</h2>");
+            content.push_str(&article_body_to_html(&transcript_paragraphs(transcript)));
+        }
+        let metadata = PostMetadata {
+            schema_type: Some("VideoObject".to_string()),
+            headline: (!self.title.is_empty()).then(|| self.title.clone()),
+            description: (!self.description.trim().is_empty())
+                .then(|| self.description.trim().to_string()),
+            authors: self.channel.iter().cloned().collect(),
+            date_published: self.publish_date.clone(),
+            publisher: Some("YouTube".to_string()),
+            keywords: self.keywords.clone(),
+            image_url: self.thumbnail_url.clone(),
+            ..PostMetadata::default()
+        };
+        Post {
+            title: self.title.clone(),
+            content,
+            featured_image_url: self.thumbnail_url.clone().unwrap_or_default(),
+            publication_date: self.publish_date.clone(),
+            author: self.channel.clone(),
+            provenance: Some(Provenance::new(FallbackStage::YouTube, &self.url)),
+            metadata: Some(metadata),
+            ..Post::default()
+        }
+    }
+}
+
+/// The video id of a YouTube watch (`/watch?v=`), `youtu.be`, Shorts,
+/// embed, or live URL; `None` for other URLs (channels, playlists, …).
+///
+/// ```
+/// use uninews::youtube_video_id;
+/// assert_eq!(
+///     youtube_video_id("https://youtu.be/dQw4w9WgXcQ?t=42").as_deref(),
+///     Some("dQw4w9WgXcQ")
+/// );
+/// assert_eq!(youtube_video_id("https://www.youtube.com/@channel/videos"), None);
+/// ```
+pub fn youtube_video_id(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    let segments: Vec<&str> = url
+        .path_segments()
+        .map(|segments| segments.filter(|segment| !segment.is_empty()).collect())
+        .unwrap_or_default();
+    let host = url.host_str().unwrap_or_default();
+    let id = if host == "youtu.be" || host.ends_with(".youtu.be") {
+        segments.first().map(|id| id.to_string())
+    } else {
+        match segments.as_slice() {
+            ["watch"] => url
+                .query_pairs()
+                .find(|(key, _)| key == "v")
+                .map(|(_, id)| id.into_owned()),
+            ["shorts" | "embed" | "live" | "v" | "e", id, ..] => Some(id.to_string()),
+            _ => None,
+        }
+    }?;
+    let valid = id.len() == 11
+        && id
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_');
+    valid.then_some(id)
+}
+
+/// Parse the player response embedded in a YouTube watch page served from
+/// `page_url`. Fails with a message starting `Not a YouTube watch page:`
+/// when the page carries no player response with video details.
+pub fn parse_youtube_watch_page(html: &str, page_url: &str) -> Result<YouTubeVideo, String> {
+    let player =
+        player_response(html).ok_or("Not a YouTube watch page: no ytInitialPlayerResponse")?;
+    let details = &player["videoDetails"];
+    let video_id = details["videoId"]
+        .as_str()
+        .ok_or("Not a YouTube watch page: player response without videoDetails")?
+        .to_string();
+    let microformat = &player["microformat"]["playerMicroformatRenderer"];
+
+    let caption_tracks = player["captions"]["playerCaptionsTracklistRenderer"]["captionTracks"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|track| {
+            Some(CaptionTrack {
+                base_url: track["baseUrl"].as_str()?.to_string(),
+                language_code: track["languageCode"].as_str()?.to_string(),
+                name: json_text(&track["name"]).unwrap_or_default(),
+                auto_generated: track["kind"].as_str() == Some("asr"),
+            })
+        })
+        .collect();
+    let thumbnail_url = details["thumbnail"]["thumbnails"]
+        .as_array()
+        .and_then(|thumbnails| thumbnails.last())
+        .and_then(|thumbnail| thumbnail["url"].as_str())
+        .map(str::to_string);
+    let text = |value: &Value| value.as_str().map(str::to_string).filter(|s| !s.is_empty());
+
+    Ok(YouTubeVideo {
+        video_id,
+        url: page_url.to_string(),
+        title: text(&details["title"])
+            .or_else(|| json_text(&microformat["title"]))
+            .unwrap_or_default(),
+        channel: text(&details["author"]).or_else(|| text(&microformat["ownerChannelName"])),
+        publish_date: text(&microformat["publishDate"])
+            .or_else(|| text(&microformat["uploadDate"])),
+        description: text(&details["shortDescription"])
+            .or_else(|| json_text(&microformat["description"]))
+            .unwrap_or_default(),
+        thumbnail_url,
+        keywords: details["keywords"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|keyword| keyword.as_str().map(str::to_string))
+            .collect(),
+        caption_tracks,
+        playability_status: text(&player["playabilityStatus"]["status"]),
+        playability_reason: text(&player["playabilityStatus"]["reason"]),
+    })
+}
+
+/// Parse a timedtext captions document: the default `<transcript><text
+/// start="…">` format or the `<timedtext><body><p t="…">` (`srv3`) one.
+/// Fails with a message starting `Not a timedtext document:`.
+pub fn parse_timedtext(body: &str) -> Result<Vec<TranscriptCue>, String> {
+    let document = roxmltree::Document::parse(body)
+        .map_err(|error| format!("Not a timedtext document: invalid XML ({})", error))?;
+    let root = document.root_element().tag_name().name();
+    if root != "transcript" && root != "timedtext" {
+        return Err(format!(
+            "Not a timedtext document: unexpected root element <{}>",
+            root
+        ));
+    }
+
+    let mut cues = Vec::new();
+    for node in document.descendants().filter(|node| node.is_element()) {
+        let start_ms = match node.tag_name().name() {
+            "text" => node
+                .attribute("start")
+                .and_then(|start| start.parse::<f64>().ok())
+                .map(|seconds| (seconds * 1_000.0) as u64),
+            "p" => node.attribute("t").and_then(|t| t.parse::<u64>().ok()),
+            _ => continue,
+        };
+        let raw: String = node
+            .descendants()
+            .filter_map(|child| child.text().filter(|_| child.is_text()))
+            .collect();
+        // Caption text is HTML-escaped inside the XML (`&amp;#39;`).
+        let decoded: String = Html::parse_fragment(&raw).root_element().text().collect();
+        let text = decoded.split_whitespace().collect::<Vec<_>>().join(" ");
+        if !text.is_empty() {
+            cues.push(TranscriptCue {
+                start_ms: start_ms.unwrap_or_default(),
+                text,
+            });
+        }
+    }
+    Ok(cues)
+}
+
+impl Scraper {
+    /// Fetch the watch page of the YouTube video at `url` with this
+    /// scraper's web client and read its details and caption tracks
+    /// ([`parse_youtube_watch_page`]).
+    ///
+    /// Fails with [`ScrapeError::Network`] / [`ScrapeError::HttpStatus`]
+    /// when the fetch fails, [`ScrapeError::BotWall`] when YouTube asks to
+    /// sign in, or [`ScrapeError::Other`] when the URL is not a video, the
+    /// page has no player response, or the video is unavailable.
+    pub async fn fetch_youtube_video(&self, url: &str) -> Result<YouTubeVideo, ScrapeError> {
+        let video_id = youtube_video_id(url)
+            .ok_or_else(|| ScrapeError::other(format!("Not a YouTube video URL: {}", url)))?;
+        let watch_url = watch_page_url(url, &video_id);
+        let page = fetch_page(self, &watch_url)
+            .await
+            .map_err(ScrapeError::network)?;
+        if !page.status.is_success() {
+            return Err(ScrapeError::http_status(
+                page.status.as_u16(),
+                format!(
+                    "YouTube request for {} returned HTTP {}",
+                    watch_url, page.status
+                ),
+            ));
+        }
+        let video = parse_youtube_watch_page(&page.body, &page.final_url)
+            .map_err(|error| ScrapeError::other(format!("{}: {}", watch_url, error)))?;
+
+        let status = video.playability_status.as_deref().unwrap_or("OK");
+        if status == "OK" {
+            return Ok(video);
+        }
+        let message = format!(
+            "YouTube video {} is not playable ({}): {}",
+            video.video_id,
+            status,
+            video
+                .playability_reason
+                .as_deref()
+                .unwrap_or("no reason given")
+        );
+        Err(if status == "LOGIN_REQUIRED" {
+            ScrapeError::bot_wall(message)
+        } else {
+            ScrapeError::other(message)
+        })
+    }
+
+    /// Fetch and parse the captions of `track` ([`parse_timedtext`]).
+    pub async fn fetch_youtube_transcript(
+        &self,
+        track: &CaptionTrack,
+    ) -> Result<Vec<TranscriptCue>, ScrapeError> {
+        let page = fetch_page(self, &track.base_url)
+            .await
+            .map_err(ScrapeError::network)?;
+        if !page.status.is_success() {
+            return Err(ScrapeError::http_status(
+                page.status.as_u16(),
+                format!("YouTube captions request returned HTTP {}", page.status),
+            ));
+        }
+        parse_timedtext(&page.body).map_err(ScrapeError::other)
+    }
+}
+
+/// Scrape a YouTube video: the host content fallback first, then the
+/// native description + transcript extraction, converted to Markdown.
+async fn scrape_youtube_url(scraper: &Scraper, url: &str, language: &str) -> Post {
+    let mut fallbacks = Vec::new();
+    if let Some(post) = try_host_content_fallback(scraper, url, None, &mut fallbacks).await {
+        return convert_scraped_post(scraper, post, language, &fallbacks).await;
+    }
+
+    fallbacks.push(FallbackStage::YouTube);
+    let video = match scraper.fetch_youtube_video(url).await {
+        Ok(video) => video,
+        Err(error) => return Post::from_error(error).with_fallbacks(&fallbacks),
+    };
+
+    let mut transcript = Vec::new();
+    if let Some(track) = video.caption_track(language) {
+        match scraper.fetch_youtube_transcript(track).await {
+            Ok(cues) if !cues.is_empty() => {
+                emit_event(ScrapeEvent::YouTubeTranscriptFetched {
+                    url: url.to_string(),
+                    language: track.language_code.clone(),
+                    auto_generated: track.auto_generated,
+                    cues: cues.len(),
+                });
+                transcript = cues;
+            }
+            Ok(_) => emit_event(ScrapeEvent::YouTubeTranscriptFailed {
+                url: url.to_string(),
+                error: "caption track is empty".to_string(),
+            }),
+            Err(error) => emit_event(ScrapeEvent::YouTubeTranscriptFailed {
+                url: url.to_string(),
+                error: error.to_string(),
+            }),
+        }
+    }
+
+    let post = video.to_post(&transcript);
+    if post.content.is_empty() {
+        return Post::from_error(ScrapeError::insufficient_content(format!(
+            "YouTube video {} has neither captions nor a description",
+            video.video_id
+        )))
+        .with_fallbacks(&fallbacks);
+    }
+    emit_event(ScrapeEvent::ContentExtracted {
+        url: url.to_string(),
+        content_bytes: post.content.len(),
+    });
+    convert_scraped_post(scraper, post, language, &fallbacks).await
+}
+
+/// The watch page for `video_id`: `url` itself when it already is one,
+/// else the canonical `www.youtube.com/watch` URL.
+fn watch_page_url(url: &str, video_id: &str) -> String {
+    match Url::parse(url) {
+        Ok(parsed) if parsed.path() == "/watch" => url.to_string(),
+        _ => format!("https://www.youtube.com/watch?v={}", video_id),
+    }
+}
+
+/// The JSON object assigned to `ytInitialPlayerResponse` in the page's
+/// scripts (`var ytInitialPlayerResponse = {…};` or
+/// `window["ytInitialPlayerResponse"] = {…};`).
+fn player_response(html: &str) -> Option<Value> {
+    const MARKER: &str = "ytInitialPlayerResponse";
+    let mut rest = html;
+    while let Some(position) = rest.find(MARKER) {
+        rest = &rest[position + MARKER.len()..];
+        let Some(value) = rest
+            .trim_start_matches(['"', '\'', ']'])
+            .trim_start()
+            .strip_prefix('=')
+            .map(str::trim_start)
+            .filter(|value| value.starts_with('{'))
+        else {
+            continue;
+        };
+        if let Some(Ok(player)) = serde_json::Deserializer::from_str(value)
+            .into_iter::<Value>()
+            .next()
+        {
+            return Some(player);
+        }
+    }
+    None
+}
+
+/// A YouTube text object: `{"simpleText": …}` or `{"runs": [{"text": …}]}`.
+fn json_text(value: &Value) -> Option<String> {
+    let text = match value["simpleText"].as_str() {
+        Some(text) => text.to_string(),
+        None => value["runs"]
+            .as_array()?
+            .iter()
+            .filter_map(|run| run["text"].as_str())
+            .collect(),
+    };
+    (!text.is_empty()).then_some(text)
+}
+
+/// The transcript as text, one line per [`PARAGRAPH_MS`] of video.
+fn transcript_paragraphs(cues: &[TranscriptCue]) -> String {
+    let mut text = String::new();
+    let mut paragraph = None;
+    for cue in cues {
+        let index = cue.start_ms / PARAGRAPH_MS;
+        match paragraph {
+            Some(current) if current == index => text.push(' '),
+            Some(_) => text.push('\n'),
+            None => {}
+        }
+        paragraph = Some(index);
+        text.push_str(&cue.text);
+    }
+    text
+}
+
+/// The lowercase primary language subtag of `code` (`pt-BR` → `pt`), with
+/// YouTube's legacy `iw` mapped to `he`.
+fn primary_subtag(code: &str) -> String {
+    let primary = code
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if primary == "iw" {
+        "he".to_string()
+    } else {
+        primary
+    }
+}
+
+/// The language code for a target `language`: a code (`es`, `pt-BR`) is
+/// used as is; common English language names map to their code.
+fn language_code(language: &str) -> Option<String> {
+    const NAMES: [(&str, &str); 24] = [
+        ("arabic", "ar"),
+        ("chinese", "zh"),
+        ("czech", "cs"),
+        ("danish", "da"),
+        ("dutch", "nl"),
+        ("english", "en"),
+        ("finnish", "fi"),
+        ("french", "fr"),
+        ("german", "de"),
+        ("greek", "el"),
+        ("hebrew", "he"),
+        ("hindi", "hi"),
+        ("indonesian", "id"),
+        ("italian", "it"),
+        ("japanese", "ja"),
+        ("korean", "ko"),
+        ("norwegian", "no"),
+        ("polish", "pl"),
+        ("portuguese", "pt"),
+        ("russian", "ru"),
+        ("spanish", "es"),
+        ("swedish", "sv"),
+        ("turkish", "tr"),
+        ("ukrainian", "uk"),
+    ];
+    let language = language.trim().to_ascii_lowercase();
+    if let Some((_, code)) = NAMES.iter().find(|(name, _)| *name == language) {
+        return Some(code.to_string());
+    }
+    let primary = primary_subtag(&language);
+    (matches!(primary.len(), 2 | 3) && primary.bytes().all(|byte| byte.is_ascii_lowercase()))
+        .then_some(primary)
+}
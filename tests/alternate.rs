@@ -0,0 +1,217 @@
+//! Integration tests for the AMP / print-view alternates tried before the
+//! Playwright render: variant detection from page markup, AMP cache URL
+//! construction, and the web pipeline reading walled and thin pages from
+//! the variant they link — in order, skipping variants that fail, and
+//! keeping the page's own result when the alternates are off.
+//!
+//! Hermetic: in-memory documents plus one `common::serve_with` loopback
+//! server per test standing in for the outlet (loopback hosts have no AMP
+//! cache URL, so nothing reaches cdn.ampproject.org), scraped with
+//! `common::hermetic_options` and the alternates on.
+
+mod common;
+
+use uninews::{
+    alternate_pages, amp_cache_url, AlternateKind, AlternatePage, FallbackStage, Scraper,
+};
+
+use common::{hermetic_options, serve_with, Route};
+
+const ARTICLE_TEXT: &str = "The city council voted on Tuesday to fund the new transit lines.";
+
+/// A server-rendered variant of the article: the body text repeated, with
+/// `marker` in the first paragraph.
+fn variant_page(marker: &str) -> String {
+    format!(
+        r#"<html><head><title>Transit plan approved</title></head><body>
+<article><h1>Transit plan approved</h1><p>{marker}</p>{}</article></body></html>"#,
+        format!("<p>{ARTICLE_TEXT}</p>").repeat(40)
+    )
+}
+
+#[test]
+fn amp_and_print_variants_are_detected_from_page_markup() {
+    let page = "https://news.example/2026/10/transit";
+    let html = r#"<html><head>
+<link rel="amphtml" href="/2026/10/transit/amp">
+<link rel="alternate" media="print" href="/2026/10/transit/print">
+</head><body><a href="/subscribe">Print edition</a></body></html>"#;
+    assert_eq!(
+        alternate_pages(page, html),
+        [
+            AlternatePage {
+                kind: AlternateKind::Amp,
+                url: "https://news.example/2026/10/transit/amp".to_string(),
+            },
+            AlternatePage {
+                kind: AlternateKind::AmpCache,
+                url: "https://news-example.cdn.ampproject.org/c/s/news.example/2026/10/transit/amp"
+                    .to_string(),
+            },
+            AlternatePage {
+                kind: AlternateKind::Print,
+                url: "https://news.example/2026/10/transit/print".to_string(),
+            },
+        ]
+    );
+
+    let print = |links: &str| {
+        alternate_pages(page, &format!("<html><body>{links}</body></html>"))
+            .into_iter()
+            .map(|alternate| alternate.url)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        print(
+            r#"<a href="javascript:window.print()">Print</a> <a href="?format=text#body">Printer-friendly  version</a>"#
+        ),
+        ["https://news.example/2026/10/transit?format=text"]
+    );
+    assert_eq!(
+        print(r#"<a class="tool" href="/2026/10/transit?print=1"><svg></svg></a>"#),
+        ["https://news.example/2026/10/transit?print=1"]
+    );
+    assert_eq!(
+        print(
+            r#"<a href="https://ads.example/print">Print</a> <a href="?print=0">Share</a> <a href="">Print</a>"#
+        ),
+        Vec::<String>::new()
+    );
+    // An AMP document that points at itself has no other variant.
+    assert_eq!(
+        alternate_pages(
+            "https://news.example/amp/transit",
+            r#"<link rel="amphtml" href="https://news.example/amp/transit#top">"#
+        ),
+        []
+    );
+    assert_eq!(alternate_pages("not a url", "<html></html>"), []);
+}
+
+#[test]
+fn amp_cache_urls_follow_the_cache_naming_rules() {
+    assert_eq!(
+        amp_cache_url("https://www.news-site.example/amp/transit?edition=us#top").as_deref(),
+        Some("https://www-news--site-example.cdn.ampproject.org/c/s/www.news-site.example/amp/transit?edition=us")
+    );
+    assert_eq!(
+        amp_cache_url("http://News.Example/amp/transit").as_deref(),
+        Some("https://news-example.cdn.ampproject.org/c/news.example/amp/transit")
+    );
+    assert_eq!(
+        amp_cache_url("https://ab-c.example/amp").as_deref(),
+        Some("https://0-ab--c-example-0.cdn.ampproject.org/c/s/ab-c.example/amp")
+    );
+    assert_eq!(amp_cache_url("https://127.0.0.1/amp/transit"), None);
+    assert_eq!(amp_cache_url("https://news.example:8443/amp/transit"), None);
+    assert_eq!(amp_cache_url("https://xn--nws-ooa.example/amp"), None);
+    assert_eq!(
+        amp_cache_url("https://news-example.cdn.ampproject.org/c/s/news.example/amp"),
+        None
+    );
+    assert_eq!(
+        amp_cache_url(&format!("https://{}.example/amp", "a".repeat(60))),
+        None
+    );
+}
+
+#[tokio::test]
+async fn walled_pages_are_read_from_their_amp_variant() {
+    let base = serve_with(|_| {
+        vec![
+            Route::html(
+                "/transit",
+                r#"<html><head><title>Just a moment...</title>
+<link rel="amphtml" href="/amp/transit"></head>
+<body><p>Verify you are human by completing the action below.</p></body></html>"#
+                    .to_string(),
+            ),
+            Route::html("/amp/transit", variant_page("AMP edition")),
+        ]
+    });
+    let url = format!("{}/transit", base);
+
+    let post = Scraper::new(hermetic_options().alternate_fallback(true))
+        .scrape(&url, "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(post.content.contains("AMP edition"), "{}", post.content);
+    assert!(post.content.contains(ARTICLE_TEXT), "{}", post.content);
+    let provenance = post.provenance.expect("provenance");
+    assert_eq!(provenance.source, FallbackStage::Alternate);
+    assert_eq!(provenance.final_url, format!("{}/amp/transit", base));
+
+    // With the alternates off, the wall is all there is.
+    let post = Scraper::new(hermetic_options().alternate_fallback(false))
+        .scrape(&url, "english")
+        .await;
+    assert!(!post.error.is_empty());
+    assert!(!post.content.contains("AMP edition"), "{}", post.content);
+}
+
+#[tokio::test]
+async fn thin_pages_fall_through_failed_variants_to_the_print_view() {
+    let base = serve_with(|_| {
+        vec![
+            Route::html(
+                "/transit",
+                r#"<html><head><title>Transit plan approved</title>
+<link rel="amphtml" href="/amp/transit"></head>
+<body><div id="app"></div><p>Transit plan approved: loading…</p>
+<a href="/transit?print=1">Print</a><script src="/bundle.js"></script></body></html>"#
+                    .to_string(),
+            ),
+            Route::html("/transit?print=1", variant_page("Print view")),
+        ]
+    });
+    let url = format!("{}/transit", base);
+
+    // The AMP document is a 404; the print view is next.
+    let post = Scraper::new(hermetic_options().alternate_fallback(true))
+        .scrape(&url, "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(post.content.contains("Print view"), "{}", post.content);
+    assert_eq!(
+        post.provenance.expect("provenance").source,
+        FallbackStage::Alternate
+    );
+
+    let post = Scraper::new(hermetic_options().alternate_fallback(false))
+        .scrape(&url, "english")
+        .await;
+    assert!(!post.content.contains("Print view"), "{}", post.content);
+}
+
+#[tokio::test]
+async fn variants_with_no_more_content_keep_the_page() {
+    let page = variant_page("Origin page");
+    let base = serve_with(|_| {
+        vec![
+            Route::html(
+                "/transit",
+                page.replace(
+                    "<title>",
+                    r#"<link rel="alternate" media="print" href="/transit/print"><title>"#,
+                ),
+            ),
+            Route::html(
+                "/transit/print",
+                "<html><body><p>Printing is disabled for this article.</p></body></html>"
+                    .to_string(),
+            ),
+        ]
+    });
+
+    // The origin page is under the 16 KiB JS-shell size, so it is thin,
+    // but the print view has even less to offer.
+    let post = Scraper::new(hermetic_options().alternate_fallback(true))
+        .scrape(&format!("{}/transit", base), "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(post.content.contains("Origin page"), "{}", post.content);
+    assert_eq!(
+        post.provenance.expect("provenance").source,
+        FallbackStage::PlainFetch
+    );
+}
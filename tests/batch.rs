@@ -0,0 +1,252 @@
+//! Integration tests for batch scraping ([`Scraper::scrape_many`]): every
+//! URL yields exactly one result, and the global concurrency limit, the
+//! per-host concurrency limit, and the per-host delay all hold as observed
+//! by the server (also after a wait for a global slot), and a host waiting
+//! out its delay leaves the global slots to other hosts.
+//!
+//! Hermetic: every URL points at a loopback server, Playwright and
+//! archive.org are disabled, and conversion is deterministic so no LLM is
+//! ever called. The event listener is process-wide, so every test in this
+//! binary serializes on `TEST_LOCK`.
+
+mod common;
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+
+use futures::StreamExt;
+use tokio::sync::Mutex;
+use uninews::{set_event_listener, ScrapeEvent, Scraper};
+
+use common::{article_page, hermetic_options, serve_logged, RequestLog, Route};
+
+/// Serializes the tests in this binary: they all emit batch events and one
+/// of them installs the process-wide listener.
+static TEST_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// A loopback server answering every request with the `Story` article
+/// after `latency`. Returns the server URL and what the server saw.
+fn spawn_slow_server(latency: Duration) -> (String, Arc<StdMutex<RequestLog>>) {
+    serve_logged(|_| {
+        vec![
+            Route::fallback("200 OK", "text/html; charset=UTF-8", article_page("Story"))
+                .delay(latency),
+        ]
+    })
+}
+
+fn batch_urls(base: &str, count: usize) -> Vec<String> {
+    (0..count)
+        .map(|i| format!("{}/story/{}", base, i))
+        .collect()
+}
+
+#[tokio::test]
+async fn every_url_yields_one_result_within_the_global_limit() {
+    let _lock = TEST_LOCK.lock().await;
+    let (base, log) = spawn_slow_server(Duration::from_millis(150));
+    let urls = batch_urls(&base, 6);
+    let scraper = Scraper::new(
+        hermetic_options()
+            .concurrency(2)
+            .per_host_concurrency(6)
+            .per_host_delay(Duration::ZERO),
+    );
+
+    let results: Vec<_> = scraper.scrape_many(urls.clone(), "english").collect().await;
+
+    assert_eq!(results.len(), urls.len());
+    let returned: HashSet<_> = results.iter().map(|(url, _)| url.clone()).collect();
+    assert_eq!(returned, urls.into_iter().collect::<HashSet<_>>());
+    for (url, post) in &results {
+        assert!(post.error.is_empty(), "{url}: {}", post.error);
+        assert_eq!(post.title, "Story");
+    }
+    assert_eq!(log.lock().unwrap().max_in_flight, 2);
+}
+
+#[tokio::test]
+async fn per_host_concurrency_limits_one_host() {
+    let _lock = TEST_LOCK.lock().await;
+    let (base, log) = spawn_slow_server(Duration::from_millis(100));
+    let scraper = Scraper::new(
+        hermetic_options()
+            .concurrency(8)
+            .per_host_concurrency(1)
+            .per_host_delay(Duration::ZERO),
+    );
+
+    let results: Vec<_> = scraper
+        .scrape_many(batch_urls(&base, 4), "english")
+        .collect()
+        .await;
+
+    assert_eq!(results.len(), 4);
+    assert_eq!(log.lock().unwrap().max_in_flight, 1);
+}
+
+#[tokio::test]
+async fn per_host_delay_spaces_request_starts() {
+    let _lock = TEST_LOCK.lock().await;
+    let (base, log) = spawn_slow_server(Duration::ZERO);
+    let delay = Duration::from_millis(200);
+    let scraper = Scraper::new(
+        hermetic_options()
+            .concurrency(8)
+            .per_host_concurrency(8)
+            .per_host_delay(delay),
+    );
+
+    let results: Vec<_> = scraper
+        .scrape_many(batch_urls(&base, 3), "english")
+        .collect()
+        .await;
+
+    assert_eq!(results.len(), 3);
+    let mut starts = log.lock().unwrap().starts.clone();
+    starts.sort();
+    assert_eq!(starts.len(), 3);
+    for pair in starts.windows(2) {
+        let gap = pair[1] - pair[0];
+        // Allow for the connect latency between the scheduled start and the
+        // server's accept.
+        assert!(gap >= delay - Duration::from_millis(50), "gap {gap:?}");
+    }
+}
+
+#[tokio::test]
+async fn per_host_delay_does_not_block_other_hosts() {
+    let _lock = TEST_LOCK.lock().await;
+    let (slow_base, slow_log) = spawn_slow_server(Duration::ZERO);
+    let (other_base, other_log) = spawn_slow_server(Duration::ZERO);
+    // Same loopback server, another host name: a separate per-host gate.
+    let other_base = other_base.replace("127.0.0.1", "localhost");
+    let delay = Duration::from_secs(1);
+    let scraper = Scraper::new(
+        hermetic_options()
+            .concurrency(1)
+            .per_host_concurrency(8)
+            .per_host_delay(delay),
+    );
+
+    let mut urls = batch_urls(&slow_base, 3);
+    urls.push(format!("{}/story/other", other_base));
+    let results: Vec<_> = scraper.scrape_many(urls, "english").collect().await;
+
+    assert_eq!(results.len(), 4);
+    let mut slow_starts = slow_log.lock().unwrap().starts.clone();
+    slow_starts.sort();
+    let other_start = other_log.lock().unwrap().starts[0];
+    // The other host's scrape runs while the first host waits out its
+    // delay, not after it.
+    assert!(
+        other_start < slow_starts[1],
+        "other host started {:?} after the first host's second scrape",
+        other_start - slow_starts[1]
+    );
+    assert!(other_start - slow_starts[0] < delay);
+}
+
+#[tokio::test]
+async fn per_host_delay_holds_after_waiting_for_a_global_slot() {
+    let _lock = TEST_LOCK.lock().await;
+    let (busy_base, _busy_log) = spawn_slow_server(Duration::from_secs(1));
+    let busy_base = busy_base.replace("127.0.0.1", "localhost");
+    let (base, log) = spawn_slow_server(Duration::ZERO);
+    let delay = Duration::from_millis(400);
+    let scraper = Scraper::new(
+        hermetic_options()
+            .concurrency(1)
+            .per_host_concurrency(2)
+            .per_host_delay(delay),
+    );
+
+    // The busy host holds the only global slot while both URLs of the
+    // other host wait out (or skip) their delay; once it frees up, they
+    // must still start a delay apart.
+    let mut urls = vec![format!("{}/story/busy", busy_base)];
+    urls.extend(batch_urls(&base, 2));
+    let results: Vec<_> = scraper.scrape_many(urls, "english").collect().await;
+
+    assert_eq!(results.len(), 3);
+    let mut starts = log.lock().unwrap().starts.clone();
+    starts.sort();
+    assert_eq!(starts.len(), 2);
+    let gap = starts[1] - starts[0];
+    assert!(gap >= delay - Duration::from_millis(50), "gap {gap:?}");
+}
+
+#[tokio::test]
+async fn batch_events_bracket_the_results() {
+    let _lock = TEST_LOCK.lock().await;
+    let (base, _log) = spawn_slow_server(Duration::ZERO);
+    let events: Arc<StdMutex<Vec<ScrapeEvent>>> = Arc::default();
+    let sink = Arc::clone(&events);
+    set_event_listener(Some(Arc::new(move |event: &ScrapeEvent| {
+        sink.lock().unwrap().push(event.clone());
+    })));
+
+    let urls = vec![format!("{}/story/ok", base), "not a url".to_string()];
+    let results: Vec<_> = Scraper::new(hermetic_options())
+        .scrape_many(urls, "english")
+        .collect()
+        .await;
+    set_event_listener(None);
+
+    assert_eq!(results.len(), 2);
+    let failed: Vec<_> = results
+        .iter()
+        .filter(|(_, post)| !post.error.is_empty())
+        .map(|(url, _)| url.as_str())
+        .collect();
+    assert_eq!(failed, ["not a url"]);
+
+    let events = events.lock().unwrap();
+    assert!(
+        matches!(events.first(), Some(ScrapeEvent::BatchStarted { urls: 2 })),
+        "{events:?}"
+    );
+    assert!(
+        matches!(
+            events.last(),
+            Some(ScrapeEvent::BatchCompleted { urls: 2, failed: 1 })
+        ),
+        "{events:?}"
+    );
+    let started = events
+        .iter()
+        .filter(|event| matches!(event, ScrapeEvent::ScrapeStarted { .. }))
+        .count();
+    assert_eq!(started, 2);
+}
+
+#[tokio::test]
+async fn dropping_the_stream_cancels_pending_scrapes() {
+    let _lock = TEST_LOCK.lock().await;
+    let (base, log) = spawn_slow_server(Duration::from_millis(100));
+    let scraper = Scraper::new(
+        hermetic_options()
+            .concurrency(1)
+            .per_host_concurrency(1)
+            .per_host_delay(Duration::ZERO),
+    );
+
+    let mut results = scraper.scrape_many(batch_urls(&base, 5), "english");
+    let (_, first) = results.next().await.expect("first result");
+    assert!(first.error.is_empty(), "{}", first.error);
+    drop(results);
+
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    // The first scrape plus at most the one that had already started.
+    assert!(log.lock().unwrap().starts.len() <= 2);
+}
+
+#[tokio::test]
+async fn empty_batch_completes_immediately() {
+    let _lock = TEST_LOCK.lock().await;
+    let results: Vec<_> = uninews::scrape_many(Vec::<String>::new(), "english", hermetic_options())
+        .collect()
+        .await;
+    assert!(results.is_empty());
+}
@@ -0,0 +1,435 @@
+//! Integration tests for Bluesky posts: post-URL shapes, the
+//! `getPostThread` parser (author threads, facets, embeds), link-only
+//! detection, and the built-in handler resolving handles, assembling
+//! threads, following link-only posts, and reporting AppView errors.
+//!
+//! Hermetic: in-memory documents plus one `common::serve_with` loopback
+//! server per test standing in for the AppView and linked sites, scraped
+//! with `common::hermetic_options`.
+
+mod common;
+
+use serde_json::{json, Value};
+use uninews::{
+    bluesky_post_ref, parse_bluesky_thread, BlueskyHandler, FallbackStage, ScrapeError, Scraper,
+    SourceHandler, BLUESKY_PUBLIC_APPVIEW,
+};
+
+use common::{article_page, hermetic_options, serve_with, Route};
+
+const ALICE: [&str; 3] = ["did:plc:alice", "alice.example", "Alice Reporter"];
+const BOB: [&str; 3] = ["did:plc:bob", "bob.bsky.social", ""];
+
+/// An `app.bsky.feed.defs#postView` by `author` (`[did, handle, display
+/// name]`) with record key `rkey`.
+fn post_view(rkey: &str, author: [&str; 3], created_at: &str, text: &str) -> Value {
+    json!({
+        "uri": format!("at://{}/app.bsky.feed.post/{}", author[0], rkey),
+        "cid": format!("cid-{}", rkey),
+        "author": {
+            "did": author[0],
+            "handle": author[1],
+            "displayName": author[2],
+            "avatar": format!("https://cdn.example/avatar/{}.jpg", author[1])
+        },
+        "record": {
+            "$type": "app.bsky.feed.post",
+            "text": text,
+            "createdAt": created_at,
+            "langs": ["en"]
+        },
+        "indexedAt": created_at
+    })
+}
+
+/// A `threadViewPost` node for `post` with `parent` and `replies`.
+fn node(post: Value, parent: Option<Value>, replies: Vec<Value>) -> Value {
+    let mut node = json!({
+        "$type": "app.bsky.feed.defs#threadViewPost",
+        "post": post,
+        "replies": replies
+    });
+    if let Some(parent) = parent {
+        node["parent"] = parent;
+    }
+    node
+}
+
+/// A link facet over the first occurrence of `linked` in `text`.
+fn link_facet(text: &str, linked: &str, uri: &str) -> Value {
+    let start = text.find(linked).expect("linked text");
+    json!({
+        "index": {"byteStart": start, "byteEnd": start + linked.len()},
+        "features": [{"$type": "app.bsky.richtext.facet#link", "uri": uri}]
+    })
+}
+
+/// Alice's three-post thread, replying to Bob, with the middle post (`2`)
+/// requested, a reply from Bob, and Alice answering Bob.
+fn alice_thread() -> Value {
+    let root = node(
+        post_view("0", BOB, "2026-10-16T07:00:00.000Z", "What happened?"),
+        None,
+        vec![],
+    );
+    let first = node(
+        post_view(
+            "1",
+            ALICE,
+            "2026-10-16T08:00:00.000Z",
+            "First: the vote passed.",
+        ),
+        Some(root),
+        vec![],
+    );
+    let mut focus = post_view(
+        "2",
+        ALICE,
+        "2026-10-16T08:01:00.000Z",
+        "Second: two new lines.",
+    );
+    focus["embed"] = json!({
+        "$type": "app.bsky.embed.images#view",
+        "images": [{
+            "thumb": "https://cdn.example/thumb/map.jpg",
+            "fullsize": "https://cdn.example/full/map.jpg",
+            "alt": "Line map"
+        }]
+    });
+    let third = node(
+        post_view(
+            "3",
+            ALICE,
+            "2026-10-16T08:02:00.000Z",
+            "Third: construction in 2027.",
+        ),
+        None,
+        vec![],
+    );
+    let thanks = node(
+        post_view("4", BOB, "2026-10-16T08:03:00.000Z", "Thanks!"),
+        None,
+        vec![node(
+            post_view("5", ALICE, "2026-10-16T08:04:00.000Z", "You're welcome."),
+            None,
+            vec![],
+        )],
+    );
+    json!({"thread": node(focus, Some(first), vec![thanks, third])})
+}
+
+#[test]
+fn post_refs_come_from_bsky_post_urls() {
+    let post_ref = |url: &str| bluesky_post_ref(url);
+    assert_eq!(
+        post_ref("https://bsky.app/profile/alice.example/post/3l6oveex3ii2l"),
+        Some(("alice.example".to_string(), "3l6oveex3ii2l".to_string()))
+    );
+    assert_eq!(
+        post_ref("https://bsky.app/profile/did:plc:alice/post/3l6oveex3ii2l?ref=share"),
+        Some(("did:plc:alice".to_string(), "3l6oveex3ii2l".to_string()))
+    );
+    assert_eq!(post_ref("https://bsky.app/profile/alice.example"), None);
+    assert_eq!(
+        post_ref("https://bsky.app/profile/alice.example/feed/news"),
+        None
+    );
+    assert_eq!(
+        post_ref("https://example.com/profile/alice.example/post/3l6oveex3ii2l"),
+        None
+    );
+
+    let handler = BlueskyHandler::default();
+    assert_eq!(handler.appview(), BLUESKY_PUBLIC_APPVIEW);
+    assert!(handler.matches("https://bsky.app/profile/alice.example/post/3l6oveex3ii2l"));
+    assert!(!handler.matches("https://example.com/news/story"));
+    assert_eq!(
+        BlueskyHandler::with_appview("http://127.0.0.1:9/").appview(),
+        "http://127.0.0.1:9"
+    );
+}
+
+#[test]
+fn threads_keep_the_authors_posts_oldest_first() {
+    let thread = parse_bluesky_thread(&alice_thread().to_string()).expect("thread");
+    let texts: Vec<&str> = thread.posts.iter().map(|post| post.text.as_str()).collect();
+    assert_eq!(
+        texts,
+        [
+            "First: the vote passed.",
+            "Second: two new lines.",
+            "Third: construction in 2027."
+        ]
+    );
+    assert_eq!(thread.focus, 1);
+
+    let focus = thread.post();
+    assert_eq!(focus.uri, "at://did:plc:alice/app.bsky.feed.post/2");
+    assert_eq!(focus.url, "https://bsky.app/profile/alice.example/post/2");
+    assert_eq!(focus.author.handle, "alice.example");
+    assert_eq!(focus.author.display_name, "Alice Reporter");
+    assert_eq!(focus.langs, ["en"]);
+    assert_eq!(focus.images.len(), 1);
+    assert_eq!(focus.images[0].url, "https://cdn.example/full/map.jpg");
+    assert_eq!(focus.images[0].alt.as_deref(), Some("Line map"));
+
+    let not_found = json!({"thread": {"$type": "app.bsky.feed.defs#notFoundPost", "uri": "at://x", "notFound": true}});
+    assert!(parse_bluesky_thread(&not_found.to_string())
+        .unwrap_err()
+        .contains("not found"));
+    assert!(parse_bluesky_thread("{}")
+        .unwrap_err()
+        .starts_with("Not a Bluesky thread:"));
+}
+
+#[test]
+fn facets_and_embeds_are_read() {
+    let text = "Plan details: news.example/plan… #transit";
+    let mut view = post_view("9", ALICE, "2026-10-16T09:00:00.000Z", text);
+    view["record"]["facets"] = json!([
+        link_facet(text, "news.example/plan…", "https://news.example/plan"),
+        {
+            "index": {"byteStart": 0, "byteEnd": 0},
+            "features": [{"$type": "app.bsky.richtext.facet#tag", "tag": "transit"}]
+        }
+    ]);
+    view["embed"] = json!({
+        "$type": "app.bsky.embed.recordWithMedia#view",
+        "media": {
+            "$type": "app.bsky.embed.external#view",
+            "external": {
+                "uri": "https://news.example/plan",
+                "title": "Council approves the transit plan",
+                "description": "Two new lines by 2027.",
+                "thumb": "https://cdn.example/card.jpg"
+            }
+        },
+        "record": {
+            "record": {
+                "$type": "app.bsky.embed.record#viewRecord",
+                "uri": "at://did:plc:bob/app.bsky.feed.post/8",
+                "author": {"did": "did:plc:bob", "handle": "bob.bsky.social"},
+                "value": {"text": "Big vote today."}
+            }
+        }
+    });
+    let thread = json!({"thread": node(view, None, vec![])});
+
+    let post = parse_bluesky_thread(&thread.to_string())
+        .expect("thread")
+        .posts
+        .remove(0);
+    assert_eq!(post.links.len(), 1);
+    assert_eq!(post.links[0].uri, "https://news.example/plan");
+    assert_eq!(
+        &post.text[post.links[0].byte_start..post.links[0].byte_end],
+        "news.example/plan…"
+    );
+    assert_eq!(post.tags, ["transit"]);
+    let external = post.external.as_ref().expect("external");
+    assert_eq!(external.title, "Council approves the transit plan");
+    assert_eq!(
+        external.description.as_deref(),
+        Some("Two new lines by 2027.")
+    );
+    let quote = post.quote.as_ref().expect("quote");
+    assert_eq!(quote.url, "https://bsky.app/profile/bob.bsky.social/post/8");
+    assert_eq!(quote.text, "Big vote today.");
+    assert!(!post.is_link_only());
+    assert_eq!(
+        post.linked_article_url().as_deref(),
+        Some("https://news.example/plan")
+    );
+}
+
+#[test]
+fn link_only_posts_point_at_their_article() {
+    let parse = |view: Value| {
+        parse_bluesky_thread(&json!({"thread": node(view, None, vec![])}).to_string())
+            .expect("thread")
+            .posts
+            .remove(0)
+    };
+
+    let text = " news.example/plan ";
+    let mut view = post_view("1", ALICE, "2026-10-16T09:00:00.000Z", text);
+    view["record"]["facets"] = json!([link_facet(
+        text,
+        "news.example/plan",
+        "https://news.example/plan"
+    )]);
+    let post = parse(view);
+    assert!(post.is_link_only());
+    assert_eq!(
+        post.linked_article_url().as_deref(),
+        Some("https://news.example/plan")
+    );
+
+    let mut view = post_view("2", ALICE, "2026-10-16T09:00:00.000Z", "");
+    view["embed"] = json!({
+        "$type": "app.bsky.embed.external#view",
+        "external": {"uri": "https://news.example/card", "title": "Card", "description": ""}
+    });
+    let post = parse(view);
+    assert!(post.is_link_only());
+    assert_eq!(
+        post.linked_article_url().as_deref(),
+        Some("https://news.example/card")
+    );
+
+    let post = parse(post_view(
+        "3",
+        ALICE,
+        "2026-10-16T09:00:00.000Z",
+        "No links here.",
+    ));
+    assert!(!post.is_link_only());
+    assert_eq!(post.linked_article_url(), None);
+}
+
+/// A `200 OK` route for requests for `path` whose query contains `query`.
+fn route(path: &str, query: &str, content_type: &'static str, body: String) -> Route {
+    Route::new(path, content_type, body).query(query)
+}
+
+/// A scraper whose Bluesky handler reads from the AppView at `appview`.
+fn hermetic_scraper(appview: &str) -> Scraper {
+    Scraper::new(hermetic_options().handler(BlueskyHandler::with_appview(appview)))
+}
+
+fn resolve_alice() -> Route {
+    route(
+        "/xrpc/com.atproto.identity.resolveHandle",
+        "handle=alice.example",
+        "application/json",
+        json!({"did": ALICE[0]}).to_string(),
+    )
+}
+
+#[tokio::test]
+async fn handler_assembles_the_authors_thread() {
+    let appview = serve_with(|_| {
+        vec![
+            resolve_alice(),
+            route(
+                "/xrpc/app.bsky.feed.getPostThread",
+                "uri=at://did:plc:alice/app.bsky.feed.post/2",
+                "application/json; charset=utf-8",
+                alice_thread().to_string(),
+            ),
+        ]
+    });
+    let url = "https://bsky.app/profile/alice.example/post/2";
+
+    let thread = BlueskyHandler::with_appview(&appview)
+        .fetch_thread(&hermetic_scraper(&appview), url)
+        .await
+        .expect("thread");
+    assert_eq!(thread.posts.len(), 3);
+    assert_eq!(thread.focus, 1);
+
+    let post = hermetic_scraper(&appview).scrape(url, "english").await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(
+        post.title,
+        "@alice.example (Alice Reporter): First: the vote passed."
+    );
+    assert_eq!(
+        post.author.as_deref(),
+        Some("@alice.example (Alice Reporter)")
+    );
+    assert_eq!(
+        post.publication_date.as_deref(),
+        Some("2026-10-16T08:00:00.000Z")
+    );
+    assert_eq!(post.featured_image_url, "https://cdn.example/full/map.jpg");
+    let first = post.content.find("the vote passed").expect("first");
+    let second = post.content.find("two new lines").expect("second");
+    let third = post.content.find("construction in 2027").expect("third");
+    assert!(first < second && second < third, "{}", post.content);
+    assert!(post
+        .content
+        .contains("![Line map](https://cdn.example/full/map.jpg)"));
+    assert!(!post.content.contains("Thanks!"), "{}", post.content);
+    assert!(!post.content.contains("welcome"), "{}", post.content);
+
+    let provenance = post.provenance.expect("provenance");
+    assert_eq!(provenance.source, FallbackStage::Bluesky);
+    assert_eq!(
+        provenance.canonical_url.as_deref(),
+        Some("https://bsky.app/profile/alice.example/post/2")
+    );
+    let metadata = post.metadata.expect("metadata");
+    assert_eq!(metadata.schema_type.as_deref(), Some("SocialMediaPosting"));
+    assert_eq!(metadata.publisher.as_deref(), Some("Bluesky"));
+    assert_eq!(metadata.authors, ["Alice Reporter"]);
+}
+
+#[tokio::test]
+async fn link_only_posts_follow_the_linked_article() {
+    let appview = serve_with(|base| {
+        let article = format!("{base}/news/plan");
+        let text = "news.example/plan";
+        let mut view = post_view("7", ALICE, "2026-10-16T09:00:00.000Z", text);
+        view["record"]["facets"] = json!([link_facet(text, text, &article)]);
+        view["embed"] = json!({
+            "$type": "app.bsky.embed.external#view",
+            "external": {"uri": article, "title": "Council approves the transit plan", "description": ""}
+        });
+        vec![
+            route(
+                "/xrpc/app.bsky.feed.getPostThread",
+                "uri=at://did:plc:alice/app.bsky.feed.post/7",
+                "application/json",
+                json!({"thread": node(view, None, vec![])}).to_string(),
+            ),
+            route("/news/plan", "", "text/html", article_page("Plan | News")),
+        ]
+    });
+
+    let post = hermetic_scraper(&appview)
+        .scrape("https://bsky.app/profile/did:plc:alice/post/7", "english")
+        .await;
+
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(post.title, "Council approves the transit plan");
+    assert!(
+        post.content.contains("fund the new transit lines"),
+        "{}",
+        post.content
+    );
+    let provenance = post.provenance.expect("provenance");
+    assert_eq!(provenance.source, FallbackStage::PlainFetch);
+    assert_eq!(provenance.final_url, format!("{}/news/plan", appview));
+}
+
+#[tokio::test]
+async fn appview_errors_are_reported() {
+    let appview = serve_with(|_| {
+        vec![
+            resolve_alice(),
+            Route::fallback(
+                "400 Bad Request",
+                "application/json",
+                "{\"error\":\"NotFound\",\"message\":\"Post not found\"}",
+            ),
+        ]
+    });
+
+    let post = hermetic_scraper(&appview)
+        .scrape("https://bsky.app/profile/alice.example/post/404", "english")
+        .await;
+    match &post.error_kind {
+        Some(ScrapeError::HttpStatus {
+            status, message, ..
+        }) => {
+            assert_eq!(*status, 400);
+            assert!(message.contains("NotFound: Post not found"), "{}", message);
+        }
+        other => panic!("expected an HTTP status error, got {:?}", other),
+    }
+    assert_eq!(
+        post.error_kind.expect("kind").fallbacks(),
+        [FallbackStage::Bluesky]
+    );
+}
@@ -0,0 +1,440 @@
+//! Integration tests for the on-disk [`ScrapeCache`]: converted posts and
+//! raw responses are served from disk on repeat scrapes, keyed so a
+//! different language, model, pipeline toggle, or source-handler setting
+//! misses the post layer, expire after the
+//! TTL, never store failures, and report hits / misses as events. Both
+//! layers are keyed by the canonical URL, so tracking-parameter variants,
+//! redirects, and declared canonicals of an article share a post, while
+//! archive.org snapshots and AMP responses never answer for the article.
+//! Every post-cache miss checks the URL for redirects first, which the
+//! request counts include.
+//!
+//! Hermetic: every scrape targets a loopback server (or, for archive.org
+//! snapshots, a stub source handler) with Playwright and archive.org
+//! disabled and deterministic conversion, so no LLM is called.
+//! Each test gets its own cache directory under the system temp dir. The
+//! event listener and env vars are process-wide, so every test in this
+//! binary serializes on `TEST_LOCK`.
+
+mod common;
+
+use std::env;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+use uninews::{
+    set_event_listener, CacheKind, HandlerFuture, Post, RedditHandler, ScrapeCache, ScrapeContext,
+    ScrapeEvent, ScrapeOptions, Scraper, SourceHandler, TelegramHandler, DEFAULT_CACHE_TTL,
+    UNINEWS_CACHE_DIR_ENV, UNINEWS_CACHE_TTL_SECS_ENV,
+};
+
+use common::{article_page, hermetic_options, serve_logged, RequestLog, Route};
+
+/// Serializes the tests that touch the event listener or env vars.
+static TEST_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// RAII helper: temporarily override an env var, restore on drop.
+struct EnvVarGuard {
+    key: &'static str,
+    previous: Option<String>,
+}
+
+impl EnvVarGuard {
+    fn set(key: &'static str, value: &str) -> Self {
+        let previous = env::var(key).ok();
+        unsafe {
+            env::set_var(key, value);
+        }
+        Self { key, previous }
+    }
+}
+
+impl Drop for EnvVarGuard {
+    fn drop(&mut self) {
+        unsafe {
+            match self.previous.as_deref() {
+                Some(previous) => env::set_var(self.key, previous),
+                None => env::remove_var(self.key),
+            }
+        }
+    }
+}
+
+/// A fresh cache directory, removed on drop.
+struct TempCacheDir(PathBuf);
+
+impl TempCacheDir {
+    fn new(name: &str) -> Self {
+        let dir = env::temp_dir().join(format!(
+            "uninews-cache-test-{}-{}",
+            std::process::id(),
+            name
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        Self(dir)
+    }
+}
+
+impl Drop for TempCacheDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// A loopback server answering every request with `status` and an
+/// extractable article (2xx) or an empty page (anything else). Returns the
+/// server URL and what the server saw.
+fn spawn_counting_server(status: &'static str) -> (String, Arc<StdMutex<RequestLog>>) {
+    let body = if status.starts_with('2') {
+        article_page("Cached story")
+    } else {
+        "<html><body></body></html>".to_string()
+    };
+    serve_logged(|_| vec![Route::fallback(status, "text/html; charset=UTF-8", body)])
+}
+
+fn cached_scraper(cache: ScrapeCache) -> Scraper {
+    Scraper::new(hermetic_options().cache(Some(cache)))
+}
+
+/// Record every cache event emitted while `run` executes.
+async fn cache_events<F: std::future::Future<Output = ()>>(run: F) -> Vec<(bool, CacheKind)> {
+    let events: Arc<StdMutex<Vec<(bool, CacheKind)>>> = Arc::default();
+    let sink = Arc::clone(&events);
+    set_event_listener(Some(Arc::new(move |event: &ScrapeEvent| match event {
+        ScrapeEvent::CacheHit { kind, .. } => sink.lock().unwrap().push((true, *kind)),
+        ScrapeEvent::CacheMiss { kind, .. } => sink.lock().unwrap().push((false, *kind)),
+        _ => {}
+    })));
+    run.await;
+    set_event_listener(None);
+    let events = events.lock().unwrap().clone();
+    events
+}
+
+#[tokio::test]
+async fn repeat_scrape_is_served_from_the_post_cache() {
+    let _lock = TEST_LOCK.lock().await;
+    let dir = TempCacheDir::new("post");
+    let (url, requests) = spawn_counting_server("200 OK");
+    let scraper = cached_scraper(ScrapeCache::new(&dir.0));
+
+    let mut posts = Vec::new();
+    let events = cache_events(async {
+        posts.push(scraper.scrape(&url, "english").await);
+        posts.push(scraper.scrape(&url, "english").await);
+    })
+    .await;
+
+    assert!(posts[0].error.is_empty(), "{}", posts[0].error);
+    assert_eq!(posts[0].title, "Cached story");
+    assert_eq!(posts[1].content, posts[0].content);
+    assert_eq!(posts[1].provenance, posts[0].provenance);
+    // The redirect check and the fetch; the repeat makes no request.
+    assert_eq!(requests.lock().unwrap().targets.len(), 2);
+    assert_eq!(
+        events,
+        [
+            (false, CacheKind::Post),
+            (false, CacheKind::Response),
+            (true, CacheKind::Post),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn tracking_parameter_variants_share_the_cache() {
+    let _lock = TEST_LOCK.lock().await;
+    let dir = TempCacheDir::new("normalized");
+    let (url, requests) = spawn_counting_server("200 OK");
+    let scraper = cached_scraper(ScrapeCache::new(&dir.0));
+
+    let first = scraper.scrape(&format!("{url}/transit"), "english").await;
+    let second = scraper
+        .scrape(
+            &format!("{url}/transit?utm_source=newsletter&fbclid=abc#comments"),
+            "english",
+        )
+        .await;
+
+    assert!(first.error.is_empty(), "{}", first.error);
+    assert_eq!(second.content, first.content);
+    assert_eq!(requests.lock().unwrap().targets.len(), 2);
+    assert_eq!(
+        second.canonical_url.as_deref(),
+        Some(format!("{url}/transit").as_str())
+    );
+}
+
+#[tokio::test]
+async fn wrapped_responses_do_not_answer_the_article_url() {
+    let _lock = TEST_LOCK.lock().await;
+    let dir = TempCacheDir::new("wrapped");
+    let (url, requests) = spawn_counting_server("200 OK");
+    let scraper = cached_scraper(ScrapeCache::new(&dir.0));
+
+    // The AMP variant normalizes to the article URL; in another language
+    // the post layer misses, and its response must not be reused either.
+    let amp = scraper
+        .scrape(&format!("{url}/transit/amp"), "spanish")
+        .await;
+    let events = cache_events(async {
+        let live = scraper.scrape(&format!("{url}/transit"), "english").await;
+        assert!(live.error.is_empty(), "{}", live.error);
+        assert_eq!(
+            live.provenance.expect("provenance").final_url,
+            format!("{url}/transit")
+        );
+    })
+    .await;
+
+    assert!(amp.error.is_empty(), "{}", amp.error);
+    // Two redirect checks and two fetches: the AMP response was not stored.
+    assert_eq!(requests.lock().unwrap().targets.len(), 4);
+    assert_eq!(
+        events,
+        [(false, CacheKind::Post), (false, CacheKind::Response)]
+    );
+}
+
+/// Answers `web.archive.org` snapshot URLs without the network, with
+/// text the live article no longer has.
+struct ArchivedCopy;
+
+impl SourceHandler for ArchivedCopy {
+    fn name(&self) -> &str {
+        "archived-copy"
+    }
+
+    fn matches(&self, url: &str) -> bool {
+        url.starts_with("https://web.archive.org/web/")
+    }
+
+    fn scrape<'a>(&'a self, _url: &'a str, _ctx: &'a ScrapeContext<'a>) -> HandlerFuture<'a> {
+        Box::pin(async move {
+            Post {
+                title: "Archived story".to_string(),
+                content: "The first draft of the transit plan.".to_string(),
+                ..Post::default()
+            }
+        })
+    }
+}
+
+#[tokio::test]
+async fn archive_snapshots_do_not_answer_for_the_live_article() {
+    let _lock = TEST_LOCK.lock().await;
+    let dir = TempCacheDir::new("snapshot");
+    let (url, requests) = spawn_counting_server("200 OK");
+    let scraper = Scraper::new(
+        hermetic_options()
+            .handler(ArchivedCopy)
+            .cache(Some(ScrapeCache::new(&dir.0))),
+    );
+
+    let snapshot = scraper
+        .scrape(
+            &format!("https://web.archive.org/web/20250101000000/{url}/transit"),
+            "english",
+        )
+        .await;
+    let events = cache_events(async {
+        let live = scraper.scrape(&format!("{url}/transit"), "english").await;
+        assert!(live.error.is_empty(), "{}", live.error);
+        assert_eq!(live.title, "Cached story");
+    })
+    .await;
+
+    assert_eq!(snapshot.title, "Archived story");
+    assert_eq!(
+        events,
+        [(false, CacheKind::Post), (false, CacheKind::Response)]
+    );
+    // Only the live scrape reached the server.
+    assert_eq!(requests.lock().unwrap().targets, ["/transit", "/transit"]);
+}
+
+#[tokio::test]
+async fn redirects_and_declared_canonicals_share_the_post() {
+    let _lock = TEST_LOCK.lock().await;
+    let dir = TempCacheDir::new("aliases");
+    let (base, requests) = serve_logged(|base| {
+        vec![
+            Route::html("/transit", article_page("Cached story")),
+            Route::html("/short", "")
+                .status("301 Moved Permanently")
+                .header("Location", &format!("{base}/transit")),
+            Route::html(
+                "/story",
+                article_page("Bridge story").replace(
+                    "</title>",
+                    &format!(r#"</title><link rel="canonical" href="{base}/bridge">"#),
+                ),
+            ),
+        ]
+    });
+    let scraper = cached_scraper(ScrapeCache::new(&dir.0));
+
+    let transit = scraper.scrape(&format!("{base}/transit"), "english").await;
+    assert!(transit.error.is_empty(), "{}", transit.error);
+    let story = scraper.scrape(&format!("{base}/story"), "english").await;
+    assert!(story.error.is_empty(), "{}", story.error);
+    let mut posts = Vec::new();
+    let events = cache_events(async {
+        // Missed, then found where the redirect ends; then cached as is.
+        posts.push(scraper.scrape(&format!("{base}/short"), "english").await);
+        posts.push(scraper.scrape(&format!("{base}/short"), "english").await);
+        // Stored under the canonical URL the story page declared.
+        posts.push(scraper.scrape(&format!("{base}/bridge"), "english").await);
+    })
+    .await;
+
+    assert_eq!(posts[0].title, "Cached story");
+    assert_eq!(posts[1].title, "Cached story");
+    assert_eq!(posts[2].title, "Bridge story");
+    assert_eq!(
+        events,
+        [
+            (false, CacheKind::Post),
+            (true, CacheKind::Post),
+            (true, CacheKind::Post),
+            (true, CacheKind::Post),
+        ]
+    );
+    assert_eq!(
+        requests.lock().unwrap().targets,
+        ["/transit", "/transit", "/story", "/story", "/short", "/transit"]
+    );
+}
+
+#[tokio::test]
+async fn other_language_misses_posts_but_reuses_the_response() {
+    let _lock = TEST_LOCK.lock().await;
+    let dir = TempCacheDir::new("language");
+    let (url, requests) = spawn_counting_server("200 OK");
+    let scraper = cached_scraper(ScrapeCache::new(&dir.0));
+
+    let first = scraper.scrape(&url, "english").await;
+    let events = cache_events(async {
+        let second = scraper.scrape(&url, "spanish").await;
+        assert!(second.error.is_empty(), "{}", second.error);
+    })
+    .await;
+
+    assert!(first.error.is_empty(), "{}", first.error);
+    // The second scrape only checks for redirects; the response is cached.
+    assert_eq!(requests.lock().unwrap().targets.len(), 3);
+    assert_eq!(
+        events,
+        [(false, CacheKind::Post), (true, CacheKind::Response)]
+    );
+}
+
+#[tokio::test]
+async fn output_changing_options_miss_posts_but_reuse_the_response() {
+    let _lock = TEST_LOCK.lock().await;
+    let dir = TempCacheDir::new("options");
+    let (url, requests) = spawn_counting_server("200 OK");
+    let first = cached_scraper(ScrapeCache::new(&dir.0))
+        .scrape(&url, "english")
+        .await;
+    assert!(first.error.is_empty(), "{}", first.error);
+
+    let defaults = hermetic_options();
+    for options in [
+        hermetic_options().max_article_pages(1),
+        hermetic_options().cms_api(!defaults.cms_api_enabled()),
+        hermetic_options().alternate_fallback(!defaults.alternate_fallback_enabled()),
+        hermetic_options().handler(TelegramHandler::default().recent_posts(3)),
+        hermetic_options().handler(RedditHandler::default().top_comments(5)),
+    ] {
+        let scraper = Scraper::new(options.cache(Some(ScrapeCache::new(&dir.0))));
+        let events = cache_events(async {
+            let post = scraper.scrape(&url, "english").await;
+            assert!(post.error.is_empty(), "{}", post.error);
+        })
+        .await;
+        assert_eq!(
+            events,
+            [(false, CacheKind::Post), (true, CacheKind::Response)]
+        );
+    }
+    assert_eq!(requests.lock().unwrap().targets.len(), 7);
+}
+
+#[tokio::test]
+async fn expired_entries_are_refetched() {
+    let _lock = TEST_LOCK.lock().await;
+    let dir = TempCacheDir::new("ttl");
+    let (url, requests) = spawn_counting_server("200 OK");
+    let scraper = cached_scraper(ScrapeCache::new(&dir.0).ttl(Duration::from_millis(200)));
+
+    scraper.scrape(&url, "english").await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    let post = scraper.scrape(&url, "english").await;
+
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(requests.lock().unwrap().targets.len(), 4);
+}
+
+#[tokio::test]
+async fn failed_scrapes_are_not_cached() {
+    let _lock = TEST_LOCK.lock().await;
+    let dir = TempCacheDir::new("failure");
+    let (url, requests) = spawn_counting_server("503 Service Unavailable");
+    let scraper = cached_scraper(ScrapeCache::new(&dir.0));
+
+    let first = scraper.scrape(&url, "english").await;
+    let second = scraper.scrape(&url, "english").await;
+
+    assert!(!first.error.is_empty());
+    assert!(!second.error.is_empty());
+    assert_eq!(requests.lock().unwrap().targets.len(), 4);
+}
+
+#[tokio::test]
+async fn corrupt_entries_are_misses() {
+    let _lock = TEST_LOCK.lock().await;
+    let dir = TempCacheDir::new("corrupt");
+    let (url, requests) = spawn_counting_server("200 OK");
+    let scraper = cached_scraper(ScrapeCache::new(&dir.0));
+
+    scraper.scrape(&url, "english").await;
+    for layer in ["posts", "responses"] {
+        for entry in std::fs::read_dir(dir.0.join(layer)).expect("cache layer dir") {
+            std::fs::write(entry.expect("entry").path(), b"{not json").expect("corrupt entry");
+        }
+    }
+    let post = scraper.scrape(&url, "english").await;
+
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(requests.lock().unwrap().targets.len(), 4);
+}
+
+#[tokio::test]
+async fn cache_is_seeded_from_the_environment() {
+    let _lock = TEST_LOCK.lock().await;
+    {
+        let _dir = EnvVarGuard::set(UNINEWS_CACHE_DIR_ENV, "  ");
+        assert_eq!(ScrapeCache::from_env(), None);
+    }
+
+    let _dir = EnvVarGuard::set(UNINEWS_CACHE_DIR_ENV, "/tmp/uninews-cache");
+    {
+        let _ttl = EnvVarGuard::set(UNINEWS_CACHE_TTL_SECS_ENV, "60");
+        let cache = ScrapeOptions::from_env()
+            .scrape_cache()
+            .cloned()
+            .expect("cache from env");
+        assert_eq!(cache.dir(), std::path::Path::new("/tmp/uninews-cache"));
+        assert_eq!(cache.ttl_duration(), Duration::from_secs(60));
+    }
+    let _ttl = EnvVarGuard::set(UNINEWS_CACHE_TTL_SECS_ENV, "0");
+    assert_eq!(
+        ScrapeCache::from_env().map(|cache| cache.ttl_duration()),
+        Some(DEFAULT_CACHE_TTL)
+    );
+    assert_eq!(ScrapeOptions::from_env().cache(None).scrape_cache(), None);
+}
@@ -0,0 +1,261 @@
+//! Integration tests for canonical URLs: tracking-parameter stripping, AMP
+//! and archive.org unwrapping, shortener detection, the page's declared
+//! canonical (`<link rel="canonical">` over `og:url`), the
+//! `Post::canonical_url` recorded by a loopback scrape, redirect resolution
+//! on an ordinary host, and batch dedup on the canonical URL.
+//!
+//! Hermetic: in-memory URLs and documents plus `common::serve_with`
+//! loopback servers standing in for the outlet, scraped with
+//! `common::hermetic_options`. Real shortener links are only classified,
+//! never followed.
+
+mod common;
+
+use futures::StreamExt;
+use uninews::{
+    canonical_url, is_short_link, is_tracking_parameter, normalize_url, FeedItem, Scraper,
+};
+
+use common::{hermetic_options, serve_with, Route};
+
+#[test]
+fn tracking_parameters_and_fragments_are_dropped() {
+    assert_eq!(
+        normalize_url(
+            "https://news.example/transit?utm_source=twitter&utm_medium=social&fbclid=abc#comments"
+        ),
+        "https://news.example/transit"
+    );
+    // Other parameters keep their order and spelling.
+    assert_eq!(
+        normalize_url("https://news.example/story.php?id=42&gclid=x&page=2&mc_cid=y"),
+        "https://news.example/story.php?id=42&page=2"
+    );
+    assert_eq!(
+        normalize_url("https://News.Example/transit?b=2&a=1"),
+        "https://news.example/transit?b=2&a=1"
+    );
+    assert_eq!(
+        normalize_url("  https://news.example/transit  "),
+        "https://news.example/transit"
+    );
+
+    assert!(is_tracking_parameter("utm_campaign"));
+    assert!(is_tracking_parameter("pk_kwd"));
+    assert!(is_tracking_parameter("MSCLKID"));
+    assert!(!is_tracking_parameter("id"));
+    assert!(!is_tracking_parameter("utm"));
+}
+
+#[test]
+fn amp_variants_fold_into_the_article_url() {
+    let article = "https://news.example/2026/10/transit";
+    for amp in [
+        "https://news.example/2026/10/transit/amp",
+        "https://news.example/amp/2026/10/transit",
+        "https://news.example/2026/10/transit?amp",
+        "https://news.example/2026/10/transit?amp=1",
+        "https://news.example/2026/10/transit?outputType=amp",
+        "https://news-example.cdn.ampproject.org/c/s/news.example/2026/10/transit/amp",
+        "https://news-example.cdn.ampproject.org/v/s/news.example/2026/10/transit?amp=1",
+        "https://www.google.com/amp/s/news.example/2026/10/transit/amp",
+    ] {
+        assert_eq!(normalize_url(amp), article, "{amp}");
+    }
+    assert_eq!(
+        normalize_url("https://news.example/2026/10/transit.amp.html"),
+        "https://news.example/2026/10/transit.html"
+    );
+    // `amp` as a value of another parameter, or inside a word, is kept.
+    assert_eq!(
+        normalize_url("https://news.example/search?q=amp"),
+        "https://news.example/search?q=amp"
+    );
+    assert_eq!(
+        normalize_url("https://news.example/ampersand-history"),
+        "https://news.example/ampersand-history"
+    );
+}
+
+#[test]
+fn archive_snapshots_unwrap_to_the_original_url() {
+    assert_eq!(
+        normalize_url(
+            "https://web.archive.org/web/20261015063000/https://news.example/transit?utm_source=rss"
+        ),
+        "https://news.example/transit"
+    );
+    assert_eq!(
+        normalize_url("https://web.archive.org/web/20261015063000id_/http://news.example/transit"),
+        "http://news.example/transit"
+    );
+    // Archive pages that are not snapshots stay as they are.
+    assert_eq!(
+        normalize_url("https://web.archive.org/web/*/news.example*"),
+        "https://web.archive.org/web/*/news.example*"
+    );
+}
+
+#[test]
+fn other_urls_pass_through() {
+    assert_eq!(normalize_url("not a url"), "not a url");
+    assert_eq!(
+        normalize_url("mailto:desk@news.example?subject=utm_source"),
+        "mailto:desk@news.example?subject=utm_source"
+    );
+    assert_eq!(
+        normalize_url("https://news.example/"),
+        "https://news.example/"
+    );
+}
+
+#[test]
+fn short_links_are_a_shortener_host_and_one_path_segment() {
+    assert!(is_short_link("https://bit.ly/3xYzAbc"));
+    assert!(is_short_link("https://t.co/AbC123"));
+    assert!(is_short_link("http://www.tinyurl.com/transit"));
+    assert!(is_short_link("https://nyti.ms/48aBcD"));
+    assert!(!is_short_link("https://bit.ly/"));
+    assert!(!is_short_link("https://bit.ly/a/b"));
+    assert!(!is_short_link("https://news.example/3xYzAbc"));
+    assert!(!is_short_link("not a url"));
+}
+
+#[test]
+fn declared_canonicals_win_over_og_url_and_the_page_url() {
+    let page = "https://news.example/transit?utm_source=rss";
+    assert_eq!(
+        canonical_url(
+            page,
+            r#"<html><head>
+<meta property="og:url" content="https://news.example/og/transit">
+<link rel="canonical" href="/2026/10/transit?utm_medium=web">
+</head></html>"#
+        ),
+        "https://news.example/2026/10/transit"
+    );
+    assert_eq!(
+        canonical_url(
+            page,
+            r#"<meta name="og:url" content="https://news.example/og/transit">"#
+        ),
+        "https://news.example/og/transit"
+    );
+    assert_eq!(
+        canonical_url(page, "<html></html>"),
+        "https://news.example/transit"
+    );
+    // A canonical pointing at the home page from an article is a theme
+    // bug; `og:url` (or the page itself) is used instead.
+    assert_eq!(
+        canonical_url(
+            page,
+            r#"<link rel="canonical" href="https://news.example/">
+<meta property="og:url" content="https://news.example/og/transit">"#
+        ),
+        "https://news.example/og/transit"
+    );
+    assert_eq!(
+        canonical_url(page, r#"<link rel="canonical" href="javascript:void(0)">"#),
+        "https://news.example/transit"
+    );
+}
+
+/// The transit article, with `head` markup.
+fn article_page(head: &str) -> String {
+    format!(
+        "<html><head><title>Transit plan approved</title>{head}</head><body><article>{}</article></body></html>",
+        "<p>The city council voted on Tuesday to fund the new transit lines.</p>".repeat(8)
+    )
+}
+
+#[tokio::test]
+async fn scraped_posts_carry_the_normalized_canonical_url() {
+    let base = serve_with(|base| {
+        vec![
+            Route::html(
+                "/transit",
+                article_page(&format!(
+                    r#"<link rel="canonical" href="{base}/2026/10/transit?utm_source=site">"#
+                )),
+            )
+            .query(""),
+            Route::html("/bridge", article_page("")).query(""),
+        ]
+    });
+    let scraper = Scraper::new(hermetic_options());
+
+    let post = scraper
+        .scrape(&format!("{base}/transit?utm_source=twitter"), "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(
+        post.canonical_url.as_deref(),
+        Some(format!("{base}/2026/10/transit").as_str())
+    );
+
+    // No declared canonical: the page's own URL, normalized.
+    let post = scraper
+        .scrape(&format!("{base}/bridge?fbclid=abc#top"), "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(
+        post.canonical_url.as_deref(),
+        Some(format!("{base}/bridge").as_str())
+    );
+    let json = serde_json::to_value(&post).expect("serialize post");
+    assert_eq!(json["canonical_url"], format!("{base}/bridge"));
+}
+
+#[tokio::test]
+async fn redirects_resolve_on_any_host() {
+    let base = serve_with(|base| {
+        vec![
+            Route::html("/go/transit", "")
+                .status("302 Found")
+                .header("Location", &format!("{base}/transit?utm_medium=social")),
+            Route::html("/transit", article_page("")),
+        ]
+    });
+    let scraper = Scraper::new(hermetic_options());
+
+    assert_eq!(
+        scraper.resolve_url(&format!("{base}/go/transit")).await,
+        format!("{base}/transit")
+    );
+    // No redirect: only normalized.
+    assert_eq!(
+        scraper
+            .resolve_url(&format!("{base}/transit?fbclid=abc"))
+            .await,
+        format!("{base}/transit")
+    );
+}
+
+#[tokio::test]
+async fn batches_yield_one_post_per_canonical_url() {
+    let base = serve_with(|base| {
+        let head = format!(r#"<link rel="canonical" href="{base}/2026/10/transit">"#);
+        vec![
+            Route::html("/transit", article_page(&head)),
+            Route::html("/print/transit", article_page(&head)),
+            Route::html("/bridge", article_page("")),
+        ]
+    });
+    let items = ["/transit", "/print/transit", "/bridge"].map(|path| FeedItem {
+        link: format!("{base}{path}"),
+        ..FeedItem::default()
+    });
+
+    let mut posts: Vec<_> = Scraper::new(hermetic_options())
+        .scrape_feed_items(items, "english")
+        .map(|(_, post)| post.canonical_url.unwrap_or_default())
+        .collect()
+        .await;
+    posts.sort();
+
+    assert_eq!(
+        posts,
+        [format!("{base}/2026/10/transit"), format!("{base}/bridge")]
+    );
+}
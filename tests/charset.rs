@@ -0,0 +1,179 @@
+//! Integration tests for charset detection and decoding: the BOM,
+//! `Content-Type`, `<meta>` / `http-equiv`, and XML declarations in
+//! precedence order, the statistical fallback, and non-UTF-8 pages
+//! scraped end to end with the encoding reported in `FetchSucceeded`.
+//!
+//! Hermetic: in-memory bodies plus one loopback server per page with
+//! Playwright and archive.org disabled and deterministic conversion.
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use encoding_rs::{EUC_KR, SHIFT_JIS, WINDOWS_1251};
+use uninews::{
+    decode_body, detect_charset, set_event_listener, CharsetSource, ScrapeEvent, Scraper,
+};
+
+use common::{hermetic_options, serve_with, Route};
+
+/// A page in `encoding` whose article repeats `sentence`, with `head`
+/// (a `<meta>` declaration, or nothing) in its `<head>`.
+fn page(encoding: &'static encoding_rs::Encoding, head: &str, sentence: &str) -> Vec<u8> {
+    let html = format!(
+        "<html><head>{head}<title>{sentence}</title></head><body><article>{}</article></body></html>",
+        format!("<p>{sentence}</p>").repeat(80)
+    );
+    encoding.encode(&html).0.into_owned()
+}
+
+const RUSSIAN: &str = "Городской совет утвердил план развития общественного транспорта.";
+const JAPANESE: &str = "市議会は火曜日に新しい交通計画を承認しました。";
+const KOREAN: &str = "시의회는 화요일에 새로운 교통 계획을 승인했습니다.";
+
+#[test]
+fn declarations_are_honored_in_precedence_order() {
+    let charset = |body: &[u8], content_type: Option<&str>| {
+        let detected = detect_charset(body, content_type, None);
+        (detected.encoding, detected.source)
+    };
+
+    assert_eq!(
+        charset(
+            b"\xEF\xBB\xBF<meta charset=\"koi8-r\">",
+            Some("text/html; charset=gbk")
+        ),
+        ("UTF-8", CharsetSource::ByteOrderMark)
+    );
+    assert_eq!(
+        charset(
+            b"<meta charset=\"koi8-r\">",
+            Some("text/html; Charset=\"Shift_JIS\"")
+        ),
+        ("Shift_JIS", CharsetSource::ContentType)
+    );
+    assert_eq!(
+        charset(
+            b"<html><head><META CHARSET=euc-kr></head>",
+            Some("text/html")
+        ),
+        ("EUC-KR", CharsetSource::Meta)
+    );
+    assert_eq!(
+        charset(
+            b"<meta http-equiv=\"Content-Type\" content=\"text/html; charset=windows-1251\" />",
+            None
+        ),
+        ("windows-1251", CharsetSource::Meta)
+    );
+    assert_eq!(
+        charset(b"<?xml version=\"1.0\" encoding='ISO-8859-1'?><rss/>", None),
+        ("windows-1252", CharsetSource::XmlDeclaration)
+    );
+    // A UTF-16 <meta> declaration on a BOM-less page is wrong by
+    // construction; browsers read it as UTF-8.
+    assert_eq!(
+        charset(b"<meta charset=\"utf-16\">", None),
+        ("UTF-8", CharsetSource::Meta)
+    );
+    // Unknown labels are skipped.
+    assert_eq!(
+        charset(
+            b"<meta charset=\"no-such-charset\"><p>plain</p>",
+            Some("text/html; charset=bogus")
+        )
+        .1,
+        CharsetSource::Detected
+    );
+}
+
+#[test]
+fn undeclared_pages_are_detected_statistically() {
+    let body = page(SHIFT_JIS, "", JAPANESE);
+    let (text, charset) = decode_body(&body, Some("text/html"), Some("https://news.example.jp/a"));
+    assert_eq!(charset.encoding, "Shift_JIS");
+    assert_eq!(charset.source, CharsetSource::Detected);
+    assert!(text.contains(JAPANESE));
+
+    let body = page(WINDOWS_1251, "", RUSSIAN);
+    let (text, charset) = decode_body(&body, None, Some("https://gazeta.example.ru/a"));
+    assert_eq!(charset.encoding, "windows-1251");
+    assert!(text.contains(RUSSIAN));
+
+    let (text, charset) = decode_body("<p>Café déjà vu</p>".as_bytes(), None, None);
+    assert_eq!(charset.encoding, "UTF-8");
+    assert_eq!(text, "<p>Café déjà vu</p>");
+}
+
+#[test]
+fn byte_order_marks_are_dropped_when_decoding() {
+    let (text, charset) = decode_body(b"\xFF\xFE<\0p\0>\0", Some("text/html"), None);
+    assert_eq!(charset.encoding, "UTF-16LE");
+    assert_eq!(text, "<p>");
+}
+
+/// Serve `body` with `content_type` as `/news/story`.
+fn serve(content_type: &'static str, body: Vec<u8>) -> String {
+    serve_with(|_| vec![Route::new("/news/story", content_type, body)])
+}
+
+#[tokio::test]
+async fn non_utf8_pages_scrape_without_mojibake() {
+    let scraper = Scraper::new(hermetic_options());
+    let cases = [
+        (
+            serve(
+                "text/html",
+                page(WINDOWS_1251, "<meta charset=\"windows-1251\">", RUSSIAN),
+            ),
+            RUSSIAN,
+            "windows-1251",
+        ),
+        (
+            serve(
+                "text/html; charset=Shift_JIS",
+                page(SHIFT_JIS, "", JAPANESE),
+            ),
+            JAPANESE,
+            "Shift_JIS",
+        ),
+        (
+            serve(
+                "text/html",
+                page(
+                    EUC_KR,
+                    "<meta http-equiv=\"Content-Type\" content=\"text/html; charset=euc-kr\">",
+                    KOREAN,
+                ),
+            ),
+            KOREAN,
+            "EUC-KR",
+        ),
+    ];
+
+    let events: Arc<Mutex<Vec<ScrapeEvent>>> = Arc::default();
+    let sink = Arc::clone(&events);
+    set_event_listener(Some(Arc::new(move |event: &ScrapeEvent| {
+        sink.lock().unwrap().push(event.clone());
+    })));
+
+    for (base, sentence, encoding) in &cases {
+        let url = format!("{}/news/story", base);
+        let post = scraper.scrape(&url, "english").await;
+
+        assert!(post.error.is_empty(), "{}", post.error);
+        assert_eq!(post.title, *sentence);
+        assert!(post.content.contains(sentence), "{}", post.content);
+
+        let reported = events.lock().unwrap().iter().find_map(|event| match event {
+            ScrapeEvent::FetchSucceeded {
+                url: fetched,
+                encoding,
+                ..
+            } if *fetched == url => encoding.clone(),
+            _ => None,
+        });
+        assert_eq!(reported.as_deref(), Some(*encoding));
+    }
+    set_event_listener(None);
+}
@@ -0,0 +1,374 @@
+//! Integration tests for the WordPress / Ghost REST API shortcut: CMS
+//! detection from page markup, API URL construction, the post parsers, and
+//! the web pipeline reading CMS-backed pages from their API — falling back
+//! to DOM extraction when the API cannot serve the post or the shortcut is
+//! off.
+//!
+//! Hermetic: in-memory documents plus one `common::serve_with` loopback
+//! server per test standing in for the blog and its API, scraped with
+//! `common::hermetic_options` and the shortcut on.
+
+mod common;
+
+use serde_json::json;
+use uninews::{
+    detect_cms_api, parse_ghost_post, parse_wordpress_post, CmsApi, CmsKind, FallbackStage, Scraper,
+};
+
+use common::{hermetic_options, serve_with, Route};
+
+/// A themed blog page with `head` markup, whose DOM body is `body_text`
+/// repeated inside navigation and sharing noise.
+fn blog_page(head: &str, body_text: &str) -> String {
+    format!(
+        r#"<html><head><title>Transit plan approved – City Blog</title>{head}</head><body>
+<nav><a href="/">Home</a> <a href="/about/">About</a></nav>
+<article><h1>Transit plan approved</h1>{}<div class="share">Share on Facebook</div></article>
+<aside>Related posts</aside></body></html>"#,
+        format!("<p>{body_text}</p>").repeat(40)
+    )
+}
+
+/// A WordPress REST API post as returned with `_embed`.
+fn wordpress_post(base: &str) -> serde_json::Value {
+    json!({
+        "id": 123,
+        "date": "2026-10-16T11:00:00",
+        "date_gmt": "2026-10-16T09:00:00",
+        "modified_gmt": "2026-10-16T10:30:00",
+        "slug": "transit-plan",
+        "link": format!("{base}/2026/10/transit-plan/"),
+        "title": {"rendered": "Council&#8217;s transit plan &amp; what it means"},
+        "content": {
+            "rendered": "<p>The council approved <strong>two new lines</strong> on Tuesday.</p>\n<p>Construction starts in 2027.</p>",
+            "protected": false
+        },
+        "excerpt": {"rendered": "<p>Two new lines &hellip;</p>\n", "protected": false},
+        "_embedded": {
+            "author": [{"id": 2, "name": "Dana Reporter"}],
+            "wp:featuredmedia": [{"id": 9, "source_url": "https://cdn.example/plan.jpg"}],
+            "wp:term": [
+                [{"id": 3, "name": "City &amp; Region", "taxonomy": "category"}],
+                [
+                    {"id": 4, "name": "transit", "taxonomy": "post_tag"},
+                    {"id": 5, "name": "budget", "taxonomy": "post_tag"}
+                ]
+            ]
+        }
+    })
+}
+
+#[test]
+fn cms_backends_are_detected_from_page_markup() {
+    let page = "https://blog.example/2026/10/transit-plan/";
+    let detect = |head: &str| detect_cms_api(page, &blog_page(head, "Body"));
+
+    assert_eq!(
+        detect(r#"<link rel="https://api.w.org/" href="/wp-json/">"#),
+        Some(CmsApi::WordPress {
+            api_root: "https://blog.example/wp-json/".to_string(),
+            post_endpoint: None,
+        })
+    );
+    assert_eq!(
+        detect(
+            r#"<link rel="https://api.w.org/" href="https://blog.example/wp-json/">
+<link rel="alternate" title="JSON" type="application/json" href="https://blog.example/wp-json/wp/v2/posts/123">"#
+        ),
+        Some(CmsApi::WordPress {
+            api_root: "https://blog.example/wp-json/".to_string(),
+            post_endpoint: Some("https://blog.example/wp-json/wp/v2/posts/123".to_string()),
+        })
+    );
+    assert_eq!(
+        detect(r#"<meta name="generator" content="WordPress 6.8.1">"#),
+        Some(CmsApi::WordPress {
+            api_root: "https://blog.example/wp-json/".to_string(),
+            post_endpoint: None,
+        })
+    );
+
+    let ghost = CmsApi::Ghost {
+        api_root: "https://blog.example/ghost/api/content/".to_string(),
+        key: "22444f78447824223cefc48062".to_string(),
+    };
+    assert_eq!(
+        detect(
+            r#"<meta name="generator" content="Ghost 5.96">
+<script defer src="https://cdn.jsdelivr.net/ghost/portal@~2.44/umd/portal.min.js" data-i18n="true" data-ghost="https://blog.example/" data-key="22444f78447824223cefc48062" data-api="https://blog.example/ghost/api/content/" crossorigin="anonymous"></script>"#
+        ),
+        Some(ghost.clone())
+    );
+    assert_eq!(
+        detect(
+            r#"<script defer src="https://cdn.jsdelivr.net/ghost/sodo-search@~1.5/umd/sodo-search.min.js" data-key="22444f78447824223cefc48062" data-styles="x" data-sodo-search="https://blog.example/" crossorigin="anonymous"></script>"#
+        ),
+        Some(ghost)
+    );
+    assert_eq!(detect(""), None);
+    // Ghost without a published Content API key cannot be read.
+    assert_eq!(
+        detect(r#"<meta name="generator" content="Ghost 5.96">"#),
+        None
+    );
+}
+
+#[test]
+fn post_urls_follow_the_slug_id_or_linked_endpoint() {
+    let wordpress = |api_root: &str| CmsApi::WordPress {
+        api_root: api_root.to_string(),
+        post_endpoint: None,
+    };
+    let api = wordpress("https://blog.example/wp-json/");
+    assert_eq!(
+        api.post_urls("https://blog.example/2026/10/transit-plan/"),
+        [
+            "https://blog.example/wp-json/wp/v2/posts?slug=transit-plan&_embed",
+            "https://blog.example/wp-json/wp/v2/pages?slug=transit-plan&_embed"
+        ]
+    );
+    assert_eq!(
+        api.post_urls("https://blog.example/?p=123"),
+        ["https://blog.example/wp-json/wp/v2/posts/123?_embed"]
+    );
+    assert!(api.post_urls("https://blog.example/").is_empty());
+    assert_eq!(
+        wordpress("https://blog.example/?rest_route=/")
+            .post_urls("https://blog.example/transit-plan/amp/")[0],
+        "https://blog.example/?rest_route=/wp/v2/posts&slug=transit-plan&_embed"
+    );
+    let linked = CmsApi::WordPress {
+        api_root: "https://blog.example/wp-json/".to_string(),
+        post_endpoint: Some("https://blog.example/wp-json/wp/v2/posts/123".to_string()),
+    };
+    assert_eq!(
+        linked.post_urls("https://blog.example/transit-plan/"),
+        ["https://blog.example/wp-json/wp/v2/posts/123?_embed"]
+    );
+
+    let ghost = CmsApi::Ghost {
+        api_root: "https://blog.example/ghost/api/content/".to_string(),
+        key: "abc123".to_string(),
+    };
+    assert_eq!(
+        ghost.post_urls("https://blog.example/transit-plan/")[0],
+        "https://blog.example/ghost/api/content/posts/slug/transit-plan/?key=abc123&include=authors,tags"
+    );
+}
+
+#[test]
+fn wordpress_posts_parse_with_embedded_terms() {
+    let post = parse_wordpress_post(&json!([wordpress_post("https://blog.example")]).to_string())
+        .expect("post");
+    assert_eq!(post.cms, CmsKind::WordPress);
+    assert_eq!(post.title, "Council\u{2019}s transit plan & what it means");
+    assert!(post.html.starts_with("<p>The council approved"));
+    assert_eq!(post.excerpt.as_deref(), Some("Two new lines \u{2026}"));
+    assert_eq!(post.authors, ["Dana Reporter"]);
+    assert_eq!(post.date_published.as_deref(), Some("2026-10-16T09:00:00Z"));
+    assert_eq!(post.date_modified.as_deref(), Some("2026-10-16T10:30:00Z"));
+    assert_eq!(post.categories, ["City & Region"]);
+    assert_eq!(post.tags, ["transit", "budget"]);
+    assert_eq!(
+        post.featured_image.as_deref(),
+        Some("https://cdn.example/plan.jpg")
+    );
+    let metadata = post.metadata();
+    assert_eq!(metadata.schema_type.as_deref(), Some("BlogPosting"));
+    assert_eq!(metadata.section.as_deref(), Some("City & Region"));
+    assert_eq!(metadata.keywords, ["transit", "budget"]);
+
+    assert!(parse_wordpress_post("[]").unwrap_err().contains("no post"));
+    let mut protected = wordpress_post("https://blog.example");
+    protected["content"]["protected"] = json!(true);
+    assert!(parse_wordpress_post(&protected.to_string())
+        .unwrap_err()
+        .contains("password-protected"));
+    assert!(parse_wordpress_post(
+        r#"{"code": "rest_post_invalid_id", "message": "Invalid post ID.", "data": {"status": 404}}"#
+    )
+    .unwrap_err()
+    .contains("Invalid post ID."));
+}
+
+#[test]
+fn ghost_posts_parse_without_internal_tags() {
+    let post = parse_ghost_post(
+        &json!({
+            "posts": [{
+                "id": "6710",
+                "slug": "transit-plan",
+                "title": "Transit plan approved",
+                "html": "<p>The council approved two new lines.</p>",
+                "custom_excerpt": null,
+                "excerpt": "The council approved two new lines.",
+                "url": "https://blog.example/transit-plan/",
+                "canonical_url": null,
+                "feature_image": "https://blog.example/content/images/plan.jpg",
+                "published_at": "2026-10-16T09:00:00.000+00:00",
+                "updated_at": "2026-10-16T10:30:00.000+00:00",
+                "authors": [{"name": "Dana Reporter"}, {"name": "Sam Editor"}],
+                "tags": [
+                    {"name": "City", "visibility": "public"},
+                    {"name": "#newsletter", "visibility": "internal"},
+                    {"name": "transit", "visibility": "public"}
+                ],
+                "primary_tag": {"name": "City", "visibility": "public"}
+            }]
+        })
+        .to_string(),
+    )
+    .expect("post");
+    assert_eq!(post.cms, CmsKind::Ghost);
+    assert_eq!(post.authors, ["Dana Reporter", "Sam Editor"]);
+    assert_eq!(
+        post.url.as_deref(),
+        Some("https://blog.example/transit-plan/")
+    );
+    assert_eq!(
+        post.excerpt.as_deref(),
+        Some("The council approved two new lines.")
+    );
+    assert_eq!(
+        post.date_published.as_deref(),
+        Some("2026-10-16T09:00:00.000+00:00")
+    );
+    assert_eq!(post.categories, ["City"]);
+    assert_eq!(post.tags, ["City", "transit"]);
+
+    assert!(parse_ghost_post(
+        r#"{"errors": [{"message": "Resource not found error, cannot read post.", "type": "NotFoundError"}]}"#
+    )
+    .unwrap_err()
+    .contains("Resource not found"));
+    assert!(
+        parse_ghost_post(r#"{"posts": [{"title": "Members only", "html": ""}]}"#)
+            .unwrap_err()
+            .contains("no public content")
+    );
+}
+
+const WORDPRESS_HEAD: &str = r#"<link rel="https://api.w.org/" href="/wp-json/">"#;
+const DOM_TEXT: &str = "Theme markup around the post, as scraped from the DOM instead.";
+
+#[tokio::test]
+async fn wordpress_pages_are_read_from_the_rest_api() {
+    let base = serve_with(|base| {
+        vec![
+            Route::html(
+                "/2026/10/transit-plan/",
+                blog_page(WORDPRESS_HEAD, DOM_TEXT),
+            ),
+            Route::json(
+                "/wp-json/wp/v2/posts?slug=transit-plan&_embed",
+                json!([wordpress_post(base)]).to_string(),
+            ),
+        ]
+    });
+    let url = format!("{}/2026/10/transit-plan/", base);
+
+    let post = Scraper::new(hermetic_options().cms_api(true))
+        .scrape(&url, "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(post.title, "Council\u{2019}s transit plan & what it means");
+    assert!(
+        post.content
+            .contains("The council approved **two new lines** on Tuesday."),
+        "{}",
+        post.content
+    );
+    assert!(!post.content.contains("Theme markup"), "{}", post.content);
+    assert!(
+        !post.content.contains("Share on Facebook"),
+        "{}",
+        post.content
+    );
+    assert_eq!(post.author.as_deref(), Some("Dana Reporter"));
+    assert_eq!(
+        post.publication_date.as_deref(),
+        Some("2026-10-16T09:00:00Z")
+    );
+    assert_eq!(post.featured_image_url, "https://cdn.example/plan.jpg");
+    let metadata = post.metadata.expect("metadata");
+    assert_eq!(metadata.section.as_deref(), Some("City & Region"));
+    assert_eq!(metadata.keywords, ["transit", "budget"]);
+
+    let provenance = post.provenance.expect("provenance");
+    assert_eq!(provenance.source, FallbackStage::CmsApi);
+    assert_eq!(provenance.final_url, url);
+    assert_eq!(provenance.canonical_url.as_deref(), Some(url.as_str()));
+    assert_eq!(provenance.http_status, Some(200));
+
+    // With the shortcut off, the same page goes through DOM extraction.
+    let post = Scraper::new(hermetic_options().cms_api(false))
+        .scrape(&url, "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(post.content.contains("Theme markup"), "{}", post.content);
+    assert_eq!(
+        post.provenance.expect("provenance").source,
+        FallbackStage::PlainFetch
+    );
+}
+
+#[tokio::test]
+async fn ghost_pages_are_read_from_the_content_api() {
+    let base = serve_with(|base| {
+        let head = format!(
+            r#"<script defer src="/portal.min.js" data-ghost="{base}/" data-key="abc123" data-api="{base}/ghost/api/content/"></script>"#
+        );
+        vec![
+            Route::html("/transit-plan/", blog_page(&head, DOM_TEXT)),
+            Route::json(
+                "/ghost/api/content/posts/slug/transit-plan/?key=abc123&include=authors,tags",
+                json!({"posts": [{
+                    "title": "Transit plan approved",
+                    "html": "<p>Read from the Ghost Content API.</p>",
+                    "url": format!("{base}/transit-plan/"),
+                    "published_at": "2026-10-16T09:00:00.000+00:00",
+                    "authors": [{"name": "Dana Reporter"}],
+                    "tags": [{"name": "City", "visibility": "public"}],
+                    "primary_tag": {"name": "City", "visibility": "public"}
+                }]})
+                .to_string(),
+            ),
+        ]
+    });
+
+    let post = Scraper::new(hermetic_options().cms_api(true))
+        .scrape(&format!("{}/transit-plan/", base), "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(post.title, "Transit plan approved");
+    assert_eq!(post.content.trim(), "Read from the Ghost Content API.");
+    assert_eq!(post.author.as_deref(), Some("Dana Reporter"));
+    assert_eq!(
+        post.provenance.expect("provenance").source,
+        FallbackStage::CmsApi
+    );
+}
+
+#[tokio::test]
+async fn api_failures_fall_back_to_dom_extraction() {
+    // The API knows neither a post nor a page with this slug.
+    let base = serve_with(|_| {
+        vec![
+            Route::html(
+                "/2026/10/transit-plan/",
+                blog_page(WORDPRESS_HEAD, DOM_TEXT),
+            ),
+            Route::fallback("404 Not Found", "application/json; charset=UTF-8", "[]"),
+        ]
+    });
+
+    let post = Scraper::new(hermetic_options().cms_api(true))
+        .scrape(&format!("{}/2026/10/transit-plan/", base), "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(post.content.contains("Theme markup"), "{}", post.content);
+    assert_eq!(
+        post.provenance.expect("provenance").source,
+        FallbackStage::PlainFetch
+    );
+}
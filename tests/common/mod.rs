@@ -0,0 +1,232 @@
+//! Scaffolding shared by the integration tests that stand a loopback
+//! server in for an outlet or an API: canned [`Route`]s served by
+//! [`serve_with`] (or [`serve_logged`], which also records what the server
+//! saw), a [`refused_base`] nothing listens on, the [`hermetic_options`]
+//! every such scrape runs with, and an [`article_page`] long enough to pass
+//! content extraction.
+//!
+//! Each test binary uses its own subset of these helpers.
+#![allow(dead_code)]
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use uninews::{ConversionMode, ScrapeOptions};
+
+/// One canned response. By default a route answers requests whose target
+/// (path and query) is exactly [`Route::path`]; [`Route::query`] relaxes
+/// that to the path alone plus a query fragment, and [`Route::accept`]
+/// narrows it to requests whose `Accept` header contains a fragment.
+pub struct Route {
+    path: String,
+    query: Option<String>,
+    accept: &'static str,
+    status: &'static str,
+    content_type: &'static str,
+    headers: String,
+    delay: Duration,
+    body: Vec<u8>,
+}
+
+impl Route {
+    /// A `200 OK` response with `content_type` and `body` for `path`.
+    pub fn new(path: &str, content_type: &'static str, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            path: path.to_string(),
+            query: None,
+            accept: "",
+            status: "200 OK",
+            content_type,
+            headers: String::new(),
+            delay: Duration::ZERO,
+            body: body.into(),
+        }
+    }
+
+    /// An HTML page at `path`.
+    pub fn html(path: &str, body: impl Into<Vec<u8>>) -> Self {
+        Self::new(path, "text/html; charset=UTF-8", body)
+    }
+
+    /// A JSON document at `path`.
+    pub fn json(path: &str, body: impl Into<Vec<u8>>) -> Self {
+        Self::new(path, "application/json; charset=utf-8", body)
+    }
+
+    /// A catch-all response for requests no earlier route answers.
+    pub fn fallback(
+        status: &'static str,
+        content_type: &'static str,
+        body: impl Into<Vec<u8>>,
+    ) -> Self {
+        Self {
+            query: Some(String::new()),
+            ..Self::new("*", content_type, body).status(status)
+        }
+    }
+
+    /// Answer with `status` instead of `200 OK`.
+    pub fn status(self, status: &'static str) -> Self {
+        Self { status, ..self }
+    }
+
+    /// Also send the header `name: value`.
+    pub fn header(self, name: &str, value: &str) -> Self {
+        Self {
+            headers: format!("{}{name}: {value}\r\n", self.headers),
+            ..self
+        }
+    }
+
+    /// Wait `delay` before answering.
+    pub fn delay(self, delay: Duration) -> Self {
+        Self { delay, ..self }
+    }
+
+    /// Match on the path alone, for requests whose query contains
+    /// `fragment` (`""` for any query).
+    pub fn query(self, fragment: &str) -> Self {
+        Self {
+            query: Some(fragment.to_string()),
+            ..self
+        }
+    }
+
+    /// Only answer requests whose `Accept` header contains `fragment`.
+    pub fn accept(self, fragment: &'static str) -> Self {
+        Self {
+            accept: fragment,
+            ..self
+        }
+    }
+
+    fn matches(&self, target: &str, accept: &str) -> bool {
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let target_matches = match &self.query {
+            None => self.path == target,
+            Some(fragment) => (self.path == "*" || self.path == path) && query.contains(fragment),
+        };
+        target_matches && accept.contains(self.accept)
+    }
+}
+
+/// What a [`serve_logged`] server saw.
+#[derive(Debug, Default)]
+pub struct RequestLog {
+    /// The target (path and query) of every request, in arrival order.
+    pub targets: Vec<String>,
+    /// When each request arrived, in arrival order.
+    pub starts: Vec<Instant>,
+    /// Requests being answered right now.
+    pub in_flight: usize,
+    /// The most requests ever answered at once.
+    pub max_in_flight: usize,
+}
+
+/// Serve the routes `routes(base)` returns on a loopback port until the
+/// process exits; the first matching route answers, and requests no
+/// route matches get an HTML 404.
+pub fn serve_with(routes: impl FnOnce(&str) -> Vec<Route>) -> String {
+    serve_logged(routes).0
+}
+
+/// [`serve_with`], also returning the log of the requests the server
+/// answers. Each connection is answered on its own thread, so slow
+/// ([`Route::delay`]) responses overlap.
+pub fn serve_logged(routes: impl FnOnce(&str) -> Vec<Route>) -> (String, Arc<Mutex<RequestLog>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind loopback server");
+    let base = format!("http://{}", listener.local_addr().expect("local addr"));
+    let routes = Arc::new(routes(&base));
+    let log = Arc::new(Mutex::new(RequestLog::default()));
+    let server_log = Arc::clone(&log);
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { break };
+            let (routes, log) = (Arc::clone(&routes), Arc::clone(&server_log));
+            std::thread::spawn(move || {
+                let mut request = [0u8; 4096];
+                let read = stream.read(&mut request).unwrap_or(0);
+                let request = String::from_utf8_lossy(&request[..read]);
+                let target = request.split_whitespace().nth(1).unwrap_or("/");
+                let accept = request
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("accept")
+                            .then(|| value.to_ascii_lowercase())
+                    })
+                    .unwrap_or_default();
+                {
+                    let mut log = log.lock().unwrap();
+                    log.targets.push(target.to_string());
+                    log.starts.push(Instant::now());
+                    log.in_flight += 1;
+                    log.max_in_flight = log.max_in_flight.max(log.in_flight);
+                }
+                let route = routes.iter().find(|route| route.matches(target, &accept));
+                let (status, content_type, headers, body) = match route {
+                    Some(route) => {
+                        std::thread::sleep(route.delay);
+                        (
+                            route.status,
+                            route.content_type,
+                            route.headers.as_str(),
+                            route.body.as_slice(),
+                        )
+                    }
+                    None => (
+                        "404 Not Found",
+                        "text/html; charset=UTF-8",
+                        "",
+                        b"<html><body>Not found</body></html>".as_slice(),
+                    ),
+                };
+                let mut response = format!(
+                    "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\n{headers}Content-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                )
+                .into_bytes();
+                response.extend_from_slice(body);
+                // Leave the in-flight count before responding: the client
+                // may start its next request as soon as this one completes.
+                log.lock().unwrap().in_flight -= 1;
+                let _ = stream.write_all(&response);
+            });
+        }
+    });
+    (base, log)
+}
+
+/// The base URL of a loopback port nothing listens on (bind, read the
+/// port, drop), so connecting to it is refused.
+pub fn refused_base() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("reserve port");
+    let addr = listener.local_addr().expect("local addr");
+    drop(listener);
+    format!("http://{addr}")
+}
+
+/// Options for a scrape that never leaves the loopback server:
+/// Playwright, archive.org, the host-fallback-first ordering, and the cache
+/// off, deterministic conversion, and a bogus LLM provider so no stage can
+/// make a live call.
+pub fn hermetic_options() -> ScrapeOptions {
+    ScrapeOptions::from_env()
+        .llm_client("definitely-not-a-provider")
+        .conversion(ConversionMode::Deterministic)
+        .playwright(false)
+        .archive_fallback(false)
+        .content_fallback_first(false)
+        .cache(None)
+}
+
+/// A news article page titled `title`, long enough to pass content
+/// extraction.
+pub fn article_page(title: &str) -> String {
+    format!(
+        "<html><head><title>{title}</title></head><body><article>{}</article></body></html>",
+        "<p>The city council voted on Tuesday to fund the new transit lines.</p>".repeat(40)
+    )
+}
@@ -0,0 +1,175 @@
+//! Integration tests for the readability-style content scorer in
+//! `uninews::html`: on pages without `<article>` tags the story container
+//! must win over navigation, related-story rails, and sign-up boxes, and
+//! boilerplate nested inside the story must be pruned. Pages with nothing
+//! to score fall back to the longest-`<article>`-else-`<body>` strategy.
+//!
+//! Hermetic: in-memory HTML only.
+
+use uninews::html::parse_scraped_post_from_html;
+use uninews::Post;
+
+const URL: &str = "https://example.com/news/story";
+
+fn parse(body: &str) -> Post {
+    parse_scraped_post_from_html(URL, body, None)
+}
+
+/// A prose paragraph of roughly 200 chars with a couple of commas.
+fn prose(topic: &str) -> String {
+    format!(
+        "<p>The {topic} was approved on Tuesday, after months of hearings, and officials said the \
+         first phase would begin next spring once funding from the regional budget is released.</p>"
+    )
+}
+
+fn story(topics: &[&str]) -> String {
+    topics.iter().map(|topic| prose(topic)).collect()
+}
+
+#[test]
+fn story_container_beats_rails_without_article_tags() {
+    let html = format!(
+        r#"<html><head><title>Transit</title></head><body>
+        <div class="site-menu"><a href="/a">World</a> <a href="/b">Business</a> <a href="/c">Sports</a></div>
+        <div class="layout">
+          <div id="story-body">{}</div>
+          <div class="related-stories">
+            <p><a href="/r1">Related: the mayor responds to criticism of the transit plan</a></p>
+            <p><a href="/r2">Related: what the new bus lanes mean for your commute</a></p>
+          </div>
+          <div class="newsletter-signup"><p>Get the morning briefing delivered to your inbox every weekday.</p></div>
+        </div>
+        <div class="page-footer-links"><a href="/about">About us</a> <a href="/jobs">Careers</a></div>
+        </body></html>"#,
+        story(&[
+            "transit plan",
+            "bridge repair",
+            "rail extension",
+            "bus network"
+        ])
+    );
+    let post = parse(&html);
+
+    assert!(post.error.is_empty(), "{}", post.error);
+    for topic in [
+        "transit plan",
+        "bridge repair",
+        "rail extension",
+        "bus network",
+    ] {
+        assert!(
+            post.content.contains(topic),
+            "missing {topic}: {}",
+            post.content
+        );
+    }
+    for noise in ["Business", "mayor responds", "morning briefing", "Careers"] {
+        assert!(
+            !post.content.contains(noise),
+            "kept {noise}: {}",
+            post.content
+        );
+    }
+}
+
+#[test]
+fn boilerplate_inside_the_story_is_pruned() {
+    let html = format!(
+        r#"<html><body><div class="story">
+          {}
+          <div class="share-tools"><p>Share this story with your friends and family today.</p></div>
+          <ul><li><a href="/1">More on transit funding decisions</a></li><li><a href="/2">More on city budget hearings</a></li></ul>
+          <p>Read the <a href="/report">full report</a> for the detailed figures, which run to forty pages.</p>
+          <div class="shadow-box"><p>Fares will stay frozen for the first year of operation, the agency said.</p></div>
+          {}
+        </div></body></html>"#,
+        story(&["transit plan", "bridge repair"]),
+        story(&["rail extension"])
+    );
+    let post = parse(&html);
+
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(
+        !post.content.contains("Share this story"),
+        "{}",
+        post.content
+    );
+    assert!(
+        !post.content.contains("More on transit"),
+        "{}",
+        post.content
+    );
+    // Links inside prose and blocks whose class only resembles a hint stay.
+    assert!(
+        post.content
+            .contains(r#"<a href="/report">full report</a>"#),
+        "{}",
+        post.content
+    );
+    assert!(
+        post.content.contains("Fares will stay frozen"),
+        "{}",
+        post.content
+    );
+    assert!(post.content.contains("rail extension"), "{}", post.content);
+}
+
+#[test]
+fn story_split_across_sibling_blocks_stays_whole() {
+    let html = format!(
+        r#"<html><body><div class="wrapper">
+          <div class="chunk">{}</div>
+          <div class="ad-slot"><p>Advertisement: upgrade your kitchen this season, with free delivery.</p></div>
+          <div class="chunk">{}</div>
+        </div></body></html>"#,
+        story(&["transit plan", "bridge repair", "rail extension"]),
+        story(&["bus network", "ferry service", "tram depot"])
+    );
+    let post = parse(&html);
+
+    for topic in ["transit plan", "tram depot"] {
+        assert!(
+            post.content.contains(topic),
+            "missing {topic}: {}",
+            post.content
+        );
+    }
+    assert!(!post.content.contains("Advertisement"), "{}", post.content);
+}
+
+#[test]
+fn link_heavy_rail_loses_to_prose() {
+    let rail: String = (0..12)
+        .map(|i| {
+            format!(
+                r#"<p><a href="/more/{i}">Another long headline about regional politics and the economy, number {i}</a></p>"#
+            )
+        })
+        .collect();
+    let html = format!(
+        r#"<html><body><div>{}</div><div>{}</div></body></html>"#,
+        rail,
+        story(&["transit plan", "bridge repair", "rail extension"])
+    );
+    let post = parse(&html);
+
+    assert!(post.content.contains("transit plan"), "{}", post.content);
+    assert!(
+        !post.content.contains("Another long headline"),
+        "{}",
+        post.content
+    );
+}
+
+#[test]
+fn pages_without_scorable_paragraphs_fall_back_to_the_body() {
+    let post = parse(
+        "<html><body><ul><li>Short item one</li><li>Short item two</li></ul>\
+         <span>Loose fragment</span></body></html>",
+    );
+
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(post.content.contains("Short item two"), "{}", post.content);
+    assert!(post.content.contains("Loose fragment"), "{}", post.content);
+}
@@ -0,0 +1,147 @@
+//! Integration tests for section-page link discovery: anchors are ranked
+//! as probable article URLs by path shape and placement, utility and
+//! off-site links are dropped, duplicates merge, and a [`Scraper`]
+//! fetches a section page and reports typed errors.
+//!
+//! Hermetic: in-memory HTML plus one loopback server with Playwright and
+//! archive.org disabled and deterministic conversion.
+
+mod common;
+
+use uninews::{rank_article_links, ScrapeError, Scraper, MIN_ARTICLE_LINK_SCORE};
+
+use common::{hermetic_options, refused_base, serve_with, Route};
+
+const SECTION_URL: &str = "https://www.example.com/technology";
+
+/// A section page mixing navigation, utility links, assets, off-site
+/// links, and article links of varying shape.
+const SECTION_PAGE: &str = r#"<html><body>
+<header><nav>
+  <a href="/">Home</a>
+  <a href="/technology">Technology</a>
+  <a href="/world">World</a>
+  <a href="/newsletters/daily-briefing-signup">Get the daily briefing newsletter</a>
+</nav></header>
+<main>
+  <article>
+    <a href="/2026/10/17/council-approves-transit-plan#comments"><img src="/img/transit.jpg"></a>
+    <h2><a href="/2026/10/17/council-approves-transit-plan">Council approves the transit plan</a></h2>
+  </article>
+  <article>
+    <h3><a href="https://edition.example.com/tech/chipmakers-race-to-build-new-fabs">Chipmakers race to build new fabs</a></h3>
+  </article>
+  <a href="/story/4821937">Read more</a>
+  <a href="/tech/ai-20261016-model-release.html">AI lab ships its new model</a>
+  <a href="/tag/artificial-intelligence">Artificial intelligence news and analysis</a>
+  <a href="/author/ada-lovelace">Ada Lovelace</a>
+  <a href="/technology?page=2">Next page</a>
+  <a href="/files/annual-report-2026.pdf">Annual report for the year 2026</a>
+  <a href="https://other.org/2026/10/17/unrelated-story-about-things">An unrelated story about things</a>
+  <a href="mailto:tips@example.com">Send us a tip</a>
+</main>
+<footer><a href="/terms-of-service-and-conditions">Terms of service and conditions</a></footer>
+</body></html>"#;
+
+#[test]
+fn article_links_are_ranked_and_utility_links_dropped() {
+    let links = rank_article_links(SECTION_PAGE, SECTION_URL);
+    let urls: Vec<&str> = links.iter().map(|link| link.url.as_str()).collect();
+
+    assert_eq!(
+        urls,
+        [
+            "https://www.example.com/2026/10/17/council-approves-transit-plan",
+            "https://www.example.com/tech/ai-20261016-model-release.html",
+            "https://edition.example.com/tech/chipmakers-race-to-build-new-fabs",
+        ]
+    );
+    assert!(links.windows(2).all(|pair| pair[0].score >= pair[1].score));
+    assert!(links
+        .iter()
+        .all(|link| link.score >= MIN_ARTICLE_LINK_SCORE));
+}
+
+#[test]
+fn duplicate_anchors_merge_under_the_longest_text() {
+    let links = rank_article_links(SECTION_PAGE, SECTION_URL);
+    let transit = links
+        .iter()
+        .filter(|link| link.url.contains("transit-plan"))
+        .collect::<Vec<_>>();
+
+    assert_eq!(transit.len(), 1);
+    assert_eq!(transit[0].text, "Council approves the transit plan");
+}
+
+#[test]
+fn numeric_ids_count_and_headline_text_breaks_the_threshold() {
+    let html = r#"<a href="/story/4821937">Read more</a>
+        <a href="/story/4821938">Mayor unveils the city budget</a>"#;
+    let links = rank_article_links(html, SECTION_URL);
+    let urls: Vec<&str> = links.iter().map(|link| link.url.as_str()).collect();
+
+    assert_eq!(urls, ["https://www.example.com/story/4821938"]);
+}
+
+#[test]
+fn unparseable_page_urls_yield_no_links() {
+    assert!(rank_article_links(SECTION_PAGE, "not a url").is_empty());
+    assert!(rank_article_links("", SECTION_URL).is_empty());
+}
+
+#[tokio::test]
+async fn scraper_discovers_links_on_a_section_page() {
+    let padding = format!("<!-- {} -->", "x".repeat(20 * 1024));
+    let base = serve_with(|_| {
+        vec![Route::html(
+            "/technology",
+            format!("{SECTION_PAGE}{padding}"),
+        )]
+    });
+
+    let links = Scraper::new(hermetic_options())
+        .discover_article_links(&format!("{}/technology", base))
+        .await
+        .expect("links");
+
+    let urls: Vec<&str> = links.iter().map(|link| link.url.as_str()).collect();
+    assert_eq!(
+        urls,
+        [
+            format!("{}/2026/10/17/council-approves-transit-plan", base),
+            format!("{}/tech/ai-20261016-model-release.html", base),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn discovery_failures_are_typed_errors() {
+    let scraper = Scraper::new(hermetic_options());
+
+    let base = serve_with(|_| Vec::new());
+    let error = scraper
+        .discover_article_links(&format!("{}/technology", base))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, ScrapeError::HttpStatus { status: 404, .. }),
+        "{error:?}"
+    );
+
+    let closed = format!("{}/technology", refused_base());
+    let error = scraper.discover_article_links(&closed).await.unwrap_err();
+    assert!(matches!(error, ScrapeError::Network { .. }), "{error:?}");
+
+    let base = serve_with(|_| {
+        vec![Route::html(
+            "/technology",
+            "<html><body><p>No links</p></body></html>",
+        )]
+    });
+    let links = scraper
+        .discover_article_links(&format!("{}/technology", base))
+        .await
+        .expect("a page without links is not an error");
+    assert!(links.is_empty());
+}
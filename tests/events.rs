@@ -122,6 +122,7 @@ fn events_serialize_with_a_snake_case_event_tag() {
         url: "https://example.com/d".to_string(),
         status: 200,
         body_bytes: 1234,
+        encoding: Some("windows-1251".to_string()),
     };
     let json = serde_json::to_value(&event).expect("event must serialize");
 
@@ -129,6 +130,7 @@ fn events_serialize_with_a_snake_case_event_tag() {
     assert_eq!(json["url"], "https://example.com/d");
     assert_eq!(json["status"], 200);
     assert_eq!(json["body_bytes"], 1234);
+    assert_eq!(json["encoding"], "windows-1251");
 }
 
 #[test]
@@ -185,3 +187,22 @@ fn playwright_fallback_events_serialize() {
     assert_eq!(failed["event"], "playwright_fallback_failed");
     assert_eq!(failed["error"], "still blocked");
 }
+
+#[test]
+fn cache_events_serialize() {
+    let hit = serde_json::to_value(ScrapeEvent::CacheHit {
+        url: "https://example.com/a".to_string(),
+        kind: uninews::CacheKind::Post,
+    })
+    .expect("event must serialize");
+    assert_eq!(hit["event"], "cache_hit");
+    assert_eq!(hit["kind"], "post");
+
+    let miss = serde_json::to_value(ScrapeEvent::CacheMiss {
+        url: "https://example.com/a".to_string(),
+        kind: uninews::CacheKind::Response,
+    })
+    .expect("event must serialize");
+    assert_eq!(miss["event"], "cache_miss");
+    assert_eq!(miss["kind"], "response");
+}
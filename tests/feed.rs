@@ -0,0 +1,376 @@
+//! Integration tests for feed ingestion: RSS 2.0 / RSS 1.0 / Atom / JSON
+//! Feed parsing, RFC 2822 date normalization, `<link rel="alternate">`
+//! discovery, merging item metadata into posts, and scraping a discovered
+//! feed end to end.
+//!
+//! Hermetic: in-memory documents plus one loopback server with Playwright
+//! and archive.org disabled and deterministic conversion.
+
+mod common;
+
+use futures::StreamExt;
+use uninews::{
+    discover_feed_urls, parse_feed, FeedEnclosure, FeedFormat, FeedItem, Post, ScrapeError, Scraper,
+};
+
+use common::{hermetic_options, serve_with, Route};
+
+const FEED_URL: &str = "https://example.com/feeds/news.xml";
+
+#[test]
+fn rss_items_carry_links_dates_authors_and_enclosures() {
+    let rss = r#"<?xml version="1.0" encoding="UTF-8"?>
+    <rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/"
+         xmlns:atom="http://www.w3.org/2005/Atom" xmlns:media="http://search.yahoo.com/mrss/">
+      <channel>
+        <title>Example News</title>
+        <atom:link href="https://example.com/feeds/news.xml" rel="self"/>
+        <link>https://example.com/</link>
+        <item>
+          <title><![CDATA[Council approves <transit> plan]]></title>
+          <media:title>Media title</media:title>
+          <link>/news/transit</link>
+          <guid isPermaLink="false">transit-42</guid>
+          <pubDate>Tue, 14 Oct 2025 08:00:00 -0500</pubDate>
+          <dc:creator>Ada Lovelace</dc:creator>
+          <dc:creator>Grace Hopper</dc:creator>
+          <enclosure url="/img/transit.jpg" type="image/jpeg" length="12345"/>
+        </item>
+        <item>
+          <title>Permalink only</title>
+          <guid>https://example.com/news/permalink</guid>
+          <pubDate>not a date</pubDate>
+        </item>
+        <item>
+          <title>No link at all</title>
+          <guid isPermaLink="false">orphan</guid>
+        </item>
+      </channel>
+    </rss>"#;
+    let feed = parse_feed(rss, FEED_URL).expect("rss feed");
+
+    assert_eq!(feed.format, FeedFormat::Rss);
+    assert_eq!(feed.url, FEED_URL);
+    assert_eq!(feed.title.as_deref(), Some("Example News"));
+    assert_eq!(feed.link.as_deref(), Some("https://example.com/"));
+    assert_eq!(
+        feed.items,
+        [
+            FeedItem {
+                link: "https://example.com/news/transit".to_string(),
+                title: Some("Council approves <transit> plan".to_string()),
+                guid: Some("transit-42".to_string()),
+                published: Some("2025-10-14T13:00:00Z".to_string()),
+                author: Some("Ada Lovelace, Grace Hopper".to_string()),
+                enclosure: Some(FeedEnclosure {
+                    url: "https://example.com/img/transit.jpg".to_string(),
+                    mime_type: Some("image/jpeg".to_string()),
+                    length: Some(12345),
+                }),
+            },
+            FeedItem {
+                link: "https://example.com/news/permalink".to_string(),
+                title: Some("Permalink only".to_string()),
+                guid: Some("https://example.com/news/permalink".to_string()),
+                published: Some("not a date".to_string()),
+                ..FeedItem::default()
+            },
+        ]
+    );
+}
+
+#[test]
+fn rfc2822_dates_normalize_to_utc() {
+    let dates = [
+        ("Fri, 31 Dec 1999 23:59:59 GMT", "1999-12-31T23:59:59Z"),
+        ("1 Jan 00 00:30 +0100", "1999-12-31T23:30:00Z"),
+        ("29 Feb 2024 12:00:00 PDT", "2024-02-29T19:00:00Z"),
+        ("Mon, 6 Jan 2026 09:15:00 XYZ", "2026-01-06T09:15:00Z"),
+        ("2026-01-06T09:15:00+01:00", "2026-01-06T09:15:00+01:00"),
+    ];
+    for (raw, expected) in dates {
+        let rss = format!(
+            "<rss><channel><item><link>https://example.com/a</link>\
+             <pubDate>{raw}</pubDate></item></channel></rss>"
+        );
+        let feed = parse_feed(&rss, FEED_URL).expect("rss feed");
+        assert_eq!(feed.items[0].published.as_deref(), Some(expected), "{raw}");
+    }
+}
+
+#[test]
+fn rss1_rdf_items_sit_beside_the_channel() {
+    let rdf = r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+        xmlns="http://purl.org/rss/1.0/" xmlns:dc="http://purl.org/dc/elements/1.1/">
+      <channel rdf:about="https://example.org/"><title>RDF News</title></channel>
+      <item rdf:about="https://example.org/one">
+        <title>One</title>
+        <dc:date>2026-10-01T10:00:00Z</dc:date>
+      </item>
+    </rdf:RDF>"#;
+    let feed = parse_feed(rdf, "https://example.org/index.rdf").expect("rdf feed");
+
+    assert_eq!(feed.format, FeedFormat::Rss);
+    assert_eq!(feed.title.as_deref(), Some("RDF News"));
+    assert_eq!(feed.items.len(), 1);
+    assert_eq!(feed.items[0].link, "https://example.org/one");
+    assert_eq!(
+        feed.items[0].published.as_deref(),
+        Some("2026-10-01T10:00:00Z")
+    );
+}
+
+#[test]
+fn atom_entries_use_alternate_links_and_feed_authors() {
+    let atom = r#"<?xml version="1.0" encoding="utf-8"?>
+    <feed xmlns="http://www.w3.org/2005/Atom">
+      <title>Example Blog</title>
+      <link rel="self" href="https://blog.example.com/atom.xml"/>
+      <link href="https://blog.example.com/"/>
+      <author><name>Blog Team</name></author>
+      <entry>
+        <title type="html">First post</title>
+        <link rel="replies" href="/first#comments"/>
+        <link rel="alternate" type="text/html" href="/first"/>
+        <link rel="enclosure" type="audio/mpeg" length="999" href="/first.mp3"/>
+        <id>tag:blog.example.com,2026:first</id>
+        <updated>2026-10-02T00:00:00Z</updated>
+        <published>2026-10-01T00:00:00Z</published>
+        <author><name>Linus Pauling</name></author>
+      </entry>
+      <entry>
+        <title>Second post</title>
+        <id>https://blog.example.com/second</id>
+        <updated>2026-10-03T00:00:00Z</updated>
+      </entry>
+    </feed>"#;
+    let feed = parse_feed(atom, "https://blog.example.com/atom.xml").expect("atom feed");
+
+    assert_eq!(feed.format, FeedFormat::Atom);
+    assert_eq!(feed.title.as_deref(), Some("Example Blog"));
+    assert_eq!(feed.link.as_deref(), Some("https://blog.example.com/"));
+
+    let first = &feed.items[0];
+    assert_eq!(first.link, "https://blog.example.com/first");
+    assert_eq!(
+        first.guid.as_deref(),
+        Some("tag:blog.example.com,2026:first")
+    );
+    assert_eq!(first.published.as_deref(), Some("2026-10-01T00:00:00Z"));
+    assert_eq!(first.author.as_deref(), Some("Linus Pauling"));
+    let enclosure = first.enclosure.as_ref().expect("enclosure");
+    assert_eq!(enclosure.url, "https://blog.example.com/first.mp3");
+    assert!(!enclosure.is_image());
+
+    let second = &feed.items[1];
+    assert_eq!(second.link, "https://blog.example.com/second");
+    assert_eq!(second.published.as_deref(), Some("2026-10-03T00:00:00Z"));
+    assert_eq!(second.author.as_deref(), Some("Blog Team"));
+}
+
+#[test]
+fn json_feed_items_parse() {
+    let json = r#"{
+        "version": "https://jsonfeed.org/version/1.1",
+        "title": "JSON News",
+        "home_page_url": "https://example.net/",
+        "authors": [{"name": "Desk"}],
+        "items": [
+            {
+                "id": 7,
+                "url": "https://example.net/seven",
+                "title": "Seven",
+                "date_published": "2026-10-07T07:00:00Z",
+                "authors": [{"name": "Ada Lovelace"}],
+                "attachments": [{"url": "/seven.png", "mime_type": "image/png", "size_in_bytes": 10}]
+            },
+            {"id": "eight", "external_url": "https://elsewhere.org/eight"},
+            {"id": "nine", "content_text": "No link to scrape."}
+        ]
+    }"#;
+    let feed = parse_feed(json, "https://example.net/feed.json").expect("json feed");
+
+    assert_eq!(feed.format, FeedFormat::JsonFeed);
+    assert_eq!(feed.title.as_deref(), Some("JSON News"));
+    assert_eq!(feed.items.len(), 2);
+    assert_eq!(feed.items[0].guid.as_deref(), Some("7"));
+    assert_eq!(feed.items[0].author.as_deref(), Some("Ada Lovelace"));
+    assert_eq!(
+        feed.items[0].enclosure.as_ref().map(|e| e.url.as_str()),
+        Some("https://example.net/seven.png")
+    );
+    assert_eq!(feed.items[1].link, "https://elsewhere.org/eight");
+    assert_eq!(feed.items[1].author.as_deref(), Some("Desk"));
+}
+
+#[test]
+fn non_feeds_are_rejected() {
+    for body in [
+        "<!DOCTYPE html><html><head><title>Home</title></head><body><p>Hi<br></body></html>",
+        "<html><body><p>Well-formed XHTML</p></body></html>",
+        r#"{"title": "Just JSON"}"#,
+        "",
+    ] {
+        let error = parse_feed(body, FEED_URL).unwrap_err();
+        assert!(error.starts_with("Not a feed:"), "{error}");
+    }
+}
+
+#[test]
+fn discovery_follows_alternate_feed_links_in_document_order() {
+    let html = r#"<html><head>
+        <link rel="stylesheet" type="text/css" href="/style.css">
+        <link rel="alternate" type="text/html" hreflang="fr" href="/fr/">
+        <link rel="alternate" type="application/atom+xml" href="/atom.xml">
+        <link rel="ALTERNATE home" type="application/rss+xml; charset=utf-8" href="https://feeds.example.com/rss">
+        <link rel="alternate" type="application/feed+json" href="/atom.xml">
+        <link rel="alternate" type="application/feed+json" href="/feed.json">
+        </head><body></body></html>"#;
+
+    assert_eq!(
+        discover_feed_urls(html, "https://example.com/section/"),
+        [
+            "https://example.com/atom.xml",
+            "https://feeds.example.com/rss",
+            "https://example.com/feed.json",
+        ]
+    );
+}
+
+#[test]
+fn page_values_win_and_feed_metadata_fills_gaps() {
+    let item = FeedItem {
+        link: "https://example.com/a".to_string(),
+        title: Some("Feed title".to_string()),
+        published: Some("2026-10-01T00:00:00Z".to_string()),
+        author: Some("Feed Author".to_string()),
+        enclosure: Some(FeedEnclosure {
+            url: "https://example.com/a.jpg".to_string(),
+            mime_type: Some("image/jpeg".to_string()),
+            length: None,
+        }),
+        ..FeedItem::default()
+    };
+
+    let mut sparse = Post::default();
+    item.merge_into(&mut sparse);
+    assert_eq!(sparse.title, "Feed title");
+    assert_eq!(sparse.author.as_deref(), Some("Feed Author"));
+    assert_eq!(
+        sparse.publication_date.as_deref(),
+        Some("2026-10-01T00:00:00Z")
+    );
+    assert_eq!(sparse.featured_image_url, "https://example.com/a.jpg");
+
+    let mut full = Post {
+        title: "Page title".to_string(),
+        author: Some("Page Author".to_string()),
+        featured_image_url: "https://example.com/og.jpg".to_string(),
+        ..Post::default()
+    };
+    item.merge_into(&mut full);
+    assert_eq!(full.title, "Page title");
+    assert_eq!(full.author.as_deref(), Some("Page Author"));
+    assert_eq!(full.featured_image_url, "https://example.com/og.jpg");
+}
+
+fn article(head: &str, topic: &str) -> String {
+    format!(
+        "<html><head>{head}</head><body><article>{}</article></body></html>",
+        format!("<p>The {topic} was approved on Tuesday after months of public hearings.</p>")
+            .repeat(8)
+    )
+}
+
+#[tokio::test]
+async fn homepage_feed_is_discovered_and_items_are_scraped_with_feed_metadata() {
+    let rss = r#"<rss version="2.0"><channel><title>Loopback News</title>
+        <item><title>Transit from the feed</title><link>/news/transit</link>
+          <author>desk@example.com (News Desk)</author>
+          <pubDate>Wed, 15 Oct 2025 06:30:00 GMT</pubDate></item>
+        <item><title>Bridge from the feed</title><link>/news/bridge</link>
+          <author>desk@example.com (News Desk)</author></item>
+        <item><title>Duplicate</title><link>/news/bridge?utm_source=rss#top</link></item>
+        </channel></rss>"#;
+    let base = serve_with(|_| {
+        vec![
+            Route::html(
+                "/",
+                r#"<html><head><link rel="alternate" type="application/rss+xml" href="/rss.xml"></head><body>Home</body></html>"#,
+            ),
+            Route::new("/rss.xml", "application/rss+xml", rss),
+            Route::html("/news/transit", article("", "transit plan")),
+            Route::html(
+                "/news/bridge",
+                article(
+                    r#"<title>Bridge page title</title><meta name="author" content="Page Author">"#,
+                    "bridge repair",
+                ),
+            ),
+        ]
+    });
+    let scraper = Scraper::new(hermetic_options());
+
+    let feed = scraper.fetch_feed(&format!("{base}/")).await.expect("feed");
+    assert_eq!(feed.url, format!("{base}/rss.xml"));
+    assert_eq!(feed.items.len(), 3);
+
+    let mut results: Vec<(FeedItem, Post)> = scraper
+        .scrape_feed(&format!("{base}/"), "english")
+        .await
+        .expect("feed stream")
+        .collect()
+        .await;
+    results.sort_by(|(a, _), (b, _)| a.link.cmp(&b.link));
+    assert_eq!(results.len(), 2);
+
+    let (bridge_item, bridge) = &results[0];
+    assert_eq!(bridge_item.title.as_deref(), Some("Bridge from the feed"));
+    assert!(bridge.error.is_empty(), "{}", bridge.error);
+    assert_eq!(bridge.title, "Bridge page title");
+    assert_eq!(bridge.author.as_deref(), Some("Page Author"));
+    assert!(
+        bridge.content.contains("bridge repair"),
+        "{}",
+        bridge.content
+    );
+
+    let (_, transit) = &results[1];
+    assert!(transit.error.is_empty(), "{}", transit.error);
+    assert_eq!(transit.title, "Transit from the feed");
+    assert_eq!(
+        transit.author.as_deref(),
+        Some("desk@example.com (News Desk)")
+    );
+    assert_eq!(
+        transit.publication_date.as_deref(),
+        Some("2025-10-15T06:30:00Z")
+    );
+}
+
+#[tokio::test]
+async fn pages_without_feeds_and_missing_feeds_fail_with_typed_errors() {
+    let base = serve_with(|_| {
+        vec![Route::html(
+            "/plain",
+            "<html><body><p>No feeds here.</p></body></html>",
+        )]
+    });
+    let scraper = Scraper::new(hermetic_options());
+
+    let error = scraper
+        .fetch_feed(&format!("{base}/plain"))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ScrapeError::Other { .. }), "{error:?}");
+    assert!(error.message().starts_with("No feed found at"), "{error}");
+
+    let error = scraper
+        .fetch_feed(&format!("{base}/missing.xml"))
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, ScrapeError::HttpStatus { status: 404, .. }),
+        "{error:?}"
+    );
+}
@@ -0,0 +1,301 @@
+//! Integration tests for Hacker News items: item-URL shapes, the Firebase
+//! item parser, story / text classification, and the built-in handler
+//! following stories, converting Ask HN text, appending a depth- and
+//! count-limited comment tree, and reporting missing items.
+//!
+//! Hermetic: in-memory documents plus one `common::serve_with` loopback
+//! server per test standing in for the Firebase API and linked sites,
+//! scraped with `common::hermetic_options`.
+
+mod common;
+
+use serde_json::{json, Value};
+use uninews::{
+    hacker_news_item_id, parse_hacker_news_item, FallbackStage, HackerNewsHandler, ScrapeError,
+    Scraper, SourceHandler,
+};
+
+use common::{article_page, hermetic_options, serve_with, Route};
+
+/// A comment item by `by` under `parent` with replies `kids`.
+fn comment(id: u64, by: &str, parent: u64, text: &str, kids: &[u64]) -> Value {
+    json!({
+        "id": id,
+        "type": "comment",
+        "by": by,
+        "time": 1_792_144_800,
+        "parent": parent,
+        "text": text,
+        "kids": kids
+    })
+}
+
+/// The comment tree under item 100:
+///
+/// - 101 alice (replies 103 bob → 105 carol, then 104 dave)
+/// - 102 erin
+/// - 106 dead, 107 deleted
+fn comment_items() -> Vec<(u64, Value)> {
+    let mut dead = comment(106, "spammer", 100, "Buy now", &[]);
+    dead["dead"] = json!(true);
+    vec![
+        (
+            101,
+            comment(
+                101,
+                "alice",
+                100,
+                "The second line matters most.<p>It serves the hospital.",
+                &[103, 104],
+            ),
+        ),
+        (102, comment(102, "erin", 100, "Finally!", &[])),
+        (
+            103,
+            comment(
+                103,
+                "bob",
+                101,
+                "Agreed, <i>especially</i> at rush hour.",
+                &[105],
+            ),
+        ),
+        (104, comment(104, "dave", 101, "What about buses?", &[])),
+        (105, comment(105, "carol", 103, "Too deep to show.", &[])),
+        (106, dead),
+        (
+            107,
+            json!({"id": 107, "type": "comment", "deleted": true, "parent": 100, "time": 1_792_144_800}),
+        ),
+    ]
+}
+
+#[test]
+fn item_ids_come_from_item_urls() {
+    assert_eq!(
+        hacker_news_item_id("https://news.ycombinator.com/item?id=8863"),
+        Some(8863)
+    );
+    assert_eq!(
+        hacker_news_item_id("https://news.ycombinator.com/item?id=8863&p=2"),
+        Some(8863)
+    );
+    assert_eq!(
+        hacker_news_item_id("https://news.ycombinator.com/item?id=abc"),
+        None
+    );
+    assert_eq!(
+        hacker_news_item_id("https://news.ycombinator.com/user?id=pg"),
+        None
+    );
+    assert_eq!(
+        hacker_news_item_id("https://example.com/item?id=8863"),
+        None
+    );
+
+    let handler = HackerNewsHandler::default();
+    assert!(handler.matches("https://news.ycombinator.com/item?id=8863"));
+    assert!(!handler.matches("https://news.ycombinator.com/"));
+}
+
+#[test]
+fn items_parse_and_classify() {
+    let story = parse_hacker_news_item(
+        &json!({
+            "id": 8863,
+            "type": "story",
+            "by": "dhouston",
+            "time": 1_175_714_200,
+            "title": "My YC app: Dropbox - Throw away your USB drive",
+            "url": "http://www.getdropbox.com/u/2/screencast.html",
+            "score": 111,
+            "descendants": 71,
+            "kids": [9224, 8917]
+        })
+        .to_string(),
+    )
+    .expect("story");
+    assert_eq!(story.kind, "story");
+    assert_eq!(story.by.as_deref(), Some("dhouston"));
+    assert_eq!(story.kids, [9224, 8917]);
+    assert_eq!(
+        story.linked_article_url(),
+        Some("http://www.getdropbox.com/u/2/screencast.html")
+    );
+    assert_eq!(
+        story.discussion_url(),
+        "https://news.ycombinator.com/item?id=8863"
+    );
+    let metadata = story.metadata();
+    assert_eq!(
+        metadata.schema_type.as_deref(),
+        Some("DiscussionForumPosting")
+    );
+    assert_eq!(metadata.authors, ["dhouston"]);
+    assert_eq!(
+        metadata.date_published.as_deref(),
+        Some("2007-04-04T19:16:40Z")
+    );
+
+    let show = parse_hacker_news_item(
+        &json!({
+            "id": 9,
+            "type": "story",
+            "title": "Show HN: A transit map",
+            "url": "https://maps.example/",
+            "text": "I built this over the weekend."
+        })
+        .to_string(),
+    )
+    .expect("show");
+    assert_eq!(show.linked_article_url(), None);
+
+    assert!(parse_hacker_news_item("null")
+        .unwrap_err()
+        .contains("does not exist"));
+    assert!(parse_hacker_news_item(r#"{"id": 5, "deleted": true}"#)
+        .unwrap_err()
+        .contains("deleted"));
+}
+
+/// Routes serving `/v0/item/:id.json` for `items`; unknown items are
+/// `null`, as on Firebase, so these go after any other routes.
+fn item_routes(items: Vec<(u64, Value)>) -> Vec<Route> {
+    items
+        .into_iter()
+        .map(|(id, item)| Route::json(&format!("/v0/item/{id}.json"), item.to_string()))
+        .chain([Route::fallback(
+            "200 OK",
+            "application/json; charset=utf-8",
+            "null",
+        )])
+        .collect()
+}
+
+fn hermetic_scraper(handler: HackerNewsHandler) -> Scraper {
+    Scraper::new(hermetic_options().handler(handler))
+}
+
+const URL: &str = "https://news.ycombinator.com/item?id=100";
+
+#[tokio::test]
+async fn stories_follow_their_url_with_a_condensed_comment_tree() {
+    let base = serve_with(|base| {
+        let mut items = comment_items();
+        items.push((
+            100,
+            json!({
+                "id": 100,
+                "type": "story",
+                "by": "urbanist",
+                "time": 1_792_141_200,
+                "title": "Council approves two new transit lines",
+                "url": format!("{base}/news/plan"),
+                "score": 250,
+                "descendants": 7,
+                "kids": [106, 107, 101, 102]
+            }),
+        ));
+        let mut routes = vec![Route::html("/news/plan", article_page("Plan | News"))];
+        routes.extend(item_routes(items));
+        routes
+    });
+    let handler = HackerNewsHandler::default()
+        .api_base(format!("{base}/v0"))
+        .comment_tree(4, 2);
+
+    let post = hermetic_scraper(handler).scrape(URL, "english").await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(
+        post.content.contains("fund the new transit lines"),
+        "{}",
+        post.content
+    );
+    let provenance = post.provenance.expect("provenance");
+    assert_eq!(provenance.source, FallbackStage::PlainFetch);
+    assert_eq!(provenance.final_url, format!("{}/news/plan", base));
+
+    let appendix = &post.content[post.content.find("## Comments").expect("appendix")..];
+    assert_eq!(
+        appendix,
+        "## Comments\n\n\
+         - **alice**: The second line matters most.\n\n  It serves the hospital.\n\
+         \x20 - **bob**: Agreed, *especially* at rush hour.\n\
+         \x20 - **dave**: What about buses?\n\
+         - **erin**: Finally!\n"
+    );
+}
+
+#[tokio::test]
+async fn ask_hn_posts_use_their_text() {
+    let base = serve_with(|_| {
+        let mut items = comment_items();
+        items.push((
+            100,
+            json!({
+                "id": 100,
+                "type": "story",
+                "by": "rider",
+                "time": 1_792_141_200,
+                "title": "Ask HN: How does your city fund transit?",
+                "text": "Ours just approved two new lines.<p>Curious how others pay for it.",
+                "kids": [101, 102]
+            }),
+        ));
+        item_routes(items)
+    });
+    let handler = HackerNewsHandler::default()
+        .api_base(format!("{base}/v0"))
+        .comment_tree(10, 1);
+
+    let post = hermetic_scraper(handler).scrape(URL, "english").await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(post.title, "Ask HN: How does your city fund transit?");
+    assert_eq!(post.author.as_deref(), Some("rider"));
+    assert_eq!(
+        post.publication_date.as_deref(),
+        Some("2026-10-16T09:00:00Z")
+    );
+    assert!(
+        post.content
+            .contains("Ours just approved two new lines.\n\nCurious how others pay for it."),
+        "{}",
+        post.content
+    );
+    // Depth 1: top-level comments only.
+    assert!(post.content.contains("- **alice**"), "{}", post.content);
+    assert!(post.content.contains("- **erin**"), "{}", post.content);
+    assert!(!post.content.contains("bob"), "{}", post.content);
+
+    let provenance = post.provenance.expect("provenance");
+    assert_eq!(provenance.source, FallbackStage::HackerNews);
+    assert_eq!(provenance.canonical_url.as_deref(), Some(URL));
+    let metadata = post.metadata.expect("metadata");
+    assert_eq!(metadata.publisher.as_deref(), Some("Hacker News"));
+
+    // Without `comment_tree` there is no appendix.
+    let post = hermetic_scraper(HackerNewsHandler::default().api_base(format!("{base}/v0")))
+        .scrape(URL, "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(!post.content.contains("## Comments"), "{}", post.content);
+}
+
+#[tokio::test]
+async fn missing_items_are_reported() {
+    let base = serve_with(|_| item_routes(vec![]));
+    let post = hermetic_scraper(HackerNewsHandler::default().api_base(format!("{base}/v0")))
+        .scrape("https://news.ycombinator.com/item?id=404", "english")
+        .await;
+
+    assert!(post.error.contains("does not exist"), "{}", post.error);
+    assert!(
+        matches!(post.error_kind, Some(ScrapeError::Other { .. })),
+        "{:?}",
+        post.error_kind
+    );
+    assert_eq!(
+        post.error_kind.expect("kind").fallbacks(),
+        [FallbackStage::HackerNews]
+    );
+}
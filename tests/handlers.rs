@@ -0,0 +1,215 @@
+//! Integration tests for pluggable source handlers: registry lookup and
+//! precedence, the built-in X handler, and a [`Scraper`] dispatching to a
+//! registered handler that uses the shared client, the conversion step,
+//! and the generic web pipeline from its [`ScrapeContext`].
+//!
+//! Hermetic: one loopback server with Playwright and archive.org disabled
+//! and deterministic conversion.
+
+mod common;
+
+use uninews::{
+    HandlerFuture, Post, ScrapeContext, ScrapeError, ScrapeOptions, Scraper, SourceHandler,
+    SourceHandlers,
+};
+
+use common::{article_page, hermetic_options, refused_base, serve_with, Route};
+
+/// Claims every URL containing `/api/`: fetches it as JSON with the
+/// shared API client and converts the `body` field. `/api/raw/…` URLs are
+/// passed through to the generic web pipeline.
+struct JsonApi;
+
+impl SourceHandler for JsonApi {
+    fn name(&self) -> &str {
+        "json-api"
+    }
+
+    fn matches(&self, url: &str) -> bool {
+        url.contains("/api/")
+    }
+
+    fn scrape<'a>(&'a self, url: &'a str, ctx: &'a ScrapeContext<'a>) -> HandlerFuture<'a> {
+        Box::pin(async move {
+            if url.contains("/api/raw/") {
+                return ctx.scrape_web(url).await;
+            }
+            let response = match ctx.api_client().get(url).send().await {
+                Ok(response) => response,
+                Err(error) => {
+                    return Post::from_error(ScrapeError::Network {
+                        message: error.to_string(),
+                        fallbacks: Vec::new(),
+                    })
+                }
+            };
+            let json: serde_json::Value = response.json().await.unwrap_or_default();
+            let post = Post {
+                title: json["title"].as_str().unwrap_or_default().to_string(),
+                content: json["body"].as_str().unwrap_or_default().to_string(),
+                ..Post::default()
+            };
+            ctx.convert(post).await.unwrap_or_else(Post::from_error)
+        })
+    }
+}
+
+/// Claims every URL; used to check precedence.
+struct CatchAll(&'static str);
+
+impl SourceHandler for CatchAll {
+    fn name(&self) -> &str {
+        self.0
+    }
+
+    fn matches(&self, _url: &str) -> bool {
+        true
+    }
+
+    fn scrape<'a>(&'a self, _url: &'a str, _ctx: &'a ScrapeContext<'a>) -> HandlerFuture<'a> {
+        Box::pin(async move { Post::default() })
+    }
+}
+
+#[test]
+fn builtin_registry_routes_x_urls_to_the_x_handler() {
+    let handlers = SourceHandlers::builtin();
+    assert_eq!(
+        handlers.names(),
+        [
+            "x",
+            "youtube",
+            "mastodon",
+            "bluesky",
+            "reddit",
+            "hackernews",
+            "telegram"
+        ]
+    );
+    let name = |url: &str| handlers.handler_for(url).map(|handler| handler.name());
+    assert_eq!(name("https://x.com/rustlang/status/1234567890"), Some("x"));
+    assert_eq!(
+        name("https://twitter.com/rustlang/status/1234567890"),
+        Some("x")
+    );
+    assert_eq!(
+        name("https://www.youtube.com/watch?v=dQw4w9WgXcQ"),
+        Some("youtube")
+    );
+    assert_eq!(name("https://www.youtube.com/@rustlang"), None);
+    assert_eq!(
+        name("https://mastodon.social/@rustlang/113579484624538435"),
+        Some("mastodon")
+    );
+    assert_eq!(
+        name("https://bsky.app/profile/rust-lang.org/post/3lbd2vqz4xk2p"),
+        Some("bluesky")
+    );
+    assert_eq!(
+        name("https://www.reddit.com/r/rust/comments/1g4x2ab/announcing_rust/"),
+        Some("reddit")
+    );
+    assert_eq!(
+        name("https://news.ycombinator.com/item?id=8863"),
+        Some("hackernews")
+    );
+    assert_eq!(name("https://t.me/durov/142"), Some("telegram"));
+    assert_eq!(name("https://example.com/news/story"), None);
+
+    assert!(SourceHandlers::new().is_empty());
+    assert_eq!(
+        ScrapeOptions::from_env().source_handlers().names(),
+        [
+            "x",
+            "youtube",
+            "mastodon",
+            "bluesky",
+            "reddit",
+            "hackernews",
+            "telegram"
+        ]
+    );
+}
+
+#[test]
+fn later_handlers_take_precedence() {
+    let mut handlers = SourceHandlers::builtin();
+    handlers.register(CatchAll("first")).register(JsonApi);
+    assert_eq!(handlers.len(), 9);
+
+    let name = |url: &str| handlers.handler_for(url).map(|handler| handler.name());
+    assert_eq!(name("https://example.com/api/story"), Some("json-api"));
+    assert_eq!(
+        name("https://x.com/rustlang/status/1234567890"),
+        Some("first")
+    );
+    assert_eq!(name("https://example.com/news/story"), Some("first"));
+
+    let options = ScrapeOptions::from_env().handlers(SourceHandlers::new());
+    assert!(options.source_handlers().is_empty());
+    assert!(format!("{options:?}").contains("handlers: []"));
+}
+
+/// Serve `/api/story` as JSON and everything else as an article page.
+fn serve() -> String {
+    serve_with(|_| {
+        vec![
+            Route::json(
+                "/api/story",
+                r#"{"title": "From the API", "body": "<h2>Budget</h2><p>The council approved the transit plan.</p>"}"#,
+            ),
+            Route::fallback(
+                "200 OK",
+                "text/html; charset=UTF-8",
+                article_page("From the page"),
+            ),
+        ]
+    })
+}
+
+fn hermetic_scraper(handlers: SourceHandlers) -> Scraper {
+    Scraper::new(hermetic_options().handlers(handlers))
+}
+
+#[tokio::test]
+async fn scraper_dispatches_to_the_matching_handler() {
+    let base = serve();
+    let mut handlers = SourceHandlers::builtin();
+    handlers.register(JsonApi);
+    let scraper = hermetic_scraper(handlers);
+
+    let post = scraper
+        .scrape(&format!("{}/api/story", base), "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(post.title, "From the API");
+    assert!(post.content.starts_with("## Budget"), "{}", post.content);
+
+    let post = scraper
+        .scrape(&format!("{}/api/raw/story", base), "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(post.title, "From the page");
+
+    let post = scraper
+        .scrape(&format!("{}/news/story", base), "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(post.title, "From the page");
+}
+
+#[tokio::test]
+async fn handler_errors_are_reported_in_the_post() {
+    let closed = format!("{}/api/story", refused_base());
+
+    let mut handlers = SourceHandlers::new();
+    handlers.register(JsonApi);
+    let post = hermetic_scraper(handlers).scrape(&closed, "english").await;
+
+    assert!(!post.error.is_empty());
+    assert!(
+        matches!(post.error_kind, Some(ScrapeError::Network { .. })),
+        "{:?}",
+        post.error_kind
+    );
+}
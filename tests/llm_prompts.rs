@@ -94,6 +94,10 @@ async fn oversized_payload_fails_loudly_instead_of_silent_empty_conversion() {
         publication_date: None,
         author: None,
         error: String::new(),
+        error_kind: None,
+        provenance: None,
+        metadata: None,
+        canonical_url: None,
     };
 
     let result = convert_content_to_markdown(post, "english", Some(1)).await;
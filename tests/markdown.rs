@@ -0,0 +1,191 @@
+//! Integration tests for the deterministic HTML → Markdown converter
+//! ([`html_to_markdown`]), the attributes the cleaner now keeps for it, and
+//! the LLM-free [`ConversionMode::Deterministic`] scrape path.
+//!
+//! Hermetic: the end-to-end scrape targets a loopback server with every
+//! network fallback disabled and a bogus LLM provider, which the
+//! deterministic mode must never reach.
+
+mod common;
+
+use uninews::html::parse_scraped_post_from_html;
+use uninews::{html_to_markdown, ConversionMode, FallbackStage, Scraper};
+
+use common::{hermetic_options, serve_with, Route};
+
+const URL: &str = "https://example.com/news/story";
+
+fn convert(html: &str) -> String {
+    html_to_markdown(html, Some(URL))
+}
+
+#[test]
+fn headings_paragraphs_and_emphasis() {
+    let markdown = convert(
+        "<article><h1>Title</h1><p>First <strong>bold</strong> and <em>soft</em>.</p>\
+         <h3>Sub</h3><p>Second <code>let x = 1;</code></p></article>",
+    );
+    assert_eq!(
+        markdown,
+        "# Title\n\nFirst **bold** and *soft*.\n\n### Sub\n\nSecond `let x = 1;`"
+    );
+}
+
+#[test]
+fn nested_and_ordered_lists() {
+    let markdown = convert(
+        "<ul><li>One</li><li>Two<ul><li>Inner</li></ul></li></ul>\
+         <ol start=\"3\"><li>Third</li><li>Fourth</li></ol>",
+    );
+    assert_eq!(markdown, "- One\n- Two\n  - Inner\n\n3. Third\n4. Fourth");
+}
+
+#[test]
+fn links_and_images_resolve_against_the_base_url() {
+    let markdown = convert(
+        "<p><a href=\"/about us\">About</a> <a href=\"javascript:alert(1)\">Bad</a> \
+         <img src=\"img/a.png\" alt=\"Chart [1]\"></p>",
+    );
+    assert_eq!(
+        markdown,
+        "[About](https://example.com/about%20us) Bad ![Chart \\[1\\]](https://example.com/news/img/a.png)"
+    );
+}
+
+#[test]
+fn blockquotes_pre_blocks_and_tables() {
+    let markdown = convert(
+        "<blockquote><p>Quoted</p><p>Twice</p></blockquote>\
+         <pre><code>fn main() {\n    *x = 1;\n}</code></pre>\
+         <table><tr><th>Name</th><th>A|B</th></tr><tr><td>x</td></tr></table>",
+    );
+    assert_eq!(
+        markdown,
+        "> Quoted\n>\n> Twice\n\n```\nfn main() {\n    *x = 1;\n}\n```\n\n\
+         | Name | A\\|B |\n| --- | --- |\n| x |  |"
+    );
+}
+
+#[test]
+fn markdown_and_html_in_text_is_escaped() {
+    let markdown = convert("<p>&lt;script&gt; *not bold* [x](y)</p>");
+    assert_eq!(markdown, "\\<script\\> \\*not bold\\* \\[x\\](y)");
+}
+
+#[test]
+fn block_markers_at_line_starts_are_escaped() {
+    assert_eq!(convert("<p># Not a heading</p>"), "\\# Not a heading");
+    assert_eq!(convert("<p>## Nor this</p>"), "\\## Nor this");
+    assert_eq!(convert("<p>- not a bullet</p>"), "\\- not a bullet");
+    assert_eq!(convert("<p>---</p>"), "\\---");
+    assert_eq!(convert("<p>+ not a bullet</p>"), "\\+ not a bullet");
+    assert_eq!(convert("<p>1. not a list</p>"), "1\\. not a list");
+    assert_eq!(convert("<p>2024) not a list</p>"), "2024\\) not a list");
+    assert_eq!(convert("<p>| not | a table |</p>"), "\\| not | a table |");
+    // After a line break too, but not mid-line or where no block opens.
+    assert_eq!(convert("<p>Scores:<br>- 3 to 1</p>"), "Scores:\n\\- 3 to 1");
+    assert_eq!(
+        convert("<p>#hashtag -5% 1.5 million, up 2. points</p>"),
+        "#hashtag -5% 1.5 million, up 2. points"
+    );
+}
+
+#[test]
+fn deep_nesting_does_not_overflow_the_stack() {
+    const DEPTH: usize = 50_000;
+    let html = format!(
+        "{}deep text{}",
+        "<marquee>".repeat(DEPTH),
+        "</marquee>".repeat(DEPTH)
+    );
+    assert_eq!(convert(&html), "deep text");
+}
+
+#[test]
+fn cleaner_keeps_link_targets_and_images_but_drops_other_attributes() {
+    let body = format!(
+        "<html><body><article><p class=\"x\" onclick=\"evil()\">Story with a \
+         <a href=\"/more\" style=\"color:red\">link</a> and \
+         <a href=\"javascript:alert(1)\">script</a>.</p>\
+         <p><img src=\"/photo.jpg\" alt=\"A &quot;photo&quot;\" width=\"10\"></p>{}</article></body></html>",
+        "<p>More of the story, told at length.</p>".repeat(4)
+    );
+    let post = parse_scraped_post_from_html(URL, &body, None);
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(
+        post.content.contains("<a href=\"/more\">link</a>"),
+        "{}",
+        post.content
+    );
+    assert!(post.content.contains("<a>script</a>"), "{}", post.content);
+    assert!(
+        post.content
+            .contains("<img src=\"/photo.jpg\" alt=\"A &quot;photo&quot;\">"),
+        "{}",
+        post.content
+    );
+    for dropped in ["class=", "onclick", "style=", "width=", "javascript:"] {
+        assert!(
+            !post.content.contains(dropped),
+            "{dropped}: {}",
+            post.content
+        );
+    }
+}
+
+#[test]
+fn paywall_word_in_a_link_target_is_not_blocked_content() {
+    let body = "<html><body><article><p>The council approved the plan. \
+                <a href=\"/paywall-faq\">Read our policies</a>.</p></article></body></html>";
+    let post = parse_scraped_post_from_html(URL, body, None);
+    assert!(post.error.is_empty(), "{}", post.error);
+}
+
+#[test]
+fn conversion_mode_round_trips_through_strings() {
+    for mode in [
+        ConversionMode::Llm,
+        ConversionMode::Deterministic,
+        ConversionMode::LlmPrepass,
+    ] {
+        assert_eq!(mode.to_string().parse::<ConversionMode>(), Ok(mode));
+    }
+    assert_eq!("LLM-Prepass".parse(), Ok(ConversionMode::LlmPrepass));
+    assert!("magic".parse::<ConversionMode>().is_err());
+    assert!(!ConversionMode::Deterministic.uses_llm());
+    assert_eq!(ConversionMode::default(), ConversionMode::Llm);
+}
+
+#[tokio::test]
+async fn deterministic_scrape_never_reaches_the_llm() {
+    let body = format!(
+        "<html><head><title>Transit plan</title></head><body><article>\
+         <h2>Approved</h2>{}<p>See <a href=\"/docs/plan\">the plan</a>.</p>\
+         </article></body></html>",
+        "<p>The council approved the transit plan after months of hearings.</p>".repeat(6)
+    );
+    let url = serve_with(|_| vec![Route::html("/", body)]);
+
+    let scraper = Scraper::new(hermetic_options());
+    let post = scraper.scrape(&url, "spanish").await;
+
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(post.title, "Transit plan");
+    assert!(
+        post.content.starts_with("## Approved\n\n"),
+        "{}",
+        post.content
+    );
+    assert!(
+        post.content
+            .contains(&format!("[the plan]({}/docs/plan)", url)),
+        "{}",
+        post.content
+    );
+    assert!(!post.content.contains('<'), "{}", post.content);
+
+    let provenance = post.provenance.expect("provenance");
+    assert_eq!(provenance.source, FallbackStage::PlainFetch);
+    assert_eq!(provenance.llm_provider, None);
+    assert!(provenance.converted_at.is_some());
+}
@@ -0,0 +1,546 @@
+//! Integration tests for Mastodon statuses: status-URL shapes, the API and
+//! ActivityStreams parsers, link-only detection, and the built-in handler
+//! assembling self-reply threads, following link-only statuses, and
+//! passing non-fediverse hosts to the web pipeline.
+//!
+//! Hermetic: in-memory documents plus one `common::serve_with` loopback
+//! server per test standing in for an instance, scraped with
+//! `common::hermetic_options`.
+
+mod common;
+
+use serde_json::{json, Value};
+use uninews::{
+    mastodon_status_id, parse_activity_note, parse_mastodon_context, parse_mastodon_status,
+    FallbackStage, MastodonHandler, MastodonSource, ScrapeError, Scraper, SourceHandler,
+};
+
+use common::{article_page, hermetic_options, serve_with, Route};
+
+/// A Mastodon API status by `account` (`[id, acct, display name]`).
+fn status(
+    id: &str,
+    account: [&str; 3],
+    created_at: &str,
+    reply_to: Option<&str>,
+    content: &str,
+) -> Value {
+    json!({
+        "id": id,
+        "created_at": created_at,
+        "in_reply_to_id": reply_to,
+        "url": format!("https://social.example/@{}/{}", account[1], id),
+        "content": content,
+        "spoiler_text": "",
+        "language": "en",
+        "edited_at": null,
+        "reblog": null,
+        "account": {
+            "id": account[0],
+            "acct": account[1],
+            "display_name": account[2],
+            "url": format!("https://social.example/@{}", account[1]),
+            "avatar": "https://social.example/avatars/1.png"
+        },
+        "media_attachments": [],
+        "tags": [],
+        "card": null
+    })
+}
+
+const ALICE: [&str; 3] = ["1", "alice", "Alice Reporter"];
+const BOB: [&str; 3] = ["2", "bob@other.example", ""];
+
+#[test]
+fn status_ids_come_from_status_url_shapes() {
+    let id = |url: &str| mastodon_status_id(url);
+    assert_eq!(
+        id("https://mastodon.social/@alice/113579484624538435").as_deref(),
+        Some("113579484624538435")
+    );
+    assert_eq!(
+        id("https://mastodon.social/@bob@other.example/42").as_deref(),
+        Some("42")
+    );
+    assert_eq!(
+        id("https://mastodon.social/users/alice/statuses/42").as_deref(),
+        Some("42")
+    );
+    assert_eq!(
+        id("https://mastodon.social/web/statuses/42").as_deref(),
+        Some("42")
+    );
+    assert_eq!(
+        id("https://mastodon.social/web/@alice/42?lang=en").as_deref(),
+        Some("42")
+    );
+    assert_eq!(id("https://mastodon.social/@alice"), None);
+    assert_eq!(id("https://mastodon.social/@alice/with_replies"), None);
+    assert_eq!(id("https://medium.com/@writer/a-story-1f2e3d"), None);
+    assert_eq!(
+        id("https://www.tiktok.com/@user/video/7300000000000000000"),
+        None
+    );
+
+    assert!(MastodonHandler.matches("https://hachyderm.io/@alice/42"));
+    assert!(!MastodonHandler.matches("https://example.com/news/story"));
+}
+
+#[test]
+fn api_statuses_and_contexts_parse() {
+    let mut boosted = status(
+        "7",
+        ALICE,
+        "2026-10-16T09:00:00.000Z",
+        None,
+        "<p>Council approves the <a href=\"https://social.example/tags/transit\" class=\"mention hashtag\" rel=\"tag\">#<span>transit</span></a> plan</p>",
+    );
+    boosted["media_attachments"] = json!([
+        {"type": "image", "url": "https://files.example/1.jpg", "description": "The new line map"},
+        {"type": "video", "url": "https://files.example/2.mp4", "description": null}
+    ]);
+    boosted["tags"] = json!([{"name": "transit"}]);
+    boosted["card"] = json!({"url": "https://news.example/plan", "title": "Transit plan", "description": "", "image": null});
+    let mut boost = status("8", BOB, "2026-10-16T10:00:00.000Z", None, "");
+    boost["reblog"] = boosted;
+
+    let parsed = parse_mastodon_status(&boost.to_string()).expect("status");
+    assert_eq!(parsed.id, "7");
+    assert_eq!(parsed.account.acct, "alice");
+    assert_eq!(parsed.account.display_name, "Alice Reporter");
+    assert_eq!(parsed.text(), "Council approves the #transit plan");
+    assert_eq!(parsed.spoiler_text, None);
+    assert_eq!(parsed.tags, ["transit"]);
+    assert_eq!(parsed.media.len(), 2);
+    assert_eq!(parsed.media[0].kind, "image");
+    assert_eq!(
+        parsed.media[0].description.as_deref(),
+        Some("The new line map")
+    );
+    let card = parsed.card.as_ref().expect("card");
+    assert_eq!(card.title, "Transit plan");
+    assert_eq!(card.description, None);
+    // The hashtag is text, and the card is not part of the text.
+    assert!(!parsed.is_link_only());
+
+    let context = json!({
+        "ancestors": [status("5", ALICE, "2026-10-16T08:00:00.000Z", None, "<p>One</p>")],
+        "descendants": [
+            {"id": "9", "deleted": true},
+            status("10", BOB, "2026-10-16T11:00:00.000Z", Some("7"), "<p>Nice</p>")
+        ]
+    });
+    let statuses = parse_mastodon_context(&context.to_string()).expect("context");
+    let ids: Vec<&str> = statuses.iter().map(|status| status.id.as_str()).collect();
+    assert_eq!(ids, ["5", "10"]);
+
+    let error = parse_mastodon_status("{\"error\": \"Record not found\"}").expect_err("error body");
+    assert!(error.starts_with("Not a Mastodon status:"), "{error}");
+    let error = parse_mastodon_context("[]").expect_err("not a context");
+    assert!(
+        error.starts_with("Not a Mastodon status context:"),
+        "{error}"
+    );
+}
+
+#[test]
+fn activity_notes_parse() {
+    let create = json!({
+        "type": "Create",
+        "actor": "https://social.example/users/alice",
+        "object": {
+            "type": "Note",
+            "id": "https://social.example/users/alice/statuses/7",
+            "url": "https://social.example/@alice/7",
+            "attributedTo": "https://social.example/users/alice",
+            "published": "2026-10-16T09:00:00Z",
+            "inReplyTo": "https://social.example/users/alice/statuses/5",
+            "summary": "Local politics",
+            "content": "<p>Council approves the plan</p>",
+            "contentMap": {"en": "<p>Council approves the plan</p>"},
+            "attachment": [{"type": "Document", "mediaType": "image/jpeg", "url": "https://files.example/1.jpg", "name": "Map"}],
+            "tag": [
+                {"type": "Hashtag", "name": "#transit"},
+                {"type": "Mention", "name": "@bob@other.example"}
+            ]
+        }
+    });
+
+    let note = parse_activity_note(&create.to_string()).expect("note");
+    assert_eq!(note.id, "https://social.example/users/alice/statuses/7");
+    assert_eq!(note.url.as_deref(), Some("https://social.example/@alice/7"));
+    assert_eq!(note.account.id, "https://social.example/users/alice");
+    assert_eq!(note.account.acct, "alice@social.example");
+    assert_eq!(
+        note.in_reply_to_id.as_deref(),
+        Some("https://social.example/users/alice/statuses/5")
+    );
+    assert_eq!(note.spoiler_text.as_deref(), Some("Local politics"));
+    assert_eq!(note.language.as_deref(), Some("en"));
+    assert_eq!(note.media[0].kind, "image");
+    assert_eq!(note.tags, ["transit"]);
+
+    let error = parse_activity_note(
+        "{\"type\": \"Person\", \"id\": \"https://social.example/users/alice\"}",
+    )
+    .expect_err("not a note");
+    assert!(error.starts_with("Not an ActivityStreams note:"), "{error}");
+}
+
+#[test]
+fn link_only_statuses_point_at_their_article() {
+    let link = "<p><a href=\"https://news.example/plan\" rel=\"nofollow noopener\"><span class=\"invisible\">https://</span><span>news.example/plan</span></a></p>";
+    let parsed = parse_mastodon_status(
+        &status("7", ALICE, "2026-10-16T09:00:00.000Z", None, link).to_string(),
+    )
+    .expect("status");
+    assert!(parsed.is_link_only());
+    assert_eq!(
+        parsed.linked_article_url().as_deref(),
+        Some("https://news.example/plan")
+    );
+
+    let mention = format!(
+        "<p><span class=\"h-card\"><a href=\"https://other.example/@bob\" class=\"u-url mention\">@<span>bob</span></a></span> {}</p>",
+        link
+    );
+    let parsed = parse_mastodon_status(
+        &status("7", ALICE, "2026-10-16T09:00:00.000Z", None, &mention).to_string(),
+    )
+    .expect("status");
+    assert!(!parsed.is_link_only());
+
+    let quote =
+        "<p><a href=\"https://other.example/@bob/42\">https://other.example/@bob/42</a></p>";
+    let parsed = parse_mastodon_status(
+        &status("7", ALICE, "2026-10-16T09:00:00.000Z", None, quote).to_string(),
+    )
+    .expect("status");
+    assert!(!parsed.is_link_only());
+    assert_eq!(parsed.linked_article_url(), None);
+}
+
+/// A `200 OK` route for requests whose `Accept` header contains `accept`
+/// (`""` matches any).
+fn route(path: &str, accept: &'static str, content_type: &'static str, body: String) -> Route {
+    Route::new(path, content_type, body).accept(accept)
+}
+
+fn hermetic_scraper() -> Scraper {
+    Scraper::new(hermetic_options())
+}
+
+#[tokio::test]
+async fn handler_assembles_the_self_reply_thread_from_the_api() {
+    let base = serve_with(|_| {
+        let context = json!({
+            "ancestors": [
+                status("100", BOB, "2026-10-16T07:00:00.000Z", None, "<p>What happened at the council?</p>"),
+                status("101", ALICE, "2026-10-16T08:00:00.000Z", Some("100"), "<p>First: the vote passed.</p>")
+            ],
+            "descendants": [
+                status("103", ALICE, "2026-10-16T08:02:00.000Z", Some("102"), "<p>Third: construction in 2027.</p>"),
+                status("104", BOB, "2026-10-16T08:03:00.000Z", Some("103"), "<p>Thanks!</p>"),
+                status("105", ALICE, "2026-10-16T08:04:00.000Z", Some("104"), "<p>You're welcome.</p>")
+            ]
+        });
+        let mut focus = status(
+            "102",
+            ALICE,
+            "2026-10-16T08:01:00.000Z",
+            Some("101"),
+            "<p>Second: two new lines.</p>",
+        );
+        focus["media_attachments"] = json!([
+            {"type": "image", "url": "https://files.example/map.jpg", "description": "Line map"}
+        ]);
+        vec![
+            route(
+                "/api/v1/statuses/102",
+                "json",
+                "application/json",
+                focus.to_string(),
+            ),
+            route(
+                "/api/v1/statuses/102/context",
+                "json",
+                "application/json; charset=utf-8",
+                context.to_string(),
+            ),
+        ]
+    });
+    let url = format!("{}/@alice/102", base);
+
+    let thread = hermetic_scraper()
+        .fetch_mastodon_thread(&url)
+        .await
+        .expect("thread");
+    let ids: Vec<&str> = thread
+        .statuses
+        .iter()
+        .map(|status| status.id.as_str())
+        .collect();
+    assert_eq!(ids, ["101", "102", "103"]);
+    assert_eq!(thread.focus, 1);
+    assert_eq!(thread.source, MastodonSource::Api);
+
+    let post = hermetic_scraper().scrape(&url, "english").await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(
+        post.title,
+        "@alice@social.example (Alice Reporter): First: the vote passed."
+    );
+    assert_eq!(
+        post.author.as_deref(),
+        Some("@alice@social.example (Alice Reporter)")
+    );
+    assert_eq!(
+        post.publication_date.as_deref(),
+        Some("2026-10-16T08:00:00.000Z")
+    );
+    assert_eq!(post.featured_image_url, "https://files.example/map.jpg");
+    let first = post.content.find("the vote passed").expect("first");
+    let second = post.content.find("two new lines").expect("second");
+    let third = post.content.find("construction in 2027").expect("third");
+    assert!(first < second && second < third, "{}", post.content);
+    assert!(post
+        .content
+        .contains("![Line map](https://files.example/map.jpg)"));
+    assert!(!post.content.contains("Thanks!"), "{}", post.content);
+    assert!(!post.content.contains("welcome"), "{}", post.content);
+
+    let provenance = post.provenance.expect("provenance");
+    assert_eq!(provenance.source, FallbackStage::Mastodon);
+    assert_eq!(
+        provenance.canonical_url.as_deref(),
+        Some("https://social.example/@alice/102")
+    );
+    let metadata = post.metadata.expect("metadata");
+    assert_eq!(metadata.schema_type.as_deref(), Some("SocialMediaPosting"));
+    assert_eq!(metadata.publisher.as_deref(), Some("social.example"));
+}
+
+#[tokio::test]
+async fn activity_streams_are_read_when_the_api_is_unavailable() {
+    let base = serve_with(|base| {
+        let note = |id: &str,
+                    reply_to: Option<&str>,
+                    published: &str,
+                    text: &str,
+                    replies: Vec<Value>| {
+            json!({
+                "@context": "https://www.w3.org/ns/activitystreams",
+                "type": "Note",
+                "id": format!("{base}/users/alice/statuses/{id}"),
+                "url": format!("{base}/@alice/{id}"),
+                "attributedTo": format!("{base}/users/alice"),
+                "published": published,
+                "inReplyTo": reply_to.map(|reply_to| format!("{base}/users/alice/statuses/{reply_to}")),
+                "content": format!("<p>{text}</p>"),
+                "replies": {"type": "Collection", "first": {"type": "CollectionPage", "items": replies}}
+            })
+        };
+        let root = note(
+            "1",
+            None,
+            "2026-10-16T08:00:00Z",
+            "First: the vote passed.",
+            vec![],
+        );
+        let reply = note(
+            "3",
+            Some("2"),
+            "2026-10-16T08:02:00Z",
+            "Third: construction in 2027.",
+            vec![],
+        );
+        let focus = note(
+            "2",
+            Some("1"),
+            "2026-10-16T08:01:00Z",
+            "Second: two new lines.",
+            vec![json!(format!("{base}/users/alice/statuses/3"))],
+        );
+        let actor = json!({
+            "type": "Person",
+            "id": format!("{base}/users/alice"),
+            "preferredUsername": "alice",
+            "name": "Alice Reporter",
+            "icon": {"type": "Image", "url": "https://files.example/alice.png"}
+        });
+        vec![
+            route(
+                "/@alice/2",
+                "activity+json",
+                "application/activity+json",
+                focus.to_string(),
+            ),
+            route(
+                "/users/alice/statuses/1",
+                "activity+json",
+                "application/activity+json",
+                root.to_string(),
+            ),
+            route(
+                "/users/alice/statuses/3",
+                "activity+json",
+                "application/activity+json",
+                reply.to_string(),
+            ),
+            route(
+                "/users/alice",
+                "activity+json",
+                "application/activity+json",
+                actor.to_string(),
+            ),
+        ]
+    });
+    let url = format!("{}/@alice/2", base);
+
+    let thread = hermetic_scraper()
+        .fetch_mastodon_thread(&url)
+        .await
+        .expect("thread");
+    assert_eq!(thread.source, MastodonSource::ActivityStreams);
+    assert_eq!(thread.statuses.len(), 3);
+    assert_eq!(thread.focus, 1);
+    assert_eq!(thread.status().account.display_name, "Alice Reporter");
+
+    let post = hermetic_scraper().scrape(&url, "english").await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(
+        post.title
+            .starts_with("@alice@127.0.0.1 (Alice Reporter): First"),
+        "{}",
+        post.title
+    );
+    assert_eq!(post.featured_image_url, "https://files.example/alice.png");
+    let first = post.content.find("the vote passed").expect("first");
+    let third = post.content.find("construction in 2027").expect("third");
+    assert!(first < third, "{}", post.content);
+}
+
+#[tokio::test]
+async fn link_only_statuses_follow_the_linked_article() {
+    let base = serve_with(|base| {
+        let link = format!(
+            "<p><a href=\"{base}/news/plan\" rel=\"nofollow noopener\">{base}/news/plan</a></p>"
+        );
+        let mut focus = status("7", ALICE, "2026-10-16T09:00:00.000Z", None, &link);
+        focus["card"] = json!({"url": format!("{base}/news/plan"), "title": "Council approves the transit plan"});
+        vec![
+            route(
+                "/api/v1/statuses/7",
+                "json",
+                "application/json",
+                focus.to_string(),
+            ),
+            route(
+                "/api/v1/statuses/7/context",
+                "json",
+                "application/json",
+                "{\"ancestors\": [], \"descendants\": []}".to_string(),
+            ),
+            route("/news/plan", "", "text/html", article_page("Plan | News")),
+        ]
+    });
+
+    let post = hermetic_scraper()
+        .scrape(&format!("{}/@alice/7", base), "english")
+        .await;
+
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(post.title, "Council approves the transit plan");
+    assert!(
+        post.content.contains("fund the new transit lines"),
+        "{}",
+        post.content
+    );
+    let provenance = post.provenance.expect("provenance");
+    assert_eq!(provenance.source, FallbackStage::PlainFetch);
+    assert_eq!(provenance.final_url, format!("{}/news/plan", base));
+}
+
+#[tokio::test]
+async fn non_fediverse_hosts_take_the_web_pipeline() {
+    let base = serve_with(|_| {
+        vec![route(
+            "/@writer/2026",
+            "",
+            "text/html",
+            article_page("Transit plan"),
+        )]
+    });
+
+    let post = hermetic_scraper()
+        .scrape(&format!("{}/@writer/2026", base), "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(post.title, "Transit plan");
+    assert_eq!(
+        post.provenance.expect("provenance").source,
+        FallbackStage::PlainFetch
+    );
+
+    // A site (or an authorized-fetch instance) refusing or failing the
+    // ActivityStreams request: the page itself is read.
+    for refusal in [
+        "401 Unauthorized",
+        "403 Forbidden",
+        "404 Not Found",
+        "406 Not Acceptable",
+        "415 Unsupported Media Type",
+        "429 Too Many Requests",
+        "503 Service Unavailable",
+    ] {
+        let base = serve_with(|_| {
+            vec![
+                route(
+                    "/@writer/2027",
+                    "activity+json",
+                    "text/plain",
+                    String::new(),
+                )
+                .status(refusal),
+                route(
+                    "/@writer/2027",
+                    "",
+                    "text/html",
+                    article_page("Budget vote"),
+                ),
+            ]
+        });
+        let post = hermetic_scraper()
+            .scrape(&format!("{}/@writer/2027", base), "english")
+            .await;
+        assert!(post.error.is_empty(), "{refusal}: {}", post.error);
+        assert_eq!(post.title, "Budget vote", "{refusal}");
+        assert_eq!(
+            post.provenance.expect("provenance").source,
+            FallbackStage::PlainFetch
+        );
+    }
+}
+
+#[tokio::test]
+async fn failures_of_the_web_pipeline_record_the_mastodon_stage() {
+    let base = serve_with(|_| {
+        vec![route("/@writer/2028", "", "text/plain", String::new())
+            .status("503 Service Unavailable")]
+    });
+    let post = hermetic_scraper()
+        .scrape(&format!("{}/@writer/2028", base), "english")
+        .await;
+    assert!(
+        matches!(
+            post.error_kind,
+            Some(ScrapeError::HttpStatus { status: 503, .. })
+        ),
+        "{:?}",
+        post.error_kind
+    );
+    assert_eq!(
+        post.error_kind.expect("kind").fallbacks()[0],
+        FallbackStage::Mastodon
+    );
+}
@@ -0,0 +1,257 @@
+//! Integration tests for JSON-LD / schema.org article metadata: field
+//! extraction, `@graph` and `@id` resolution, precedence of the `<meta>`
+//! tags, and `articleBody` as the content source for thin DOM extractions.
+
+use uninews::html::parse_scraped_post_from_html;
+use uninews::{Post, PostMetadata};
+
+const URL: &str = "https://example.com/news/story";
+
+fn story_paragraphs() -> String {
+    "<p>The council approved the transit plan on Tuesday after months of public hearings.</p>"
+        .repeat(8)
+}
+
+fn page(head: &str, body: &str) -> String {
+    format!(
+        "<html><head><title>Headline</title>{}</head><body>{}</body></html>",
+        head, body
+    )
+}
+
+fn json_ld(json: &str) -> String {
+    format!(r#"<script type="application/ld+json">{}</script>"#, json)
+}
+
+#[test]
+fn news_article_fields_fill_metadata_and_post_fallbacks() {
+    let head = json_ld(
+        r#"{
+            "@context": "https://schema.org",
+            "@type": "NewsArticle",
+            "headline": "Council approves transit plan",
+            "description": "After months of hearings.",
+            "author": [
+                {"@type": "Person", "name": "Ada Lovelace"},
+                {"@type": "Person", "name": "Grace Hopper"},
+                "Ada Lovelace"
+            ],
+            "datePublished": "2026-10-14T08:00:00Z",
+            "dateModified": "2026-10-15T09:30:00Z",
+            "publisher": {"@type": "Organization", "name": "Example Times"},
+            "articleSection": ["Local", "Politics"],
+            "keywords": "transit, council ,, budget",
+            "image": {"@type": "ImageObject", "url": "https://example.com/hero.jpg"}
+        }"#,
+    );
+    let post = parse_scraped_post_from_html(
+        URL,
+        &page(&head, &format!("<article>{}</article>", story_paragraphs())),
+        None,
+    );
+    assert!(post.error.is_empty(), "{}", post.error);
+
+    assert_eq!(
+        post.metadata,
+        Some(PostMetadata {
+            schema_type: Some("NewsArticle".to_string()),
+            headline: Some("Council approves transit plan".to_string()),
+            description: Some("After months of hearings.".to_string()),
+            authors: vec!["Ada Lovelace".to_string(), "Grace Hopper".to_string()],
+            date_published: Some("2026-10-14T08:00:00Z".to_string()),
+            date_modified: Some("2026-10-15T09:30:00Z".to_string()),
+            publisher: Some("Example Times".to_string()),
+            section: Some("Local".to_string()),
+            keywords: vec![
+                "transit".to_string(),
+                "council".to_string(),
+                "budget".to_string()
+            ],
+            image_url: Some("https://example.com/hero.jpg".to_string()),
+        })
+    );
+    assert_eq!(post.author.as_deref(), Some("Ada Lovelace, Grace Hopper"));
+    assert_eq!(
+        post.publication_date.as_deref(),
+        Some("2026-10-14T08:00:00Z")
+    );
+    assert_eq!(post.featured_image_url, "https://example.com/hero.jpg");
+}
+
+#[test]
+fn graph_references_resolve_by_id() {
+    let head = json_ld(
+        r#"{
+            "@context": "https://schema.org",
+            "@graph": [
+                {"@type": "WebPage", "@id": "https://example.com/news/story#webpage", "name": "Story"},
+                {
+                    "@type": ["Article", "BlogPosting"],
+                    "@id": "https://example.com/news/story#article",
+                    "headline": "Graph headline",
+                    "author": {"@id": "https://example.com/#/person/1"},
+                    "publisher": {"@id": "https://example.com/#org"},
+                    "mainEntityOfPage": {"@id": "https://example.com/news/story#webpage"}
+                },
+                {"@type": "Person", "@id": "https://example.com/#/person/1", "name": "Linus Pauling"},
+                {"@type": "Organization", "@id": "https://example.com/#org", "name": "Example Blog"}
+            ]
+        }"#,
+    );
+    let post = parse_scraped_post_from_html(
+        URL,
+        &page(&head, &format!("<article>{}</article>", story_paragraphs())),
+        None,
+    );
+
+    let metadata = post.metadata.expect("metadata");
+    assert_eq!(metadata.schema_type.as_deref(), Some("Article"));
+    assert_eq!(metadata.headline.as_deref(), Some("Graph headline"));
+    assert_eq!(metadata.authors, ["Linus Pauling"]);
+    assert_eq!(metadata.publisher.as_deref(), Some("Example Blog"));
+}
+
+#[test]
+fn news_article_outranks_other_nodes_across_blocks() {
+    let head = format!(
+        "{}{}",
+        json_ld(r#"[{"@type": "BreadcrumbList"}, {"@type": "Article", "headline": "Generic"}]"#),
+        json_ld(
+            r#"<![CDATA[ {"@type": "schema:ReportageNewsArticle", "headline": "Specific"} ]]>"#
+        )
+    );
+    let post = parse_scraped_post_from_html(
+        URL,
+        &page(&head, &format!("<article>{}</article>", story_paragraphs())),
+        None,
+    );
+
+    let metadata = post.metadata.expect("metadata");
+    assert_eq!(
+        metadata.schema_type.as_deref(),
+        Some("ReportageNewsArticle")
+    );
+    assert_eq!(metadata.headline.as_deref(), Some("Specific"));
+}
+
+#[test]
+fn meta_tags_take_precedence_over_json_ld() {
+    let head = format!(
+        r#"<meta name="author" content="Meta Author">
+           <meta property="article:published_time" content="2026-01-01T00:00:00Z">
+           <meta property="og:image" content="https://example.com/og.jpg">{}"#,
+        json_ld(
+            r#"{"@type": "NewsArticle", "author": "LD Author",
+                "datePublished": "2025-12-31", "image": "https://example.com/ld.jpg"}"#
+        )
+    );
+    let post = parse_scraped_post_from_html(
+        URL,
+        &page(&head, &format!("<article>{}</article>", story_paragraphs())),
+        None,
+    );
+
+    assert_eq!(post.author.as_deref(), Some("Meta Author"));
+    assert_eq!(
+        post.publication_date.as_deref(),
+        Some("2026-01-01T00:00:00Z")
+    );
+    assert_eq!(post.featured_image_url, "https://example.com/og.jpg");
+    assert_eq!(post.metadata.expect("metadata").authors, ["LD Author"]);
+}
+
+#[test]
+fn article_body_replaces_thin_dom_content() {
+    let body_text =
+        "The council approved the transit plan on Tuesday after months of hearings. ".repeat(6);
+    let head = json_ld(&format!(
+        r#"{{"@type": "NewsArticle", "articleBody": "{}\n\nFares stay at <2 dollars & change."}}"#,
+        body_text
+    ));
+    let post = parse_scraped_post_from_html(
+        URL,
+        &page(
+            &head,
+            "<article><p>Subscribe for the full story.</p></article>",
+        ),
+        None,
+    );
+
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(
+        post.content.starts_with("<p>The council approved"),
+        "{}",
+        post.content
+    );
+    assert!(
+        post.content
+            .ends_with("<p>Fares stay at &lt;2 dollars &amp; change.</p>"),
+        "{}",
+        post.content
+    );
+}
+
+#[test]
+fn article_body_rescues_an_empty_dom() {
+    let head = json_ld(&format!(
+        r#"{{"@type": "NewsArticle", "articleBody": "{}"}}"#,
+        "Rendered only by JavaScript, but described in JSON-LD. ".repeat(8)
+    ));
+    let post = parse_scraped_post_from_html(URL, &page(&head, "<div id=\"app\"></div>"), None);
+
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(post.content.contains("described in JSON-LD"));
+}
+
+#[test]
+fn rich_dom_content_is_kept_over_article_body() {
+    let head = json_ld(r#"{"@type": "NewsArticle", "articleBody": "Short summary only."}"#);
+    let post = parse_scraped_post_from_html(
+        URL,
+        &page(&head, &format!("<article>{}</article>", story_paragraphs())),
+        None,
+    );
+
+    assert!(post.content.contains("public hearings"), "{}", post.content);
+    assert!(!post.content.contains("Short summary only."));
+}
+
+#[test]
+fn malformed_or_missing_json_ld_leaves_metadata_unset() {
+    let head = format!(
+        "{}{}",
+        json_ld(r#"{"@type": "NewsArticle", "headline": "#),
+        json_ld(r#"{"@type": "Organization", "name": "Not an article"}"#)
+    );
+    let post = parse_scraped_post_from_html(
+        URL,
+        &page(&head, &format!("<article>{}</article>", story_paragraphs())),
+        None,
+    );
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(post.metadata, None);
+
+    let json = serde_json::to_value(&post).expect("serialize");
+    assert!(json.get("metadata").is_none(), "{json}");
+}
+
+#[test]
+fn metadata_json_omits_empty_fields_and_round_trips() {
+    let post = Post {
+        content: "Body".to_string(),
+        metadata: Some(PostMetadata {
+            authors: vec!["Ada Lovelace".to_string()],
+            section: Some("Science".to_string()),
+            ..PostMetadata::default()
+        }),
+        ..Post::default()
+    };
+    let json = serde_json::to_value(&post).expect("serialize");
+    assert_eq!(
+        json["metadata"],
+        serde_json::json!({"authors": ["Ada Lovelace"], "section": "Science"})
+    );
+
+    let round_trip: Post = serde_json::from_value(json).expect("deserialize");
+    assert_eq!(round_trip.metadata, post.metadata);
+}
@@ -0,0 +1,283 @@
+//! Integration tests for multi-page article following: next-page link
+//! detection (`?page=2`, `/2/`, `/page/2/`, and links that must not count
+//! as the next page) and the web pipeline concatenating the pages in
+//! order, within the page cap and without revisiting pages.
+//!
+//! Hermetic: in-memory documents plus one `common::serve_with` loopback
+//! server per test standing in for the paginated outlet, scraped with
+//! `common::hermetic_options`.
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use uninews::{next_page_url, set_event_listener, ScrapeEvent, Scraper};
+
+use common::{hermetic_options, serve_with, Route};
+
+/// One page of a paginated feature: `page_text` repeated as the body,
+/// with `links` (the pagination markup) after it.
+fn feature_page(head: &str, page_text: &str, links: &str) -> String {
+    format!(
+        r#"<html><head><title>The new transit lines – City News</title>{head}</head><body>
+<nav><a href="/">Home</a> <a href="/city/">City</a></nav>
+<article><h1>The new transit lines</h1>{}<nav class="pagination">{links}</nav></article>
+</body></html>"#,
+        format!("<p>{page_text}</p>").repeat(40)
+    )
+}
+
+#[test]
+fn next_pages_are_detected_from_rel_next_and_anchors() {
+    let next = |url: &str, links: &str| next_page_url(url, &feature_page("", "Body", links));
+
+    assert_eq!(
+        next_page_url(
+            "https://news.example/feature/transit",
+            &feature_page(
+                r#"<link rel="next" href="/feature/transit?page=2">"#,
+                "Body",
+                ""
+            )
+        )
+        .as_deref(),
+        Some("https://news.example/feature/transit?page=2")
+    );
+    assert_eq!(
+        next(
+            "https://news.example/feature/transit/",
+            r#"<a href="/feature/transit/2/#top">Next page</a>"#
+        )
+        .as_deref(),
+        Some("https://news.example/feature/transit/2/")
+    );
+    assert_eq!(
+        next(
+            "https://news.example/feature/transit/2/",
+            r#"<a href="/feature/transit/">1</a> <a href="/feature/transit/3/">3</a>"#
+        )
+        .as_deref(),
+        Some("https://news.example/feature/transit/3/")
+    );
+    assert_eq!(
+        next(
+            "https://news.example/2026/10/transit/page/2/?utm_source=feed",
+            r#"<a href="https://news.example/2026/10/transit/page/3/">Next</a>"#
+        )
+        .as_deref(),
+        Some("https://news.example/2026/10/transit/page/3/")
+    );
+    assert_eq!(
+        next(
+            "https://news.example/story.php?id=42&page=2",
+            r#"<a href="story.php?page=3&amp;id=42">Next</a>"#
+        )
+        .as_deref(),
+        Some("https://news.example/story.php?page=3&id=42")
+    );
+}
+
+#[test]
+fn other_links_are_not_taken_for_the_next_page() {
+    let next =
+        |url: &str, head: &str, links: &str| next_page_url(url, &feature_page(head, "Body", links));
+    let url = "https://news.example/feature/transit";
+
+    // The next blog post, another host, another article's page 2.
+    assert_eq!(
+        next(
+            url,
+            r#"<link rel="next" href="/feature/budget">"#,
+            r#"<a href="https://mirror.example/feature/transit?page=2">Mirror</a>
+<a href="/feature/budget?page=2">More budget</a>"#
+        ),
+        None
+    );
+    // Skipped and earlier page numbers, other query parameters.
+    assert_eq!(
+        next(
+            url,
+            "",
+            r#"<a href="/feature/transit?page=3">3</a> <a href="/feature/transit?page=2&amp;sort=new">Newest</a>"#
+        ),
+        None
+    );
+    assert_eq!(
+        next(
+            "https://news.example/feature/transit?page=3",
+            "",
+            r#"<a href="/feature/transit?page=2">Previous</a> <a href="/feature/transit">1</a>"#
+        ),
+        None
+    );
+    // `p` is WordPress's post id, and large numbers are ids, not pages.
+    assert_eq!(
+        next(
+            "https://blog.example/?p=41",
+            "",
+            r#"<a href="/?p=42">Next post</a>"#
+        ),
+        None
+    );
+    assert_eq!(
+        next(
+            "https://news.example/story/1234",
+            "",
+            r#"<a href="/story/1235">Next story</a>"#
+        ),
+        None
+    );
+    // Small ids and dates look like page numbers, but the links do not say
+    // they lead to the next page.
+    assert_eq!(
+        next(
+            "https://news.example/story/42",
+            "",
+            r#"<a href="/story/43">Next story</a>"#
+        ),
+        None
+    );
+    assert_eq!(
+        next(
+            "https://news.example/2024/05/15",
+            "",
+            r#"<a href="/2024/05/16">Thursday, May 16</a>"#
+        ),
+        None
+    );
+    assert_eq!(next(url, "", ""), None);
+}
+
+const PAGE_ONE: &str = "The city council voted on Tuesday to fund the new transit lines.";
+const PAGE_TWO: &str = "Construction of the northern line starts in the spring of 2027.";
+const PAGE_THREE: &str = "Residents can comment on the station designs until December.";
+/// Times each page's text appears in its body ([`feature_page`]).
+const PAGE_TEXT_REPEATS: usize = 40;
+
+/// A three-page feature: page 1 links page 2 with `rel="next"`, page 2
+/// links both neighbours, and page 3 links back to pages 1 and 2.
+fn three_page_feature() -> Vec<Route> {
+    vec![
+        Route::html(
+            "/feature/transit",
+            feature_page(
+                r#"<link rel="next" href="/feature/transit?page=2">"#,
+                PAGE_ONE,
+                r#"<a href="/feature/transit?page=2">Next page</a>"#,
+            ),
+        ),
+        Route::html(
+            "/feature/transit?page=2",
+            feature_page(
+                r#"<link rel="prev" href="/feature/transit">"#,
+                PAGE_TWO,
+                r#"<a href="/feature/transit">Previous page</a> <a href="/feature/transit?page=3">Next page</a>"#,
+            ),
+        ),
+        Route::html(
+            "/feature/transit?page=3",
+            feature_page(
+                "",
+                PAGE_THREE,
+                r#"<a href="/feature/transit">1</a> <a href="/feature/transit?page=2">Previous page</a>"#,
+            ),
+        ),
+    ]
+}
+
+#[tokio::test]
+async fn paginated_articles_are_concatenated_in_order() {
+    let base = serve_with(|_| three_page_feature());
+    let url = format!("{}/feature/transit", base);
+
+    // Only test in this binary that installs a listener; events from the
+    // other tests' servers are filtered out by URL.
+    let events: Arc<Mutex<Vec<ScrapeEvent>>> = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&events);
+    let prefix = base.clone();
+    set_event_listener(Some(Arc::new(move |event: &ScrapeEvent| {
+        let url = match event {
+            ScrapeEvent::FetchSucceeded { url, .. }
+            | ScrapeEvent::ArticlePagesFollowed { url, .. } => url,
+            _ => return,
+        };
+        if url.starts_with(&prefix) {
+            sink.lock().unwrap().push(event.clone());
+        }
+    })));
+
+    let post = Scraper::new(hermetic_options())
+        .scrape(&url, "english")
+        .await;
+    set_event_listener(None);
+
+    assert!(post.error.is_empty(), "{}", post.error);
+    let one = post.content.find(PAGE_ONE).expect("page 1 text");
+    let two = post.content.find(PAGE_TWO).expect("page 2 text");
+    let three = post.content.find(PAGE_THREE).expect("page 3 text");
+    assert!(one < two && two < three, "{}", post.content);
+    // Page 3's links back do not pull pages 1 or 2 in again.
+    assert_eq!(
+        post.content.matches(PAGE_ONE).count(),
+        PAGE_TEXT_REPEATS,
+        "{}",
+        post.content
+    );
+    assert_eq!(post.provenance.expect("provenance").final_url, url);
+
+    let recorded = events.lock().unwrap();
+    let fetched: Vec<&str> = recorded
+        .iter()
+        .filter_map(|event| match event {
+            ScrapeEvent::FetchSucceeded { url, .. } => Some(url.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        fetched,
+        [
+            url.clone(),
+            format!("{url}?page=2"),
+            format!("{url}?page=3")
+        ],
+        "{:?}",
+        *recorded
+    );
+    assert!(
+        recorded.iter().any(|event| matches!(
+            event,
+            ScrapeEvent::ArticlePagesFollowed { url: followed, pages: 3 } if *followed == url
+        )),
+        "{:?}",
+        *recorded
+    );
+}
+
+#[tokio::test]
+async fn the_page_cap_and_failed_pages_end_following() {
+    let base = serve_with(|_| three_page_feature());
+    let url = format!("{}/feature/transit", base);
+
+    let post = Scraper::new(hermetic_options().max_article_pages(2))
+        .scrape(&url, "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(post.content.contains(PAGE_TWO), "{}", post.content);
+    assert!(!post.content.contains(PAGE_THREE), "{}", post.content);
+
+    let post = Scraper::new(hermetic_options().max_article_pages(1))
+        .scrape(&url, "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(post.content.contains(PAGE_ONE), "{}", post.content);
+    assert!(!post.content.contains(PAGE_TWO), "{}", post.content);
+
+    // Page 2 is missing: page 1 is kept on its own.
+    let base = serve_with(|_| three_page_feature().into_iter().take(1).collect());
+    let post = Scraper::new(hermetic_options())
+        .scrape(&format!("{}/feature/transit", base), "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(post.content.contains(PAGE_ONE), "{}", post.content);
+    assert!(!post.content.contains("Not found"), "{}", post.content);
+}
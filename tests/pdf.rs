@@ -0,0 +1,225 @@
+//! Integration tests for PDF documents: detection by `Content-Type` and
+//! magic bytes, page-text and document-information extraction, and the web
+//! pipeline scraping a linked PDF with its page boundaries intact.
+//!
+//! Hermetic: PDFs are built in memory and served from one loopback server
+//! with Playwright and archive.org disabled and deterministic conversion.
+
+mod common;
+
+use uninews::{is_pdf, parse_pdf, FallbackStage, ScrapeError, Scraper};
+
+use common::{hermetic_options, serve_with, Route};
+
+/// A minimal PDF with one page per entry of `pages` (each a list of text
+/// lines, `""` for a paragraph break) in Helvetica, and `info` as the body
+/// of the document information dictionary.
+fn build_pdf(pages: &[&[&str]], info: &str) -> Vec<u8> {
+    let page_count = pages.len();
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            (0..page_count)
+                .map(|index| format!("{} 0 R", 4 + index * 2))
+                .collect::<Vec<_>>()
+                .join(" "),
+            page_count
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+    ];
+    for (index, lines) in pages.iter().enumerate() {
+        let mut stream = String::from("BT /F1 12 Tf 14 TL 72 720 Td");
+        for line in lines.iter() {
+            if line.is_empty() {
+                stream.push_str(" T*");
+            } else {
+                stream.push_str(&format!(" ({}) Tj T*", line));
+            }
+        }
+        stream.push_str(" ET");
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            5 + index * 2
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}\nendstream",
+            stream.len(),
+            stream
+        ));
+    }
+    objects.push(format!("<< {} >>", info));
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", index + 1, object).as_bytes());
+    }
+    let xref = pdf.len();
+    pdf.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+    );
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R /Info {} 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            objects.len(),
+            xref
+        )
+        .as_bytes(),
+    );
+    pdf
+}
+
+/// Two pages: a headline and two paragraphs (one word hyphenated across a
+/// line break), then a closing paragraph.
+const REPORT: [&[&str]; 2] = [
+    &[
+        "Council approves the transit plan",
+        "",
+        "The city council voted on Tues-",
+        "day to fund the new lines.",
+        "",
+        "Construction starts next year.",
+    ],
+    &["Appendix: the approved budget."],
+];
+
+/// UTF-16BE title, PDFDocEncoding author, dates with and without a zone.
+const REPORT_INFO: &str = "/Title <FEFF005400720061006E00730069007400200070006C0061006E> \
+     /Author (City Clerk) /Subject (Transit funding) /Keywords (transit; budget, council) \
+     /CreationDate (D:20261016093000+02'00') /ModDate (D:20261017)";
+
+#[test]
+fn pdfs_are_detected_by_content_type_or_magic_bytes() {
+    assert!(is_pdf(Some("application/pdf"), b""));
+    assert!(is_pdf(Some("Application/PDF; charset=binary"), b""));
+    assert!(is_pdf(Some("application/x-pdf"), b""));
+    assert!(is_pdf(Some("application/octet-stream"), b"%PDF-1.4\n"));
+    assert!(is_pdf(None, b"\xEF\xBB\xBF\r\n%PDF-1.7\n"));
+
+    assert!(!is_pdf(Some("text/html; charset=utf-8"), b"%PDF-1.4\n"));
+    assert!(!is_pdf(Some("application/xhtml+xml"), b"%PDF-1.4\n"));
+    assert!(!is_pdf(None, b"<!doctype html><p>Hello</p>"));
+}
+
+#[test]
+fn page_text_and_document_information_are_extracted() {
+    let document = parse_pdf(&build_pdf(&REPORT, REPORT_INFO)).expect("pdf");
+
+    assert_eq!(document.title.as_deref(), Some("Transit plan"));
+    assert_eq!(document.author.as_deref(), Some("City Clerk"));
+    assert_eq!(document.subject.as_deref(), Some("Transit funding"));
+    assert_eq!(document.keywords, ["transit", "budget", "council"]);
+    assert_eq!(
+        document.creation_date.as_deref(),
+        Some("2026-10-16T09:30:00+02:00")
+    );
+    assert_eq!(
+        document.modification_date.as_deref(),
+        Some("2026-10-17T00:00:00Z")
+    );
+    assert_eq!(document.pages.len(), 2);
+    assert!(document.pages[0].contains("Council approves the transit plan"));
+    assert!(document.pages[1].contains("Appendix: the approved budget."));
+    assert!(!document.is_empty());
+}
+
+#[test]
+fn unreadable_pdfs_are_errors() {
+    let error = parse_pdf(b"%PDF-1.4\nthis is not really a pdf").expect_err("garbage");
+    assert!(error.starts_with("Not a readable PDF:"), "{error}");
+
+    let blank = parse_pdf(&build_pdf(&[&[]], "/Producer (Scanner)")).expect("pdf");
+    assert!(blank.is_empty());
+    assert_eq!(blank.title, None);
+}
+
+#[tokio::test]
+async fn linked_pdfs_are_scraped_with_page_boundaries() {
+    let base = serve_with(|_| {
+        vec![Route::new(
+            "/files/transit-report.pdf",
+            "application/pdf",
+            build_pdf(&REPORT, REPORT_INFO),
+        )]
+    });
+    let url = format!("{}/files/transit-report.pdf", base);
+
+    let post = Scraper::new(hermetic_options())
+        .scrape(&url, "english")
+        .await;
+
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(post.title, "Transit plan");
+    assert_eq!(post.author.as_deref(), Some("City Clerk"));
+    assert_eq!(
+        post.publication_date.as_deref(),
+        Some("2026-10-16T09:30:00+02:00")
+    );
+    assert!(
+        post.content.contains(
+            "The city council voted on Tuesday to fund the new lines.\n\nConstruction starts next year.\n\n---\n\nAppendix: the approved budget."
+        ),
+        "{}",
+        post.content
+    );
+    let provenance = post.provenance.expect("provenance");
+    assert_eq!(provenance.source, FallbackStage::PlainFetch);
+    assert_eq!(provenance.http_status, Some(200));
+    let metadata = post.metadata.expect("metadata");
+    assert_eq!(metadata.schema_type.as_deref(), Some("DigitalDocument"));
+    assert_eq!(metadata.description.as_deref(), Some("Transit funding"));
+}
+
+#[tokio::test]
+async fn untitled_octet_stream_pdfs_use_their_first_line() {
+    let base = serve_with(|_| {
+        vec![Route::new(
+            "/download?id=42",
+            "application/octet-stream",
+            build_pdf(&REPORT, "/Producer (Word)"),
+        )]
+    });
+
+    let post = Scraper::new(hermetic_options())
+        .scrape(&format!("{}/download?id=42", base), "english")
+        .await;
+
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(post.title, "Council approves the transit plan");
+    assert_eq!(post.author, None);
+}
+
+#[tokio::test]
+async fn image_only_pdfs_are_insufficient_content() {
+    let base = serve_with(|_| {
+        vec![Route::new(
+            "/scan.pdf",
+            "application/pdf",
+            build_pdf(&[&[]], "/Producer (Scanner)"),
+        )]
+    });
+
+    let post = Scraper::new(hermetic_options())
+        .scrape(&format!("{}/scan.pdf", base), "english")
+        .await;
+
+    assert!(
+        matches!(
+            post.error_kind,
+            Some(ScrapeError::InsufficientContent { .. })
+        ),
+        "{:?}",
+        post.error_kind
+    );
+    assert_eq!(
+        post.error_kind.expect("kind").fallbacks(),
+        [FallbackStage::PlainFetch]
+    );
+}
@@ -0,0 +1,120 @@
+//! Integration tests for [`Provenance`]: the RFC 3339 formatter, canonical
+//! URL extraction, JSON shape, and the provenance recorded by an end-to-end
+//! loopback scrape.
+//!
+//! Hermetic: the scrape goes through a [`Scraper`] with Playwright,
+//! archive.org, and the host-fallback-first ordering disabled, and a bogus
+//! LLM provider so the conversion stage can never make a live call.
+
+mod common;
+
+use uninews::html::parse_scraped_post_from_html;
+use uninews::{format_rfc3339_utc, ConversionMode, FallbackStage, Post, Provenance, Scraper};
+
+use common::{hermetic_options, serve_with, Route};
+
+const URL: &str = "https://example.com/news/story?ref=home";
+
+fn article_page(head: &str) -> String {
+    format!(
+        "<html><head><title>Headline</title>{}</head><body><article>{}</article></body></html>",
+        head,
+        "<p>The council approved the transit plan on Tuesday after months of public hearings.</p>"
+            .repeat(8)
+    )
+}
+
+#[test]
+fn rfc3339_formatter_handles_epoch_and_leap_days() {
+    assert_eq!(format_rfc3339_utc(0), "1970-01-01T00:00:00Z");
+    assert_eq!(format_rfc3339_utc(951_782_400), "2000-02-29T00:00:00Z");
+    assert_eq!(format_rfc3339_utc(1_709_251_199), "2024-02-29T23:59:59Z");
+    assert_eq!(format_rfc3339_utc(1_709_251_200), "2024-03-01T00:00:00Z");
+}
+
+#[test]
+fn parsed_post_records_plain_fetch_and_resolved_canonical() {
+    let html = article_page(r#"<link rel="canonical" href="/news/story">"#);
+    let post = parse_scraped_post_from_html(URL, &html, None);
+    assert!(post.error.is_empty(), "{}", post.error);
+
+    let provenance = post.provenance.expect("provenance");
+    assert_eq!(provenance.source, FallbackStage::PlainFetch);
+    assert_eq!(provenance.final_url, URL);
+    assert_eq!(
+        provenance.canonical_url.as_deref(),
+        Some("https://example.com/news/story")
+    );
+    assert!(!provenance.is_archived());
+    assert!(
+        provenance.fetched_at.ends_with('Z'),
+        "{}",
+        provenance.fetched_at
+    );
+}
+
+#[test]
+fn non_http_canonical_is_ignored() {
+    let html = article_page(r#"<link rel="canonical" href="javascript:alert(1)">"#);
+    let post = parse_scraped_post_from_html(URL, &html, None);
+    assert_eq!(post.provenance.expect("provenance").canonical_url, None);
+}
+
+#[test]
+fn provenance_json_omits_unset_fields() {
+    let post = Post {
+        content: "Body".to_string(),
+        provenance: Some(Provenance {
+            source: FallbackStage::Archive,
+            final_url: "https://web.archive.org/web/20260101000000/https://example.com/"
+                .to_string(),
+            canonical_url: None,
+            http_status: Some(200),
+            snapshot_url: Some(
+                "https://web.archive.org/web/20260101000000/https://example.com/".to_string(),
+            ),
+            snapshot_timestamp: Some("20260101000000".to_string()),
+            llm_provider: None,
+            llm_model: None,
+            fetched_at: "2026-10-16T09:30:00Z".to_string(),
+            converted_at: None,
+        }),
+        ..Post::default()
+    };
+    let json = serde_json::to_value(&post).expect("serialize");
+    let provenance = &json["provenance"];
+    assert_eq!(provenance["source"], "archive");
+    assert_eq!(provenance["snapshot_timestamp"], "20260101000000");
+    for omitted in ["canonical_url", "llm_provider", "llm_model", "converted_at"] {
+        assert!(provenance.get(omitted).is_none(), "{omitted}: {provenance}");
+    }
+
+    let round_trip: Post = serde_json::from_value(json).expect("deserialize");
+    assert!(round_trip.provenance.expect("provenance").is_archived());
+    assert!(serde_json::to_value(Post::default()).expect("serialize")["provenance"].is_null());
+}
+
+#[tokio::test]
+async fn loopback_scrape_records_status_and_final_url() {
+    let url = serve_with(|_| {
+        vec![Route::html(
+            "/",
+            article_page(r#"<link rel="canonical" href="https://news.example.org/transit">"#),
+        )]
+    });
+    let scraper = Scraper::new(hermetic_options().conversion(ConversionMode::Llm));
+    let post = scraper.scrape(&url, "english").await;
+    // The bogus provider fails the conversion; the fetch provenance remains.
+    assert!(!post.error.is_empty());
+
+    let provenance = post.provenance.expect("provenance");
+    assert_eq!(provenance.source, FallbackStage::PlainFetch);
+    assert_eq!(provenance.final_url.trim_end_matches('/'), url);
+    assert_eq!(provenance.http_status, Some(200));
+    assert_eq!(
+        provenance.canonical_url.as_deref(),
+        Some("https://news.example.org/transit")
+    );
+    assert_eq!(provenance.llm_provider, None);
+    assert_eq!(provenance.converted_at, None);
+}
@@ -0,0 +1,382 @@
+//! Integration tests for Reddit submissions: submission-URL shapes, the
+//! `.json` listing parser, self / media / link classification, the top
+//! comments selection, and the built-in handler converting self posts,
+//! following link posts, appending comments, and reporting errors.
+//!
+//! Hermetic: in-memory documents plus one `common::serve_with` loopback
+//! server per test standing in for reddit.com and linked sites, scraped
+//! with `common::hermetic_options`.
+
+mod common;
+
+use serde_json::{json, Value};
+use uninews::{
+    parse_reddit_thread, reddit_post_id, FallbackStage, RedditHandler, ScrapeError, Scraper,
+    SourceHandler,
+};
+
+use common::{article_page, hermetic_options, serve_with, Route};
+
+/// A submission (`t3` thing data) in r/transit by `urbanist`.
+fn submission(id: &str, title: &str) -> Value {
+    json!({
+        "id": id,
+        "name": format!("t3_{}", id),
+        "title": title,
+        "author": "urbanist",
+        "subreddit": "transit",
+        "subreddit_name_prefixed": "r/transit",
+        "permalink": format!("/r/transit/comments/{}/council_vote/", id),
+        "url": format!("https://www.reddit.com/r/transit/comments/{}/council_vote/", id),
+        "created_utc": 1_792_141_200.0,
+        "score": 412,
+        "num_comments": 3,
+        "is_self": true,
+        "selftext": "",
+        "selftext_html": null,
+        "link_flair_text": "News",
+        "over_18": false,
+        "media": null
+    })
+}
+
+/// A top-level comment (`t1` thing).
+fn comment(id: &str, author: &str, score: i64, body: &str) -> Value {
+    json!({
+        "kind": "t1",
+        "data": {
+            "id": id,
+            "author": author,
+            "body": body,
+            "score": score,
+            "created_utc": 1_792_144_800.0,
+            "permalink": format!("/r/transit/comments/abc123/council_vote/{}/", id),
+            "stickied": false
+        }
+    })
+}
+
+/// The two listings `/comments/:id.json` returns.
+fn listings(submission: Value, comments: Vec<Value>) -> Value {
+    json!([
+        {"kind": "Listing", "data": {"children": [{"kind": "t3", "data": submission}]}},
+        {"kind": "Listing", "data": {"children": comments}}
+    ])
+}
+
+fn self_post() -> Value {
+    let mut post = submission("abc123", "Council approves two new transit lines");
+    post["selftext"] = json!("The vote passed **7-2**.\n\nConstruction starts in 2027.");
+    post["selftext_html"] = json!("<!-- SC_OFF --><div class=\"md\"><p>The vote passed <strong>7-2</strong>.</p>\n\n<p>Construction starts in 2027.</p>\n</div><!-- SC_ON -->");
+    post
+}
+
+fn comments() -> Vec<Value> {
+    let mut sticky = comment("c0", "AutoModerator", 1, "Please keep it civil.");
+    sticky["data"]["stickied"] = json!(true);
+    vec![
+        sticky,
+        comment("c1", "rider", 12, "Finally!"),
+        comment(
+            "c2",
+            "planner",
+            57,
+            "The second line matters most.\n\nIt serves the hospital.",
+        ),
+        comment("c3", "[deleted]", 30, "[removed]"),
+        comment("c4", "skeptic", 1, "We'll see."),
+        json!({"kind": "more", "data": {"count": 40, "children": ["c5", "c6"]}}),
+    ]
+}
+
+#[test]
+fn submission_ids_come_from_submission_url_shapes() {
+    let id = |url: &str| reddit_post_id(url);
+    assert_eq!(
+        id("https://www.reddit.com/r/transit/comments/abc123/council_vote/").as_deref(),
+        Some("abc123")
+    );
+    assert_eq!(
+        id("https://old.reddit.com/r/transit/comments/ABC123").as_deref(),
+        Some("abc123")
+    );
+    assert_eq!(
+        id("https://www.reddit.com/r/transit/comments/abc123/council_vote/c2xyz/?context=3")
+            .as_deref(),
+        Some("abc123")
+    );
+    assert_eq!(
+        id("https://reddit.com/comments/abc123").as_deref(),
+        Some("abc123")
+    );
+    assert_eq!(
+        id("https://www.reddit.com/user/urbanist/comments/abc123/notes/").as_deref(),
+        Some("abc123")
+    );
+    assert_eq!(id("https://redd.it/abc123").as_deref(), Some("abc123"));
+    assert_eq!(id("https://www.reddit.com/r/transit/"), None);
+    assert_eq!(id("https://www.reddit.com/r/transit/wiki/index"), None);
+    assert_eq!(id("https://notreddit.com/r/transit/comments/abc123/"), None);
+
+    let handler = RedditHandler::default();
+    assert!(handler.matches("https://www.reddit.com/r/transit/comments/abc123/council_vote/"));
+    assert!(!handler.matches("https://example.com/news/story"));
+}
+
+#[test]
+fn threads_parse_with_their_top_comments() {
+    let thread =
+        parse_reddit_thread(&listings(self_post(), comments()).to_string()).expect("thread");
+    let submission = &thread.submission;
+    assert_eq!(submission.id, "abc123");
+    assert_eq!(submission.title, "Council approves two new transit lines");
+    assert_eq!(submission.author, "urbanist");
+    assert_eq!(submission.subreddit, "transit");
+    assert_eq!(
+        submission.permalink,
+        "https://www.reddit.com/r/transit/comments/abc123/council_vote/"
+    );
+    assert_eq!(submission.created_utc, 1_792_141_200);
+    assert_eq!(submission.created_at(), "2026-10-16T09:00:00Z");
+    assert!(submission.is_self);
+    assert_eq!(submission.linked_article_url(), None);
+    assert_eq!(thread.comments.len(), 5);
+
+    let top: Vec<&str> = thread
+        .top_comments(3)
+        .iter()
+        .map(|comment| comment.author.as_str())
+        .collect();
+    assert_eq!(top, ["planner", "rider", "skeptic"]);
+
+    let metadata = submission.metadata();
+    assert_eq!(
+        metadata.schema_type.as_deref(),
+        Some("DiscussionForumPosting")
+    );
+    assert_eq!(
+        metadata.headline.as_deref(),
+        Some("Council approves two new transit lines")
+    );
+    assert_eq!(metadata.authors, ["u/urbanist"]);
+    assert_eq!(metadata.section.as_deref(), Some("r/transit"));
+    assert_eq!(
+        metadata.date_published.as_deref(),
+        Some("2026-10-16T09:00:00Z")
+    );
+    assert_eq!(metadata.keywords, ["News"]);
+
+    assert!(parse_reddit_thread("{}")
+        .unwrap_err()
+        .starts_with("Not a Reddit thread:"));
+}
+
+#[test]
+fn link_posts_are_told_apart_from_media_posts() {
+    let parse = |submission: Value| {
+        parse_reddit_thread(&listings(submission, vec![]).to_string())
+            .expect("thread")
+            .submission
+    };
+
+    let mut link = submission("l1", "Transit plan approved");
+    link["is_self"] = json!(false);
+    link["post_hint"] = json!("link");
+    link["url"] = json!("https://news.example/plan");
+    assert_eq!(
+        parse(link.clone()).linked_article_url().as_deref(),
+        Some("https://news.example/plan")
+    );
+
+    let mut image = submission("i1", "The new line map");
+    image["is_self"] = json!(false);
+    image["post_hint"] = json!("image");
+    image["url"] = json!("https://i.redd.it/map.png");
+    let image = parse(image);
+    assert_eq!(image.linked_article_url(), None);
+    assert_eq!(image.images[0].url, "https://i.redd.it/map.png");
+
+    let mut gallery = submission("g1", "Station renders");
+    gallery["is_self"] = json!(false);
+    gallery["url"] = json!("https://www.reddit.com/gallery/g1");
+    gallery["gallery_data"] = json!({"items": [
+        {"media_id": "m1", "caption": "North entrance"},
+        {"media_id": "m2"}
+    ]});
+    gallery["media_metadata"] = json!({
+        "m1": {"s": {"u": "https://preview.redd.it/m1.jpg"}},
+        "m2": {"s": {"u": "https://preview.redd.it/m2.jpg"}}
+    });
+    let gallery = parse(gallery);
+    assert_eq!(gallery.linked_article_url(), None);
+    assert_eq!(gallery.images.len(), 2);
+    assert_eq!(gallery.images[0].caption.as_deref(), Some("North entrance"));
+
+    let mut video = submission("v1", "Test run");
+    video["is_self"] = json!(false);
+    video["post_hint"] = json!("hosted:video");
+    video["url"] = json!("https://v.redd.it/v1");
+    video["media"] = json!({"reddit_video": {"fallback_url": "https://v.redd.it/v1/DASH_720.mp4"}});
+    let video = parse(video);
+    assert_eq!(video.linked_article_url(), None);
+    assert_eq!(
+        video.video.as_deref(),
+        Some("https://v.redd.it/v1/DASH_720.mp4")
+    );
+
+    let mut crosspost = submission("x1", "Crossposted: transit plan approved");
+    crosspost["is_self"] = json!(false);
+    crosspost["url"] = json!("/r/news/comments/l1/transit_plan_approved/");
+    crosspost["crosspost_parent_list"] = json!([link]);
+    assert_eq!(
+        parse(crosspost).linked_article_url().as_deref(),
+        Some("https://news.example/plan")
+    );
+}
+
+/// A `200 OK` route for requests for `path`, whatever their query.
+fn route(path: &str, content_type: &'static str, body: String) -> Route {
+    Route::new(path, content_type, body).query("")
+}
+
+fn hermetic_scraper(handler: RedditHandler) -> Scraper {
+    Scraper::new(hermetic_options().handler(handler))
+}
+
+const URL: &str = "https://www.reddit.com/r/transit/comments/abc123/council_vote/";
+
+#[tokio::test]
+async fn self_posts_convert_with_the_comments_appendix() {
+    let base = serve_with(|_| {
+        vec![route(
+            "/comments/abc123.json",
+            "application/json; charset=UTF-8",
+            listings(self_post(), comments()).to_string(),
+        )]
+    });
+    let handler = RedditHandler::default().base_url(&base).top_comments(2);
+
+    let post = hermetic_scraper(handler).scrape(URL, "english").await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(post.title, "Council approves two new transit lines");
+    assert_eq!(post.author.as_deref(), Some("u/urbanist"));
+    assert_eq!(
+        post.publication_date.as_deref(),
+        Some("2026-10-16T09:00:00Z")
+    );
+    assert!(
+        post.content.contains("The vote passed **7-2**."),
+        "{}",
+        post.content
+    );
+    let appendix = post.content.find("## Top comments").expect("appendix");
+    let planner = post
+        .content
+        .find("**u/planner** · 57 points\n\n> The second line matters most.\n>\n> It serves the hospital.")
+        .expect("planner");
+    let rider = post
+        .content
+        .find("**u/rider** · 12 points\n\n> Finally!")
+        .expect("rider");
+    assert!(appendix < planner && planner < rider, "{}", post.content);
+    assert!(!post.content.contains("skeptic"), "{}", post.content);
+    assert!(!post.content.contains("civil"), "{}", post.content);
+
+    let provenance = post.provenance.expect("provenance");
+    assert_eq!(provenance.source, FallbackStage::Reddit);
+    assert_eq!(provenance.canonical_url.as_deref(), Some(URL));
+    let metadata = post.metadata.expect("metadata");
+    assert_eq!(metadata.section.as_deref(), Some("r/transit"));
+    assert_eq!(metadata.authors, ["u/urbanist"]);
+
+    // Without `top_comments` there is no appendix.
+    let post = hermetic_scraper(RedditHandler::default().base_url(&base))
+        .scrape(URL, "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(!post.content.contains("Top comments"), "{}", post.content);
+}
+
+#[tokio::test]
+async fn link_posts_follow_the_linked_article() {
+    let base = serve_with(|base| {
+        let mut link = submission("abc123", "Council approves two new transit lines");
+        link["is_self"] = json!(false);
+        link["post_hint"] = json!("link");
+        link["url"] = json!(format!("{base}/news/plan"));
+        vec![
+            route(
+                "/comments/abc123.json",
+                "application/json",
+                listings(link, comments()).to_string(),
+            ),
+            route("/news/plan", "text/html", article_page("Plan | News")),
+        ]
+    });
+    let handler = RedditHandler::default().base_url(&base).top_comments(1);
+
+    let post = hermetic_scraper(handler).scrape(URL, "english").await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(
+        post.content.contains("fund the new transit lines"),
+        "{}",
+        post.content
+    );
+    assert!(
+        post.content.contains("**u/planner** · 57 points"),
+        "{}",
+        post.content
+    );
+    let provenance = post.provenance.expect("provenance");
+    assert_eq!(provenance.source, FallbackStage::PlainFetch);
+    assert_eq!(provenance.final_url, format!("{}/news/plan", base));
+    let metadata = post.metadata.expect("metadata");
+    assert_eq!(
+        metadata.headline.as_deref(),
+        Some("Council approves two new transit lines")
+    );
+    assert_eq!(metadata.section.as_deref(), Some("r/transit"));
+}
+
+#[tokio::test]
+async fn errors_and_block_pages_are_reported() {
+    let base = serve_with(|_| {
+        vec![
+            route(
+                "/comments/blocked.json",
+                "text/html",
+                "<html><body>You've been blocked by network security.</body></html>".to_string(),
+            )
+            .status("403 Forbidden"),
+            Route::fallback(
+                "404 Not Found",
+                "application/json",
+                "{\"message\": \"Not Found\", \"error\": 404}",
+            ),
+        ]
+    });
+    let scraper = hermetic_scraper(RedditHandler::default().base_url(&base));
+
+    let post = scraper
+        .scrape("https://www.reddit.com/r/transit/comments/gone/", "english")
+        .await;
+    assert!(
+        matches!(
+            post.error_kind,
+            Some(ScrapeError::HttpStatus { status: 404, .. })
+        ),
+        "{:?}",
+        post.error_kind
+    );
+    assert_eq!(
+        post.error_kind.expect("kind").fallbacks(),
+        [FallbackStage::Reddit]
+    );
+
+    let post = scraper.scrape("https://redd.it/blocked", "english").await;
+    assert!(
+        matches!(post.error_kind, Some(ScrapeError::BotWall { .. })),
+        "{:?}",
+        post.error_kind
+    );
+}
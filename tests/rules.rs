@@ -0,0 +1,284 @@
+//! Integration tests for per-domain extraction rules ([`ExtractionRules`]):
+//! rule selectors win over the generic heuristics and `<meta>` tags, host
+//! matching picks the most specific rule, rules files are validated, the
+//! bundled set loads, and a [`Scraper`] applies the rules in its options.
+//!
+//! Hermetic: in-memory HTML plus one loopback server with Playwright and
+//! archive.org disabled and deterministic conversion. The env-var test
+//! serializes on `TEST_LOCK`.
+
+mod common;
+
+use std::env;
+
+use tokio::sync::Mutex;
+use uninews::html::parse_scraped_post_with_rules;
+use uninews::{ExtractionRules, ScrapeOptions, Scraper, SiteRule, UNINEWS_RULES_FILE_ENV};
+
+use common::{hermetic_options, serve_with, Route};
+
+/// Serializes the tests that touch env vars.
+static TEST_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// RAII helper: temporarily override an env var, restore on drop.
+struct EnvVarGuard {
+    key: &'static str,
+    previous: Option<String>,
+}
+
+impl EnvVarGuard {
+    fn set(key: &'static str, value: &str) -> Self {
+        let previous = env::var(key).ok();
+        unsafe {
+            env::set_var(key, value);
+        }
+        Self { key, previous }
+    }
+}
+
+impl Drop for EnvVarGuard {
+    fn drop(&mut self) {
+        unsafe {
+            match self.previous.as_deref() {
+                Some(previous) => env::set_var(self.key, previous),
+                None => env::remove_var(self.key),
+            }
+        }
+    }
+}
+
+const STORY: &str =
+    "<p>The council approved the transit plan on Tuesday after months of public hearings.</p>";
+
+/// A page whose `<article>` is a decoy and whose real story sits in
+/// `div.story-text`, with a promo box inside the story.
+fn site_page() -> String {
+    format!(
+        r#"<html><head><title>Generic title</title>
+        <meta name="author" content="Meta Author">
+        <meta property="article:published_time" content="2026-01-01T00:00:00Z">
+        <meta property="og:image" content="https://example.com/og.jpg">
+        </head><body>
+        <article><p>Teaser card for another story entirely, shown above the fold.</p></article>
+        <h1 class="headline">Rule headline</h1>
+        <span class="byline"><a href="/ada">Ada Lovelace</a></span>
+        <span class="byline"><a href="/grace">Grace Hopper</a></span>
+        <time datetime="2026-10-14T08:00:00Z">October 14</time>
+        <figure class="lead"><img src="/images/lead.jpg" alt="Lead"></figure>
+        <div class="story-text">{}<div class="promo">Subscribe to our daily podcast today.</div>{}</div>
+        </body></html>"#,
+        STORY.repeat(3),
+        STORY.repeat(3)
+    )
+}
+
+fn site_rules() -> ExtractionRules {
+    let mut rules = ExtractionRules::new();
+    rules
+        .add_rule(
+            SiteRule::new(["example.com"])
+                .content("div.missing")
+                .content("div.story-text")
+                .remove(".promo")
+                .title("h1.headline")
+                .author(".byline")
+                .date("time")
+                .image("figure.lead img"),
+        )
+        .expect("valid rule");
+    rules
+}
+
+#[test]
+fn rule_selectors_win_over_generic_extraction() {
+    let post = parse_scraped_post_with_rules(
+        "https://www.example.com/news/story",
+        &site_page(),
+        None,
+        &site_rules(),
+    );
+
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(
+        post.content.starts_with("<div><p>The council"),
+        "{}",
+        post.content
+    );
+    assert!(!post.content.contains("Teaser card"), "{}", post.content);
+    assert!(!post.content.contains("podcast"), "{}", post.content);
+    assert_eq!(post.title, "Rule headline");
+    assert_eq!(post.author.as_deref(), Some("Ada Lovelace, Grace Hopper"));
+    assert_eq!(
+        post.publication_date.as_deref(),
+        Some("2026-10-14T08:00:00Z")
+    );
+    assert_eq!(
+        post.featured_image_url,
+        "https://www.example.com/images/lead.jpg"
+    );
+}
+
+#[test]
+fn title_override_still_wins_and_other_hosts_use_heuristics() {
+    let rules = site_rules();
+    let post = parse_scraped_post_with_rules(
+        "https://example.com/news/story",
+        &site_page(),
+        Some("Caller title"),
+        &rules,
+    );
+    assert_eq!(post.title, "Caller title");
+
+    let post = parse_scraped_post_with_rules("https://other.org/story", &site_page(), None, &rules);
+    assert_eq!(post.title, "Generic title");
+    assert_eq!(post.author.as_deref(), Some("Meta Author"));
+    assert_eq!(post.featured_image_url, "https://example.com/og.jpg");
+    assert!(post.content.contains("transit plan"), "{}", post.content);
+}
+
+#[test]
+fn unmatched_content_selectors_fall_back_to_heuristics() {
+    let mut rules = ExtractionRules::new();
+    rules
+        .add_rule(SiteRule::new(["example.com"]).content("div.nowhere"))
+        .unwrap();
+    let html = format!(
+        "<html><body><article>{}</article></body></html>",
+        STORY.repeat(4)
+    );
+
+    let post = parse_scraped_post_with_rules("https://example.com/a", &html, None, &rules);
+
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(post.content.contains("transit plan"), "{}", post.content);
+}
+
+#[test]
+fn most_specific_host_wins_and_later_rules_break_ties() {
+    let mut rules = ExtractionRules::new();
+    for rule in [
+        SiteRule::new(["*"]).title("h1.any"),
+        SiteRule::new(["*.example.com"]).title("h1.first"),
+        SiteRule::new(["EXAMPLE.com"]).title("h1.second"),
+        SiteRule::new(["news.example.com"]).title("h1.news"),
+    ] {
+        rules.add_rule(rule).unwrap();
+    }
+
+    let title_for = |url: &str| rules.rule_for_url(url).and_then(|rule| rule.title.clone());
+    assert_eq!(
+        title_for("https://example.com/a").as_deref(),
+        Some("h1.second")
+    );
+    assert_eq!(
+        title_for("https://www.example.com/a").as_deref(),
+        Some("h1.second")
+    );
+    assert_eq!(
+        title_for("https://news.example.com/a").as_deref(),
+        Some("h1.news")
+    );
+    assert_eq!(
+        title_for("https://notexample.com/a").as_deref(),
+        Some("h1.any")
+    );
+    assert_eq!(title_for("not a url"), None);
+}
+
+#[test]
+fn rules_json_is_validated_and_round_trips() {
+    let json =
+        r#"{"rules": [{"hosts": ["example.com"], "content": ["main"], "author": ".byline"}]}"#;
+    let rules = ExtractionRules::from_json(json).expect("valid rules");
+    assert_eq!(rules.len(), 1);
+    assert_eq!(
+        rules.to_json(),
+        r#"{"rules":[{"hosts":["example.com"],"content":["main"],"author":".byline"}]}"#
+    );
+    assert_eq!(ExtractionRules::from_json(&rules.to_json()), Ok(rules));
+
+    let unknown_field =
+        ExtractionRules::from_json(r#"{"rules": [{"hosts": ["a.com"], "body": "x"}]}"#);
+    assert!(unknown_field.unwrap_err().contains("unknown field"));
+
+    let bad_selector = ExtractionRules::from_json(
+        r#"{"rules": [{"hosts": ["a.com"]}, {"hosts": ["b.com"], "remove": ["div["]}]}"#,
+    );
+    let error = bad_selector.unwrap_err();
+    assert!(error.starts_with("rule 1:"), "{error}");
+    assert!(error.contains("div["), "{error}");
+
+    assert!(ExtractionRules::new()
+        .add_rule(SiteRule::new([" "]).content("main"))
+        .is_err());
+}
+
+#[test]
+fn bundled_rules_load_and_match_known_sites() {
+    let rules = ExtractionRules::bundled();
+    assert!(!rules.is_empty());
+    let wikipedia = rules
+        .rule_for_url("https://en.wikipedia.org/wiki/Rust_(programming_language)")
+        .expect("wikipedia rule");
+    assert!(wikipedia
+        .remove
+        .iter()
+        .any(|selector| selector == ".mw-editsection"));
+    assert_eq!(rules.rule_for_url("https://example.com/"), None);
+}
+
+#[tokio::test]
+async fn rules_file_from_env_is_layered_on_the_bundled_set() {
+    let _lock = TEST_LOCK.lock().await;
+    let path = env::temp_dir().join(format!("uninews-rules-test-{}.json", std::process::id()));
+    std::fs::write(
+        &path,
+        r##"{"rules": [{"hosts": ["wikipedia.org"], "content": ["#bodyContent"]}]}"##,
+    )
+    .expect("write rules file");
+
+    {
+        let _file = EnvVarGuard::set(UNINEWS_RULES_FILE_ENV, path.to_str().unwrap());
+        let rules = ScrapeOptions::from_env().extraction_rules().clone();
+        assert_eq!(rules.len(), ExtractionRules::bundled().len() + 1);
+        let wikipedia = rules
+            .rule_for_url("https://en.wikipedia.org/wiki/Rust")
+            .expect("wikipedia rule");
+        assert_eq!(wikipedia.content, ["#bodyContent"]);
+    }
+
+    std::fs::write(
+        &path,
+        r#"{"rules": [{"hosts": ["a.com"], "content": ["div["]}]}"#,
+    )
+    .expect("write rules file");
+    {
+        let _file = EnvVarGuard::set(UNINEWS_RULES_FILE_ENV, path.to_str().unwrap());
+        assert_eq!(ExtractionRules::from_env(), ExtractionRules::bundled());
+    }
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn scraper_applies_the_rules_in_its_options() {
+    let base = serve_with(|_| vec![Route::html("/story", site_page())]);
+
+    let mut rules = ExtractionRules::new();
+    rules
+        .add_rule(
+            SiteRule::new(["127.0.0.1"])
+                .content(".story-text")
+                .remove(".promo")
+                .title("h1.headline"),
+        )
+        .unwrap();
+    let scraper = Scraper::new(hermetic_options().rules(rules));
+
+    let post = scraper.scrape(&format!("{base}/story"), "english").await;
+
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(post.title, "Rule headline");
+    assert!(post.content.contains("transit plan"), "{}", post.content);
+    assert!(!post.content.contains("Teaser card"), "{}", post.content);
+    assert!(!post.content.contains("podcast"), "{}", post.content);
+}
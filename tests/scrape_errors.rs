@@ -0,0 +1,253 @@
+//! Integration tests for the typed [`ScrapeError`] classification carried
+//! in `Post::error_kind`: serde shape and backward compatibility,
+//! `Post::into_result`, retryability, and end-to-end classification of
+//! loopback failures.
+//!
+//! Hermetic: scrapes go through a [`Scraper`] with Playwright, archive.org,
+//! and the host-fallback-first ordering disabled, and a bogus LLM provider
+//! so the conversion stage can never make a live call.
+
+mod common;
+
+use uninews::{ConversionMode, FallbackStage, Post, ScrapeError, Scraper};
+
+use common::{hermetic_options, refused_base, serve_with, Route};
+
+fn article_page(paragraph: &str) -> String {
+    format!(
+        "<html><head><title>Headline</title></head><body><article>{}</article></body></html>",
+        format!("<p>{}</p>", paragraph).repeat(8)
+    )
+}
+
+/// A hermetic scraper that still reaches the (bogus) LLM stage.
+fn hermetic_scraper() -> Scraper {
+    Scraper::new(hermetic_options().conversion(ConversionMode::Llm))
+}
+
+#[test]
+fn successful_post_json_omits_error_kind() {
+    let post = Post {
+        title: "Title".to_string(),
+        content: "Body".to_string(),
+        ..Post::default()
+    };
+    let json = serde_json::to_value(&post).expect("serialize");
+    assert!(json.get("error_kind").is_none(), "got: {json}");
+    assert_eq!(json["error"], "");
+}
+
+#[test]
+fn failed_post_json_carries_tagged_error_kind() {
+    let post = Post {
+        error: "Failed to fetch URL: boom".to_string(),
+        error_kind: Some(ScrapeError::Network {
+            message: "Failed to fetch URL: boom".to_string(),
+            fallbacks: vec![FallbackStage::PlainFetch, FallbackStage::Archive],
+        }),
+        ..Post::default()
+    };
+    let json = serde_json::to_value(&post).expect("serialize");
+    assert_eq!(json["error_kind"]["kind"], "network");
+    assert_eq!(
+        json["error_kind"]["fallbacks"],
+        serde_json::json!(["plain_fetch", "archive"])
+    );
+
+    let round_trip: Post = serde_json::from_value(json).expect("deserialize");
+    assert_eq!(round_trip.error_kind, post.error_kind);
+}
+
+#[test]
+fn legacy_json_without_error_kind_still_deserializes() {
+    let json = r#"{"title":"","content":"","featured_image_url":"","publication_date":null,"author":null,"error":"LLM Error: boom"}"#;
+    let post: Post = serde_json::from_str(json).expect("legacy JSON");
+    assert!(post.error_kind.is_none());
+
+    let error = post.into_result().expect_err("non-empty error");
+    assert_eq!(
+        error,
+        ScrapeError::Other {
+            message: "LLM Error: boom".to_string(),
+            fallbacks: Vec::new(),
+        }
+    );
+    assert_eq!(error.to_string(), "LLM Error: boom");
+}
+
+#[test]
+fn into_result_is_ok_for_successful_posts() {
+    let post = Post {
+        content: "Body".to_string(),
+        ..Post::default()
+    };
+    assert_eq!(post.into_result().expect("ok").content, "Body");
+}
+
+#[test]
+fn fallback_stage_display_and_from_str_round_trip() {
+    for stage in [
+        FallbackStage::PlainFetch,
+        FallbackStage::ChromeRender,
+        FallbackStage::Playwright,
+        FallbackStage::ContentFallback,
+        FallbackStage::Archive,
+        FallbackStage::CmsApi,
+        FallbackStage::Alternate,
+    ] {
+        assert_eq!(stage.to_string().parse::<FallbackStage>(), Ok(stage));
+    }
+    assert!("carrier-pigeon".parse::<FallbackStage>().is_err());
+}
+
+#[test]
+fn retryability_follows_the_failure_class() {
+    let fallbacks = Vec::new();
+    let retryable = [
+        ScrapeError::Network {
+            message: String::new(),
+            fallbacks: fallbacks.clone(),
+        },
+        ScrapeError::HttpStatus {
+            status: 503,
+            message: String::new(),
+            fallbacks: fallbacks.clone(),
+        },
+        ScrapeError::HttpStatus {
+            status: 429,
+            message: String::new(),
+            fallbacks: fallbacks.clone(),
+        },
+        ScrapeError::XApi {
+            status: Some(429),
+            message: String::new(),
+            fallbacks: fallbacks.clone(),
+        },
+    ];
+    for error in &retryable {
+        assert!(error.is_retryable(), "{error:?}");
+    }
+
+    let permanent = [
+        ScrapeError::HttpStatus {
+            status: 404,
+            message: String::new(),
+            fallbacks: fallbacks.clone(),
+        },
+        ScrapeError::BlockedContent {
+            message: String::new(),
+            fallbacks: fallbacks.clone(),
+        },
+        ScrapeError::LlmConfig {
+            message: String::new(),
+            fallbacks: fallbacks.clone(),
+        },
+        ScrapeError::XApi {
+            status: None,
+            message: String::new(),
+            fallbacks,
+        },
+    ];
+    for error in &permanent {
+        assert!(!error.is_retryable(), "{error:?}");
+    }
+}
+
+#[tokio::test]
+async fn x_failures_record_the_x_api_stage() {
+    // No status id: the X handler fails before any request is made.
+    let post = hermetic_scraper()
+        .scrape("https://x.com/someone", "english")
+        .await;
+    let error = post.error_kind.expect("typed error");
+    assert!(matches!(error, ScrapeError::XApi { .. }), "{error:?}");
+    assert_eq!(error.fallbacks(), [FallbackStage::XApi]);
+}
+
+#[tokio::test]
+async fn refused_connection_is_a_network_error() {
+    let post = hermetic_scraper().scrape(&refused_base(), "english").await;
+    let error = post.clone().into_result().expect_err("refused");
+    assert!(matches!(error, ScrapeError::Network { .. }), "{error:?}");
+    assert_eq!(error.fallbacks(), &[FallbackStage::PlainFetch]);
+    assert!(
+        post.error.starts_with("Failed to fetch URL"),
+        "{}",
+        post.error
+    );
+}
+
+#[tokio::test]
+async fn server_error_page_is_an_http_status_error() {
+    let url = serve_with(|_| vec![Route::html("/", "").status("500 Internal Server Error")]);
+    let error = hermetic_scraper()
+        .scrape(&url, "english")
+        .await
+        .into_result()
+        .expect_err("500");
+    match error {
+        ScrapeError::HttpStatus { status, .. } => assert_eq!(status, 500),
+        other => panic!("expected HttpStatus, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn cloudflare_challenge_is_a_bot_wall() {
+    let url = serve_with(|_| {
+        vec![Route::html(
+            "/",
+            "<html><body><p>Checking your browser</p></body></html>",
+        )
+        .status("403 Forbidden")
+        .header("Server", "cloudflare")
+        .header("cf-ray", "1234")]
+    });
+    let error = hermetic_scraper()
+        .scrape(&url, "english")
+        .await
+        .into_result()
+        .expect_err("wall");
+    assert!(matches!(error, ScrapeError::BotWall { .. }), "{error:?}");
+    assert!(error.is_retryable());
+}
+
+#[tokio::test]
+async fn paywall_page_is_blocked_content() {
+    let url = serve_with(|_| {
+        vec![Route::html(
+            "/",
+            article_page("Subscribe to unlock this story and every other story on the site today."),
+        )]
+    });
+    let error = hermetic_scraper()
+        .scrape(&url, "english")
+        .await
+        .into_result()
+        .expect_err("paywall");
+    assert!(
+        matches!(error, ScrapeError::BlockedContent { .. }),
+        "{error:?}"
+    );
+    assert!(error.message().starts_with("BlockedContent:"));
+}
+
+#[tokio::test]
+async fn unsupported_llm_provider_is_an_llm_config_error() {
+    let url = serve_with(|_| {
+        vec![Route::html(
+            "/",
+            article_page(
+                "The council approved the transit plan on Tuesday after months of public hearings.",
+            ),
+        )]
+    });
+    let post = hermetic_scraper().scrape(&url, "english").await;
+    assert_eq!(post.title, "Headline");
+    let error = post.into_result().expect_err("bogus provider");
+    assert!(matches!(error, ScrapeError::LlmConfig { .. }), "{error:?}");
+    assert_eq!(error.fallbacks(), &[FallbackStage::PlainFetch]);
+    assert!(
+        error.message().contains("Unsupported UNINEWS_LLM_CLIENT"),
+        "{error}"
+    );
+}
@@ -0,0 +1,189 @@
+//! Integration tests for the per-instance [`Scraper`] / [`ScrapeOptions`]
+//! API: environment seeding, builder overrides, secret redaction, and two
+//! differently configured scrapers running concurrently in one process.
+//!
+//! Hermetic: every scrape targets a loopback server, Playwright and
+//! archive.org are disabled through the options, and the LLM providers are
+//! bogus names so the conversion stage fails before any network call (this
+//! dev shell may export real LLM keys).
+
+mod common;
+
+use std::env;
+use std::time::{Duration, Instant};
+
+use tokio::sync::Mutex;
+use uninews::{
+    ConversionMode, ScrapeOptions, Scraper, DEFAULT_LLM_CONTEXT_WINDOW, DEFAULT_MAX_ARTICLE_PAGES,
+    UNINEWS_ALTERNATE_FALLBACK_ENV, UNINEWS_ARCHIVE_FALLBACK_ENV, UNINEWS_CMS_API_ENV,
+    UNINEWS_CONTENT_FALLBACK_FIRST_ENV, UNINEWS_LLM_CONTEXT_WINDOW_ENV, UNINEWS_PLAYWRIGHT_ENV,
+    UNINEWS_PLAYWRIGHT_TIMEOUT_MS_ENV,
+};
+
+use common::{article_page, hermetic_options, serve_with, Route};
+
+/// Serializes the tests that read or write process-wide env vars.
+static TEST_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// RAII helper: temporarily override an env var, restore on drop.
+struct EnvVarGuard {
+    key: &'static str,
+    previous: Option<String>,
+}
+
+impl EnvVarGuard {
+    fn set(key: &'static str, value: &str) -> Self {
+        let previous = env::var(key).ok();
+        unsafe {
+            env::set_var(key, value);
+        }
+        Self { key, previous }
+    }
+}
+
+impl Drop for EnvVarGuard {
+    fn drop(&mut self) {
+        unsafe {
+            match self.previous.as_deref() {
+                Some(previous) => env::set_var(self.key, previous),
+                None => env::remove_var(self.key),
+            }
+        }
+    }
+}
+
+/// Options with every network fallback off and `llm_client` converting,
+/// so only the plain fetch runs before the LLM stage fails.
+fn llm_options(llm_client: &str) -> ScrapeOptions {
+    hermetic_options()
+        .conversion(ConversionMode::Llm)
+        .llm_client(llm_client)
+}
+
+#[tokio::test]
+async fn from_env_seeds_every_field_from_the_environment() {
+    let _lock = TEST_LOCK.lock().await;
+    let _client = EnvVarGuard::set("UNINEWS_LLM_CLIENT", " Claude ");
+    let _model = EnvVarGuard::set("UNINEWS_LLM_MODEL", "claude-sonnet-test");
+    let _window = EnvVarGuard::set(UNINEWS_LLM_CONTEXT_WINDOW_ENV, "64000");
+    let _pw = EnvVarGuard::set(UNINEWS_PLAYWRIGHT_ENV, "0");
+    let _pw_timeout = EnvVarGuard::set(UNINEWS_PLAYWRIGHT_TIMEOUT_MS_ENV, "1500");
+    let _archive = EnvVarGuard::set(UNINEWS_ARCHIVE_FALLBACK_ENV, "0");
+    let _first = EnvVarGuard::set(UNINEWS_CONTENT_FALLBACK_FIRST_ENV, "1");
+    let _cms = EnvVarGuard::set(UNINEWS_CMS_API_ENV, " Off ");
+    let _alternate = EnvVarGuard::set(UNINEWS_ALTERNATE_FALLBACK_ENV, "no");
+
+    let options = ScrapeOptions::from_env();
+    assert_eq!(options.llm_client_name(), "claude");
+    assert_eq!(options.llm_model_override(), Some("claude-sonnet-test"));
+    assert_eq!(options.context_window_tokens(), 64_000);
+    assert!(!options.playwright_enabled());
+    assert_eq!(
+        options.playwright_timeout_duration(),
+        Duration::from_millis(1500)
+    );
+    assert!(!options.archive_fallback_enabled());
+    assert!(options.content_fallback_first_enabled());
+    assert!(!options.cms_api_enabled());
+    assert!(!options.alternate_fallback_enabled());
+    assert!(options.clone().cms_api(true).cms_api_enabled());
+    assert!(options
+        .alternate_fallback(true)
+        .alternate_fallback_enabled());
+}
+
+#[tokio::test]
+async fn setters_override_env_and_reject_zero_values() {
+    let _lock = TEST_LOCK.lock().await;
+    let _window = EnvVarGuard::set(UNINEWS_LLM_CONTEXT_WINDOW_ENV, "");
+
+    let options = ScrapeOptions::from_env()
+        .llm_client("OpenRouter")
+        .llm_model("qwen/qwen3.7-max")
+        .context_window(0)
+        .playwright_timeout(Duration::ZERO)
+        .connect_timeout(Duration::ZERO)
+        .request_timeout(Duration::from_secs(5))
+        .max_article_pages(0);
+
+    assert_eq!(options.llm_client_name(), "openrouter");
+    assert_eq!(options.llm_model_override(), Some("qwen/qwen3.7-max"));
+    assert_eq!(options.context_window_tokens(), DEFAULT_LLM_CONTEXT_WINDOW);
+    assert!(!options.playwright_timeout_duration().is_zero());
+    assert_eq!(options.connect_timeout_duration(), Duration::from_secs(10));
+    assert_eq!(options.request_timeout_duration(), Duration::from_secs(5));
+    assert_eq!(options.article_page_limit(), DEFAULT_MAX_ARTICLE_PAGES);
+    assert_eq!(options.clone().max_article_pages(1).article_page_limit(), 1);
+
+    let options = options.context_window(2_000_000).llm_model("  ");
+    assert_eq!(options.context_window_tokens(), 2_000_000);
+    assert_eq!(options.llm_model_override(), None);
+}
+
+#[tokio::test]
+async fn debug_output_redacts_the_api_key() {
+    let _lock = TEST_LOCK.lock().await;
+    let options = ScrapeOptions::from_env().llm_api_key("sk-super-secret-value");
+    let rendered = format!("{:?}", options);
+    assert!(
+        !rendered.contains("sk-super-secret-value"),
+        "API key leaked: {rendered}"
+    );
+    assert!(rendered.contains("<redacted>"), "got: {rendered}");
+
+    let scraper = Scraper::new(options);
+    assert!(!format!("{:?}", scraper).contains("sk-super-secret-value"));
+}
+
+#[tokio::test]
+async fn concurrent_scrapers_use_their_own_llm_configuration() {
+    let _lock = TEST_LOCK.lock().await;
+    // The env names a third provider: neither scraper may pick it up.
+    let _llm = EnvVarGuard::set("UNINEWS_LLM_CLIENT", "env-provider-must-not-be-used");
+
+    let first = Scraper::new(llm_options("first-bogus-provider"));
+    let second = Scraper::new(llm_options("second-bogus-provider"));
+    let article = || serve_with(|_| vec![Route::html("/", article_page("Transit plan approved"))]);
+    let (first_url, second_url) = (article(), article());
+
+    let (first_post, second_post) = tokio::join!(
+        first.scrape(&first_url, "english"),
+        second.scrape(&second_url, "english"),
+    );
+
+    assert!(
+        first_post.error.contains("'first-bogus-provider'"),
+        "got: {}",
+        first_post.error
+    );
+    assert!(
+        second_post.error.contains("'second-bogus-provider'"),
+        "got: {}",
+        second_post.error
+    );
+    // The plain fetch + extraction still ran; only the LLM stage failed.
+    assert_eq!(first_post.title, "Transit plan approved");
+    assert_eq!(second_post.title, "Transit plan approved");
+}
+
+#[tokio::test]
+async fn request_timeout_option_bounds_the_plain_fetch() {
+    let _lock = TEST_LOCK.lock().await;
+    let scraper = Scraper::new(hermetic_options().request_timeout(Duration::from_secs(1)));
+    // The server holds the connection open well past the timeout.
+    let url = serve_with(|_| vec![Route::html("/", "").delay(Duration::from_secs(30))]);
+
+    let started = Instant::now();
+    let post = scraper.scrape(&url, "english").await;
+
+    assert!(
+        post.error.contains("Failed to fetch URL"),
+        "got: {}",
+        post.error
+    );
+    assert!(
+        started.elapsed() < Duration::from_secs(10),
+        "request timeout ignored: took {:?}",
+        started.elapsed()
+    );
+}
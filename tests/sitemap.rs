@@ -0,0 +1,324 @@
+//! Integration tests for sitemap ingestion: `<urlset>` and Google News
+//! `<news:news>` parsing, sitemap indexes, gzip'd sitemaps, `max_age`
+//! filtering, merging news metadata into posts, and scraping a sitemap end
+//! to end.
+//!
+//! Hermetic: in-memory documents plus one loopback server with Playwright
+//! and archive.org disabled and deterministic conversion.
+
+mod common;
+
+use std::io::Write;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use futures::StreamExt;
+use uninews::{
+    format_rfc3339_utc, parse_sitemap, Post, PostMetadata, ScrapeError, Scraper, SitemapDocument,
+    SitemapEntry, SitemapNews,
+};
+
+use common::{hermetic_options, serve_with, Route};
+
+const SITEMAP_URL: &str = "https://example.com/news-sitemap.xml";
+
+fn gzip(body: &str) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(body.as_bytes()).expect("gzip write");
+    encoder.finish().expect("gzip finish")
+}
+
+fn hours_ago(hours: u64) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("clock after epoch")
+        .as_secs();
+    format_rfc3339_utc(now - hours * 3_600)
+}
+
+const NEWS_URLSET: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9"
+        xmlns:news="http://www.google.com/schemas/sitemap-news/0.9"
+        xmlns:image="http://www.google.com/schemas/sitemap-image/1.1">
+  <url>
+    <loc>https://example.com/news/transit</loc>
+    <lastmod>2026-10-16T10:00:00+02:00</lastmod>
+    <news:news>
+      <news:publication>
+        <news:name>Example Times</news:name>
+        <news:language>en</news:language>
+      </news:publication>
+      <news:publication_date>2026-10-16T08:00:00Z</news:publication_date>
+      <news:title><![CDATA[Council approves transit plan]]></news:title>
+      <news:keywords>transit, council ,, budget</news:keywords>
+    </news:news>
+    <image:image><image:loc>https://example.com/img/transit.jpg</image:loc></image:image>
+  </url>
+  <url>
+    <loc>/news/relative</loc>
+  </url>
+  <url>
+    <lastmod>2026-10-16</lastmod>
+  </url>
+</urlset>"#;
+
+#[test]
+fn news_urlset_entries_carry_the_news_extension() {
+    let document = parse_sitemap(NEWS_URLSET.as_bytes(), SITEMAP_URL).expect("urlset");
+
+    assert_eq!(
+        document,
+        SitemapDocument::UrlSet(vec![
+            SitemapEntry {
+                loc: "https://example.com/news/transit".to_string(),
+                lastmod: Some("2026-10-16T10:00:00+02:00".to_string()),
+                news: Some(SitemapNews {
+                    publication_name: Some("Example Times".to_string()),
+                    publication_language: Some("en".to_string()),
+                    publication_date: Some("2026-10-16T08:00:00Z".to_string()),
+                    title: Some("Council approves transit plan".to_string()),
+                    keywords: vec![
+                        "transit".to_string(),
+                        "council".to_string(),
+                        "budget".to_string()
+                    ],
+                }),
+            },
+            SitemapEntry {
+                loc: "https://example.com/news/relative".to_string(),
+                ..SitemapEntry::default()
+            },
+        ])
+    );
+}
+
+#[test]
+fn sitemap_indexes_and_gzipped_sitemaps_parse() {
+    let index = r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+      <sitemap><loc>https://example.com/sitemap-1.xml.gz</loc><lastmod>2026-10-01</lastmod></sitemap>
+      <sitemap><loc>https://example.com/sitemap-2.xml</loc></sitemap>
+    </sitemapindex>"#;
+    let SitemapDocument::Index(children) =
+        parse_sitemap(&gzip(index), "https://example.com/sitemap.xml.gz").expect("index")
+    else {
+        panic!("expected a sitemap index");
+    };
+    let locs: Vec<&str> = children.iter().map(|child| child.loc.as_str()).collect();
+    assert_eq!(
+        locs,
+        [
+            "https://example.com/sitemap-1.xml.gz",
+            "https://example.com/sitemap-2.xml"
+        ]
+    );
+    assert_eq!(children[0].lastmod.as_deref(), Some("2026-10-01"));
+}
+
+#[test]
+fn non_sitemaps_are_rejected() {
+    for body in [
+        "<rss><channel></channel></rss>".as_bytes().to_vec(),
+        b"<html><body>Not XML<br></body></html>".to_vec(),
+        vec![0x1f, 0x8b, 0x00, 0x01],
+        Vec::new(),
+    ] {
+        let error = parse_sitemap(&body, SITEMAP_URL).unwrap_err();
+        assert!(error.starts_with("Not a sitemap:"), "{error}");
+    }
+}
+
+#[test]
+fn news_metadata_fills_gaps_only() {
+    let SitemapDocument::UrlSet(entries) =
+        parse_sitemap(NEWS_URLSET.as_bytes(), SITEMAP_URL).expect("urlset")
+    else {
+        panic!("expected a urlset");
+    };
+    let entry = &entries[0];
+
+    let mut sparse = Post::default();
+    entry.merge_into(&mut sparse);
+    assert_eq!(sparse.title, "Council approves transit plan");
+    assert_eq!(
+        sparse.publication_date.as_deref(),
+        Some("2026-10-16T08:00:00Z")
+    );
+    let metadata = sparse.metadata.expect("metadata");
+    assert_eq!(metadata.keywords, ["transit", "council", "budget"]);
+    assert_eq!(metadata.publisher.as_deref(), Some("Example Times"));
+
+    let mut full = Post {
+        title: "Page title".to_string(),
+        publication_date: Some("2026-10-15".to_string()),
+        metadata: Some(PostMetadata {
+            keywords: vec!["page".to_string()],
+            ..PostMetadata::default()
+        }),
+        ..Post::default()
+    };
+    entry.merge_into(&mut full);
+    assert_eq!(full.title, "Page title");
+    assert_eq!(full.publication_date.as_deref(), Some("2026-10-15"));
+    let metadata = full.metadata.expect("metadata");
+    assert_eq!(metadata.keywords, ["page"]);
+    assert_eq!(metadata.publisher.as_deref(), Some("Example Times"));
+
+    let mut untouched = Post::default();
+    entries[1].merge_into(&mut untouched);
+    assert_eq!(untouched.metadata, None);
+}
+
+fn article(head: &str, topic: &str) -> Vec<u8> {
+    format!(
+        "<html><head>{head}</head><body><article>{}</article></body></html>",
+        format!("<p>The {topic} was approved on Tuesday after months of public hearings.</p>")
+            .repeat(8)
+    )
+    .into_bytes()
+}
+
+fn news_url(base: &str, path: &str, published: &str, title: &str) -> String {
+    format!(
+        r#"<url><loc>{base}{path}</loc><news:news>
+             <news:publication><news:name>Loopback Times</news:name><news:language>en</news:language></news:publication>
+             <news:publication_date>{published}</news:publication_date>
+             <news:title>{title}</news:title><news:keywords>city, transit</news:keywords>
+           </news:news></url>"#
+    )
+}
+
+#[tokio::test]
+async fn sitemap_index_is_followed_filtered_and_scraped_with_news_metadata() {
+    let base = serve_with(|base| {
+        let news = format!(
+            r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9"
+                 xmlns:news="http://www.google.com/schemas/sitemap-news/0.9">{}{}{}</urlset>"#,
+            news_url(
+                base,
+                "/news/transit",
+                &hours_ago(2),
+                "Transit from the sitemap"
+            ),
+            news_url(
+                base,
+                "/news/bridge",
+                &hours_ago(1),
+                "Bridge from the sitemap"
+            ),
+            news_url(base, "/news/archive", "2001-09-09T01:46:40Z", "Old story"),
+        );
+        let index = format!(
+            r#"<sitemapindex xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+                 <sitemap><loc>{base}/news.xml.gz</loc><lastmod>{}</lastmod></sitemap>
+                 <sitemap><loc>{base}/missing.xml</loc></sitemap>
+                 <sitemap><loc>{base}/2001.xml</loc><lastmod>2001-09-09</lastmod></sitemap>
+               </sitemapindex>"#,
+            hours_ago(1)
+        );
+        vec![
+            Route::new("/sitemap_index.xml", "application/xml", index),
+            Route::new("/news.xml.gz", "application/x-gzip", gzip(&news)),
+            Route::new("/2001.xml", "application/xml", "<urlset/>"),
+            Route::html("/news/transit", article("", "transit plan")),
+            Route::html(
+                "/news/bridge",
+                article("<title>Bridge page title</title>", "bridge repair"),
+            ),
+            Route::html("/news/archive", article("", "archive")),
+        ]
+    });
+    let scraper = Scraper::new(hermetic_options());
+    let two_days = Some(Duration::from_secs(48 * 3_600));
+
+    let sitemap = scraper
+        .fetch_sitemap(&format!("{base}/sitemap_index.xml"), two_days)
+        .await
+        .expect("sitemap");
+    assert_eq!(
+        sitemap.sitemaps,
+        [
+            format!("{base}/sitemap_index.xml"),
+            format!("{base}/news.xml.gz")
+        ]
+    );
+    let locs: Vec<&str> = sitemap
+        .entries
+        .iter()
+        .map(|entry| entry.loc.as_str())
+        .collect();
+    assert_eq!(
+        locs,
+        [
+            format!("{base}/news/transit"),
+            format!("{base}/news/bridge")
+        ]
+    );
+
+    let unfiltered = scraper
+        .fetch_sitemap(&format!("{base}/sitemap_index.xml"), None)
+        .await
+        .expect("sitemap");
+    assert_eq!(unfiltered.entries.len(), 3);
+    assert_eq!(unfiltered.sitemaps.len(), 3);
+
+    let mut results: Vec<(SitemapEntry, Post)> = scraper
+        .scrape_sitemap(&format!("{base}/sitemap_index.xml"), "english", two_days)
+        .await
+        .expect("sitemap stream")
+        .collect()
+        .await;
+    results.sort_by(|(a, _), (b, _)| a.loc.cmp(&b.loc));
+    assert_eq!(results.len(), 2);
+
+    let (_, bridge) = &results[0];
+    assert!(bridge.error.is_empty(), "{}", bridge.error);
+    assert_eq!(bridge.title, "Bridge page title");
+    assert!(
+        bridge.content.contains("bridge repair"),
+        "{}",
+        bridge.content
+    );
+
+    let (transit_entry, transit) = &results[1];
+    assert!(transit.error.is_empty(), "{}", transit.error);
+    assert_eq!(transit.title, "Transit from the sitemap");
+    assert_eq!(
+        transit.publication_date,
+        transit_entry
+            .news
+            .as_ref()
+            .and_then(|news| news.publication_date.clone())
+    );
+    let metadata = transit.metadata.as_ref().expect("metadata");
+    assert_eq!(metadata.keywords, ["city", "transit"]);
+    assert_eq!(metadata.publisher.as_deref(), Some("Loopback Times"));
+}
+
+#[tokio::test]
+async fn root_sitemap_failures_are_typed_errors() {
+    let base = serve_with(|_| {
+        vec![Route::new(
+            "/page.xml",
+            "application/xml",
+            "<html><body><p>Not a sitemap</p></body></html>",
+        )]
+    });
+    let scraper = Scraper::new(hermetic_options());
+
+    let error = scraper
+        .fetch_sitemap(&format!("{base}/missing.xml"), None)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, ScrapeError::HttpStatus { status: 404, .. }),
+        "{error:?}"
+    );
+
+    let error = scraper
+        .fetch_sitemap(&format!("{base}/page.xml"), None)
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ScrapeError::Other { .. }), "{error:?}");
+    assert!(error.message().contains("Not a sitemap"), "{error}");
+}
@@ -0,0 +1,372 @@
+//! Integration tests for Telegram channel posts: post- and channel-URL
+//! shapes, the `t.me/s/` web preview parser, link-only detection, and the
+//! built-in handler converting single posts, following link-only posts,
+//! paging back for a channel's recent posts, and reporting missing posts.
+//!
+//! Hermetic: in-memory documents plus one `common::serve_with` loopback
+//! server per test standing in for `t.me` and linked sites, scraped with
+//! `common::hermetic_options`.
+
+mod common;
+
+use uninews::{
+    parse_telegram_channel, telegram_target, FallbackStage, ScrapeError, Scraper, SourceHandler,
+    TelegramForward, TelegramHandler, TelegramTarget,
+};
+
+use common::{article_page, hermetic_options, serve_with, Route};
+
+/// A web-preview message `transitnews/<id>` posted at `date` with `body`
+/// (the markup between the author line and the footer).
+fn message(id: u64, date: &str, body: &str) -> String {
+    format!(
+        r#"<div class="tgme_widget_message_wrap js-widget_message_wrap">
+<div class="tgme_widget_message text_not_supported_wrap js-widget_message" data-post="transitnews/{id}" data-view="x">
+<div class="tgme_widget_message_bubble">
+<div class="tgme_widget_message_author accent_color"><a class="tgme_widget_message_owner_name" href="https://t.me/transitnews"><span dir="auto">City Transit News</span></a></div>
+{body}
+<div class="tgme_widget_message_footer compact js-message_footer"><div class="tgme_widget_message_info short js-message_info">
+<span class="tgme_widget_message_views">1.2K</span>
+<span class="tgme_widget_message_meta"><a class="tgme_widget_message_date" href="https://t.me/transitnews/{id}"><time datetime="{date}" class="time">09:00</time></a></span>
+</div></div>
+</div></div></div>"#
+    )
+}
+
+/// A text block for [`message`].
+fn text(html: &str) -> String {
+    format!(r#"<div class="tgme_widget_message_text js-message_text" dir="auto">{html}</div>"#)
+}
+
+/// A `t.me/s/transitnews` page holding `messages`.
+fn preview_page(messages: &[String]) -> String {
+    format!(
+        r#"<!DOCTYPE html><html><head><title>City Transit News – Telegram</title></head><body>
+<header class="tgme_header"></header>
+<section class="tgme_channel_history js-message_history">{}</section>
+<div class="tgme_channel_info">
+<div class="tgme_channel_info_header">
+<i class="tgme_page_photo_image bgcolor0" data-content="C"><img src="https://cdn4.telesco.pe/file/transitnews.jpg"></i>
+<div class="tgme_channel_info_header_title"><span dir="auto">City Transit News</span></div>
+<div class="tgme_channel_info_header_username"><a href="https://t.me/transitnews">@transitnews</a></div>
+</div>
+<div class="tgme_channel_info_description">Buses, trains, and the council votes behind them.</div>
+</div>
+</body></html>"#,
+        messages.concat()
+    )
+}
+
+/// Post 42: a forwarded post with formatting, an emoji, a hashtag, a link,
+/// a two-photo album, and a video.
+fn rich_post() -> String {
+    message(
+        42,
+        "2026-10-16T09:00:00+00:00",
+        &format!(
+            r#"<div class="tgme_widget_message_forwarded_from accent_color">Forwarded from <a class="tgme_widget_message_forwarded_from_name" href="https://t.me/citycouncil/7"><span dir="auto">City Council</span></a></div>
+<div class="tgme_widget_message_grouped_wrap"><a class="tgme_widget_message_photo_wrap grouped_media_wrap" href="https://t.me/transitnews/42?single" style="width:200px;background-image:url('https://cdn4.telesco.pe/file/map.jpg')"></a><a class="tgme_widget_message_photo_wrap grouped_media_wrap" href="https://t.me/transitnews/43?single" style="background-image:url(&quot;//cdn4.telesco.pe/file/route.jpg&quot;)"></a></div>
+<a class="tgme_widget_message_video_player" href="https://t.me/transitnews/44"><i class="tgme_widget_message_video_thumb" style="background-image:url('https://cdn4.telesco.pe/file/route.jpg')"></i><video src="https://cdn4.telesco.pe/file/ride.mp4" class="tgme_widget_message_video js-message_video"></video></a>
+{}"#,
+            text(
+                r#"<b>Two new lines approved</b> <i class="emoji" style="background-image:url('//telegram.org/img/emoji/40/F09F9A8C.png')"><b>🚌</b></i><br/><br/>The council voted to <i>fund</i> both lines. <a href="?q=%23transit">#transit</a><br/>Details: <a href="https://news.example/plan" target="_blank" rel="noopener">news.example/plan</a>"#
+            )
+        ),
+    )
+}
+
+#[test]
+fn targets_come_from_t_me_urls() {
+    let post = |channel: &str, id| {
+        Some(TelegramTarget::Post {
+            channel: channel.to_string(),
+            id,
+        })
+    };
+    assert_eq!(
+        telegram_target("https://t.me/transitnews/42"),
+        post("transitnews", 42)
+    );
+    assert_eq!(
+        telegram_target("https://t.me/s/transitnews/42?embed=1"),
+        post("transitnews", 42)
+    );
+    assert_eq!(
+        telegram_target("https://telegram.me/transitnews/42"),
+        post("transitnews", 42)
+    );
+    assert_eq!(
+        telegram_target("https://t.me/s/transitnews"),
+        Some(TelegramTarget::Channel {
+            channel: "transitnews".to_string()
+        })
+    );
+    for url in [
+        "https://t.me/joinchat/AbCdEfGh",
+        "https://t.me/+AbCdEfGh",
+        "https://t.me/c/1234567890/42",
+        "https://t.me/addstickers/transit",
+        "https://t.me/transitnews/latest",
+        "https://example.com/transitnews/42",
+    ] {
+        assert_eq!(telegram_target(url), None, "{url}");
+    }
+
+    let handler = TelegramHandler::default();
+    assert!(handler.matches("https://t.me/transitnews/42"));
+    assert!(handler.matches("https://t.me/transitnews"));
+    let posts_only = TelegramHandler::default().recent_posts(0);
+    assert!(posts_only.matches("https://t.me/transitnews/42"));
+    assert!(!posts_only.matches("https://t.me/transitnews"));
+}
+
+#[test]
+fn previews_parse_posts_and_channel_details() {
+    let link_only = message(
+        43,
+        "2026-10-16T10:00:00+00:00",
+        &text(r#"<a href="https://news.example/plan">https://news.example/plan</a>"#),
+    );
+    let channel =
+        parse_telegram_channel(&preview_page(&[rich_post(), link_only])).expect("channel preview");
+    assert_eq!(channel.username, "transitnews");
+    assert_eq!(channel.title, "City Transit News");
+    assert_eq!(
+        channel.description.as_deref(),
+        Some("Buses, trains, and the council votes behind them.")
+    );
+    assert_eq!(
+        channel.image.as_deref(),
+        Some("https://cdn4.telesco.pe/file/transitnews.jpg")
+    );
+    assert_eq!(channel.posts.len(), 2);
+
+    let post = &channel.posts[0];
+    assert_eq!(post.id, 42);
+    assert_eq!(post.url, "https://t.me/transitnews/42");
+    assert_eq!(post.author, "City Transit News");
+    assert_eq!(post.date.as_deref(), Some("2026-10-16T09:00:00+00:00"));
+    assert_eq!(
+        post.text,
+        "Two new lines approved 🚌\n\nThe council voted to fund both lines. #transit\nDetails: news.example/plan"
+    );
+    assert!(post
+        .html
+        .starts_with("<b>Two new lines approved</b> 🚌<br>"));
+    assert_eq!(
+        post.forwarded_from,
+        Some(TelegramForward {
+            name: "City Council".to_string(),
+            url: Some("https://t.me/citycouncil/7".to_string()),
+        })
+    );
+    // Hashtag searches stay on Telegram; only the article is external.
+    assert_eq!(post.links, ["https://news.example/plan"]);
+    assert_eq!(
+        post.images,
+        [
+            "https://cdn4.telesco.pe/file/map.jpg",
+            "https://cdn4.telesco.pe/file/route.jpg"
+        ]
+    );
+    assert_eq!(post.videos, ["https://cdn4.telesco.pe/file/ride.mp4"]);
+    assert!(!post.is_link_only());
+
+    let post = &channel.posts[1];
+    assert!(post.is_link_only());
+    assert_eq!(
+        post.linked_article_url().as_deref(),
+        Some("https://news.example/plan")
+    );
+
+    assert!(parse_telegram_channel(
+        "<html><body><div class=\"tgme_page\">Send message</div></body></html>"
+    )
+    .unwrap_err()
+    .starts_with("Not a Telegram channel preview:"));
+}
+
+fn hermetic_scraper(handler: TelegramHandler) -> Scraper {
+    Scraper::new(hermetic_options().handler(handler))
+}
+
+#[tokio::test]
+async fn single_posts_keep_text_media_and_forward_source() {
+    let base = serve_with(|_| {
+        vec![Route::html(
+            "/s/transitnews?before=43",
+            preview_page(&[
+                message(41, "2026-10-15T09:00:00+00:00", &text("Older news")),
+                rich_post(),
+            ]),
+        )]
+    });
+
+    let post = hermetic_scraper(TelegramHandler::default().base_url(base))
+        .scrape("https://t.me/transitnews/42", "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(
+        post.title,
+        "City Transit News: Two new lines approved 🚌 The council voted to fund both lines. #transit Details:"
+    );
+    assert_eq!(post.author.as_deref(), Some("City Transit News"));
+    assert_eq!(
+        post.publication_date.as_deref(),
+        Some("2026-10-16T09:00:00+00:00")
+    );
+    assert_eq!(
+        post.featured_image_url,
+        "https://cdn4.telesco.pe/file/map.jpg"
+    );
+    for expected in [
+        "Forwarded from [City Council](https://t.me/citycouncil/7)",
+        "**Two new lines approved** 🚌",
+        "The council voted to *fund* both lines.",
+        "[#transit](https://t.me/s/transitnews?q=%23transit)",
+        "[news.example/plan](https://news.example/plan)",
+        "https://cdn4.telesco.pe/file/route.jpg",
+        "[Video](https://cdn4.telesco.pe/file/ride.mp4)",
+    ] {
+        assert!(
+            post.content.contains(expected),
+            "{expected}\n{}",
+            post.content
+        );
+    }
+    assert!(!post.content.contains("Older news"), "{}", post.content);
+
+    let provenance = post.provenance.expect("provenance");
+    assert_eq!(provenance.source, FallbackStage::Telegram);
+    assert_eq!(
+        provenance.canonical_url.as_deref(),
+        Some("https://t.me/transitnews/42")
+    );
+    let metadata = post.metadata.expect("metadata");
+    assert_eq!(metadata.schema_type.as_deref(), Some("SocialMediaPosting"));
+    assert_eq!(metadata.publisher.as_deref(), Some("Telegram"));
+}
+
+#[tokio::test]
+async fn link_only_posts_follow_the_linked_article() {
+    let base = serve_with(|base| {
+        let body = format!(
+            r#"{}<a class="tgme_widget_message_link_preview" href="{base}/news/plan"><div class="link_preview_site_name accent_color">News</div><div class="link_preview_title">Council approves the transit plan</div><div class="link_preview_description">Two new lines.</div></a>"#,
+            text(&format!(
+                r#"<a href="{base}/news/plan">{base}/news/plan</a>"#
+            ))
+        );
+        vec![
+            Route::html(
+                "/s/transitnews?before=8",
+                preview_page(&[message(7, "2026-10-16T09:00:00+00:00", &body)]),
+            ),
+            Route::html("/news/plan", article_page("Plan | News")),
+        ]
+    });
+
+    let post = hermetic_scraper(TelegramHandler::default().base_url(base.clone()))
+        .scrape("https://t.me/transitnews/7", "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(post.title, "Council approves the transit plan");
+    assert!(
+        post.content.contains("fund the new transit lines"),
+        "{}",
+        post.content
+    );
+    let provenance = post.provenance.expect("provenance");
+    assert_eq!(provenance.source, FallbackStage::PlainFetch);
+    assert_eq!(provenance.final_url, format!("{}/news/plan", base));
+}
+
+#[tokio::test]
+async fn channel_urls_page_back_for_the_recent_posts() {
+    let base = serve_with(|_| {
+        let page = |ids: std::ops::RangeInclusive<u64>| {
+            let messages: Vec<String> = ids
+                .map(|id| {
+                    message(
+                        id,
+                        &format!("2026-10-{id:02}T09:00:00+00:00"),
+                        &text(&format!("Update number {id}")),
+                    )
+                })
+                .collect();
+            preview_page(&messages)
+        };
+        vec![
+            Route::html("/s/transitnews", page(14..=16)),
+            Route::html("/s/transitnews?before=14", page(11..=13)),
+        ]
+    });
+
+    let post = hermetic_scraper(TelegramHandler::default().base_url(base).recent_posts(5))
+        .scrape("https://t.me/s/transitnews", "english")
+        .await;
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(post.title, "City Transit News");
+    assert_eq!(
+        post.publication_date.as_deref(),
+        Some("2026-10-16T09:00:00+00:00")
+    );
+    let positions: Vec<usize> = (12..=16)
+        .rev()
+        .map(|id| {
+            post.content
+                .find(&format!("Update number {id}"))
+                .unwrap_or_else(|| panic!("post {id} missing\n{}", post.content))
+        })
+        .collect();
+    assert!(
+        positions.windows(2).all(|pair| pair[0] < pair[1]),
+        "{}",
+        post.content
+    );
+    assert!(
+        !post.content.contains("Update number 11"),
+        "{}",
+        post.content
+    );
+    assert!(
+        post.content
+            .contains("[2026-10-16T09:00:00+00:00](https://t.me/transitnews/16)"),
+        "{}",
+        post.content
+    );
+
+    let provenance = post.provenance.expect("provenance");
+    assert_eq!(provenance.source, FallbackStage::Telegram);
+    assert_eq!(
+        provenance.canonical_url.as_deref(),
+        Some("https://t.me/s/transitnews")
+    );
+}
+
+#[tokio::test]
+async fn missing_posts_are_reported() {
+    let base = serve_with(|_| {
+        vec![Route::html(
+            "/s/transitnews?before=100",
+            preview_page(&[message(
+                98,
+                "2026-10-16T09:00:00+00:00",
+                &text("Still here"),
+            )]),
+        )]
+    });
+
+    let post = hermetic_scraper(TelegramHandler::default().base_url(base))
+        .scrape("https://t.me/transitnews/99", "english")
+        .await;
+    assert!(post.error.contains("has no post 99"), "{}", post.error);
+    assert!(
+        matches!(post.error_kind, Some(ScrapeError::Other { .. })),
+        "{:?}",
+        post.error_kind
+    );
+    assert_eq!(
+        post.error_kind.expect("kind").fallbacks(),
+        [FallbackStage::Telegram]
+    );
+}
@@ -0,0 +1,315 @@
+//! Integration tests for native YouTube extraction: video-id parsing, the
+//! watch page's player response, caption-track selection, both timedtext
+//! formats, and the built-in handler scraping a video end to end.
+//!
+//! Hermetic: in-memory documents plus one loopback server (standing in
+//! for youtube.com behind a delegating test handler) with Playwright and
+//! archive.org disabled and deterministic conversion.
+
+mod common;
+
+use uninews::{
+    parse_timedtext, parse_youtube_watch_page, youtube_video_id, FallbackStage, HandlerFuture,
+    ScrapeContext, ScrapeError, Scraper, SourceHandler, SourceHandlers, YouTubeHandler,
+};
+
+use common::{hermetic_options, serve_with, Route};
+
+const WATCH_URL: &str = "https://www.youtube.com/watch?v=dQw4w9WgXcQ";
+
+/// A watch page whose player response lists `tracks` (a JSON array) and
+/// reports `status` as its playability.
+fn watch_page(tracks: &str, status: &str) -> String {
+    format!(
+        r#"<html><head><title>Transit plan explained - YouTube</title></head><body>
+<script>var ytInitialData = {{"contents": {{}}}};</script>
+<script>var ytInitialPlayerResponse = {{
+  "playabilityStatus": {{"status": "{status}", "reason": "Sign in to confirm your age"}},
+  "videoDetails": {{
+    "videoId": "dQw4w9WgXcQ",
+    "title": "Transit plan explained",
+    "author": "City Desk",
+    "shortDescription": "What the council approved.\nLinks: https://example.com/plan",
+    "keywords": ["transit", "council"],
+    "thumbnail": {{"thumbnails": [
+      {{"url": "https://i.ytimg.com/vi/dQw4w9WgXcQ/default.jpg"}},
+      {{"url": "https://i.ytimg.com/vi/dQw4w9WgXcQ/maxresdefault.jpg"}}
+    ]}}
+  }},
+  "microformat": {{"playerMicroformatRenderer": {{
+    "publishDate": "2026-10-16",
+    "ownerChannelName": "City Desk"
+  }}}},
+  "captions": {{"playerCaptionsTracklistRenderer": {{"captionTracks": {tracks}}}}}
+}};var meta = document.createElement('meta');</script>
+</body></html>"#
+    )
+}
+
+/// Auto-generated English, manual English, and manual Spanish tracks.
+fn tracks(base: &str) -> String {
+    format!(
+        r#"[
+      {{"baseUrl": "{base}/api/timedtext?lang=en&kind=asr", "languageCode": "en",
+        "name": {{"simpleText": "English (auto-generated)"}}, "kind": "asr"}},
+      {{"baseUrl": "{base}/api/timedtext?lang=en", "languageCode": "en",
+        "name": {{"runs": [{{"text": "English"}}]}}}},
+      {{"baseUrl": "{base}/api/timedtext?lang=es", "languageCode": "es-419",
+        "name": {{"simpleText": "Spanish (Latin America)"}}}}
+    ]"#
+    )
+}
+
+const TIMEDTEXT: &str = r#"<?xml version="1.0" encoding="utf-8" ?><transcript>
+<text start="0.5" dur="2.1">The council met on Tuesday.</text>
+<text start="3.2" dur="2.4">It&amp;#39;s approved   the
+transit plan.</text>
+<text start="61.0" dur="1.5">Construction starts next year.</text>
+<text start="64.0" dur="1.0">   </text>
+</transcript>"#;
+
+#[test]
+fn video_ids_come_from_every_video_url_shape() {
+    let id = |url: &str| youtube_video_id(url);
+    assert_eq!(id(WATCH_URL).as_deref(), Some("dQw4w9WgXcQ"));
+    assert_eq!(
+        id("https://m.youtube.com/watch?feature=share&v=dQw4w9WgXcQ").as_deref(),
+        Some("dQw4w9WgXcQ")
+    );
+    assert_eq!(
+        id("https://youtu.be/dQw4w9WgXcQ?t=42").as_deref(),
+        Some("dQw4w9WgXcQ")
+    );
+    assert_eq!(
+        id("https://www.youtube.com/shorts/dQw4w9WgXcQ").as_deref(),
+        Some("dQw4w9WgXcQ")
+    );
+    assert_eq!(
+        id("https://www.youtube.com/embed/dQw4w9WgXcQ?start=10").as_deref(),
+        Some("dQw4w9WgXcQ")
+    );
+    assert_eq!(id("https://www.youtube.com/watch?v=too-short"), None);
+    assert_eq!(id("https://www.youtube.com/@citydesk/videos"), None);
+    assert_eq!(id("https://www.youtube.com/playlist?list=PL123"), None);
+    assert_eq!(id("not a url"), None);
+
+    assert!(YouTubeHandler.matches(WATCH_URL));
+    assert!(!YouTubeHandler.matches("https://www.youtube.com/@citydesk"));
+    assert!(!YouTubeHandler.matches("https://example.com/watch?v=dQw4w9WgXcQ"));
+}
+
+#[test]
+fn player_response_supplies_details_and_caption_tracks() {
+    let html = watch_page(&tracks("https://www.youtube.com"), "OK");
+    let video = parse_youtube_watch_page(&html, WATCH_URL).expect("video");
+
+    assert_eq!(video.video_id, "dQw4w9WgXcQ");
+    assert_eq!(video.url, WATCH_URL);
+    assert_eq!(video.title, "Transit plan explained");
+    assert_eq!(video.channel.as_deref(), Some("City Desk"));
+    assert_eq!(video.publish_date.as_deref(), Some("2026-10-16"));
+    assert!(video.description.starts_with("What the council approved."));
+    assert_eq!(
+        video.thumbnail_url.as_deref(),
+        Some("https://i.ytimg.com/vi/dQw4w9WgXcQ/maxresdefault.jpg")
+    );
+    assert_eq!(video.keywords, ["transit", "council"]);
+    assert_eq!(video.playability_status.as_deref(), Some("OK"));
+
+    let names: Vec<&str> = video
+        .caption_tracks
+        .iter()
+        .map(|track| track.name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        [
+            "English (auto-generated)",
+            "English",
+            "Spanish (Latin America)"
+        ]
+    );
+    assert!(video.caption_tracks[0].auto_generated);
+    assert!(!video.caption_tracks[1].auto_generated);
+
+    let error = parse_youtube_watch_page("<html><body>Video</body></html>", WATCH_URL)
+        .expect_err("no player response");
+    assert!(error.starts_with("Not a YouTube watch page:"), "{error}");
+}
+
+#[test]
+fn caption_selection_prefers_manual_tracks_in_the_target_language() {
+    let html = watch_page(&tracks("https://www.youtube.com"), "OK");
+    let mut video = parse_youtube_watch_page(&html, WATCH_URL).expect("video");
+    let chosen = |video: &uninews::YouTubeVideo, language: &str| {
+        video
+            .caption_track(language)
+            .map(|track| track.name.clone())
+    };
+
+    assert_eq!(chosen(&video, "english").as_deref(), Some("English"));
+    assert_eq!(chosen(&video, "en-US").as_deref(), Some("English"));
+    assert_eq!(
+        chosen(&video, "Spanish").as_deref(),
+        Some("Spanish (Latin America)")
+    );
+    // No French track: the first manual track wins over auto captions.
+    assert_eq!(chosen(&video, "french").as_deref(), Some("English"));
+
+    video.caption_tracks.remove(1);
+    assert_eq!(
+        chosen(&video, "english").as_deref(),
+        Some("English (auto-generated)")
+    );
+    video.caption_tracks.clear();
+    assert_eq!(chosen(&video, "english"), None);
+}
+
+#[test]
+fn timedtext_parses_in_both_caption_formats() {
+    let cues = parse_timedtext(TIMEDTEXT).expect("transcript format");
+    let texts: Vec<&str> = cues.iter().map(|cue| cue.text.as_str()).collect();
+    assert_eq!(
+        texts,
+        [
+            "The council met on Tuesday.",
+            "It's approved the transit plan.",
+            "Construction starts next year.",
+        ]
+    );
+    assert_eq!(cues[0].start_ms, 500);
+    assert_eq!(cues[2].start_ms, 61_000);
+
+    let srv3 = r#"<timedtext format="3"><body>
+<p t="1200" d="1800"><s>Hello</s><s t="400"> world</s></p>
+<p t="4000" d="900">Bye &amp; thanks</p>
+</body></timedtext>"#;
+    let cues = parse_timedtext(srv3).expect("srv3 format");
+    let texts: Vec<&str> = cues.iter().map(|cue| cue.text.as_str()).collect();
+    assert_eq!(texts, ["Hello world", "Bye & thanks"]);
+    assert_eq!(cues[0].start_ms, 1_200);
+
+    let error = parse_timedtext("<html><body/></html>").expect_err("not timedtext");
+    assert!(error.starts_with("Not a timedtext document:"), "{error}");
+}
+
+/// Hands loopback `/watch` URLs to the built-in [`YouTubeHandler`], which
+/// only claims real YouTube hosts.
+struct LoopbackYouTube;
+
+impl SourceHandler for LoopbackYouTube {
+    fn name(&self) -> &str {
+        "loopback-youtube"
+    }
+
+    fn matches(&self, url: &str) -> bool {
+        url.starts_with("http://127.0.0.1:") && url.contains("/watch?v=")
+    }
+
+    fn scrape<'a>(&'a self, url: &'a str, ctx: &'a ScrapeContext<'a>) -> HandlerFuture<'a> {
+        YouTubeHandler.scrape(url, ctx)
+    }
+}
+
+fn hermetic_scraper() -> Scraper {
+    let mut handlers = SourceHandlers::builtin();
+    handlers.register(LoopbackYouTube);
+    Scraper::new(hermetic_options().handlers(handlers))
+}
+
+#[tokio::test]
+async fn handler_scrapes_description_and_transcript() {
+    let base = serve_with(|base| {
+        vec![
+            Route::html("/watch?v=dQw4w9WgXcQ", watch_page(&tracks(base), "OK")),
+            Route::new(
+                "/api/timedtext?lang=en",
+                "text/xml; charset=UTF-8",
+                TIMEDTEXT,
+            ),
+        ]
+    });
+
+    let url = format!("{}/watch?v=dQw4w9WgXcQ", base);
+    let post = hermetic_scraper().scrape(&url, "english").await;
+
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert_eq!(post.title, "Transit plan explained");
+    assert_eq!(post.author.as_deref(), Some("City Desk"));
+    assert_eq!(post.publication_date.as_deref(), Some("2026-10-16"));
+    assert_eq!(
+        post.featured_image_url,
+        "https://i.ytimg.com/vi/dQw4w9WgXcQ/maxresdefault.jpg"
+    );
+    assert!(post.content.contains("## Description"), "{}", post.content);
+    assert!(
+        post.content.contains("## Transcript\n\nThe council met on Tuesday. It's approved the transit plan.\n\nConstruction starts next year."),
+        "{}",
+        post.content
+    );
+    let provenance = post.provenance.expect("provenance");
+    assert_eq!(provenance.source, FallbackStage::YouTube);
+    assert_eq!(provenance.final_url, url);
+    let metadata = post.metadata.expect("metadata");
+    assert_eq!(metadata.schema_type.as_deref(), Some("VideoObject"));
+    assert_eq!(metadata.authors, ["City Desk"]);
+}
+
+#[tokio::test]
+async fn missing_captions_fall_back_to_the_description() {
+    // The Spanish track 404s; the description alone is still the content.
+    let base = serve_with(|base| {
+        vec![Route::html(
+            "/watch?v=dQw4w9WgXcQ",
+            watch_page(&tracks(base), "OK"),
+        )]
+    });
+
+    let url = format!("{}/watch?v=dQw4w9WgXcQ", base);
+    let post = hermetic_scraper().scrape(&url, "spanish").await;
+
+    assert!(post.error.is_empty(), "{}", post.error);
+    assert!(
+        post.content.contains("What the council approved."),
+        "{}",
+        post.content
+    );
+    assert!(!post.content.contains("Transcript"), "{}", post.content);
+}
+
+#[tokio::test]
+async fn unplayable_and_missing_videos_are_typed_errors() {
+    let base = serve_with(|_| {
+        vec![Route::html(
+            "/watch?v=dQw4w9WgXcQ",
+            watch_page("[]", "LOGIN_REQUIRED"),
+        )]
+    });
+    let scraper = hermetic_scraper();
+
+    let post = scraper
+        .scrape(&format!("{}/watch?v=dQw4w9WgXcQ", base), "english")
+        .await;
+    assert!(
+        matches!(post.error_kind, Some(ScrapeError::BotWall { .. })),
+        "{:?}",
+        post.error_kind
+    );
+    assert!(post.error.contains("Sign in to confirm"), "{}", post.error);
+
+    let post = scraper
+        .scrape(&format!("{}/watch?v=aaaaaaaaaaa", base), "english")
+        .await;
+    assert!(
+        matches!(
+            post.error_kind,
+            Some(ScrapeError::HttpStatus { status: 404, .. })
+        ),
+        "{:?}",
+        post.error_kind
+    );
+    assert_eq!(
+        post.error_kind.expect("kind").fallbacks(),
+        [FallbackStage::YouTube]
+    );
+}